@@ -0,0 +1,21 @@
+// Module 4 and Module 5 one dimensional array kernels.
+pub mod max_subarray;
+pub mod partition;
+pub mod prefix_sum;
+pub mod reversal;
+pub mod rotation;
+pub mod streaming_sum;
+pub mod subarray_sum;
+
+/// Creates a `Vec<i32>` holding `1..=element_count`.
+pub fn create_array(element_count: usize) -> Vec<i32> {
+    let mut arr = if element_count == 0 {
+        Vec::new()
+    } else {
+        Vec::with_capacity(element_count)
+    };
+    for i in 1..=(element_count as i32) {
+        arr.push(i)
+    }
+    arr
+}
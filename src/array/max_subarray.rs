@@ -0,0 +1,87 @@
+// Maximum sum subarray (Kadane's algorithm)
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SubarraySum {
+    pub sum: i32,
+    // inclusive bounds, like range_sum_prefix_sum_arr
+    pub l: usize,
+    pub r: usize,
+}
+
+// O(n^2): every l, growing r one element at a time
+pub fn max_subarray_sum_naive(arr: &[i32]) -> Option<SubarraySum> {
+    let mut best: Option<SubarraySum> = None;
+    for l in 0..arr.len() {
+        let mut sum = 0;
+        for r in l..arr.len() {
+            sum += arr[r];
+            if best.is_none_or(|best| sum > best.sum) {
+                best = Some(SubarraySum { sum, l, r });
+            }
+        }
+    }
+    best
+}
+
+// O(n): the best subarray ending at r either extends the best one ending at r - 1 or starts
+// fresh at r, whichever is bigger. So a running sum that has gone negative is dropped, it
+// can only drag down whatever comes after it. All negative arrays return their largest
+// element, None only for an empty array.
+pub fn max_subarray_sum_kadane(arr: &[i32]) -> Option<SubarraySum> {
+    let mut best: Option<SubarraySum> = None;
+    let mut running_sum = 0;
+    let mut running_l = 0;
+    for (r, &value) in arr.iter().enumerate() {
+        if r == 0 || running_sum < 0 {
+            running_sum = value;
+            running_l = r;
+        } else {
+            running_sum += value;
+        }
+        if best.is_none_or(|best| running_sum > best.sum) {
+            best = Some(SubarraySum {
+                sum: running_sum,
+                l: running_l,
+                r,
+            });
+        }
+    }
+    best
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bench::data::XorShift64;
+
+    #[test]
+    fn test_max_subarray_sum_kadane() {
+        let arr = [-2, 1, -3, 4, -1, 2, 1, -5, 4];
+        assert_eq!(
+            max_subarray_sum_kadane(&arr),
+            Some(SubarraySum { sum: 6, l: 3, r: 6 })
+        );
+        assert_eq!(
+            max_subarray_sum_kadane(&[-3, -1, -2]),
+            Some(SubarraySum {
+                sum: -1,
+                l: 1,
+                r: 1
+            })
+        );
+        assert_eq!(max_subarray_sum_kadane(&[]), None);
+    }
+
+    #[test]
+    fn test_max_subarray_sum_kadane_against_naive() {
+        let mut rng = XorShift64::new(45);
+        for len in 1..40 {
+            let arr: Vec<i32> = (0..len)
+                .map(|_| (rng.next_u64() % 21) as i32 - 10)
+                .collect();
+            let kadane = max_subarray_sum_kadane(&arr).unwrap();
+            assert_eq!(kadane.sum, max_subarray_sum_naive(&arr).unwrap().sum);
+            assert_eq!(arr[kadane.l..=kadane.r].iter().sum::<i32>(), kadane.sum);
+        }
+    }
+}
@@ -0,0 +1,93 @@
+pub fn move_zeros_to_end(arr: &mut [i32]) {
+    // Move non 0's forward
+    let mut write_pos = 0;
+    for read_pos in 0..arr.len() {
+        if arr[read_pos] != 0 {
+            // Move to write pos
+            arr[write_pos] = arr[read_pos];
+            // Move write pos forward.
+            write_pos += 1;
+        }
+    }
+
+    // Fill remainder from write pos to end with 0's
+    // Insight: no need to care about the 0's at all, rather we were making sure
+    // the non zero ordering is maintained.
+    for i in write_pos..arr.len() {
+        arr[i] = 0;
+    }
+}
+
+pub fn dutch_flag(arr: &mut [i32]) {
+    // three  boundry zones(with a 4th working zone):
+    // 0..low -> Confirmed 0's (this boundry is moving up from the start)
+    // low..mid -> Confirmed 1's (this boundry is moving up by the processing)
+    // mid..high -> Unknowns in progress of being tested.
+    // high..len -> Confirmed 2's (this boundry is moving down from the end)
+    if arr.is_empty() {
+        return;
+    }
+    // high is exclusive so a slice of only 2's never steps below 0.
+    let (mut low, mut mid, mut high) = (0, 0, arr.len());
+    while mid < high {
+        // mid is our working on zone, so therefore once mid==high all zones sorted
+        match arr[mid] {
+            0 => {
+                // We have a 0 at the mid point so move it into the low boundry
+                // and move the low boundry and mid point on. Note: mid can be
+                // moved on since the value from low move up potentially would be
+                // a 0.
+                arr.swap(mid, low);
+                low += 1;
+                mid += 1;
+            }
+            1 => {
+                // we are checking at mid and found a mid so move on the mid boundry
+                mid += 1;
+            }
+            2 => {
+                // we found a high in the mid position so we need to swap it with
+                // the high boundry, however note that a mid could have been moved
+                // into mid position with the swap so we do not move the mid boundry
+                // check up.
+                high -= 1; // high boundry moving down from the end, the others move up.
+                arr.swap(mid, high);
+            }
+            _ => panic!("We are only implementing for 3 distinct values in this throw away."),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_move_zeros_to_end_flag() {
+        let mut arr = vec![0, 1, 0, 3, 12];
+        move_zeros_to_end(&mut arr);
+        assert_eq!(arr, vec![1, 3, 12, 0, 0]);
+    }
+
+    #[test]
+    fn test_dutch_flag() {
+        let mut arr = [2, 0, 2, 1, 1, 0];
+        dutch_flag(&mut arr);
+        assert_eq!(arr, [0, 0, 1, 1, 2, 2]);
+    }
+
+    #[test]
+    fn dutch_flag_handles_empty_and_all_twos() {
+        let mut empty: [i32; 0] = [];
+        dutch_flag(&mut empty);
+        assert_eq!(empty, []);
+
+        let mut single = [2];
+        dutch_flag(&mut single);
+        assert_eq!(single, [2]);
+
+        let mut all_twos = [2, 2, 2];
+        dutch_flag(&mut all_twos);
+        assert_eq!(all_twos, [2, 2, 2]);
+    }
+}
@@ -0,0 +1,182 @@
+use crate::parallel::map_row_bands;
+
+pub fn make_prefix_sum_array(arr: &[i32]) -> Vec<i32> {
+    let size = arr.len();
+    if size == 0 {
+        return Vec::new();
+    }
+
+    let mut prefix_sum_array = Vec::with_capacity(size);
+    prefix_sum_array.push(arr[0]);
+    for i in 1..size {
+        prefix_sum_array.push(prefix_sum_array[i - 1] + arr[i]);
+    }
+    prefix_sum_array
+}
+
+// Naive range sum - results in O(n) time complexity
+pub fn range_sum_naive(arr: &[i32], l: usize, r: usize) -> i32 {
+    let mut total = 0;
+    for i in l..=r {
+        total += arr[i];
+    }
+    total
+}
+
+// O(1) implementation using prefix_arr
+pub fn range_sum_prefix_sum_arr(prefix_arr: &[i32], l: usize, r: usize) -> i32 {
+    if l == 0 {
+        prefix_arr[r]
+    } else {
+        prefix_arr[r] - prefix_arr[l - 1]
+    }
+}
+
+pub fn make_sufix_sum_array(arr: &[i32]) -> Vec<i32> {
+    // I accidently made the suffix one misunderstanding the question - I mis read
+    // the expected outcome.
+    // We will linear process - pre calculate all the results from right to left.
+    // Sums from i to len for value of i, but does it cumlatively from right to left.
+    let size = arr.len();
+    if size == 0 {
+        return Vec::new();
+    }
+    let mut suffix_sum_array = vec![0; size];
+    suffix_sum_array[size - 1] = arr[size - 1];
+    if size > 1 {
+        for i in (0..(size - 1)).rev() {
+            // current slot value + the following sum value already calculated.
+            suffix_sum_array[i] = arr[i] + suffix_sum_array[i + 1];
+        }
+    }
+    suffix_sum_array
+}
+
+// Two pass parallel scan. Pass 1: every thread takes a band of the array and writes the
+// prefix sums of just its band. The band totals are then scanned on this thread to get
+// each band's offset (the sum of everything before it), and pass 2 adds that offset onto
+// every element of the band, again one thread per band.
+pub fn make_prefix_sum_array_parallel(arr: &[i32], thread_count: usize) -> Vec<i32> {
+    let mut prefix_sum_array = vec![0; arr.len()];
+    let band_totals = map_row_bands(&mut prefix_sum_array, 1, thread_count, |_, range, band| {
+        let mut running_sum = 0;
+        for (prefix_sum, value) in band.iter_mut().zip(&arr[range]) {
+            running_sum += value;
+            *prefix_sum = running_sum;
+        }
+        running_sum
+    });
+
+    let mut band_offsets = Vec::with_capacity(band_totals.len());
+    let mut offset = 0;
+    for band_total in band_totals {
+        band_offsets.push(offset);
+        offset += band_total;
+    }
+
+    map_row_bands(
+        &mut prefix_sum_array,
+        1,
+        thread_count,
+        |band_idx, _, band| {
+            let offset = band_offsets[band_idx];
+            if offset != 0 {
+                for prefix_sum in band {
+                    *prefix_sum += offset;
+                }
+            }
+        },
+    );
+    prefix_sum_array
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_range_sum_naive() {
+        let arr = vec![2, 4, 6, 8, 10];
+
+        let cases = vec![
+            (0, 0, 2),  // sum of [2] = 2
+            (0, 2, 12), // sum of [2,4,6] = 12
+            (1, 3, 18), // sum of [4,6,8] = 18
+            (2, 4, 24), // sum of [6,8,10] = 24
+            (0, 4, 30), // sum of entire array = 30
+        ];
+
+        for (l, r, expected) in cases {
+            let result = range_sum_naive(&arr, l, r);
+            assert_eq!(result, expected, "Failed for range ({}, {})", l, r);
+        }
+    }
+
+    #[test]
+    fn test_range_sum_prefix_sum_arr() {
+        let arr = vec![2, 4, 6, 8, 10];
+        let prefix_arr = make_prefix_sum_array(&arr);
+
+        let cases = vec![
+            (0, 0, 2),  // sum of [2] = 2
+            (0, 2, 12), // sum of [2,4,6] = 12
+            (1, 3, 18), // sum of [4,6,8] = 18
+            (2, 4, 24), // sum of [6,8,10] = 24
+            (0, 4, 30), // sum of entire array = 30
+        ];
+
+        for (l, r, expected) in cases {
+            let result = range_sum_prefix_sum_arr(&prefix_arr, l, r);
+            assert_eq!(result, expected, "Failed for range ({}, {})", l, r);
+        }
+    }
+
+    #[test]
+    fn test_prefix_array_sums() {
+        let cases = vec![
+            // (input, expected_prefix)
+            (vec![], vec![]),
+            (vec![5], vec![5]),
+            (vec![1, 2, 3], vec![1, 3, 6]),
+            (vec![2, 4, 6, 8, 10], vec![2, 6, 12, 20, 30]),
+            (vec![10, -2, 3, -1], vec![10, 8, 11, 10]),
+            (vec![-1, -2, -3], vec![-1, -3, -6]),
+        ];
+
+        for (input, expected) in cases {
+            let output = make_prefix_sum_array(&input);
+            assert_eq!(output, expected, "Failed on input: {:?}", input);
+        }
+    }
+
+    #[test]
+    fn test_suffix_array_sums() {
+        let cases = vec![
+            // (input, expected_suffix)
+            (vec![], vec![]),
+            (vec![5], vec![5]),
+            (vec![1, 2, 3], vec![6, 5, 3]),
+            (vec![2, 4, 6, 8, 10], vec![30, 28, 24, 18, 10]),
+            (vec![10, -2, 3, -1], vec![10, 0, 2, -1]),
+            (vec![-1, -2, -3], vec![-6, -5, -3]),
+        ];
+
+        for (input, expected) in cases {
+            let output = make_sufix_sum_array(&input);
+            assert_eq!(output, expected, "Failed on input: {:?}", input);
+        }
+    }
+
+    #[test]
+    fn test_make_prefix_sum_array_parallel() {
+        for len in [0, 1, 5, 1000] {
+            let arr: Vec<i32> = (0..len).map(|v| v % 13 - 6).collect();
+            for thread_count in [1, 2, 3, 16] {
+                assert_eq!(
+                    make_prefix_sum_array_parallel(&arr, thread_count),
+                    make_prefix_sum_array(&arr)
+                );
+            }
+        }
+    }
+}
@@ -0,0 +1,113 @@
+pub fn rotate_right_naive<T: Copy>(arr: &mut [T], rotation: usize) {
+    if arr.is_empty() {
+        return;
+    }
+    // Move one position rotation times - O(rotation*n) time!
+    for _ in 0..rotation {
+        let temp = arr[arr.len() - 1]; // save last element
+        for i in (1..arr.len()).rev() {
+            arr[i] = arr[i - 1]; // shift everything right
+        }
+        arr[0] = temp; // put saved element at front
+    }
+}
+
+// Realize that Array is broken into two parts, A and B -> B needs to move left of A for right rotations
+// and A needs to move right of B for left rotations.
+// So if we are moving left algorithmically we
+// reverse A and reverse B, then reverse the whole array.
+// And if we are moving right algorithmically we
+// reverse the whole array, reverse B and reverse A
+// important to note that the actual order of A and B reversals don't matter, only that
+// it happens before or after the global reverse of the array.
+//
+// The amount we are rotating left can also be converted to a right rotation using
+// the equivalence that left by rotation == right by (n − rotation mod n)
+pub fn rotate_right<T: Copy>(arr: &mut [T], rotation: usize) {
+    let len = arr.len();
+    if len != 0 {
+        let rotation = rotation % len;
+        arr.reverse();
+        arr[..rotation].reverse();
+        arr[rotation..].reverse();
+    }
+}
+
+pub fn rotate_left<T: Copy>(arr: &mut [T], rotation: usize) {
+    let len = arr.len();
+    if len != 0 {
+        let rotation = rotation % len;
+        // Solution A - mathematical mirror
+        arr[rotation..].reverse();
+        arr[..rotation].reverse();
+        arr.reverse();
+    }
+}
+
+pub fn rotate_left_math_rotate_right<T: Copy>(arr: &mut [T], rotation: usize) {
+    let len = arr.len();
+    if len != 0 {
+        let rotation = rotation % len;
+        // Solution B - Mathematically converted to a right rotation amount.
+        // left by rotation == right by (n − rotation mod n)
+        let right_rotation = (len - rotation) % len;
+        rotate_right(arr, right_rotation);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::array::create_array;
+
+    #[test]
+    fn rotate_right_naive_on_empty_slice() {
+        let mut arr: [i32; 0] = [];
+        rotate_right_naive(&mut arr, 3);
+        assert_eq!(arr, []);
+    }
+
+    #[test]
+    fn test_rotate_right_naive() {
+        let mut arr = create_array(5);
+        rotate_right_naive(&mut arr, 2);
+        // after 2 right-shifts [1,2,3,4,5] → [4,5,1,2,3]
+        assert_eq!(arr, vec![4, 5, 1, 2, 3]);
+
+        let mut arr = create_array(3);
+        rotate_right_naive(&mut arr, 5); // 5 % 3 == 2
+        assert_eq!(arr, vec![2, 3, 1]);
+    }
+
+    #[test]
+    fn test_rotate_right() {
+        let mut arr = create_array(5);
+        rotate_right(&mut arr, 2);
+        // after 2 right-shifts [1,2,3,4,5] → [4,5,1,2,3]
+        assert_eq!(arr, vec![4, 5, 1, 2, 3]);
+
+        let mut arr = create_array(3);
+        rotate_right(&mut arr, 5); // 5 % 3 == 2
+        assert_eq!(arr, vec![2, 3, 1]);
+
+        let mut arr = create_array(0);
+        rotate_right(&mut arr, 5); // no dividing by 0 errors.
+        assert_eq!(arr, Vec::new());
+    }
+
+    #[test]
+    fn test_rotate_left() {
+        let mut arr = create_array(5);
+        rotate_left(&mut arr, 2);
+        // after 2 left-shifts [1,2,3,4,5] → [4,5,1,2,3]
+        assert_eq!(arr, vec![3, 4, 5, 1, 2]);
+
+        let mut arr = create_array(3);
+        rotate_left(&mut arr, 5); // 5 % 3 == 2
+        assert_eq!(arr, vec![3, 1, 2]);
+
+        let mut arr = create_array(0);
+        rotate_left(&mut arr, 5); // no dividing by 0 errors.
+        assert_eq!(arr, Vec::new());
+    }
+}
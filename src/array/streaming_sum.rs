@@ -0,0 +1,405 @@
+// Streaming versions of the prefix sum tricks in subarray_sum.rs: the input is consumed one
+// number at a time from an iterator or a reader and never stored, so the answers can be
+// computed over logs that do not fit in memory.
+//
+// Every subarray question here only ever looks back at earlier prefix sums, never at the
+// values themselves, so the hashmap of prefix sums is all that has to be kept:
+// - SubarraySumCounter: subarrays summing to k, counts of every prefix sum seen so far.
+// - LongestSubarrayWithSum: longest subarray summing to k, the first index each prefix sum
+//   was seen at (a later repeat can only give a shorter subarray).
+// - DivisibleSubarrayCounter: subarrays whose sum is divisible by k, two prefix sums with
+//   the same remainder mod k bound one, so only the counts per remainder are needed.
+//
+// The first two still grow with the number of distinct prefix sums, the third with the number
+// of distinct remainders, O(min(k, n)) no matter how long the stream is. Sums are i64 so long
+// streams of i32 readings do not overflow.
+use std::collections::HashMap;
+use std::io::{BufReader, Read};
+
+#[derive(Debug, Clone)]
+pub struct RunningPrefixSums<I> {
+    values: I,
+    prefix_sum: i64,
+}
+
+impl<I: Iterator<Item = T>, T: Into<i64>> Iterator for RunningPrefixSums<I> {
+    type Item = i64;
+
+    fn next(&mut self) -> Option<i64> {
+        self.prefix_sum += self.values.next()?.into();
+        Some(self.prefix_sum)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.values.size_hint()
+    }
+}
+
+// make_prefix_sum_array, one value at a time
+pub fn running_prefix_sums<I, T>(values: I) -> RunningPrefixSums<I::IntoIter>
+where
+    I: IntoIterator<Item = T>,
+    T: Into<i64>,
+{
+    RunningPrefixSums {
+        values: values.into_iter(),
+        prefix_sum: 0,
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct SubarraySumCounter {
+    k: i64,
+    prefix_sum: i64,
+    // prefix sum -> times seen, starting with the empty prefix (0) seen once
+    seen_counts: HashMap<i64, u64>,
+    count: u64,
+}
+
+impl SubarraySumCounter {
+    pub fn new(k: i64) -> Self {
+        SubarraySumCounter {
+            k,
+            prefix_sum: 0,
+            seen_counts: HashMap::from([(0, 1)]),
+            count: 0,
+        }
+    }
+
+    // Returns how many of the subarrays ending at this value sum to k
+    pub fn push(&mut self, value: i64) -> u64 {
+        self.prefix_sum += value;
+        let ending_here = self
+            .seen_counts
+            .get(&(self.prefix_sum - self.k))
+            .copied()
+            .unwrap_or(0);
+        self.count += ending_here;
+        *self.seen_counts.entry(self.prefix_sum).or_insert(0) += 1;
+        ending_here
+    }
+
+    pub fn count(&self) -> u64 {
+        self.count
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct LongestSubarrayWithSum {
+    k: i64,
+    prefix_sum: i64,
+    len: usize,
+    // prefix sum -> number of values before the first time it was reached
+    first_seen: HashMap<i64, usize>,
+    // inclusive (l, r)
+    longest: Option<(usize, usize)>,
+}
+
+impl LongestSubarrayWithSum {
+    pub fn new(k: i64) -> Self {
+        LongestSubarrayWithSum {
+            k,
+            prefix_sum: 0,
+            len: 0,
+            first_seen: HashMap::from([(0, 0)]),
+            longest: None,
+        }
+    }
+
+    pub fn push(&mut self, value: i64) {
+        self.prefix_sum += value;
+        self.len += 1;
+        let r = self.len - 1;
+        if let Some(&l) = self.first_seen.get(&(self.prefix_sum - self.k))
+            && self
+                .longest
+                .is_none_or(|(best_l, best_r)| r - l > best_r - best_l)
+        {
+            self.longest = Some((l, r));
+        }
+        self.first_seen.entry(self.prefix_sum).or_insert(self.len);
+    }
+
+    // The first of the longest subarrays summing to k so far, as inclusive (l, r)
+    pub fn longest(&self) -> Option<(usize, usize)> {
+        self.longest
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct DivisibleSubarrayCounter {
+    k: i64,
+    prefix_remainder: i64,
+    // remainder -> times seen, the empty prefix has remainder 0. A map rather than k counters
+    // so a huge divisor does not allocate up front.
+    seen_counts: HashMap<i64, u64>,
+    count: u64,
+}
+
+impl DivisibleSubarrayCounter {
+    pub fn new(k: i64) -> Self {
+        if k <= 0 {
+            panic!("divisor must be > 0")
+        }
+        DivisibleSubarrayCounter {
+            k,
+            prefix_remainder: 0,
+            seen_counts: HashMap::from([(0, 1)]),
+            count: 0,
+        }
+    }
+
+    // Returns how many of the subarrays ending at this value have a sum divisible by k
+    pub fn push(&mut self, value: i64) -> u64 {
+        // rem_euclid keeps negative values in 0..k. Both terms are below k, but their sum can
+        // pass i64::MAX once k > i64::MAX / 2, so it is added in i128.
+        let sum = self.prefix_remainder as i128 + value.rem_euclid(self.k) as i128;
+        self.prefix_remainder = (sum % self.k as i128) as i64;
+        let seen = self.seen_counts.entry(self.prefix_remainder).or_insert(0);
+        let ending_here = *seen;
+        *seen += 1;
+        self.count += ending_here;
+        ending_here
+    }
+
+    pub fn count(&self) -> u64 {
+        self.count
+    }
+}
+
+pub fn count_subarrays_sum_k_streaming<I, T>(values: I, k: i64) -> u64
+where
+    I: IntoIterator<Item = T>,
+    T: Into<i64>,
+{
+    let mut counter = SubarraySumCounter::new(k);
+    for value in values {
+        counter.push(value.into());
+    }
+    counter.count()
+}
+
+pub fn longest_subarray_with_sum_k_streaming<I, T>(values: I, k: i64) -> Option<(usize, usize)>
+where
+    I: IntoIterator<Item = T>,
+    T: Into<i64>,
+{
+    let mut longest = LongestSubarrayWithSum::new(k);
+    for value in values {
+        longest.push(value.into());
+    }
+    longest.longest()
+}
+
+pub fn count_subarrays_divisible_by_k_streaming<I, T>(values: I, k: i64) -> u64
+where
+    I: IntoIterator<Item = T>,
+    T: Into<i64>,
+{
+    let mut counter = DivisibleSubarrayCounter::new(k);
+    for value in values {
+        counter.push(value.into());
+    }
+    counter.count()
+}
+
+// Longest token that is still worth parsing, i64::MIN is 20 characters
+const MAX_NUMBER_TOKEN_LEN: usize = 32;
+
+fn parse_number_token(token: &[u8], line_number: usize) -> Result<i64, String> {
+    std::str::from_utf8(token)
+        .ok()
+        .and_then(|token| token.parse::<i64>().ok())
+        .ok_or_else(|| {
+            format!(
+                "Line {}: '{}' is not an integer.",
+                line_number,
+                String::from_utf8_lossy(token)
+            )
+        })
+}
+
+// Reads whitespace or comma separated integers and hands each one to on_value, so several
+// counters can be fed in one pass over a file. Lines starting with '#' are comments. Returns
+// how many numbers were read.
+//
+// The input is tokenized a byte at a time through a BufReader and only the current number is
+// buffered, never a whole line, so a log written as one huge comma separated line still runs
+// in constant memory. The line number is only kept for the error messages.
+pub fn for_each_number(reader: impl Read, mut on_value: impl FnMut(i64)) -> Result<usize, String> {
+    let mut number_count = 0;
+    let mut line_number = 1;
+    let mut token = Vec::with_capacity(MAX_NUMBER_TOKEN_LEN);
+    // Nothing but whitespace seen on this line yet, so a '#' starts a comment
+    let mut at_line_start = true;
+    let mut in_comment = false;
+    for byte in BufReader::new(reader).bytes() {
+        let byte = byte.map_err(|e| format!("Failed to read line {}: {}", line_number, e))?;
+        if in_comment && byte != b'\n' {
+            continue;
+        }
+        if byte.is_ascii_whitespace() || byte == b',' {
+            if !token.is_empty() {
+                on_value(parse_number_token(&token, line_number)?);
+                number_count += 1;
+                token.clear();
+            }
+            if byte == b'\n' {
+                line_number += 1;
+                at_line_start = true;
+                in_comment = false;
+            } else if byte == b',' {
+                at_line_start = false;
+            }
+        } else if byte == b'#' && at_line_start {
+            in_comment = true;
+        } else {
+            if token.len() == MAX_NUMBER_TOKEN_LEN {
+                return Err(format!(
+                    "Line {}: '{}...' is not an integer.",
+                    line_number,
+                    String::from_utf8_lossy(&token)
+                ));
+            }
+            token.push(byte);
+            at_line_start = false;
+        }
+    }
+    if !token.is_empty() {
+        on_value(parse_number_token(&token, line_number)?);
+        number_count += 1;
+    }
+    Ok(number_count)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::array::prefix_sum::make_prefix_sum_array;
+    use crate::array::subarray_sum::count_sub_arrays_sum_k_using_prefix_and_hashmap;
+    use crate::bench::data::XorShift64;
+    use std::io::Cursor;
+
+    fn random_arr(rng: &mut XorShift64, len: usize) -> Vec<i32> {
+        (0..len).map(|_| (rng.next_u64() % 11) as i32 - 5).collect()
+    }
+
+    #[test]
+    fn streaming_matches_the_slice_versions() {
+        let mut rng = XorShift64::new(48);
+        for len in 0..60 {
+            let arr = random_arr(&mut rng, len);
+            let k = (rng.next_u64() % 11) as i32 - 5;
+            let prefix_sums: Vec<i64> = running_prefix_sums(arr.iter().copied()).collect();
+            let expected_prefix_sums: Vec<i64> = make_prefix_sum_array(&arr)
+                .into_iter()
+                .map(i64::from)
+                .collect();
+            assert_eq!(prefix_sums, expected_prefix_sums);
+            assert_eq!(
+                count_subarrays_sum_k_streaming(arr.iter().copied(), k as i64),
+                count_sub_arrays_sum_k_using_prefix_and_hashmap(&arr, k) as u64
+            );
+        }
+    }
+
+    #[test]
+    fn longest_and_divisible_match_brute_force() {
+        let mut rng = XorShift64::new(7);
+        for len in 1..40 {
+            let arr = random_arr(&mut rng, len);
+            let k = (rng.next_u64() % 7) as i64 - 3;
+            let divisor = (rng.next_u64() % 6) as i64 + 1;
+            let mut longest_len = 0;
+            let mut divisible = 0;
+            for l in 0..len {
+                for r in l..len {
+                    let sum: i64 = arr[l..=r].iter().map(|&v| v as i64).sum();
+                    if sum == k {
+                        longest_len = longest_len.max(r - l + 1);
+                    }
+                    if sum % divisor == 0 {
+                        divisible += 1;
+                    }
+                }
+            }
+            let longest = longest_subarray_with_sum_k_streaming(arr.iter().copied(), k);
+            match longest {
+                None => assert_eq!(longest_len, 0),
+                Some((l, r)) => {
+                    assert_eq!(r - l + 1, longest_len);
+                    assert_eq!(arr[l..=r].iter().map(|&v| v as i64).sum::<i64>(), k);
+                }
+            }
+            assert_eq!(
+                count_subarrays_divisible_by_k_streaming(arr.iter().copied(), divisor),
+                divisible
+            );
+        }
+    }
+
+    #[test]
+    fn divisible_counter_handles_divisors_near_i64_max() {
+        let k = i64::MAX;
+        let values = [k - 1, k - 1, 2, -1, k, 1];
+        let mut expected = 0;
+        for start in 0..values.len() {
+            let mut sum = 0i128;
+            for &value in &values[start..] {
+                sum += value as i128;
+                if sum % k as i128 == 0 {
+                    expected += 1;
+                }
+            }
+        }
+        assert_eq!(
+            count_subarrays_divisible_by_k_streaming(values, k),
+            expected
+        );
+        assert!(expected > 0);
+    }
+
+    #[test]
+    fn numbers_from_a_reader() {
+        let log = "# sensor 3\n4, 5 0\n-2,-3\n\n1\n";
+        let mut sum_counter = SubarraySumCounter::new(5);
+        let mut divisible_counter = DivisibleSubarrayCounter::new(5);
+        let number_count = for_each_number(Cursor::new(log), |value| {
+            sum_counter.push(value);
+            divisible_counter.push(value);
+        });
+        assert_eq!(number_count, Ok(6));
+        assert_eq!(
+            sum_counter.count(),
+            count_subarrays_sum_k_streaming([4, 5, 0, -2, -3, 1], 5)
+        );
+        assert_eq!(divisible_counter.count(), 7);
+        assert_eq!(
+            for_each_number(Cursor::new("1 2\n3 x4\n"), |_| {}),
+            Err(String::from("Line 2: 'x4' is not an integer."))
+        );
+
+        // One long line, a last number without a newline, and a runaway token
+        let one_line: String = (0..10_000).map(|v| format!("{},", v % 7 - 3)).collect();
+        let mut sum = 0;
+        let number_count =
+            for_each_number(Cursor::new(one_line + "\n# done\n 4"), |value| sum += value);
+        assert_eq!(
+            (number_count, sum),
+            (Ok(10_001), (0..10_000).map(|v| v % 7 - 3).sum::<i64>() + 4)
+        );
+        assert_eq!(
+            for_each_number(Cursor::new(format!("1\n{}", "9".repeat(40))), |_| {}),
+            Err(format!(
+                "Line 2: '{}...' is not an integer.",
+                "9".repeat(32)
+            ))
+        );
+        // A huge divisor only keeps the remainders it has seen
+        let mut huge_divisor = DivisibleSubarrayCounter::new(1_000_000_000_000);
+        for value in [1_000_000_000_000, -3, 3, 2_000_000_000_000] {
+            huge_divisor.push(value);
+        }
+        assert_eq!(huge_divisor.count(), 6);
+    }
+}
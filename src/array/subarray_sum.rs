@@ -0,0 +1,266 @@
+// These exersices make extensive use of prefix_sum.rs
+
+use super::prefix_sum::{make_prefix_sum_array, range_sum_naive, range_sum_prefix_sum_arr};
+use std::collections::HashMap;
+
+// Naive implementation of checking how many sub arrays there are in
+// arr that sum to the total of k - I.e. linear combinations of elements
+// that total to k
+// This is the naive implementation so its going to be a brute force
+// every time you check. O(n^3) implementation.
+pub fn count_sub_arrays_sum_k_using_naive_approach(arr: &[i32], k: i32) -> i32 {
+    let mut count = 0;
+    for l in 0..arr.len() {
+        for r in l..arr.len() {
+            let naive_sum_value = range_sum_naive(arr, l, r);
+            if naive_sum_value == k {
+                count += 1;
+            }
+        }
+    }
+    count
+}
+
+// Here we do the same as the naive implementation except we create a
+// prefix_sum_arry and reuse it so we have O(1) checks on the sum_value
+// Theoretically it brings O(n^3) down to O(n^2)
+pub fn count_sub_arrays_sum_k_using_prefix_sum_array(arr: &[i32], k: i32) -> i32 {
+    let prefix_sum_array = make_prefix_sum_array(arr);
+    let mut count = 0;
+    for l in 0..arr.len() {
+        for r in l..arr.len() {
+            let prefix_array_sum_value = range_sum_prefix_sum_arr(&prefix_sum_array, l, r);
+            if prefix_array_sum_value == k {
+                count += 1;
+            }
+        }
+    }
+    count
+}
+
+// THIS IS THE IMPLEMENTATION OF count_subarrays_sum_k TO KNOW:
+// Here we do a hashmap implementation directly with the logic we used to build
+// a prefix the same as the naive implementation except we create a prefix array
+// bringing the overall time down to O(n)
+//
+// This part was stupid hard to understand at least for me:
+// arr_value is technically arr[j] in this discussion
+// 1) prefix_sum[j] is the sum of arr[0]..arr[j]
+// 2) prefix_sum[i] is some sub array of arr[0]..arr[j] that precedes the position j
+// 3) prefix_sum[j] - prefix_sum[i-1] = the sum of arr[i..=j]  (this is normal prefix sum array behaviour still)
+// Note: we saw in range_sum_prefix_sum_arr how this subtraction of the part
+// preceeding the start of prefix_sum[i] from prefix_sum[j] would convert
+// prefix_sum[j] into sum of arr[i..=j] aka
+// "To get the sum from i to j, take the sum up to j, and subtract what came before i."
+// 4) We are trying to count the cases where sum(arr[i..j])==k
+// This means we are effectively trying to count the number of cases as we go where
+// prefix_sum[j] - prefix_sum[i-1] == k
+// **So to put it another way, is if you have prefix_sum[j], then if such a set         <--- THIS IS THE KEY INSIGHT
+// existed before it would have been seen at prefix_sum[i-1] == prefix_sum[j] - k **    <--- THIS IS THE KEY INSIGHT
+// 5) This is the key insight, that we can know if such a set existed at a previous time
+// if we added prefix_sum[j] continually to a hash map while it is running, then the
+// entries in the hashmap effectively become prefix_sum[i] or prefix_sum[i-1]
+// 6) So effectively if the encountering of these totals in the hash map are incremented
+// the hash map can be inspected for (prefix_sum[j] - k) from step 4) for the existance
+// of such a preceding set, and it can be added to the total of matches that add up to k
+// that has been seen.
+pub fn count_sub_arrays_sum_k_using_prefix_and_hashmap(arr: &[i32], k: i32) -> i32 {
+    if arr.is_empty() {
+        return 0;
+    }
+    let mut count = 0;
+    let mut prefix_sum = 0;
+    let mut hash_map: HashMap<i32, i32> = HashMap::new();
+    // This insertion of 0 with value 1 confused me for a long time.
+    // Similar to the prefix_sum_arrays condition when l=0 pivots straight to
+    // pre_sub_arr[r] to return the arr[0..r] total this basically needs the 0
+    // in place for scenarios where the arr[0..j] = k and there is no arr[i-1]
+    // because its from the start of the array, under those conditions the
+    // prefix_sum_i_minus_1 doesn't really exist and therefore evaluates to 0.
+    // This hardcoding ensures that when we go looking in the hashmap that we find
+    // it with 1 so that 1 can be added to the count.
+    hash_map.insert(0, 1);
+    for element_value in arr {
+        prefix_sum += element_value; // prefix_sum[j] is captured.
+        let prefix_sum_i_minus_1 = prefix_sum - k;
+        if let Some(prefix_sum_i_minus_1_seen_count) = hash_map.get(&prefix_sum_i_minus_1) {
+            // We have seen it before
+            count += prefix_sum_i_minus_1_seen_count;
+        }
+        *hash_map.entry(prefix_sum).or_insert(0) += 1; // increment prefix_sum_i_minus_1 seen count.
+    }
+    count
+}
+
+//----
+// My Notes
+// Naive:
+// Visiting each sub array which equals to k would be O(n^2) complexity since you
+// are looping once for each size, and once for each starting position of that size
+//
+// The calculating of their size can be made O(1) using a prefix array.
+//
+// So the question here is how do we change visiting of each sub array into
+// visiting O(n) or less..., the answer is a "sliding window" of 1 which
+// calculates the prefix sum since prefix sums allow us to make O(1) assessments
+// of previously encountered data's relation to current data in a progression.
+//
+// So we wish to know if a sub array exists such that sum of (arr[i]..=arr[n]) = k
+// Prefix arrays encode the sum of arr[i]..=arr[n] as prefix[n] - prefix[i-1]
+// i.e. you chop off the part of the total that is irrelevant.
+// so we are effectively looking for k = prefix[n] - prefix[i-1]
+//
+// So when processing calculating prefix[n] to determine if such a sub array exist
+// that it would sum to k means did we see prefix[i-1] before,
+// prefix[i-1] can be found with some algebra prefix[i-1] = prefix[n] - k.
+//
+// So does this prefix array element exist? this would be a O(n) enquiry on your
+// existing data if you were to loop over it so we need to bring in a hashmap
+// map(prefix[i-1]) = "exists" -> however we want to know more than if it existed
+// before, we wish to know how many times has it existed since all instances would
+// be combinations that can potentially sum to prefix[n] if k is added.
+// Therefore map(prefix[i-1]) does not equal "exists", but instead
+// map(prefix[i-1]) = how many times has the value been seen.
+//
+// Therefore while we process the array for prefix sums, we build a map of how many
+// times we have seen a prefix array position sum to the resulting value.
+
+// In addition to this an edge case exists, this is where i-1 is prior to the start
+// of the array, this comes into play for prefix sums of 0..n, to handle this we
+// need to include a edge case record of prefix sum value 0 being seen 1 times
+// representing the prefix[i-1] that falls out of bounds prior to the start of the
+// array.
+
+// And if I didn't understand my previous paragraph there is this AI generated "refinement"
+// ****
+// Edge case (subarrays that start at index 0):
+// The algebra uses prefix[i-1], and i-1 can be -1 (the empty prefix before the array).
+// By definition prefix[-1] = 0, and there is exactly one such index before scanning.
+// So we initialize the hashmap with map[0] = 1 to count that single prior index.
+// This makes the lookup `map[prefix[j] - k]` automatically count subarrays starting at 0.
+// ****
+
+// And AI suposid "cleanup" version of my notes, but I don't know havent' read
+// over this just leaving it here for now:
+//  {{{{
+// (polished)
+// Naive:
+// Checking every subarray to see if it sums to k is O(n^2):
+//  - Outer loop for start position
+//  - Inner loop for end position
+//
+// Using a prefix-sum array lets you compute any subarray sum in O(1).
+//
+// Goal: reduce visiting every subarray to O(n).
+// Idea: scan once and keep counts of previously seen prefix sums.
+// Let prefix[t] = sum(arr[0..t]) and define prefix[-1] = 0 (empty prefix).
+//
+// sum(arr[i..j]) = prefix[j] - prefix[i-1].
+// We want sum(...) = k, so:
+//   prefix[i-1] = prefix[j] - k
+//
+// While scanning index j (computing prefix[j]):
+//  - The number of subarrays ending at j with sum k equals
+//    map[prefix[j] - k], where map stores counts of prior prefix values.
+//  - Then increment map[prefix[j]].
+//
+// Hashmap definition:
+//  - key   = prefix sum value
+//  - value = how many times that prefix sum has been seen so far
+//
+// Edge case (subarrays that start at index 0):
+//  - prefix[-1] = 0 is a valid prior prefix index (the empty prefix).
+//  - There is exactly one such index before scanning, so initialize map[0] = 1.
+//    That lets the formula map[prefix[j] - k] correctly count subarrays that
+//    begin at index 0 without special-casing.
+//
+// Invariant (useful to reason about correctness):
+//  - Before processing any elements, the hashmap counts prefix[-1]=0 once:
+//    map[0] = 1.
+//
+// Complexity:
+//  - Time: O(n)
+//  - Space: O(n) (for the hashmap)
+//  }}}}
+
+/// This is identical to the challenge_count_sub_arrays_sum_k problem I just added
+/// better notes this time round.
+pub fn subarray_sum(nums: &[i32], k: i32) -> i32 {
+    if nums.is_empty() {
+        return 0;
+    }
+    let mut count = 0;
+    let mut prefix_sum = 0;
+    let mut hash_map: HashMap<i32, i32> = HashMap::new();
+
+    hash_map.insert(0, 1);
+    for element_value in nums {
+        prefix_sum += element_value;
+        let prefix_sum_i_minus_1 = prefix_sum - k;
+        if let Some(prefix_sum_i_minus_1_seen_count) = hash_map.get(&prefix_sum_i_minus_1) {
+            // We have seen it before
+            count += prefix_sum_i_minus_1_seen_count;
+        }
+        *hash_map.entry(prefix_sum).or_insert(0) += 1; // Increment new seen value
+    }
+    count
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_count_subarrays_sum_k_with_naive_approach() {
+        let cases = vec![
+            (vec![], 0, 0),                        // Empty array
+            (vec![1], 1, 1),                       // Single match
+            (vec![1], 2, 0),                       // No match
+            (vec![1, 1, 1], 2, 2),                 // [1,1] x2
+            (vec![1, 2, 3], 3, 2),                 // [1,2], [3]
+            (vec![3, 4, 7, 2, -3, 1, 4, 2], 7, 4), // Multiple subarrays
+            (vec![1, -1, 0], 0, 3),                // [1,-1], [0], [1,-1,0]
+        ];
+
+        for (arr, k, expected) in cases {
+            let result = count_sub_arrays_sum_k_using_naive_approach(&arr, k);
+            assert_eq!(result, expected, "Failed on arr: {:?}, k: {}", arr, k);
+        }
+    }
+
+    #[test]
+    fn test_count_subarrays_sum_k_with_prefix_sum_array() {
+        let cases = vec![
+            (vec![], 0, 0),                        // Empty array
+            (vec![1], 1, 1),                       // Single match
+            (vec![1], 2, 0),                       // No match
+            (vec![1, 1, 1], 2, 2),                 // [1,1] x2
+            (vec![1, 2, 3], 3, 2),                 // [1,2], [3]
+            (vec![3, 4, 7, 2, -3, 1, 4, 2], 7, 4), // Multiple subarrays
+            (vec![1, -1, 0], 0, 3),                // [1,-1], [0], [1,-1,0]
+        ];
+
+        for (arr, k, expected) in cases {
+            let result = count_sub_arrays_sum_k_using_prefix_sum_array(&arr, k);
+            assert_eq!(result, expected, "Failed on arr: {:?}, k: {}", arr, k);
+        }
+    }
+
+    #[test]
+    fn test_count_subarrays_sum_k_with_prefix_and_hashmap() {
+        let cases = vec![
+            (vec![], 0, 0),                        // Empty array
+            (vec![1], 1, 1),                       // Single match
+            (vec![1], 2, 0),                       // No match
+            (vec![1, 1, 1], 2, 2),                 // [1,1] x2
+            (vec![1, 2, 3], 3, 2),                 // [1,2], [3]
+            (vec![3, 4, 7, 2, -3, 1, 4, 2], 7, 4), // Multiple subarrays
+            (vec![1, -1, 0], 0, 3),                // [1,-1], [0], [1,-1,0]
+        ];
+
+        for (arr, k, expected) in cases {
+            let result = count_sub_arrays_sum_k_using_prefix_and_hashmap(&arr, k);
+            assert_eq!(result, expected, "Failed on arr: {:?}, k: {}", arr, k);
+        }
+    }
+}
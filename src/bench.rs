@@ -0,0 +1,43 @@
+// Timing helpers shared by the tutorial benchmarks.
+pub mod baseline;
+pub mod data;
+pub mod export;
+pub mod harness;
+pub mod json;
+pub mod report;
+pub mod session;
+pub mod stats;
+
+use std::time::Duration;
+
+pub fn median_duration_index_u128(arr_durations: &[Duration]) -> usize {
+    if arr_durations.is_empty() {
+        panic!("No results provided in array")
+    }
+
+    // Pair each value with its original index
+    let mut indexed: Vec<(usize, u128)> = arr_durations
+        .iter()
+        .copied()
+        .enumerate()
+        .map(|(i, dur)| (i, dur.as_nanos()))
+        .collect();
+
+    // Sort by value, keeping original indices
+    indexed.sort_unstable_by_key(|&(_, val)| val);
+
+    let mid = arr_durations.len() / 2;
+
+    if arr_durations.len() % 2 == 1 {
+        indexed[mid].0 // Odd: middle element
+    } else {
+        // Even: pick the earlier of the two middle indices (or customize as needed)
+        let i1 = indexed[mid - 1].0;
+        let i2 = indexed[mid].0;
+        if arr_durations[i1] <= arr_durations[i2] {
+            i1
+        } else {
+            i2
+        }
+    }
+}
@@ -0,0 +1,356 @@
+// Compare a run against a saved baseline (a file previously written with --format csv or json).
+// Results are matched on (bench, case, size) and compared by median; anything slower than
+// the baseline by more than the threshold is a regression.
+use super::export::{CSV_HEADER, ResultRow};
+use super::json::{self, Json};
+use super::stats::Stats;
+use std::fs;
+use std::time::Duration;
+
+#[derive(Debug, Clone, Copy)]
+pub struct CompareConfig {
+    // 0.10 = flag anything more than 10% slower than the baseline
+    pub threshold: f64,
+    // ignore differences smaller than this, tiny kernels are mostly timer noise
+    pub noise_floor: Duration,
+}
+
+impl Default for CompareConfig {
+    fn default() -> Self {
+        CompareConfig {
+            threshold: 0.10,
+            noise_floor: Duration::from_micros(1),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Regression {
+    pub bench: String,
+    pub case: String,
+    pub size: usize,
+    pub baseline: Duration,
+    pub current: Duration,
+}
+
+impl Regression {
+    pub fn ratio(&self) -> f64 {
+        self.current.as_nanos() as f64 / self.baseline.as_nanos().max(1) as f64
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Comparison {
+    pub regressions: Vec<Regression>,
+    // matched rows that stayed within the threshold
+    pub unchanged: usize,
+    // rows present in the current run but not in the baseline
+    pub missing_from_baseline: usize,
+}
+
+pub fn load_baseline_file(path: &str) -> Result<Vec<ResultRow>, String> {
+    let text = fs::read_to_string(path).map_err(|e| format!("{}: {}", path, e))?;
+    parse_baseline(&text).map_err(|e| format!("{}: {}", path, e))
+}
+
+// Accepts either export format, JSON documents start with '{'
+pub fn parse_baseline(text: &str) -> Result<Vec<ResultRow>, String> {
+    if text.trim_start().starts_with('{') {
+        parse_json_baseline(text)
+    } else {
+        parse_csv_baseline(text)
+    }
+}
+
+fn parse_json_baseline(text: &str) -> Result<Vec<ResultRow>, String> {
+    let document = json::parse(text)?;
+    let results = document
+        .get("results")
+        .and_then(Json::as_array)
+        .ok_or("missing \"results\" array")?;
+    results
+        .iter()
+        .enumerate()
+        .map(|(i, result)| {
+            let text_field = |key: &str| {
+                result
+                    .get(key)
+                    .and_then(Json::as_str)
+                    .map(str::to_string)
+                    .ok_or(format!("result {}: missing \"{}\"", i, key))
+            };
+            let number_field = |key: &str| {
+                result
+                    .get(key)
+                    .and_then(Json::as_u64)
+                    .ok_or(format!("result {}: missing \"{}\"", i, key))
+            };
+            Ok(ResultRow {
+                bench: text_field("bench")?,
+                case: text_field("case")?,
+                size: number_field("size")? as usize,
+                data_size: number_field("data_size")? as usize,
+                stats: Stats {
+                    min: Duration::from_nanos(number_field("min_ns")?),
+                    median: Duration::from_nanos(number_field("median_ns")?),
+                    mean: Duration::from_nanos(number_field("mean_ns")?),
+                    stddev: Duration::from_nanos(number_field("stddev_ns")?),
+                    p95: Duration::from_nanos(number_field("p95_ns")?),
+                },
+            })
+        })
+        .collect()
+}
+
+// Splits one CSV record, handling "quoted, fields" with "" as an escaped quote
+fn split_csv_line(line: &str) -> Result<Vec<String>, String> {
+    let mut fields = Vec::new();
+    let mut field = String::new();
+    let mut chars = line.chars().peekable();
+    let mut in_quotes = false;
+    while let Some(c) = chars.next() {
+        match (c, in_quotes) {
+            ('"', true) if chars.peek() == Some(&'"') => {
+                chars.next();
+                field.push('"');
+            }
+            ('"', true) => in_quotes = false,
+            ('"', false) if field.is_empty() => in_quotes = true,
+            (',', false) => fields.push(std::mem::take(&mut field)),
+            (c, _) => field.push(c),
+        }
+    }
+    if in_quotes {
+        return Err(format!("unterminated quote in '{}'", line));
+    }
+    fields.push(field);
+    Ok(fields)
+}
+
+// Groups the physical lines into records, a quoted field can hold a newline so a record keeps
+// taking lines while it has an odd number of quotes ("" escapes come in pairs). Returns the
+// index of each record's first line, skipping blank and "#" metadata lines between records.
+fn csv_records(text: &str) -> Vec<(usize, String)> {
+    let mut records = Vec::new();
+    let mut open: Option<(usize, String)> = None;
+    for (line_idx, line) in text.split('\n').enumerate() {
+        let (start_idx, mut record) = match open.take() {
+            Some((start_idx, mut record)) => {
+                record.push('\n');
+                record.push_str(line);
+                (start_idx, record)
+            }
+            None if line.starts_with('#') || line.trim().is_empty() => continue,
+            None => (line_idx, line.to_string()),
+        };
+        if record.matches('"').count() % 2 == 1 {
+            open = Some((start_idx, record));
+        } else {
+            if record.ends_with('\r') {
+                record.pop();
+            }
+            records.push((start_idx, record));
+        }
+    }
+    // An unterminated quote reaches split_csv_line as is and is reported there
+    records.extend(open);
+    records
+}
+
+fn parse_csv_baseline(text: &str) -> Result<Vec<ResultRow>, String> {
+    let mut records = csv_records(text).into_iter();
+    match records.next() {
+        Some((_, header)) if header.trim() == CSV_HEADER => {}
+        _ => return Err(format!("expected header '{}'", CSV_HEADER)),
+    }
+    records
+        .map(|(line_idx, line)| {
+            let line = line.as_str();
+            let fields = split_csv_line(line)?;
+            if fields.len() != 9 {
+                return Err(format!(
+                    "line {}: expected 9 fields, found {}",
+                    line_idx + 1,
+                    fields.len()
+                ));
+            }
+            let number = |idx: usize| {
+                fields[idx]
+                    .trim()
+                    .parse::<u64>()
+                    .map_err(|_| format!("line {}: invalid number '{}'", line_idx + 1, fields[idx]))
+            };
+            Ok(ResultRow {
+                bench: fields[0].clone(),
+                case: fields[1].clone(),
+                size: number(2)? as usize,
+                data_size: number(3)? as usize,
+                stats: Stats {
+                    min: Duration::from_nanos(number(4)?),
+                    median: Duration::from_nanos(number(5)?),
+                    mean: Duration::from_nanos(number(6)?),
+                    stddev: Duration::from_nanos(number(7)?),
+                    p95: Duration::from_nanos(number(8)?),
+                },
+            })
+        })
+        .collect()
+}
+
+pub fn compare(baseline: &[ResultRow], current: &[ResultRow], config: CompareConfig) -> Comparison {
+    let mut comparison = Comparison::default();
+    for row in current {
+        let Some(base) = baseline
+            .iter()
+            .find(|b| b.bench == row.bench && b.case == row.case && b.size == row.size)
+        else {
+            comparison.missing_from_baseline += 1;
+            continue;
+        };
+        let baseline_median = base.stats.median;
+        let current_median = row.stats.median;
+        let allowed = baseline_median.as_nanos() as f64 * (1.0 + config.threshold);
+        let slower_by = current_median.saturating_sub(baseline_median);
+        if current_median.as_nanos() as f64 > allowed && slower_by > config.noise_floor {
+            comparison.regressions.push(Regression {
+                bench: row.bench.clone(),
+                case: row.case.clone(),
+                size: row.size,
+                baseline: baseline_median,
+                current: current_median,
+            });
+        } else {
+            comparison.unchanged += 1;
+        }
+    }
+    comparison
+}
+
+pub fn print_comparison(comparison: &Comparison, config: CompareConfig) {
+    println!(
+        "---> Baseline comparison (threshold {:.1}%, noise floor {:?})",
+        config.threshold * 100.0,
+        config.noise_floor
+    );
+    for regression in &comparison.regressions {
+        println!(
+            "REGRESSION {} / {} @ {}: {} ns -> {} ns ({:.2}x)",
+            regression.bench,
+            regression.case,
+            regression.size,
+            regression.baseline.as_nanos(),
+            regression.current.as_nanos(),
+            regression.ratio()
+        );
+    }
+    println!(
+        "{} regressed, {} within threshold, {} not in baseline",
+        comparison.regressions.len(),
+        comparison.unchanged,
+        comparison.missing_from_baseline
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bench::export::{Metadata, to_csv, to_json};
+    use crate::bench::harness::Bench;
+
+    fn row(case: &str, size: usize, median_ns: u64) -> ResultRow {
+        let median = Duration::from_nanos(median_ns);
+        ResultRow {
+            bench: "bench".to_string(),
+            case: case.to_string(),
+            size,
+            data_size: size,
+            stats: Stats {
+                min: median,
+                median,
+                mean: median,
+                stddev: Duration::ZERO,
+                p95: median,
+            },
+        }
+    }
+
+    fn config() -> CompareConfig {
+        CompareConfig {
+            threshold: 0.10,
+            noise_floor: Duration::from_nanos(100),
+        }
+    }
+
+    #[test]
+    fn flags_only_rows_beyond_threshold_and_noise_floor() {
+        let baseline = vec![row("a", 1, 10_000), row("b", 1, 10_000), row("c", 1, 200)];
+        let current = vec![
+            row("a", 1, 10_900), // 9% slower, fine
+            row("b", 1, 12_000), // 20% slower, regression
+            row("c", 1, 290),    // 45% slower but only 90ns, noise
+            row("d", 1, 1),      // new case
+        ];
+        let comparison = compare(&baseline, &current, config());
+        assert_eq!(comparison.regressions.len(), 1);
+        assert_eq!(comparison.regressions[0].case, "b");
+        assert!((comparison.regressions[0].ratio() - 1.2).abs() < 1e-9);
+        assert_eq!(comparison.unchanged, 2);
+        assert_eq!(comparison.missing_from_baseline, 1);
+    }
+
+    #[test]
+    fn faster_is_never_a_regression() {
+        let comparison = compare(&[row("a", 1, 10_000)], &[row("a", 1, 10)], config());
+        assert!(comparison.regressions.is_empty());
+    }
+
+    #[test]
+    fn csv_and_json_exports_load_back() {
+        let report = Bench::new("rt, \"quoted\"", vec![2, 4], |size| vec![3u32; size])
+            .warmup_iterations(0)
+            .measured_iterations(2)
+            .case("sum", |arr| arr.iter().sum::<u32>())
+            .run();
+        let metadata = Metadata::collect();
+        let expected = crate::bench::export::result_rows(std::slice::from_ref(&report));
+        let from_csv = parse_baseline(&to_csv(std::slice::from_ref(&report), &metadata)).unwrap();
+        let from_json = parse_baseline(&to_json(std::slice::from_ref(&report), &metadata)).unwrap();
+        assert_eq!(from_csv, expected);
+        assert_eq!(from_json, expected);
+        assert!(
+            compare(&from_csv, &expected, config())
+                .regressions
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn rejects_bad_baselines() {
+        assert!(parse_baseline("not,a,header\n").is_err());
+        assert!(parse_baseline(&format!("{}\nb,c,1,1,1,1,1,1\n", CSV_HEADER)).is_err());
+        assert!(parse_baseline(&format!("{}\nb,c,x,1,1,1,1,1,1\n", CSV_HEADER)).is_err());
+        assert!(parse_baseline("{\"metadata\":{}}").is_err());
+    }
+
+    #[test]
+    fn csv_split_handles_quotes() {
+        assert_eq!(
+            split_csv_line("\"a, b\",\"say \"\"hi\"\"\",c").unwrap(),
+            vec!["a, b", "say \"hi\"", "c"]
+        );
+        assert!(split_csv_line("\"open").is_err());
+    }
+
+    #[test]
+    fn csv_titles_with_newlines_and_quotes_load_back() {
+        let report = Bench::new("a\nb, \"c\"\r\n", vec![2], |size| vec![1u32; size])
+            .warmup_iterations(0)
+            .measured_iterations(1)
+            .case("say \"hi\"\nagain", |arr| arr.iter().sum::<u32>())
+            .run();
+        let expected = crate::bench::export::result_rows(std::slice::from_ref(&report));
+        let csv = to_csv(std::slice::from_ref(&report), &Metadata::collect());
+        assert_eq!(parse_baseline(&csv).unwrap(), expected);
+        assert!(parse_baseline(&format!("{}\n\"open,c,1,1,1,1,1,1,1\n", CSV_HEADER)).is_err());
+    }
+}
@@ -0,0 +1,272 @@
+// Machine readable benchmark output. Every report is flattened into one row per
+// (benchmark, case, size) with all timings in nanoseconds regardless of the table unit,
+// plus metadata describing where the numbers came from.
+use super::harness::BenchReport;
+use super::json::Json;
+use super::stats::Stats;
+use std::fs;
+use std::process::Command;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OutputFormat {
+    Table,
+    Csv,
+    Json,
+}
+
+impl OutputFormat {
+    pub fn parse(name: &str) -> Option<OutputFormat> {
+        match name.to_ascii_lowercase().as_str() {
+            "table" => Some(OutputFormat::Table),
+            "csv" => Some(OutputFormat::Csv),
+            "json" => Some(OutputFormat::Json),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Metadata {
+    pub git_commit: Option<String>,
+    pub cpu_model: Option<String>,
+    pub build_profile: String,
+    pub timestamp_unix: u64,
+}
+
+impl Metadata {
+    pub fn collect() -> Metadata {
+        Metadata {
+            git_commit: git_commit(),
+            cpu_model: cpu_model(),
+            build_profile: build_profile().to_string(),
+            timestamp_unix: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|d| d.as_secs())
+                .unwrap_or(0),
+        }
+    }
+
+    fn fields(&self) -> Vec<(&'static str, String)> {
+        vec![
+            ("git_commit", self.git_commit.clone().unwrap_or_default()),
+            ("cpu_model", self.cpu_model.clone().unwrap_or_default()),
+            ("build_profile", self.build_profile.clone()),
+            ("timestamp_unix", self.timestamp_unix.to_string()),
+        ]
+    }
+}
+
+fn git_commit() -> Option<String> {
+    let output = Command::new("git")
+        .args(["rev-parse", "HEAD"])
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    let commit = String::from_utf8(output.stdout).ok()?.trim().to_string();
+    (!commit.is_empty()).then_some(commit)
+}
+
+pub fn cpu_model() -> Option<String> {
+    let cpuinfo = fs::read_to_string("/proc/cpuinfo").ok()?;
+    cpu_model_from_cpuinfo(&cpuinfo)
+}
+
+fn cpu_model_from_cpuinfo(cpuinfo: &str) -> Option<String> {
+    cpuinfo
+        .lines()
+        .filter_map(|line| line.split_once(':'))
+        .find(|(key, _)| key.trim() == "model name")
+        .map(|(_, value)| value.trim().to_string())
+}
+
+pub fn build_profile() -> &'static str {
+    if cfg!(debug_assertions) {
+        "debug"
+    } else {
+        "release"
+    }
+}
+
+// One measured case at one size, the unit shared by the exporters and the baseline compare
+#[derive(Debug, Clone, PartialEq)]
+pub struct ResultRow {
+    pub bench: String,
+    pub case: String,
+    pub size: usize,
+    pub data_size: usize,
+    pub stats: Stats,
+}
+
+pub fn result_rows(reports: &[BenchReport]) -> Vec<ResultRow> {
+    let mut rows = Vec::new();
+    for report in reports {
+        for size_result in &report.rows {
+            for (case, stats) in report.case_names.iter().zip(&size_result.stats) {
+                rows.push(ResultRow {
+                    bench: report.title.clone(),
+                    case: case.clone(),
+                    size: size_result.size,
+                    data_size: size_result.data_size,
+                    stats: *stats,
+                });
+            }
+        }
+    }
+    rows
+}
+
+pub const CSV_HEADER: &str = "bench,case,size,data_size,min_ns,median_ns,mean_ns,stddev_ns,p95_ns";
+
+fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+// Metadata goes in leading "# key=value" comment lines so the body stays a plain table
+pub fn to_csv(reports: &[BenchReport], metadata: &Metadata) -> String {
+    let mut out = String::new();
+    for (key, value) in metadata.fields() {
+        out.push_str(&format!("# {}={}\n", key, value));
+    }
+    out.push_str(CSV_HEADER);
+    out.push('\n');
+    for row in result_rows(reports) {
+        let stats = row.stats;
+        out.push_str(&format!(
+            "{},{},{},{},{},{},{},{},{}\n",
+            csv_field(&row.bench),
+            csv_field(&row.case),
+            row.size,
+            row.data_size,
+            stats.min.as_nanos(),
+            stats.median.as_nanos(),
+            stats.mean.as_nanos(),
+            stats.stddev.as_nanos(),
+            stats.p95.as_nanos(),
+        ));
+    }
+    out
+}
+
+fn nanos_json(duration: Duration) -> Json {
+    Json::Number(duration.as_nanos() as f64)
+}
+
+pub fn to_json(reports: &[BenchReport], metadata: &Metadata) -> String {
+    let metadata_json = Json::Object(vec![
+        (
+            "git_commit".to_string(),
+            metadata.git_commit.as_deref().into(),
+        ),
+        (
+            "cpu_model".to_string(),
+            metadata.cpu_model.as_deref().into(),
+        ),
+        (
+            "build_profile".to_string(),
+            metadata.build_profile.as_str().into(),
+        ),
+        ("timestamp_unix".to_string(), metadata.timestamp_unix.into()),
+    ]);
+    let results = result_rows(reports)
+        .into_iter()
+        .map(|row| {
+            Json::Object(vec![
+                ("bench".to_string(), row.bench.as_str().into()),
+                ("case".to_string(), row.case.as_str().into()),
+                ("size".to_string(), row.size.into()),
+                ("data_size".to_string(), row.data_size.into()),
+                ("min_ns".to_string(), nanos_json(row.stats.min)),
+                ("median_ns".to_string(), nanos_json(row.stats.median)),
+                ("mean_ns".to_string(), nanos_json(row.stats.mean)),
+                ("stddev_ns".to_string(), nanos_json(row.stats.stddev)),
+                ("p95_ns".to_string(), nanos_json(row.stats.p95)),
+            ])
+        })
+        .collect();
+    let document = Json::Object(vec![
+        ("metadata".to_string(), metadata_json),
+        ("results".to_string(), Json::Array(results)),
+    ]);
+    format!("{}\n", document)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bench::harness::Bench;
+
+    fn metadata() -> Metadata {
+        Metadata {
+            git_commit: Some("abc123".to_string()),
+            cpu_model: None,
+            build_profile: "release".to_string(),
+            timestamp_unix: 1_700_000_000,
+        }
+    }
+
+    fn report() -> BenchReport {
+        Bench::new("sum, squared", vec![4, 8], |size| vec![1u64; size])
+            .warmup_iterations(0)
+            .measured_iterations(3)
+            .case("iter \"sum\"", |arr| arr.iter().sum::<u64>())
+            .case("max", |arr| arr.iter().max().copied())
+            .run()
+    }
+
+    #[test]
+    fn cpu_model_is_read_from_model_name_line() {
+        let cpuinfo = "processor\t: 0\nvendor_id\t: GenuineIntel\nmodel name\t: Intel(R) Core(TM) i7 CPU\nflags\t\t: fpu\n";
+        assert_eq!(
+            cpu_model_from_cpuinfo(cpuinfo),
+            Some("Intel(R) Core(TM) i7 CPU".to_string())
+        );
+        assert_eq!(cpu_model_from_cpuinfo("processor : 0\n"), None);
+    }
+
+    #[test]
+    fn csv_has_metadata_header_and_one_row_per_case_and_size() {
+        let csv = to_csv(&[report()], &metadata());
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(lines[0], "# git_commit=abc123");
+        assert_eq!(lines[1], "# cpu_model=");
+        assert_eq!(lines[2], "# build_profile=release");
+        assert_eq!(lines[3], "# timestamp_unix=1700000000");
+        assert_eq!(lines[4], CSV_HEADER);
+        assert_eq!(lines.len(), 5 + 2 * 2);
+        assert!(lines[5].starts_with("\"sum, squared\",\"iter \"\"sum\"\"\",4,4,"));
+        assert!(lines[6].starts_with("\"sum, squared\",max,4,4,"));
+        assert!(lines[8].starts_with("\"sum, squared\",max,8,8,"));
+    }
+
+    #[test]
+    fn json_contains_metadata_and_results() {
+        let text = to_json(&[report()], &metadata());
+        let document = crate::bench::json::parse(&text).unwrap();
+        let metadata = document.get("metadata").unwrap();
+        assert_eq!(metadata.get("git_commit").unwrap().as_str(), Some("abc123"));
+        assert_eq!(metadata.get("cpu_model"), Some(&Json::Null));
+        let results = document.get("results").unwrap().as_array().unwrap();
+        assert_eq!(results.len(), 4);
+        assert_eq!(
+            results[0].get("case").unwrap().as_str(),
+            Some("iter \"sum\"")
+        );
+        assert_eq!(results[3].get("size").unwrap().as_u64(), Some(8));
+        assert!(results[3].get("median_ns").unwrap().as_u64().is_some());
+    }
+
+    #[test]
+    fn output_format_names() {
+        assert_eq!(OutputFormat::parse("CSV"), Some(OutputFormat::Csv));
+        assert_eq!(OutputFormat::parse("json"), Some(OutputFormat::Json));
+        assert_eq!(OutputFormat::parse("table"), Some(OutputFormat::Table));
+        assert_eq!(OutputFormat::parse("xml"), None);
+    }
+}
@@ -0,0 +1,456 @@
+// Benchmark harness replacing the "loop 10 times, push start.elapsed(), pick the median" pattern
+// that every tutorial module used to hand roll.
+//
+// A Bench sweeps a list of sizes. For every size the setup closure builds the input once, then
+// each registered case is run for `warmup_iterations` untimed and `measured_iterations` timed runs.
+// Inputs and outputs go through black_box so the optimizer can't hoist or delete the work.
+//
+//  let report = Bench::new("Row vs Column sum", vec![256, 512], |size| make_matrix(size, size, 1))
+//      .case("Column-based", |matrix| col_sum_matrix(matrix))
+//      .case("Row-based", |matrix| row_sum_matrix(matrix))
+//      .compare("Column-based", "Row-based")
+//      .run();
+//  report.print_ratio_table();
+use super::report::{TimeUnit, print_header, print_output_row_ratio_compare_result};
+use super::session;
+use super::stats::Stats;
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+#[derive(Debug, Clone, Copy)]
+pub struct BenchConfig {
+    pub warmup_iterations: usize,
+    pub measured_iterations: usize,
+    pub unit: TimeUnit,
+}
+
+impl Default for BenchConfig {
+    fn default() -> Self {
+        BenchConfig {
+            warmup_iterations: 2,
+            measured_iterations: 10,
+            unit: TimeUnit::Nanos,
+        }
+    }
+}
+
+// Each case times itself so cases that need a fresh copy of the input can clone outside the timer
+type TimedRoutine<'a, I> = Box<dyn FnMut(&I) -> Duration + 'a>;
+
+struct Case<'a, I> {
+    name: String,
+    routine: TimedRoutine<'a, I>,
+    // overrides BenchConfig::measured_iterations, for cases too fast to time in a handful of runs
+    measured_iterations: Option<usize>,
+}
+
+pub struct Bench<'a, I> {
+    title: String,
+    sizes: Vec<usize>,
+    size_column: String,
+    data_size: Box<dyn Fn(usize) -> usize + 'a>,
+    setup: Box<dyn FnMut(usize) -> I + 'a>,
+    cases: Vec<Case<'a, I>>,
+    comparisons: Vec<(String, String)>,
+    config: BenchConfig,
+}
+
+impl<'a, I> Bench<'a, I> {
+    pub fn new(title: &str, sizes: Vec<usize>, setup: impl FnMut(usize) -> I + 'a) -> Self {
+        Bench {
+            title: title.to_string(),
+            sizes,
+            size_column: "Data Size".to_string(),
+            data_size: Box::new(|size| size),
+            setup: Box::new(setup),
+            cases: Vec::new(),
+            comparisons: Vec::new(),
+            config: BenchConfig::default(),
+        }
+    }
+
+    pub fn config(mut self, config: BenchConfig) -> Self {
+        self.config = config;
+        self
+    }
+
+    pub fn warmup_iterations(mut self, warmup_iterations: usize) -> Self {
+        self.config.warmup_iterations = warmup_iterations;
+        self
+    }
+
+    pub fn measured_iterations(mut self, measured_iterations: usize) -> Self {
+        self.config.measured_iterations = measured_iterations;
+        self
+    }
+
+    pub fn unit(mut self, unit: TimeUnit) -> Self {
+        self.config.unit = unit;
+        self
+    }
+
+    // Header of the first column in the tables, e.g. "Data Size^2" for square matrices
+    pub fn size_column(mut self, size_column: &str) -> Self {
+        self.size_column = size_column.to_string();
+        self
+    }
+
+    // What gets printed in the size column, e.g. the element count of a matrix of side `size`
+    pub fn data_size(mut self, data_size: impl Fn(usize) -> usize + 'a) -> Self {
+        self.data_size = Box::new(data_size);
+        self
+    }
+
+    // Run `routine` against the shared input built by setup. Drops of the output are not timed.
+    pub fn case<R>(mut self, name: &str, mut routine: impl FnMut(&I) -> R + 'a) -> Self {
+        self.assert_unique_case(name);
+        self.cases.push(Case {
+            name: name.to_string(),
+            routine: Box::new(move |input| {
+                let start = Instant::now();
+                let output = black_box(routine(black_box(input)));
+                let elapsed = start.elapsed();
+                drop(output);
+                elapsed
+            }),
+            measured_iterations: None,
+        });
+        self
+    }
+
+    // Run `routine` against a fresh clone of the input each iteration, for in-place kernels.
+    // The clone (and its drop) happens outside of the timed region.
+    pub fn case_mut<R>(mut self, name: &str, mut routine: impl FnMut(&mut I) -> R + 'a) -> Self
+    where
+        I: Clone,
+    {
+        self.assert_unique_case(name);
+        self.cases.push(Case {
+            name: name.to_string(),
+            routine: Box::new(move |input| {
+                let mut fresh = input.clone();
+                let start = Instant::now();
+                let output = black_box(routine(black_box(&mut fresh)));
+                let elapsed = start.elapsed();
+                drop(output);
+                drop(fresh);
+                elapsed
+            }),
+            measured_iterations: None,
+        });
+        self
+    }
+
+    // Hand `routine` its own clone of the input by value, for kernels that consume their input
+    // (e.g. the transposes). Again the clone happens outside of the timed region.
+    pub fn case_owned<R>(mut self, name: &str, mut routine: impl FnMut(I) -> R + 'a) -> Self
+    where
+        I: Clone,
+    {
+        self.assert_unique_case(name);
+        self.cases.push(Case {
+            name: name.to_string(),
+            routine: Box::new(move |input| {
+                let fresh = input.clone();
+                let start = Instant::now();
+                let output = black_box(routine(black_box(fresh)));
+                let elapsed = start.elapsed();
+                drop(output);
+                elapsed
+            }),
+            measured_iterations: None,
+        });
+        self
+    }
+
+    pub fn case_iterations(mut self, name: &str, measured_iterations: usize) -> Self {
+        match self.cases.iter_mut().find(|case| case.name == name) {
+            Some(case) => case.measured_iterations = Some(measured_iterations),
+            None => panic!("case_iterations: no case named '{}' registered", name),
+        }
+        self
+    }
+
+    // Adds a (baseline, candidate) column triple to the ratio table: baseline / candidate
+    pub fn compare(mut self, baseline: &str, candidate: &str) -> Self {
+        for name in [baseline, candidate] {
+            if !self.cases.iter().any(|case| case.name == name) {
+                panic!("compare: no case named '{}' registered", name);
+            }
+        }
+        self.comparisons
+            .push((baseline.to_string(), candidate.to_string()));
+        self
+    }
+
+    fn assert_unique_case(&self, name: &str) {
+        if self.cases.iter().any(|case| case.name == name) {
+            panic!("case '{}' registered twice", name);
+        }
+    }
+
+    pub fn run(mut self) -> BenchReport {
+        let zero_iterations = self.config.measured_iterations == 0
+            || self
+                .cases
+                .iter()
+                .any(|case| case.measured_iterations == Some(0));
+        if zero_iterations {
+            panic!("measured_iterations must be at least 1");
+        }
+        let mut rows: Vec<SizeResult> = Vec::with_capacity(self.sizes.len());
+        for &size in &self.sizes {
+            let input = (self.setup)(size);
+            let mut stats: Vec<Stats> = Vec::with_capacity(self.cases.len());
+            for case in self.cases.iter_mut() {
+                for _ in 0..self.config.warmup_iterations {
+                    (case.routine)(&input);
+                }
+                let measured_iterations = case
+                    .measured_iterations
+                    .unwrap_or(self.config.measured_iterations);
+                let samples: Vec<Duration> = (0..measured_iterations)
+                    .map(|_| (case.routine)(&input))
+                    .collect();
+                stats.push(Stats::from_samples(&samples));
+            }
+            rows.push(SizeResult {
+                size,
+                data_size: (self.data_size)(size),
+                stats,
+            });
+        }
+        let report = BenchReport {
+            title: self.title,
+            size_column: self.size_column,
+            config: self.config,
+            case_names: self.cases.into_iter().map(|case| case.name).collect(),
+            comparisons: self.comparisons,
+            rows,
+        };
+        session::record(&report);
+        report
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct SizeResult {
+    pub size: usize,
+    pub data_size: usize,
+    // one entry per case, same order as BenchReport::case_names
+    pub stats: Vec<Stats>,
+}
+
+#[derive(Debug, Clone)]
+pub struct BenchReport {
+    pub title: String,
+    pub size_column: String,
+    pub config: BenchConfig,
+    pub case_names: Vec<String>,
+    pub comparisons: Vec<(String, String)>,
+    pub rows: Vec<SizeResult>,
+}
+
+impl BenchReport {
+    pub fn case_index(&self, name: &str) -> Option<usize> {
+        self.case_names
+            .iter()
+            .position(|case_name| case_name == name)
+    }
+
+    pub fn stats(&self, case_name: &str, size: usize) -> Option<Stats> {
+        let case_idx = self.case_index(case_name)?;
+        self.rows
+            .iter()
+            .find(|row| row.size == size)
+            .map(|row| row.stats[case_idx])
+    }
+
+    // The classic tutorial table: size, then "baseline | candidate | ratio" per comparison (medians)
+    pub fn print_ratio_table(&self) {
+        self.print_ratio_table_using(|stats| stats.median);
+    }
+
+    // Same table but with a different statistic, e.g. the mean for cases near the timer resolution
+    pub fn print_ratio_table_using(&self, statistic: impl Fn(&Stats) -> Duration) {
+        println!("---> {}", self.title);
+        let suffix = self.config.unit.suffix();
+        let mut columns: Vec<String> = vec![self.size_column.clone()];
+        for (baseline, candidate) in &self.comparisons {
+            columns.push(format!("{} ({})", baseline, suffix));
+            columns.push(format!("{} ({})", candidate, suffix));
+            // Ratio values get a bit big so hacking a bit of space
+            columns.push("      Ratio".to_string());
+        }
+        let columns: Vec<&str> = columns.iter().map(|c| c.as_str()).collect();
+        print_header(&columns);
+
+        let pairs: Vec<(usize, usize)> = self
+            .comparisons
+            .iter()
+            .map(|(baseline, candidate)| {
+                (
+                    self.case_index(baseline).unwrap(),
+                    self.case_index(candidate).unwrap(),
+                )
+            })
+            .collect();
+        for row in &self.rows {
+            let duration_data: Vec<(Duration, Duration)> = pairs
+                .iter()
+                .map(|&(b, c)| (statistic(&row.stats[b]), statistic(&row.stats[c])))
+                .collect();
+            print_output_row_ratio_compare_result(
+                &columns,
+                row.data_size,
+                &duration_data,
+                self.config.unit,
+            );
+        }
+    }
+
+    // One line per (size, case) with the full set of statistics
+    pub fn print_summary(&self) {
+        println!("---> {} (summary)", self.title);
+        let suffix = self.config.unit.suffix();
+        let case_width = self
+            .case_names
+            .iter()
+            .map(|name| name.chars().count())
+            .max()
+            .unwrap_or(0)
+            .max(4);
+        let size_width = self.size_column.chars().count();
+        println!(
+            " {:<size_width$} | {:<case_width$} | {:>12} | {:>12} | {:>12} | {:>12} | {:>12}",
+            self.size_column,
+            "Case",
+            format!("Min ({})", suffix),
+            format!("Median ({})", suffix),
+            format!("Mean ({})", suffix),
+            format!("Stddev ({})", suffix),
+            format!("p95 ({})", suffix),
+        );
+        println!(
+            "{}|{}|{}",
+            "-".repeat(size_width + 2),
+            "-".repeat(case_width + 2),
+            vec!["-".repeat(14); 5].join("|")
+        );
+        let unit = self.config.unit;
+        for row in &self.rows {
+            for (name, stats) in self.case_names.iter().zip(&row.stats) {
+                println!(
+                    " {:<size_width$} | {:<case_width$} | {:>12} | {:>12} | {:>12} | {:>12} | {:>12}",
+                    row.data_size,
+                    name,
+                    unit.value(stats.min),
+                    unit.value(stats.median),
+                    unit.value(stats.mean),
+                    unit.value(stats.stddev),
+                    unit.value(stats.p95),
+                );
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::Cell;
+
+    #[test]
+    fn runs_warmup_and_measured_iterations_per_size() {
+        let setup_calls = Cell::new(0);
+        let case_calls = Cell::new(0);
+        let report = Bench::new("counting", vec![1, 2, 4], |size| {
+            setup_calls.set(setup_calls.get() + 1);
+            vec![0u8; size]
+        })
+        .warmup_iterations(3)
+        .measured_iterations(5)
+        .case("len", |input| {
+            case_calls.set(case_calls.get() + 1);
+            input.len()
+        })
+        .run();
+
+        assert_eq!(setup_calls.get(), 3);
+        assert_eq!(case_calls.get(), 3 * (3 + 5));
+        assert_eq!(report.rows.len(), 3);
+        assert_eq!(report.case_names, vec!["len".to_string()]);
+    }
+
+    #[test]
+    fn case_iterations_override() {
+        let fast_calls = Cell::new(0);
+        let _ = Bench::new("override", vec![1], |size| size)
+            .warmup_iterations(0)
+            .measured_iterations(2)
+            .case("slow", |n| *n)
+            .case("fast", |n| {
+                fast_calls.set(fast_calls.get() + 1);
+                *n
+            })
+            .case_iterations("fast", 50)
+            .run();
+        assert_eq!(fast_calls.get(), 50);
+    }
+
+    #[test]
+    fn case_mut_gets_fresh_input_every_iteration() {
+        let report = Bench::new("fresh", vec![4], |size| vec![1; size])
+            .warmup_iterations(1)
+            .measured_iterations(3)
+            .case_mut("zero_first", |input: &mut Vec<i32>| {
+                assert_eq!(input[0], 1, "input was not reset between iterations");
+                input[0] = 0;
+            })
+            .run();
+        assert!(report.stats("zero_first", 4).is_some());
+    }
+
+    #[test]
+    fn case_owned_gets_its_own_copy() {
+        let report = Bench::new("owned", vec![3], |size| vec![7; size])
+            .measured_iterations(2)
+            .case_owned("into_sum", |input: Vec<i32>| input.into_iter().sum::<i32>())
+            .case("still_intact", |input| assert_eq!(input, &vec![7; 3]))
+            .run();
+        assert_eq!(report.case_names.len(), 2);
+    }
+
+    #[test]
+    fn data_size_and_lookup() {
+        let report = Bench::new("square", vec![2, 3], |size| size)
+            .data_size(|size| size * size)
+            .case("a", |n| *n + 1)
+            .case("b", |n| *n * 2)
+            .compare("a", "b")
+            .run();
+        assert_eq!(report.rows[0].data_size, 4);
+        assert_eq!(report.rows[1].data_size, 9);
+        assert_eq!(report.case_index("b"), Some(1));
+        assert!(report.stats("a", 3).is_some());
+        assert!(report.stats("a", 5).is_none());
+        assert!(report.stats("missing", 2).is_none());
+        assert_eq!(report.comparisons, vec![("a".to_string(), "b".to_string())]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn compare_unknown_case() {
+        let _ = Bench::new("bad", vec![1], |size| size)
+            .case("a", |n| *n)
+            .compare("a", "nope");
+    }
+
+    #[test]
+    #[should_panic]
+    fn duplicate_case_name() {
+        let _ = Bench::new("bad", vec![1], |size| size)
+            .case("a", |n| *n)
+            .case("a", |n| *n);
+    }
+}
@@ -0,0 +1,92 @@
+use std::time::Duration;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TimeUnit {
+    Nanos,
+    Micros,
+}
+
+impl TimeUnit {
+    pub fn suffix(&self) -> &'static str {
+        match self {
+            TimeUnit::Nanos => "ns",
+            TimeUnit::Micros => "µs",
+        }
+    }
+
+    pub fn value(&self, duration: Duration) -> u128 {
+        match self {
+            TimeUnit::Nanos => duration.as_nanos(),
+            TimeUnit::Micros => duration.as_micros(),
+        }
+    }
+}
+
+pub fn print_header_one_metric_result() {
+    println!("Data size | Time (µs)");
+    println!("----------|----------");
+}
+
+pub fn print_output_one_metric_result(element_count: usize, time: Duration) {
+    let time_uq = time.as_micros() as f64;
+    println!("{:<9} | {:>9} ", element_count, time_uq);
+}
+
+pub fn print_header(col_names: &[&str]) {
+    let header = format!(
+        " {} \n{}\n",          // Combination
+        col_names.join(" | "), // Header Text
+        col_names
+            .iter()
+            .map(|name| "-".repeat(name.chars().count() + 2))
+            .collect::<Vec<String>>()
+            .join("|")  // Line
+    );
+    print!("{}", header);
+}
+
+pub fn print_output_row_ratio_compare_result_micros(
+    col_names: &[&str],
+    size: usize,
+    duration_data: Vec<(Duration, Duration)>,
+) {
+    print_output_row_ratio_compare_result(col_names, size, &duration_data, TimeUnit::Micros);
+}
+
+pub fn print_output_row_ratio_compare_result_nanos(
+    col_names: &[&str],
+    size: usize,
+    duration_data: Vec<(Duration, Duration)>,
+) {
+    print_output_row_ratio_compare_result(col_names, size, &duration_data, TimeUnit::Nanos);
+}
+
+// col_names is laid out as the size column followed by a (first, second, ratio) triple per pair
+pub fn print_output_row_ratio_compare_result(
+    col_names: &[&str],
+    size: usize,
+    duration_data: &[(Duration, Duration)],
+    unit: TimeUnit,
+) {
+    // print size first
+    print!(" {:<1$} ", size, col_names[0].chars().count());
+    for (i, durations) in duration_data.iter().enumerate() {
+        let first = unit.value(durations.0);
+        let second = unit.value(durations.1);
+        let ratio = { durations.0.as_nanos().max(1) as f64 / durations.1.as_nanos().max(1) as f64 };
+        let column_base_idx = i * 3;
+        let first_column_width = col_names[column_base_idx + 1].chars().count();
+        let second_column_width = col_names[column_base_idx + 2].chars().count();
+        let ratio_column_width = col_names[column_base_idx + 3].chars().count();
+        print!(
+            "| {0:>1$} | {2:3$} | {4:5$.1}x ",
+            first,
+            first_column_width,
+            second,
+            second_column_width,
+            ratio,
+            ratio_column_width - 1 // -1 for the x
+        );
+    }
+    println!()
+}
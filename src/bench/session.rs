@@ -0,0 +1,48 @@
+// Collects every BenchReport produced while a session is active so main can export them
+// (CSV / JSON) or compare them against a baseline once the tutorial modules have run.
+// Off by default so the harness doesn't hold on to reports nobody asked for.
+use super::harness::BenchReport;
+use std::sync::Mutex;
+
+static SESSION: Mutex<Option<Vec<BenchReport>>> = Mutex::new(None);
+
+pub fn start() {
+    *SESSION.lock().unwrap() = Some(Vec::new());
+}
+
+pub fn is_active() -> bool {
+    SESSION.lock().unwrap().is_some()
+}
+
+// Called by Bench::run, a no-op unless start() was called
+pub fn record(report: &BenchReport) {
+    if let Some(reports) = SESSION.lock().unwrap().as_mut() {
+        reports.push(report.clone());
+    }
+}
+
+// Ends the session and hands back everything recorded since start()
+pub fn finish() -> Vec<BenchReport> {
+    SESSION.lock().unwrap().take().unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bench::harness::Bench;
+
+    // Other tests run benches in parallel, so only look for our own report
+    #[test]
+    fn records_reports_between_start_and_finish() {
+        start();
+        assert!(is_active());
+        Bench::new("session test bench", vec![1], |size| size)
+            .measured_iterations(1)
+            .case("noop", |n| *n)
+            .run();
+        let reports = finish();
+        assert!(!is_active());
+        assert!(reports.iter().any(|r| r.title == "session test bench"));
+        assert!(finish().is_empty());
+    }
+}
@@ -0,0 +1,103 @@
+// Summary statistics over the samples collected for one benchmark case at one size.
+use std::time::Duration;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Stats {
+    pub min: Duration,
+    pub median: Duration,
+    pub mean: Duration,
+    pub stddev: Duration,
+    pub p95: Duration,
+}
+
+impl Stats {
+    pub fn from_samples(samples: &[Duration]) -> Stats {
+        if samples.is_empty() {
+            panic!("No samples provided")
+        }
+        let mut sorted: Vec<Duration> = samples.to_vec();
+        sorted.sort_unstable();
+
+        let count = sorted.len();
+        let mean_ns = sorted.iter().map(|d| d.as_nanos()).sum::<u128>() as f64 / count as f64;
+        // population variance, the samples are all the runs we have
+        let variance_ns = sorted
+            .iter()
+            .map(|d| {
+                let delta = d.as_nanos() as f64 - mean_ns;
+                delta * delta
+            })
+            .sum::<f64>()
+            / count as f64;
+
+        let median = if count % 2 == 1 {
+            sorted[count / 2]
+        } else {
+            // Even: average the two middle samples
+            (sorted[count / 2 - 1] + sorted[count / 2]) / 2
+        };
+
+        // nearest-rank percentile: smallest sample with at least 95% of samples <= it
+        let p95_rank = (count * 95).div_ceil(100).max(1);
+
+        Stats {
+            min: sorted[0],
+            median,
+            mean: Duration::from_nanos(mean_ns.round() as u64),
+            stddev: Duration::from_nanos(variance_ns.sqrt().round() as u64),
+            p95: sorted[p95_rank - 1],
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn nanos(values: &[u64]) -> Vec<Duration> {
+        values.iter().map(|&v| Duration::from_nanos(v)).collect()
+    }
+
+    #[test]
+    fn single_sample() {
+        let stats = Stats::from_samples(&nanos(&[42]));
+        assert_eq!(stats.min, Duration::from_nanos(42));
+        assert_eq!(stats.median, Duration::from_nanos(42));
+        assert_eq!(stats.mean, Duration::from_nanos(42));
+        assert_eq!(stats.stddev, Duration::ZERO);
+        assert_eq!(stats.p95, Duration::from_nanos(42));
+    }
+
+    #[test]
+    fn unsorted_odd_samples() {
+        let stats = Stats::from_samples(&nanos(&[9, 1, 5, 3, 7]));
+        assert_eq!(stats.min, Duration::from_nanos(1));
+        assert_eq!(stats.median, Duration::from_nanos(5));
+        assert_eq!(stats.mean, Duration::from_nanos(5));
+        // sqrt(((4^2 + 2^2) * 2) / 5) = sqrt(8) = 2.83
+        assert_eq!(stats.stddev, Duration::from_nanos(3));
+        assert_eq!(stats.p95, Duration::from_nanos(9));
+    }
+
+    #[test]
+    fn even_samples_median_is_average_of_middle_pair() {
+        let stats = Stats::from_samples(&nanos(&[10, 40, 20, 30]));
+        assert_eq!(stats.median, Duration::from_nanos(25));
+        assert_eq!(stats.mean, Duration::from_nanos(25));
+    }
+
+    #[test]
+    fn p95_ignores_single_outlier_in_large_sample() {
+        let mut values: Vec<u64> = (1..=100).collect();
+        values[99] = 1_000_000;
+        let stats = Stats::from_samples(&nanos(&values));
+        assert_eq!(stats.p95, Duration::from_nanos(95));
+        assert_eq!(stats.min, Duration::from_nanos(1));
+    }
+
+    #[test]
+    #[should_panic]
+    fn empty_samples() {
+        Stats::from_samples(&[]);
+    }
+}
@@ -0,0 +1,10 @@
+// Software model of a CPU cache hierarchy, so the access pattern experiments from module 6
+// (row vs column sums, naive vs tiled transpose and matmul) can be explained with exact hit and
+// miss counts per level instead of noisy timings or hardware counters.
+//
+//  let mut caches = CacheHierarchy::typical();
+//  row_sum_matrix_simulated(&matrix, &mut caches);
+//  caches.print_report("Row-based sum");
+pub mod cache;
+pub mod hierarchy;
+pub mod kernels;
@@ -0,0 +1,300 @@
+// A single set-associative cache level. Addresses are split into line / set / tag, each set
+// holds `ways` lines and picks a victim with either true LRU or tree pseudo-LRU.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReplacementPolicy {
+    Lru,
+    // binary tree of `ways - 1` bits pointing away from the most recently used half,
+    // what most real L1/L2 caches implement instead of exact LRU
+    TreePlru,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CacheConfig {
+    pub size_bytes: usize,
+    pub line_size: usize,
+    pub ways: usize,
+    pub policy: ReplacementPolicy,
+}
+
+impl CacheConfig {
+    pub fn new(size_bytes: usize, line_size: usize, ways: usize) -> CacheConfig {
+        CacheConfig {
+            size_bytes,
+            line_size,
+            ways,
+            policy: ReplacementPolicy::Lru,
+        }
+    }
+
+    pub fn policy(mut self, policy: ReplacementPolicy) -> CacheConfig {
+        self.policy = policy;
+        self
+    }
+
+    pub fn set_count(&self) -> usize {
+        self.size_bytes / (self.line_size * self.ways)
+    }
+
+    pub fn validate(&self) -> Result<(), String> {
+        if !self.line_size.is_power_of_two() {
+            return Err(format!(
+                "line size {} is not a power of two",
+                self.line_size
+            ));
+        }
+        if self.ways == 0
+            || self.size_bytes == 0
+            || !self.size_bytes.is_multiple_of(self.line_size * self.ways)
+        {
+            return Err(format!(
+                "{} bytes can't be split into {}-way sets of {} byte lines",
+                self.size_bytes, self.ways, self.line_size
+            ));
+        }
+        if self.policy == ReplacementPolicy::TreePlru && !self.ways.is_power_of_two() {
+            return Err(format!(
+                "tree PLRU needs a power of two ways, got {}",
+                self.ways
+            ));
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CacheStats {
+    pub hits: u64,
+    pub misses: u64,
+    pub evictions: u64,
+}
+
+impl CacheStats {
+    pub fn accesses(&self) -> u64 {
+        self.hits + self.misses
+    }
+
+    pub fn hit_rate(&self) -> f64 {
+        if self.accesses() == 0 {
+            0.0
+        } else {
+            self.hits as f64 / self.accesses() as f64
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+struct CacheSet {
+    tags: Vec<Option<u64>>,
+    // Lru: last access stamp per way, TreePlru: the tree bits (ways - 1 of them)
+    recency: Vec<u64>,
+}
+
+#[derive(Debug, Clone)]
+pub struct SetAssociativeCache {
+    config: CacheConfig,
+    sets: Vec<CacheSet>,
+    line_shift: u32,
+    // not always a power of two (a 105M 15-way L3 has 114688 sets) so sets are picked by modulo
+    set_count: u64,
+    clock: u64,
+    stats: CacheStats,
+}
+
+impl SetAssociativeCache {
+    pub fn new(config: CacheConfig) -> Result<SetAssociativeCache, String> {
+        config.validate()?;
+        let recency_len = match config.policy {
+            ReplacementPolicy::Lru => config.ways,
+            ReplacementPolicy::TreePlru => config.ways - 1,
+        };
+        Ok(SetAssociativeCache {
+            config,
+            sets: vec![
+                CacheSet {
+                    tags: vec![None; config.ways],
+                    recency: vec![0; recency_len],
+                };
+                config.set_count()
+            ],
+            line_shift: config.line_size.trailing_zeros(),
+            set_count: config.set_count() as u64,
+            clock: 0,
+            stats: CacheStats::default(),
+        })
+    }
+
+    pub fn config(&self) -> CacheConfig {
+        self.config
+    }
+
+    pub fn stats(&self) -> CacheStats {
+        self.stats
+    }
+
+    // Empties the cache and zeroes the counters
+    pub fn reset(&mut self) {
+        for set in self.sets.iter_mut() {
+            set.tags.fill(None);
+            set.recency.fill(0);
+        }
+        self.clock = 0;
+        self.stats = CacheStats::default();
+    }
+
+    // Looks up the line holding `address`, filling it on a miss. Returns true on a hit.
+    pub fn access(&mut self, address: u64) -> bool {
+        let line = address >> self.line_shift;
+        let set_idx = (line % self.set_count) as usize;
+        let tag = line / self.set_count;
+        self.clock += 1;
+        let policy = self.config.policy;
+        let clock = self.clock;
+        let set = &mut self.sets[set_idx];
+
+        if let Some(way) = set.tags.iter().position(|&t| t == Some(tag)) {
+            touch(set, policy, way, clock);
+            self.stats.hits += 1;
+            return true;
+        }
+
+        self.stats.misses += 1;
+        let way = match set.tags.iter().position(|t| t.is_none()) {
+            Some(empty) => empty,
+            None => {
+                self.stats.evictions += 1;
+                victim(set, policy)
+            }
+        };
+        set.tags[way] = Some(tag);
+        touch(set, policy, way, clock);
+        false
+    }
+}
+
+fn touch(set: &mut CacheSet, policy: ReplacementPolicy, way: usize, clock: u64) {
+    match policy {
+        ReplacementPolicy::Lru => set.recency[way] = clock,
+        ReplacementPolicy::TreePlru => {
+            // walk from the root to `way`, pointing every node at the other half
+            let (mut node, mut lo, mut hi) = (0, 0, set.tags.len());
+            while hi - lo > 1 {
+                let mid = (lo + hi) / 2;
+                if way < mid {
+                    set.recency[node] = 1;
+                    node = 2 * node + 1;
+                    hi = mid;
+                } else {
+                    set.recency[node] = 0;
+                    node = 2 * node + 2;
+                    lo = mid;
+                }
+            }
+        }
+    }
+}
+
+fn victim(set: &CacheSet, policy: ReplacementPolicy) -> usize {
+    match policy {
+        ReplacementPolicy::Lru => (0..set.tags.len())
+            .min_by_key(|&way| set.recency[way])
+            .unwrap(),
+        ReplacementPolicy::TreePlru => {
+            let (mut node, mut lo, mut hi) = (0, 0, set.tags.len());
+            while hi - lo > 1 {
+                let mid = (lo + hi) / 2;
+                if set.recency[node] == 1 {
+                    node = 2 * node + 2;
+                    lo = mid;
+                } else {
+                    node = 2 * node + 1;
+                    hi = mid;
+                }
+            }
+            lo
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // one set, 4 ways, 64 byte lines: every line maps to the same set
+    fn one_set(policy: ReplacementPolicy) -> SetAssociativeCache {
+        SetAssociativeCache::new(CacheConfig::new(256, 64, 4).policy(policy)).unwrap()
+    }
+
+    #[test]
+    fn validates_geometry() {
+        assert!(CacheConfig::new(48 * 1024, 64, 12).validate().is_ok());
+        assert!(CacheConfig::new(32 * 1024, 48, 8).validate().is_err());
+        assert!(CacheConfig::new(1000, 64, 4).validate().is_err());
+        assert!(CacheConfig::new(0, 64, 4).validate().is_err());
+        assert!(CacheConfig::new(107520 * 1024, 64, 15).validate().is_ok());
+        assert!(
+            CacheConfig::new(48 * 1024, 64, 12)
+                .policy(ReplacementPolicy::TreePlru)
+                .validate()
+                .is_err()
+        );
+        assert_eq!(CacheConfig::new(32 * 1024, 64, 8).set_count(), 64);
+    }
+
+    #[test]
+    fn same_line_hits_and_sets_are_independent() {
+        let mut cache = SetAssociativeCache::new(CacheConfig::new(1024, 64, 2)).unwrap();
+        assert!(!cache.access(0));
+        assert!(cache.access(4));
+        assert!(cache.access(63));
+        assert!(!cache.access(64));
+        // 8 sets, so 0 and 8 * 64 share set 0 and both fit in its 2 ways
+        assert!(!cache.access(512));
+        assert!(cache.access(0));
+        assert_eq!(
+            cache.stats(),
+            CacheStats {
+                hits: 3,
+                misses: 3,
+                evictions: 0
+            }
+        );
+    }
+
+    #[test]
+    fn lru_evicts_least_recently_used() {
+        let mut cache = one_set(ReplacementPolicy::Lru);
+        for line in 0..4 {
+            cache.access(line * 64);
+        }
+        cache.access(0); // 1 is now the oldest
+        assert!(!cache.access(4 * 64));
+        assert!(cache.access(0));
+        assert!(!cache.access(64));
+        assert_eq!(cache.stats().evictions, 2);
+    }
+
+    #[test]
+    fn tree_plru_evicts_away_from_recent_accesses() {
+        let mut cache = one_set(ReplacementPolicy::TreePlru);
+        for line in 0..4 {
+            cache.access(line * 64);
+        }
+        // after touching 3 then 0 the root points at the right half and the right node
+        // points away from 3, so way 2 goes even though LRU would have picked way 1
+        cache.access(0);
+        assert!(!cache.access(4 * 64));
+        assert!(cache.access(0));
+        assert!(cache.access(3 * 64));
+        assert!(cache.access(64));
+        assert!(!cache.access(2 * 64));
+    }
+
+    #[test]
+    fn reset_clears_lines_and_counters() {
+        let mut cache = one_set(ReplacementPolicy::Lru);
+        cache.access(0);
+        cache.reset();
+        assert_eq!(cache.stats(), CacheStats::default());
+        assert!(!cache.access(0));
+    }
+}
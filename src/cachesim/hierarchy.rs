@@ -0,0 +1,182 @@
+// L1 -> L2 -> L3 -> memory. An access walks down the levels until one hits and every level it
+// missed in gets the line filled on the way back (non-inclusive, non-exclusive: evictions in
+// one level don't touch the others).
+use super::cache::{CacheConfig, CacheStats, ReplacementPolicy, SetAssociativeCache};
+use crate::tuning::cache::{CacheLevel, read_cache_levels};
+
+#[derive(Debug, Clone)]
+pub struct CacheHierarchy {
+    levels: Vec<SetAssociativeCache>,
+    memory_accesses: u64,
+}
+
+impl CacheHierarchy {
+    // Levels in order, L1 first
+    pub fn new(configs: &[CacheConfig]) -> Result<CacheHierarchy, String> {
+        if configs.is_empty() {
+            return Err("a cache hierarchy needs at least one level".to_string());
+        }
+        let levels = configs
+            .iter()
+            .enumerate()
+            .map(|(idx, &config)| {
+                SetAssociativeCache::new(config).map_err(|e| format!("L{}: {}", idx + 1, e))
+            })
+            .collect::<Result<Vec<_>, String>>()?;
+        Ok(CacheHierarchy {
+            levels,
+            memory_accesses: 0,
+        })
+    }
+
+    // A generic desktop part: 32K 8-way L1d, 1M 16-way L2, 8M 16-way L3, 64 byte lines
+    pub fn typical() -> CacheHierarchy {
+        CacheHierarchy::new(&[
+            CacheConfig::new(32 * 1024, 64, 8),
+            CacheConfig::new(1024 * 1024, 64, 16),
+            CacheConfig::new(8 * 1024 * 1024, 64, 16),
+        ])
+        .unwrap()
+    }
+
+    // The data caches of the machine we are running on as reported by sysfs
+    pub fn from_cache_levels(
+        caches: &[CacheLevel],
+        policy: ReplacementPolicy,
+    ) -> Result<CacheHierarchy, String> {
+        let configs: Vec<CacheConfig> = caches
+            .iter()
+            .filter(|cache| cache.holds_data())
+            .map(|cache| {
+                CacheConfig::new(cache.size_bytes, cache.line_size, cache.ways).policy(policy)
+            })
+            .collect();
+        CacheHierarchy::new(&configs)
+    }
+
+    // This machine's caches, or `typical` when sysfs has nothing usable
+    pub fn host() -> CacheHierarchy {
+        CacheHierarchy::from_cache_levels(&read_cache_levels(), ReplacementPolicy::Lru)
+            .unwrap_or_else(|_| CacheHierarchy::typical())
+    }
+
+    pub fn level_configs(&self) -> Vec<CacheConfig> {
+        self.levels.iter().map(|level| level.config()).collect()
+    }
+
+    // Touches every L1 line overlapped by `size_bytes` starting at `address`
+    pub fn access(&mut self, address: u64, size_bytes: usize) {
+        let line_size = self.levels[0].config().line_size as u64;
+        let first_line = address / line_size;
+        let last_line = (address + size_bytes.max(1) as u64 - 1) / line_size;
+        for line in first_line..=last_line {
+            self.access_line(line * line_size);
+        }
+    }
+
+    fn access_line(&mut self, address: u64) {
+        for level in self.levels.iter_mut() {
+            if level.access(address) {
+                return;
+            }
+        }
+        self.memory_accesses += 1;
+    }
+
+    pub fn stats(&self) -> Vec<CacheStats> {
+        self.levels.iter().map(|level| level.stats()).collect()
+    }
+
+    // Misses in the last level, i.e. lines fetched from RAM
+    pub fn memory_accesses(&self) -> u64 {
+        self.memory_accesses
+    }
+
+    pub fn reset(&mut self) {
+        for level in self.levels.iter_mut() {
+            level.reset();
+        }
+        self.memory_accesses = 0;
+    }
+
+    pub fn print_report(&self, title: &str) {
+        println!("---> {}", title);
+        println!(
+            " {:<5} | {:>12} | {:>12} | {:>12} | {:>8}",
+            "Level", "Accesses", "Hits", "Misses", "Hit rate"
+        );
+        println!("-------|--------------|--------------|--------------|----------");
+        for (idx, stats) in self.stats().iter().enumerate() {
+            println!(
+                " L{:<4} | {:>12} | {:>12} | {:>12} | {:>7.2}%",
+                idx + 1,
+                stats.accesses(),
+                stats.hits,
+                stats.misses,
+                stats.hit_rate() * 100.0
+            );
+        }
+        println!(" {:<5} | {:>12} |", "RAM", self.memory_accesses);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tuning::cache::CacheKind;
+
+    fn tiny() -> CacheHierarchy {
+        // L1 holds 2 lines, L2 holds 8
+        CacheHierarchy::new(&[CacheConfig::new(128, 64, 2), CacheConfig::new(512, 64, 8)]).unwrap()
+    }
+
+    #[test]
+    fn misses_fall_through_and_fill_every_level() {
+        let mut caches = tiny();
+        caches.access(0, 4);
+        caches.access(64, 4);
+        caches.access(128, 4);
+        // line 0 was evicted from L1 but is still in L2
+        caches.access(0, 4);
+        let stats = caches.stats();
+        assert_eq!((stats[0].hits, stats[0].misses), (0, 4));
+        assert_eq!((stats[1].hits, stats[1].misses), (1, 3));
+        assert_eq!(caches.memory_accesses(), 3);
+
+        caches.reset();
+        assert_eq!(caches.memory_accesses(), 0);
+        assert_eq!(caches.stats()[1], CacheStats::default());
+    }
+
+    #[test]
+    fn unaligned_accesses_touch_both_lines() {
+        let mut caches = tiny();
+        caches.access(60, 8);
+        assert_eq!(caches.stats()[0].misses, 2);
+        caches.access(0, 0);
+        assert_eq!(caches.stats()[0].hits, 1);
+    }
+
+    #[test]
+    fn builds_from_sysfs_levels_skipping_instruction_caches() {
+        let level = |level, kind, size_bytes, ways| CacheLevel {
+            level,
+            kind,
+            size_bytes,
+            line_size: 64,
+            ways,
+        };
+        let caches = [
+            level(1, CacheKind::Data, 48 * 1024, 12),
+            level(1, CacheKind::Instruction, 32 * 1024, 8),
+            level(2, CacheKind::Unified, 2048 * 1024, 16),
+        ];
+        let hierarchy = CacheHierarchy::from_cache_levels(&caches, ReplacementPolicy::Lru).unwrap();
+        let configs = hierarchy.level_configs();
+        assert_eq!(configs.len(), 2);
+        assert_eq!(configs[0].size_bytes, 48 * 1024);
+        assert_eq!(configs[1].ways, 16);
+        assert!(CacheHierarchy::from_cache_levels(&[], ReplacementPolicy::Lru).is_err());
+        assert_eq!(CacheHierarchy::typical().level_configs().len(), 3);
+    }
+}
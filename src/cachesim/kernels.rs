@@ -0,0 +1,347 @@
+// Instrumented copies of the module 6 kernels. Each one computes the same result as the real
+// kernel and reports every element it reads or writes to the cache hierarchy.
+//
+// Addresses come from a fake allocator rather than the real heap so the counts are the same on
+// every run: a Vec<Vec<i32>> gets one line aligned allocation per row, one after the other.
+use super::hierarchy::CacheHierarchy;
+
+const ELEMENT_SIZE: usize = size_of::<i32>();
+
+pub struct SimAllocator {
+    next: u64,
+}
+
+impl SimAllocator {
+    pub fn new() -> SimAllocator {
+        SimAllocator { next: 0x1000_0000 }
+    }
+
+    // Cache line (64 byte) aligned, like a fresh allocation from a page
+    pub fn alloc(&mut self, size_bytes: usize) -> u64 {
+        let address = self.next;
+        self.next += (size_bytes.max(1) as u64).div_ceil(64) * 64;
+        address
+    }
+}
+
+impl Default for SimAllocator {
+    fn default() -> Self {
+        SimAllocator::new()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MatrixLayout {
+    // Vec<Vec<T>>: every row is its own allocation
+    Vec2d,
+    RowMajor,
+    ColMajor,
+}
+
+// Where each element of a row_count x col_count matrix lives in the simulated address space
+pub struct SimMatrix {
+    layout: MatrixLayout,
+    row_count: usize,
+    col_count: usize,
+    // one entry per row for Vec2d, a single base address otherwise
+    bases: Vec<u64>,
+}
+
+impl SimMatrix {
+    pub fn new(
+        allocator: &mut SimAllocator,
+        row_count: usize,
+        col_count: usize,
+        layout: MatrixLayout,
+    ) -> SimMatrix {
+        let bases = match layout {
+            MatrixLayout::Vec2d => (0..row_count)
+                .map(|_| allocator.alloc(col_count * ELEMENT_SIZE))
+                .collect(),
+            MatrixLayout::RowMajor | MatrixLayout::ColMajor => {
+                vec![allocator.alloc(row_count * col_count * ELEMENT_SIZE)]
+            }
+        };
+        SimMatrix {
+            layout,
+            row_count,
+            col_count,
+            bases,
+        }
+    }
+
+    pub fn address(&self, row: usize, col: usize) -> u64 {
+        let offset = match self.layout {
+            MatrixLayout::Vec2d => return self.bases[row] + (col * ELEMENT_SIZE) as u64,
+            MatrixLayout::RowMajor => row * self.col_count + col,
+            MatrixLayout::ColMajor => col * self.row_count + row,
+        };
+        self.bases[0] + (offset * ELEMENT_SIZE) as u64
+    }
+
+    pub fn touch(&self, caches: &mut CacheHierarchy, row: usize, col: usize) {
+        caches.access(self.address(row, col), ELEMENT_SIZE);
+    }
+}
+
+// row_sum_matrix: walks each row left to right, one miss per 16 elements
+pub fn row_sum_matrix_simulated(matrix: &[Vec<i32>], caches: &mut CacheHierarchy) -> i32 {
+    let row_count = matrix.len();
+    let col_count = matrix[0].len();
+    let sim = SimMatrix::new(
+        &mut SimAllocator::new(),
+        row_count,
+        col_count,
+        MatrixLayout::Vec2d,
+    );
+    let mut sum = 0;
+    for row_idx in 0..row_count {
+        for col_idx in 0..col_count {
+            sim.touch(caches, row_idx, col_idx);
+            sum += matrix[row_idx][col_idx];
+        }
+    }
+    sum
+}
+
+// col_sum_matrix: walks down each column, a new line per element once the column's lines
+// no longer fit in L1
+pub fn col_sum_matrix_simulated(matrix: &[Vec<i32>], caches: &mut CacheHierarchy) -> i32 {
+    let row_count = matrix.len();
+    let col_count = matrix[0].len();
+    let sim = SimMatrix::new(
+        &mut SimAllocator::new(),
+        row_count,
+        col_count,
+        MatrixLayout::Vec2d,
+    );
+    let mut sum = 0;
+    for col_idx in 0..col_count {
+        for row_idx in 0..row_count {
+            sim.touch(caches, row_idx, col_idx);
+            sum += matrix[row_idx][col_idx];
+        }
+    }
+    sum
+}
+
+// Out of place transpose, reads rows of the source and writes columns of the destination
+pub fn transpose_2d_vec_simulated(
+    matrix: &[Vec<i32>],
+    caches: &mut CacheHierarchy,
+) -> Vec<Vec<i32>> {
+    transpose_2d_vec_loop_tiling_simulated(matrix, usize::MAX, caches)
+}
+
+// The rectangular path of transpose_2d_vec_loop_tiling_aka_block_tiling. A block size of
+// usize::MAX is a single block, i.e. the naive transpose.
+pub fn transpose_2d_vec_loop_tiling_simulated(
+    matrix: &[Vec<i32>],
+    block_size: usize,
+    caches: &mut CacheHierarchy,
+) -> Vec<Vec<i32>> {
+    let row_count = matrix.len();
+    let col_count = matrix[0].len();
+    let mut allocator = SimAllocator::new();
+    let source = SimMatrix::new(&mut allocator, row_count, col_count, MatrixLayout::Vec2d);
+    let destination = SimMatrix::new(&mut allocator, col_count, row_count, MatrixLayout::Vec2d);
+    let mut new_matrix = vec![vec![0; row_count]; col_count];
+
+    for block_start_row_idx in (0..row_count).step_by(block_size.min(row_count)) {
+        for block_start_col_idx in (0..col_count).step_by(block_size.min(col_count)) {
+            let row_end = block_start_row_idx
+                .saturating_add(block_size)
+                .min(row_count);
+            let col_end = block_start_col_idx
+                .saturating_add(block_size)
+                .min(col_count);
+            for cell_row_idx in block_start_row_idx..row_end {
+                for cell_col_idx in block_start_col_idx..col_end {
+                    source.touch(caches, cell_row_idx, cell_col_idx);
+                    destination.touch(caches, cell_col_idx, cell_row_idx);
+                    new_matrix[cell_col_idx][cell_row_idx] = matrix[cell_row_idx][cell_col_idx];
+                }
+            }
+        }
+    }
+    new_matrix
+}
+
+// matrix_multiply_triple_loop_vec_2d: the inner loop walks down a column of B
+pub fn matrix_multiply_triple_loop_vec_2d_simulated(
+    matrix_a: &[Vec<i32>],
+    matrix_b: &[Vec<i32>],
+    caches: &mut CacheHierarchy,
+) -> Vec<Vec<i32>> {
+    let a_row_count = matrix_a.len();
+    let k = matrix_a[0].len();
+    let b_col_count = matrix_b[0].len();
+    let mut allocator = SimAllocator::new();
+    let sim_a = SimMatrix::new(&mut allocator, a_row_count, k, MatrixLayout::Vec2d);
+    let sim_b = SimMatrix::new(&mut allocator, k, b_col_count, MatrixLayout::Vec2d);
+    let sim_c = SimMatrix::new(
+        &mut allocator,
+        a_row_count,
+        b_col_count,
+        MatrixLayout::Vec2d,
+    );
+    let mut matrix_c = vec![vec![0; b_col_count]; a_row_count];
+
+    for a_row_idx in 0..a_row_count {
+        for b_col_idx in 0..b_col_count {
+            let mut total = 0;
+            for k_idx in 0..k {
+                sim_a.touch(caches, a_row_idx, k_idx);
+                sim_b.touch(caches, k_idx, b_col_idx);
+                total += matrix_a[a_row_idx][k_idx] * matrix_b[k_idx][b_col_idx];
+            }
+            sim_c.touch(caches, a_row_idx, b_col_idx);
+            matrix_c[a_row_idx][b_col_idx] = total;
+        }
+    }
+    matrix_c
+}
+
+// matrix_multiply_loop_tiling: C[i][j] is read and written on every inner iteration
+pub fn matrix_multiply_loop_tiling_simulated(
+    matrix_a: &[Vec<i32>],
+    matrix_b: &[Vec<i32>],
+    block_size: usize,
+    caches: &mut CacheHierarchy,
+) -> Vec<Vec<i32>> {
+    let a_row_count = matrix_a.len();
+    let k = matrix_a[0].len();
+    let b_col_count = matrix_b[0].len();
+    let mut allocator = SimAllocator::new();
+    let sim_a = SimMatrix::new(&mut allocator, a_row_count, k, MatrixLayout::Vec2d);
+    let sim_b = SimMatrix::new(&mut allocator, k, b_col_count, MatrixLayout::Vec2d);
+    let sim_c = SimMatrix::new(
+        &mut allocator,
+        a_row_count,
+        b_col_count,
+        MatrixLayout::Vec2d,
+    );
+    let mut matrix_c = vec![vec![0; b_col_count]; a_row_count];
+
+    for block_row_c in (0..a_row_count).step_by(block_size) {
+        for block_col_c in (0..b_col_count).step_by(block_size) {
+            for block_k in (0..k).step_by(block_size) {
+                for row_in_c_block in block_row_c..(block_row_c + block_size).min(a_row_count) {
+                    for col_in_c_block in block_col_c..(block_col_c + block_size).min(b_col_count) {
+                        for k_in_block in block_k..(block_k + block_size).min(k) {
+                            sim_a.touch(caches, row_in_c_block, k_in_block);
+                            sim_b.touch(caches, k_in_block, col_in_c_block);
+                            sim_c.touch(caches, row_in_c_block, col_in_c_block);
+                            matrix_c[row_in_c_block][col_in_c_block] += matrix_a[row_in_c_block]
+                                [k_in_block]
+                                * matrix_b[k_in_block][col_in_c_block];
+                        }
+                    }
+                }
+            }
+        }
+    }
+    matrix_c
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cachesim::cache::CacheConfig;
+    use crate::matrix::make_matrix;
+    use crate::matrix::multiply::matrix_multiply_triple_loop_vec_2d;
+    use crate::matrix::sum::{col_sum_matrix, row_sum_matrix};
+    use crate::matrix::transpose::transpose_2d_vec;
+
+    fn small_caches() -> CacheHierarchy {
+        // 4K L1 and 32K L2 so a 256x256 i32 matrix (256K) overflows both
+        CacheHierarchy::new(&[
+            CacheConfig::new(4 * 1024, 64, 4),
+            CacheConfig::new(32 * 1024, 64, 8),
+        ])
+        .unwrap()
+    }
+
+    #[test]
+    fn layouts_place_elements() {
+        let mut allocator = SimAllocator::new();
+        let vec_2d = SimMatrix::new(&mut allocator, 3, 5, MatrixLayout::Vec2d);
+        // 5 i32 = 20 bytes, rounded up to a line per row
+        assert_eq!(vec_2d.address(1, 0) - vec_2d.address(0, 0), 64);
+        assert_eq!(vec_2d.address(2, 3) - vec_2d.address(2, 0), 12);
+        let row_major = SimMatrix::new(&mut allocator, 3, 5, MatrixLayout::RowMajor);
+        assert!(row_major.address(0, 0) >= vec_2d.address(2, 4));
+        assert_eq!(row_major.address(1, 2) - row_major.address(0, 0), 7 * 4);
+        let col_major = SimMatrix::new(&mut allocator, 3, 5, MatrixLayout::ColMajor);
+        assert_eq!(col_major.address(1, 2) - col_major.address(0, 0), 7 * 4);
+        assert_eq!(col_major.address(2, 1) - col_major.address(0, 0), 5 * 4);
+    }
+
+    #[test]
+    fn row_major_sum_misses_once_per_line_column_sum_every_element() {
+        let matrix = make_matrix(256, 256, 0);
+        let mut rows = small_caches();
+        let mut cols = small_caches();
+        assert_eq!(
+            row_sum_matrix_simulated(&matrix, &mut rows),
+            row_sum_matrix(&matrix)
+        );
+        assert_eq!(
+            col_sum_matrix_simulated(&matrix, &mut cols),
+            col_sum_matrix(&matrix)
+        );
+
+        let elements = 256 * 256;
+        // 16 i32 per 64 byte line
+        assert_eq!(rows.stats()[0].misses, elements / 16);
+        assert_eq!(rows.memory_accesses(), elements / 16);
+        // 256 rows * 1 line each doesn't fit in a 64 line L1, so every access misses
+        assert_eq!(cols.stats()[0].misses, elements);
+        // the column's 256 lines would fit in the 512 line L2, but the 1K row stride maps them
+        // onto 4 of its 64 sets (32 lines), so L2 thrashes as well
+        assert_eq!(cols.stats()[1].hits, 0);
+        assert_eq!(cols.memory_accesses(), elements);
+    }
+
+    #[test]
+    fn tiling_cuts_misses_for_transpose_and_matmul() {
+        // odd sizes on purpose, power of two row strides alias onto a few sets and thrash
+        // even inside a tile (try 128x128 to see it)
+        let matrix = make_matrix(100, 75, 0);
+        let mut naive = small_caches();
+        let mut tiled = small_caches();
+        let expected = transpose_2d_vec(matrix.clone());
+        assert_eq!(transpose_2d_vec_simulated(&matrix, &mut naive), expected);
+        assert_eq!(
+            transpose_2d_vec_loop_tiling_simulated(&matrix, 8, &mut tiled),
+            expected
+        );
+        assert!(tiled.stats()[0].misses * 4 < naive.stats()[0].misses);
+
+        let matrix_a = make_matrix(64, 48, 0);
+        let matrix_b = make_matrix(48, 80, 0);
+        let expected = matrix_multiply_triple_loop_vec_2d(&matrix_a, &matrix_b);
+        let mut naive = small_caches();
+        let mut tiled = small_caches();
+        assert_eq!(
+            matrix_multiply_triple_loop_vec_2d_simulated(&matrix_a, &matrix_b, &mut naive),
+            expected
+        );
+        assert_eq!(
+            matrix_multiply_loop_tiling_simulated(&matrix_a, &matrix_b, 16, &mut tiled),
+            expected
+        );
+        assert!(tiled.stats()[0].misses < naive.stats()[0].misses);
+    }
+
+    #[test]
+    fn simulation_is_deterministic() {
+        let matrix = make_matrix(64, 64, 0);
+        let mut first = CacheHierarchy::typical();
+        let mut second = CacheHierarchy::typical();
+        col_sum_matrix_simulated(&matrix, &mut first);
+        col_sum_matrix_simulated(&matrix, &mut second);
+        assert_eq!(first.stats(), second.stats());
+        assert_eq!(first.memory_accesses(), second.memory_accesses());
+    }
+}
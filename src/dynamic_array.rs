@@ -0,0 +1,3 @@
+// Module 3 dynamic arrays, built on the raw allocator instead of Vec.
+pub mod growth;
+pub mod my_vector;
@@ -0,0 +1,178 @@
+// Growth policies for MyVector, picked as a type parameter: MyVector<T, Golden>.
+//
+// A policy only decides the next capacity when a push does not fit, the vector does the
+// allocating and keeps the copy cost accounting in GrowthStats. The policies are zero-sized
+// marker types, so the choice is made at compile time and costs nothing per push.
+//
+// Module 3 mini-challenge, copies for n pushes when every grow has to copy:
+// - FixedIncrement: n / STEP grows copying on average n / 2 elements each, O(n^2) total.
+// - Factor (x2, x1.5) and Fibonacci (about x1.618): a geometric series, under
+//   n / (factor - 1) copies in total, so O(1) amortized per push.
+// - JemallocSizeClasses: x1.5, then rounded up to the size class jemalloc would hand out for
+//   that many bytes anyway, so the slack the allocator rounds up to becomes usable slots.
+
+pub trait GrowthPolicy {
+    // Capacity to grow to when `required` slots are needed but only `capacity` are allocated
+    // (required > capacity). element_size is size_of::<T>() in bytes and is never 0.
+    fn grow(capacity: usize, required: usize, element_size: usize) -> usize;
+
+    // Short label for the tutorial tables
+    fn name() -> String;
+}
+
+// Adds STEP slots on every grow
+#[derive(Debug, Clone, Copy, Default)]
+pub struct FixedIncrement<const STEP: usize = 1000>;
+
+impl<const STEP: usize> GrowthPolicy for FixedIncrement<STEP> {
+    fn grow(capacity: usize, required: usize, _element_size: usize) -> usize {
+        capacity.saturating_add(STEP.max(1)).max(required)
+    }
+
+    fn name() -> String {
+        format!("+{}", STEP)
+    }
+}
+
+// Multiplies the capacity by NUMERATOR / DENOMINATOR on every grow, always adding at least
+// one slot so small capacities (1 * 3 / 2 == 1) still make progress.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Factor<const NUMERATOR: usize, const DENOMINATOR: usize>;
+
+// Same growth as Vec, the default policy
+pub type Doubling = Factor<2, 1>;
+
+// Increases size by golden ratio, except its a aproximation of 1.5 since
+// integer arithmatic is supose to be faster... meh.
+pub type Golden = Factor<3, 2>;
+
+impl<const NUMERATOR: usize, const DENOMINATOR: usize> GrowthPolicy
+    for Factor<NUMERATOR, DENOMINATOR>
+{
+    fn grow(capacity: usize, required: usize, _element_size: usize) -> usize {
+        let grown = capacity.max(1).saturating_mul(NUMERATOR) / DENOMINATOR.max(1);
+        grown.max(capacity.saturating_add(1)).max(required)
+    }
+
+    fn name() -> String {
+        if DENOMINATOR == 1 {
+            format!("x{}", NUMERATOR)
+        } else {
+            format!("x{}/{}", NUMERATOR, DENOMINATOR)
+        }
+    }
+}
+
+// Capacities walk the Fibonacci numbers 1, 2, 3, 5, 8, 13, ... so the factor tends to the
+// real golden ratio (1.618...) instead of 1.5.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Fibonacci;
+
+impl GrowthPolicy for Fibonacci {
+    fn grow(capacity: usize, required: usize, _element_size: usize) -> usize {
+        let (mut smaller, mut larger) = (1usize, 2usize);
+        while larger <= capacity || larger < required {
+            (smaller, larger) = (larger, smaller.saturating_add(larger));
+            if larger == usize::MAX {
+                break;
+            }
+        }
+        larger.max(required)
+    }
+
+    fn name() -> String {
+        String::from("fibonacci")
+    }
+}
+
+// Grows by 1.5 and then fills the whole jemalloc size class the new allocation lands in
+#[derive(Debug, Clone, Copy, Default)]
+pub struct JemallocSizeClasses;
+
+impl GrowthPolicy for JemallocSizeClasses {
+    fn grow(capacity: usize, required: usize, element_size: usize) -> usize {
+        let slots = Golden::grow(capacity, required, element_size);
+        let bytes = slots.saturating_mul(element_size);
+        (jemalloc_size_class(bytes) / element_size).max(slots)
+    }
+
+    fn name() -> String {
+        String::from("jemalloc")
+    }
+}
+
+// The allocation size jemalloc rounds a request of `bytes` up to: 8, then multiples of 16 up
+// to 128, then four evenly spaced classes per doubling (160, 192, 224, 256, 320, ...).
+pub fn jemalloc_size_class(bytes: usize) -> usize {
+    if bytes <= 8 {
+        return 8;
+    }
+    if bytes <= 128 {
+        return bytes.next_multiple_of(16);
+    }
+    // bytes is in (group_base, 2 * group_base], which is split in four steps
+    let group_base = 1usize << (usize::BITS - 1 - (bytes - 1).leading_zeros());
+    bytes
+        .checked_next_multiple_of(group_base / 4)
+        .unwrap_or(usize::MAX)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn capacities<P: GrowthPolicy>(grow_count: usize, element_size: usize) -> Vec<usize> {
+        let mut capacity = 0;
+        (0..grow_count)
+            .map(|_| {
+                capacity = P::grow(capacity, capacity + 1, element_size);
+                capacity
+            })
+            .collect()
+    }
+
+    #[test]
+    fn policies_follow_their_sequences() {
+        assert_eq!(capacities::<FixedIncrement>(3, 4), [1000, 2000, 3000]);
+        assert_eq!(capacities::<FixedIncrement<3>>(3, 4), [3, 6, 9]);
+        assert_eq!(capacities::<Doubling>(5, 4), [2, 4, 8, 16, 32]);
+        assert_eq!(capacities::<Golden>(7, 4), [1, 2, 3, 4, 6, 9, 13]);
+        assert_eq!(capacities::<Fibonacci>(7, 4), [2, 3, 5, 8, 13, 21, 34]);
+        // i32 slots filling the 8, 16, 32, 48, 80, 128 and 192 byte classes
+        assert_eq!(
+            capacities::<JemallocSizeClasses>(7, 4),
+            [2, 4, 8, 12, 20, 32, 48]
+        );
+        assert_eq!(Golden::name(), "x3/2");
+        assert_eq!(FixedIncrement::<1000>::name(), "+1000");
+    }
+
+    #[test]
+    fn policies_always_cover_the_required_capacity() {
+        for (capacity, required) in [(0, 1), (0, 5000), (10, 11), (10, 5000), (1 << 20, 1 << 30)] {
+            for element_size in [1, 4, 24, 1000] {
+                for grown in [
+                    FixedIncrement::<1000>::grow(capacity, required, element_size),
+                    Doubling::grow(capacity, required, element_size),
+                    Golden::grow(capacity, required, element_size),
+                    Fibonacci::grow(capacity, required, element_size),
+                    JemallocSizeClasses::grow(capacity, required, element_size),
+                ] {
+                    assert!(grown >= required && grown > capacity);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn jemalloc_size_classes() {
+        let classes: Vec<usize> = [1, 8, 9, 17, 100, 128, 129, 161, 256, 257, 1000, 4097]
+            .into_iter()
+            .map(jemalloc_size_class)
+            .collect();
+        assert_eq!(
+            classes,
+            [8, 8, 16, 32, 112, 128, 160, 192, 256, 320, 1024, 5120]
+        );
+    }
+}
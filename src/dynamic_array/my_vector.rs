@@ -0,0 +1,704 @@
+// MyVector<T, P>, the module 3 dynamic array on top of the raw allocator. P is the growth
+// policy (growth.rs), doubling unless another one is named: MyVector<i32, Golden>.
+//
+// Invariants everything below relies on:
+// - data points to an allocation of exactly capacity slots, or is dangling when nothing is
+//   allocated (capacity 0, or a zero-sized T which never needs memory).
+// - The first size slots are initialized, the rest are not. Elements are moved in and out
+//   with ptr::write/ptr::read so an uninitialized slot is never dropped or read as a T.
+// - size is lowered before elements are dropped, so a panicking Drop can leak the rest of the
+//   tail but never drop anything twice.
+//
+// Every allocator call is recorded in GrowthStats, so the amortized analysis can be checked
+// against what the allocator actually did instead of only the worst case.
+use super::growth::{Doubling, GrowthPolicy};
+use std::alloc::{Layout, alloc, dealloc, handle_alloc_error, realloc};
+use std::fmt;
+use std::marker::PhantomData;
+use std::mem::{self, ManuallyDrop};
+use std::ops::{Deref, DerefMut, Index, IndexMut};
+use std::ptr::{self, NonNull};
+use std::slice;
+
+pub struct MyVector<T, P: GrowthPolicy = Doubling> {
+    data: NonNull<T>, // Pointer to start of allocated capacity.
+    size: usize,      // Current number of slots filled.
+    capacity: usize,  // Current number of slots available.
+    stats: GrowthStats,
+    // MyVector owns its T values, this tells the drop checker so
+    _owns: PhantomData<T>,
+    // The policy is only a type, fn() -> P keeps it out of the Send/Sync and drop checks
+    _policy: PhantomData<fn() -> P>,
+}
+
+// Same rules as Vec: the raw pointer is only ever used through &self/&mut self.
+unsafe impl<T: Send, P: GrowthPolicy> Send for MyVector<T, P> {}
+unsafe impl<T: Sync, P: GrowthPolicy> Sync for MyVector<T, P> {}
+
+// What the allocations of one vector have cost so far
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct GrowthStats {
+    // alloc and realloc calls, the first allocation and shrink_to_fit included
+    pub reallocations: usize,
+    // reallocs that could not resize in place, the allocator returned a new block
+    pub moves: usize,
+    // bytes of live elements the allocator copied on those moves
+    pub bytes_copied: usize,
+    // bytes_copied if every realloc had moved, the cost the textbook analysis counts
+    pub worst_case_bytes_copied: usize,
+}
+
+const fn is_zero_sized<T>() -> bool {
+    mem::size_of::<T>() == 0
+}
+
+fn make_memory_layout<T>(capacity: usize) -> Layout {
+    // Errors when capacity * size_of::<T>() overflows isize
+    Layout::array::<T>(capacity).expect("capacity overflow")
+}
+
+// Implement Drop trait so that the elements and then the memory are freed.
+impl<T, P: GrowthPolicy> Drop for MyVector<T, P> {
+    fn drop(&mut self) {
+        // Frees the memory from its own drop, so it still runs while unwinding out of an
+        // element's panicking Drop (the job Vec's RawVec field does). clear() has already
+        // set size to 0 by then.
+        struct FreeOnDrop<'a, T, P: GrowthPolicy>(&'a mut MyVector<T, P>);
+
+        impl<T, P: GrowthPolicy> Drop for FreeOnDrop<'_, T, P> {
+            fn drop(&mut self) {
+                self.0.resize_capacity(0);
+            }
+        }
+
+        let guard = FreeOnDrop(self);
+        guard.0.clear();
+    }
+}
+
+impl<T> MyVector<T> {
+    pub fn new() -> Self {
+        Self::new_with_policy()
+    }
+
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self::with_capacity_and_policy(capacity)
+    }
+}
+
+impl<T, P: GrowthPolicy> MyVector<T, P> {
+    fn validate_index(&self, idx: usize) {
+        if self.size <= idx {
+            panic!(
+                "index out of bounds: the len is {} but the index is {}",
+                self.size, idx
+            );
+        }
+    }
+
+    // new for any policy, like Vec::new_in: `MyVector::new()` alone could not infer P
+    pub fn new_with_policy() -> Self {
+        Self {
+            data: NonNull::dangling(),
+            size: 0,
+            capacity: 0,
+            stats: GrowthStats::default(),
+            _owns: PhantomData,
+            _policy: PhantomData,
+        }
+    }
+
+    pub fn with_capacity_and_policy(capacity: usize) -> Self {
+        let mut new_vec = Self::new_with_policy();
+        new_vec.resize_capacity(capacity);
+        new_vec
+    }
+
+    // Moves the elements into an allocation of exactly `capacity` slots, capacity >= len.
+    // Zero-sized types never allocate, and capacity 0 frees the allocation.
+    fn resize_capacity(&mut self, capacity: usize) {
+        debug_assert!(capacity >= self.size);
+        if is_zero_sized::<T>() || capacity == self.capacity {
+            return;
+        }
+        if capacity == 0 {
+            unsafe {
+                // Note: need to always cast to u8 when deallocating.
+                dealloc(
+                    self.data.as_ptr().cast::<u8>(),
+                    make_memory_layout::<T>(self.capacity),
+                );
+            }
+            self.data = NonNull::dangling();
+            self.capacity = 0;
+            return;
+        }
+        let live_bytes = self.size * mem::size_of::<T>();
+        let old_address = self.data.as_ptr().addr();
+        let new_layout = make_memory_layout::<T>(capacity);
+        let new_data = if self.capacity == 0 {
+            // first allocation
+            unsafe { alloc(new_layout) }
+        } else {
+            // resize the existing allocation, realloc copies the bytes over
+            unsafe {
+                realloc(
+                    self.data.as_ptr().cast::<u8>(),
+                    make_memory_layout::<T>(self.capacity),
+                    new_layout.size(), // Is size in u8 so needs to be multiplied
+                )
+            }
+        };
+        self.data = match NonNull::new(new_data.cast::<T>()) {
+            Some(data) => data,
+            None => handle_alloc_error(new_layout),
+        };
+        self.stats.reallocations += 1;
+        if self.capacity != 0 {
+            self.stats.worst_case_bytes_copied += live_bytes;
+            // Only compares addresses, the old pointer is never used again
+            if self.data.as_ptr().addr() != old_address {
+                self.stats.moves += 1;
+                self.stats.bytes_copied += live_bytes;
+            }
+        }
+        self.capacity = capacity;
+    }
+
+    pub fn len(&self) -> usize {
+        self.size
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.size == 0
+    }
+
+    // Zero-sized types never run out of room, like Vec this reports usize::MAX for them
+    pub fn capacity(&self) -> usize {
+        if is_zero_sized::<T>() {
+            usize::MAX
+        } else {
+            self.capacity
+        }
+    }
+
+    fn is_full(&self) -> bool {
+        self.size == self.capacity()
+    }
+
+    pub fn growth_stats(&self) -> GrowthStats {
+        self.stats
+    }
+
+    // Allocated but unused bytes right now
+    pub fn wasted_capacity_bytes(&self) -> usize {
+        (self.capacity() - self.size) * mem::size_of::<T>()
+    }
+
+    // Grows to hold at least additional more elements, as far as the policy says.
+    pub fn reserve(&mut self, additional: usize) {
+        let needed = self
+            .size
+            .checked_add(additional)
+            .expect("capacity overflow");
+        if needed > self.capacity() {
+            // max() so a policy returning too little cannot break the capacity >= len invariant
+            let grown = P::grow(self.capacity, needed, mem::size_of::<T>()).max(needed);
+            self.resize_capacity(grown);
+        }
+    }
+
+    // Kept out of line so push inlines to a compare and a write whatever the policy costs
+    #[cold]
+    #[inline(never)]
+    fn grow_one(&mut self) {
+        self.reserve(1);
+    }
+
+    // Grows by the policy when full, amortized O(1) for the geometric policies
+    pub fn push(&mut self, element: T) {
+        if self.is_full() {
+            self.grow_one();
+        }
+        self.push_raw(element);
+    }
+
+    fn push_raw(&mut self, element: T) {
+        if self.size == usize::MAX {
+            // Only reachable with a zero-sized T
+            panic!("capacity overflow");
+        }
+        unsafe {
+            // write, not assignment: assigning would drop the uninitialized old slot
+            ptr::write(self.data.as_ptr().add(self.size), element);
+        }
+        self.size += 1;
+    }
+
+    pub fn pop(&mut self) -> Option<T> {
+        if self.size == 0 {
+            return None;
+        }
+        self.size -= 1;
+        // The slot is outside len now, so reading it out moves the value to the caller
+        unsafe { Some(ptr::read(self.data.as_ptr().add(self.size))) }
+    }
+
+    // Shifts everything from idx on one slot right, O(len - idx)
+    pub fn insert(&mut self, idx: usize, element: T) {
+        if idx > self.size {
+            panic!(
+                "insertion index (is {}) should be <= len (is {})",
+                idx, self.size
+            );
+        }
+        if self.is_full() {
+            self.grow_one();
+        }
+        unsafe {
+            let slot = self.data.as_ptr().add(idx);
+            // ptr::copy handles the overlap, like memmove
+            ptr::copy(slot, slot.add(1), self.size - idx);
+            ptr::write(slot, element);
+        }
+        self.size += 1;
+    }
+
+    // Shifts everything after idx one slot left, O(len - idx)
+    pub fn remove(&mut self, idx: usize) -> T {
+        self.validate_index(idx);
+        unsafe {
+            let slot = self.data.as_ptr().add(idx);
+            let element = ptr::read(slot);
+            ptr::copy(slot.add(1), slot, self.size - idx - 1);
+            self.size -= 1;
+            element
+        }
+    }
+
+    // O(1) removal that moves the last element into the hole, order is not kept
+    pub fn swap_remove(&mut self, idx: usize) -> T {
+        self.validate_index(idx);
+        let last = self.size - 1;
+        self.swap(idx, last);
+        self.pop().unwrap()
+    }
+
+    // Drops every element from len on, the capacity is kept
+    pub fn truncate(&mut self, len: usize) {
+        if len >= self.size {
+            return;
+        }
+        let tail_len = self.size - len;
+        // Lower size first so a panicking drop cannot lead to a double drop
+        self.size = len;
+        unsafe {
+            let tail = ptr::slice_from_raw_parts_mut(self.data.as_ptr().add(len), tail_len);
+            ptr::drop_in_place(tail);
+        }
+    }
+
+    pub fn clear(&mut self) {
+        self.truncate(0);
+    }
+
+    // Gives back the unused capacity, an empty vector frees its allocation
+    pub fn shrink_to_fit(&mut self) {
+        if self.capacity > self.size {
+            self.resize_capacity(self.size);
+        }
+    }
+
+    pub fn as_slice(&self) -> &[T] {
+        // data is non-null and aligned even when dangling, and the first size slots are initialized
+        unsafe { slice::from_raw_parts(self.data.as_ptr(), self.size) }
+    }
+
+    pub fn as_mut_slice(&mut self) -> &mut [T] {
+        unsafe { slice::from_raw_parts_mut(self.data.as_ptr(), self.size) }
+    }
+}
+
+impl<T, P: GrowthPolicy> Default for MyVector<T, P> {
+    fn default() -> Self {
+        Self::new_with_policy()
+    }
+}
+
+// The slice methods (iter, sort, contains, first, windows, ...) all come through Deref
+impl<T, P: GrowthPolicy> Deref for MyVector<T, P> {
+    type Target = [T];
+
+    fn deref(&self) -> &[T] {
+        self.as_slice()
+    }
+}
+
+impl<T, P: GrowthPolicy> DerefMut for MyVector<T, P> {
+    fn deref_mut(&mut self) -> &mut [T] {
+        self.as_mut_slice()
+    }
+}
+
+// read‑only indexing: &mylist[i] -> &T
+impl<T, P: GrowthPolicy> Index<usize> for MyVector<T, P> {
+    type Output = T;
+
+    fn index(&self, idx: usize) -> &T {
+        self.validate_index(idx);
+        unsafe { &*self.data.as_ptr().add(idx) }
+    }
+}
+
+// mutable indexing: &mut mylist[i] -> &mut T
+impl<T, P: GrowthPolicy> IndexMut<usize> for MyVector<T, P> {
+    fn index_mut(&mut self, idx: usize) -> &mut T {
+        self.validate_index(idx);
+        unsafe { &mut *self.data.as_ptr().add(idx) }
+    }
+}
+
+impl<T: Clone, P: GrowthPolicy> Clone for MyVector<T, P> {
+    // If a clone panics the new vector only drops the clones pushed so far
+    fn clone(&self) -> Self {
+        let mut new_vec = Self::with_capacity_and_policy(self.size);
+        for element in self.iter() {
+            new_vec.push_raw(element.clone());
+        }
+        new_vec
+    }
+}
+
+impl<T: fmt::Debug, P: GrowthPolicy> fmt::Debug for MyVector<T, P> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl<T: PartialEq, P: GrowthPolicy> PartialEq for MyVector<T, P> {
+    fn eq(&self, other: &Self) -> bool {
+        self.as_slice() == other.as_slice()
+    }
+}
+
+impl<T: Eq, P: GrowthPolicy> Eq for MyVector<T, P> {}
+
+impl<T, P: GrowthPolicy> Extend<T> for MyVector<T, P> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        let iter = iter.into_iter();
+        self.reserve(iter.size_hint().0);
+        for element in iter {
+            self.push(element);
+        }
+    }
+}
+
+impl<T, P: GrowthPolicy> FromIterator<T> for MyVector<T, P> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut new_vec = Self::new_with_policy();
+        new_vec.extend(iter);
+        new_vec
+    }
+}
+
+impl<'a, T, P: GrowthPolicy> IntoIterator for &'a MyVector<T, P> {
+    type Item = &'a T;
+    type IntoIter = slice::Iter<'a, T>;
+
+    fn into_iter(self) -> slice::Iter<'a, T> {
+        self.iter()
+    }
+}
+
+impl<'a, T, P: GrowthPolicy> IntoIterator for &'a mut MyVector<T, P> {
+    type Item = &'a mut T;
+    type IntoIter = slice::IterMut<'a, T>;
+
+    fn into_iter(self) -> slice::IterMut<'a, T> {
+        self.iter_mut()
+    }
+}
+
+// Owning iterator: takes over the allocation, moves elements out front to back (or back to
+// front) and drops whatever was not consumed before freeing the memory.
+pub struct IntoIter<T, P: GrowthPolicy = Doubling> {
+    // Holds the allocation with size 0 so dropping it only frees the memory
+    allocation: MyVector<T, P>,
+    // Unconsumed elements are the slots front..back
+    front: usize,
+    back: usize,
+}
+
+impl<T, P: GrowthPolicy> IntoIterator for MyVector<T, P> {
+    type Item = T;
+    type IntoIter = IntoIter<T, P>;
+
+    fn into_iter(self) -> IntoIter<T, P> {
+        let mut this = ManuallyDrop::new(self);
+        let back = this.size;
+        this.size = 0;
+        IntoIter {
+            // Moves the pointer and capacity out, `this` is never dropped
+            allocation: unsafe { ptr::read(&*this) },
+            front: 0,
+            back,
+        }
+    }
+}
+
+impl<T, P: GrowthPolicy> Iterator for IntoIter<T, P> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        if self.front == self.back {
+            return None;
+        }
+        self.front += 1;
+        unsafe { Some(ptr::read(self.allocation.data.as_ptr().add(self.front - 1))) }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.back - self.front;
+        (remaining, Some(remaining))
+    }
+}
+
+impl<T, P: GrowthPolicy> DoubleEndedIterator for IntoIter<T, P> {
+    fn next_back(&mut self) -> Option<T> {
+        if self.front == self.back {
+            return None;
+        }
+        self.back -= 1;
+        unsafe { Some(ptr::read(self.allocation.data.as_ptr().add(self.back))) }
+    }
+}
+
+impl<T, P: GrowthPolicy> ExactSizeIterator for IntoIter<T, P> {}
+
+impl<T, P: GrowthPolicy> Drop for IntoIter<T, P> {
+    fn drop(&mut self) {
+        let (front, back) = (self.front, self.back);
+        // Mark everything consumed first, then drop what is left, then `allocation` frees
+        self.front = back;
+        unsafe {
+            let rest = ptr::slice_from_raw_parts_mut(
+                self.allocation.data.as_ptr().add(front),
+                back - front,
+            );
+            ptr::drop_in_place(rest);
+        }
+    }
+}
+
+// The tests stay small so they also run under Miri, which checks every unsafe block for
+// undefined behaviour and reports leaked allocations:
+//     cargo +nightly miri test --lib dynamic_array
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bench::data::XorShift64;
+    use crate::dynamic_array::growth::{FixedIncrement, Golden, JemallocSizeClasses};
+    use std::cell::Cell;
+    use std::rc::Rc;
+
+    // Counts its drops in a shared cell, so a test can tell an element was dropped exactly once
+    #[derive(Debug, Clone)]
+    struct DropCounter {
+        value: i32,
+        drops: Rc<Cell<usize>>,
+    }
+
+    impl Drop for DropCounter {
+        fn drop(&mut self) {
+            self.drops.set(self.drops.get() + 1);
+        }
+    }
+
+    fn counters(count: i32, drops: &Rc<Cell<usize>>) -> MyVector<DropCounter> {
+        (0..count)
+            .map(|value| DropCounter {
+                value,
+                drops: Rc::clone(drops),
+            })
+            .collect()
+    }
+
+    fn random_operations_match_vec_with<P: GrowthPolicy>(seed: u64) {
+        let mut rng = XorShift64::new(seed);
+        let mut my_vector: MyVector<String, P> = MyVector::new_with_policy();
+        let mut expected: Vec<String> = Vec::new();
+        for step in 0..400 {
+            let value = step.to_string();
+            match rng.next_u64() % 8 {
+                0..=3 => my_vector.push(value.clone()),
+                4 => {
+                    let idx = rng.next_u64() as usize % (expected.len() + 1);
+                    my_vector.insert(idx, value.clone());
+                    expected.insert(idx, value.clone());
+                    continue;
+                }
+                5 if !expected.is_empty() => {
+                    let idx = rng.next_u64() as usize % expected.len();
+                    assert_eq!(my_vector.remove(idx), expected.remove(idx));
+                    continue;
+                }
+                6 if !expected.is_empty() => {
+                    let idx = rng.next_u64() as usize % expected.len();
+                    assert_eq!(my_vector.swap_remove(idx), expected.swap_remove(idx));
+                    continue;
+                }
+                _ => {
+                    assert_eq!(my_vector.pop(), expected.pop());
+                    continue;
+                }
+            }
+            expected.push(value);
+        }
+        assert_eq!(my_vector.as_slice(), expected.as_slice());
+        assert!(my_vector.capacity() >= my_vector.len());
+    }
+
+    #[test]
+    fn random_operations_match_vec() {
+        random_operations_match_vec_with::<Doubling>(49);
+        random_operations_match_vec_with::<Golden>(50);
+        random_operations_match_vec_with::<FixedIncrement<7>>(51);
+        random_operations_match_vec_with::<JemallocSizeClasses>(52);
+    }
+
+    #[test]
+    fn growth_stats_count_every_allocator_call() {
+        let mut doubling: MyVector<u32> = MyVector::new();
+        let mut fixed: MyVector<u32, FixedIncrement<10>> = MyVector::new_with_policy();
+        for value in 0..100 {
+            doubling.push(value);
+            fixed.push(value);
+        }
+        // 2, 4, ..., 128 slots and 10, 20, ..., 100 slots
+        assert_eq!(doubling.growth_stats().reallocations, 7);
+        assert_eq!(fixed.growth_stats().reallocations, 10);
+        // Every grow happens when full, so the worst case copies 2 + 4 + ... + 64 elements
+        let stats = doubling.growth_stats();
+        assert_eq!(stats.worst_case_bytes_copied, 126 * 4);
+        assert!(stats.bytes_copied <= stats.worst_case_bytes_copied);
+        // The first allocation has nothing to move
+        assert!(stats.moves < stats.reallocations);
+        assert_eq!(fixed.growth_stats().worst_case_bytes_copied, 450 * 4);
+        assert_eq!(doubling.wasted_capacity_bytes(), 28 * 4);
+        assert_eq!(fixed.wasted_capacity_bytes(), 0);
+        doubling.shrink_to_fit();
+        assert_eq!(doubling.wasted_capacity_bytes(), 0);
+        assert_eq!(doubling.growth_stats().reallocations, 8);
+        // A clone allocates once and has its own stats
+        assert_eq!(doubling.clone().growth_stats().reallocations, 1);
+    }
+
+    #[test]
+    fn every_element_is_dropped_exactly_once() {
+        let drops = Rc::new(Cell::new(0));
+        let mut my_vector = counters(10, &drops);
+        my_vector.truncate(7);
+        assert_eq!(drops.get(), 3);
+        let removed = my_vector.remove(0);
+        let popped = my_vector.pop().unwrap();
+        assert_eq!((removed.value, popped.value), (0, 6));
+        drop((removed, popped));
+        assert_eq!(drops.get(), 5);
+        my_vector.shrink_to_fit();
+        assert_eq!(my_vector.capacity(), 5);
+        let cloned = my_vector.clone();
+        drop(my_vector);
+        assert_eq!(drops.get(), 10);
+        drop(cloned);
+        assert_eq!(drops.get(), 15);
+
+        // A partly consumed owning iterator drops the rest
+        let drops = Rc::new(Cell::new(0));
+        let mut iter = counters(6, &drops).into_iter();
+        assert_eq!(iter.next().map(|counter| counter.value), Some(0));
+        assert_eq!(iter.next_back().map(|counter| counter.value), Some(5));
+        assert_eq!(iter.len(), 4);
+        drop(iter);
+        assert_eq!(drops.get(), 6);
+    }
+
+    #[test]
+    fn panicking_element_drop_still_frees_the_buffer() {
+        // Panics when dropped if armed. Miri reports the buffer as leaked if Drop skips it.
+        struct PanicOnDrop {
+            armed: bool,
+            drops: Rc<Cell<usize>>,
+        }
+
+        impl Drop for PanicOnDrop {
+            fn drop(&mut self) {
+                self.drops.set(self.drops.get() + 1);
+                if self.armed {
+                    panic!("element drop panicked");
+                }
+            }
+        }
+
+        let drops = Rc::new(Cell::new(0));
+        let mut my_vector = MyVector::new();
+        for idx in 0..5 {
+            my_vector.push(PanicOnDrop {
+                armed: idx == 2,
+                drops: Rc::clone(&drops),
+            });
+        }
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| drop(my_vector)));
+        assert!(result.is_err());
+        // drop_in_place keeps dropping the rest of the slice after the panic
+        assert_eq!(drops.get(), 5);
+    }
+
+    #[test]
+    fn zero_capacity_and_zero_sized_types() {
+        let mut empty: MyVector<u64> = MyVector::with_capacity(0);
+        assert_eq!((empty.len(), empty.capacity()), (0, 0));
+        assert_eq!(empty.pop(), None);
+        assert!(empty.iter().next().is_none());
+        empty.shrink_to_fit();
+        empty.push(1);
+        empty.clear();
+        empty.shrink_to_fit();
+        assert_eq!(empty.capacity(), 0);
+
+        let mut units: MyVector<()> = MyVector::with_capacity(10);
+        for _ in 0..1000 {
+            units.push(());
+        }
+        units.insert(3, ());
+        units.remove(0);
+        assert_eq!((units.len(), units.capacity()), (1000, usize::MAX));
+        assert_eq!(units.into_iter().count(), 1000);
+    }
+
+    #[test]
+    fn slices_iterators_and_collections() {
+        let mut my_vector: MyVector<i32> = [5, 3, 9].into_iter().collect();
+        my_vector.extend([1, 7]);
+        my_vector.sort();
+        assert_eq!(my_vector.as_slice(), [1, 3, 5, 7, 9]);
+        for value in &mut my_vector {
+            *value *= 10;
+        }
+        assert_eq!((&my_vector).into_iter().sum::<i32>(), 250);
+        assert!(my_vector.contains(&70));
+        my_vector[0] = -1;
+        assert_eq!(format!("{:?}", my_vector), "[-1, 30, 50, 70, 90]");
+        assert_eq!(my_vector.clone(), my_vector);
+        assert_eq!(
+            my_vector.into_iter().rev().collect::<Vec<i32>>(),
+            [90, 70, 50, 30, -1]
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "index out of bounds: the len is 2 but the index is 2")]
+    fn index_past_len_panics() {
+        let my_vector: MyVector<i32> = [1, 2].into_iter().collect();
+        let _ = my_vector[2];
+    }
+}
@@ -0,0 +1,33 @@
+//! Array and matrix kernels built up while working through the tutorial
+//! modules, exposed as a library so they can be reused outside of the
+//! tutorial binary.
+//!
+//! - [`array`]: reversal, rotation, partitioning, prefix sums, subarray sums (also streamed)
+//!   and Kadane.
+//! - [`dynamic_array`]: `MyVector`, the module 3 dynamic array on the raw allocator, with
+//!   pluggable growth policies and reallocation/copy cost accounting.
+//! - [`window`]: fixed and variable sliding window techniques, and a generic window toolkit.
+//! - [`matrix`]: flattening, Morton/Hilbert layouts, transpose, rotate, row/col sums,
+//!   matrix multiplication (tiled, cache-oblivious, Strassen, packed GEMM, transpose
+//!   flags, batched), LU/QR solve, inverse and least squares, sparse COO/CSR/CSC,
+//!   2D Kadane, spiral/zig-zag/diagonal/snake traversals, convolution and 2d prefix
+//!   sums.
+//! - [`bench`]: timing helpers, report printers and deterministic data generators.
+//! - [`cachesim`]: set-associative cache simulator and instrumented matrix kernels.
+//! - [`parallel`]: row-band partitioning over scoped threads for the `*_parallel` kernels.
+//! - [`range_query`]: Fenwick trees, segment trees and sparse tables for range sums, min and max,
+//!   1d and 2d.
+//! - [`simd`]: explicit SSE2/AVX2 kernels with runtime feature detection and scalar fallbacks.
+//! - [`tuning`]: cache-aware block size auto-tuning for the tiled matrix kernels.
+//! - [`visualize`]: step-through visualizer for the tiled matmul, transpose and rotate loops.
+pub mod array;
+pub mod bench;
+pub mod cachesim;
+pub mod dynamic_array;
+pub mod matrix;
+pub mod parallel;
+pub mod range_query;
+pub mod simd;
+pub mod tuning;
+pub mod visualize;
+pub mod window;
@@ -1,16 +1,114 @@
 mod tutorial;
-use std::env;
+use dsa_image_processing::{
+    bench::{
+        baseline::{CompareConfig, compare, load_baseline_file, print_comparison},
+        export::{Metadata, OutputFormat, result_rows, to_csv, to_json},
+        harness::BenchReport,
+        session,
+    },
+    parallel,
+};
+use std::{env, fs, process};
 use tutorial::{
     arrays_module1::arrays_module1_memory_access_patterns,
     arrays_module2::arrays_module2_performance_tradeoffs_benchmark,
     arrays_module3::arrays_module3_dynamic_arrays_and_amortized_analysis,
     arrays_module4::arrays_module4_core_array_algorithms,
     arrays_module5::arrays_module5_sliding_windows_and_prefix_sum_tricks,
-    arrays_module6::arrays_module6_multi_dimensional_arrays_and_cache,
+    arrays_module6::{
+        arrays_module6_access_order_heatmaps, arrays_module6_block_size_auto_tuning,
+        arrays_module6_multi_dimensional_arrays_and_cache, arrays_module6_tiled_loop_visualizer,
+    },
 };
 
+const USAGE: &str = "usage: dsa-image-processing <module|*|tune|visualize|heatmap> [--format table|csv|json] [--output FILE] [--baseline FILE] [--threshold PERCENT] [--threads N]";
+
+struct Options {
+    choice: Option<String>,
+    format: OutputFormat,
+    output: Option<String>,
+    baseline: Option<String>,
+    compare_config: CompareConfig,
+    threads: Option<usize>,
+}
+
+fn parse_options(args: &[String]) -> Result<Options, String> {
+    let mut options = Options {
+        choice: None,
+        format: OutputFormat::Table,
+        output: None,
+        baseline: None,
+        compare_config: CompareConfig::default(),
+        threads: None,
+    };
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        let mut value = |flag: &str| {
+            args.next()
+                .cloned()
+                .ok_or(format!("{} expects a value", flag))
+        };
+        match arg.as_str() {
+            "--format" => {
+                let name = value("--format")?;
+                options.format = OutputFormat::parse(&name).ok_or(format!(
+                    "unknown format '{}', expected table, csv or json",
+                    name
+                ))?;
+            }
+            "--output" => options.output = Some(value("--output")?),
+            "--baseline" => options.baseline = Some(value("--baseline")?),
+            "--threshold" => {
+                let percent = value("--threshold")?;
+                let percent: f64 = percent
+                    .parse()
+                    .ok()
+                    .filter(|percent: &f64| *percent >= 0.0)
+                    .ok_or(format!("invalid threshold '{}'", percent))?;
+                options.compare_config.threshold = percent / 100.0;
+            }
+            "--threads" => {
+                let count = value("--threads")?;
+                let count: usize = count
+                    .parse()
+                    .ok()
+                    .filter(|&count| count > 0)
+                    .ok_or(format!("invalid thread count '{}'", count))?;
+                options.threads = Some(count);
+            }
+            flag if flag.starts_with("--") => return Err(format!("unknown option '{}'", flag)),
+            choice if options.choice.is_none() => options.choice = Some(choice.to_string()),
+            extra => return Err(format!("unexpected argument '{}'", extra)),
+        }
+    }
+    // stdout carries the tutorial tables, so an export printed there would not parse
+    if options.format != OutputFormat::Table && options.output.is_none() {
+        return Err(String::from("--format csv|json needs --output FILE"));
+    }
+    Ok(options)
+}
+
 fn main() {
-    if let Some(choice) = env::args().nth(1).as_deref() {
+    let args: Vec<String> = env::args().skip(1).collect();
+    let options = match parse_options(&args) {
+        Ok(options) => options,
+        Err(message) => {
+            eprintln!("{}\n{}", message, USAGE);
+            process::exit(2);
+        }
+    };
+
+    if let Some(threads) = options.threads {
+        parallel::set_thread_count(threads);
+    }
+
+    // Only keep hold of the benchmark reports when something is going to consume them
+    let collect_results = options.format != OutputFormat::Table || options.baseline.is_some();
+    if collect_results {
+        session::start();
+    }
+
+    if let Some(choice) = options.choice.as_deref() {
         if choice == "*" {
             let valid_choices = ["1", "2", "3", "4", "5", "6"];
             for choice in valid_choices {
@@ -25,9 +123,45 @@ fn main() {
         println!(
             "No tutorial module specified or it is invalid. Specify which tutorial module you wish to run by specifying a number following the command i.e. cargo run --release -- 2"
         );
+        println!("{}", USAGE);
+    }
+
+    if collect_results {
+        let reports = session::finish();
+        if let Err(message) = export_results(&options, &reports) {
+            eprintln!("{}", message);
+            process::exit(2);
+        }
+        if let Some(baseline_path) = options.baseline.as_deref() {
+            let baseline = match load_baseline_file(baseline_path) {
+                Ok(baseline) => baseline,
+                Err(message) => {
+                    eprintln!("could not load baseline {}", message);
+                    process::exit(2);
+                }
+            };
+            let comparison = compare(&baseline, &result_rows(&reports), options.compare_config);
+            print_comparison(&comparison, options.compare_config);
+            if !comparison.regressions.is_empty() {
+                process::exit(1);
+            }
+        }
     }
 }
 
+fn export_results(options: &Options, reports: &[BenchReport]) -> Result<(), String> {
+    let text = match options.format {
+        OutputFormat::Table => return Ok(()),
+        OutputFormat::Csv => to_csv(reports, &Metadata::collect()),
+        OutputFormat::Json => to_json(reports, &Metadata::collect()),
+    };
+    // parse_options makes sure csv and json always come with an --output path
+    let Some(path) = options.output.as_deref() else {
+        return Err(String::from("--format csv|json needs --output FILE"));
+    };
+    fs::write(path, text).map_err(|e| format!("could not write {}: {}", path, e))
+}
+
 fn run_choice(choice: &str) {
     match choice {
         "1" => arrays_module1_memory_access_patterns(),
@@ -36,6 +170,9 @@ fn run_choice(choice: &str) {
         "4" => arrays_module4_core_array_algorithms(),
         "5" => arrays_module5_sliding_windows_and_prefix_sum_tricks(),
         "6" => arrays_module6_multi_dimensional_arrays_and_cache(),
+        "tune" => arrays_module6_block_size_auto_tuning(),
+        "visualize" => arrays_module6_tiled_loop_visualizer(),
+        "heatmap" => arrays_module6_access_order_heatmaps(),
         _ => {}
     }
 }
@@ -0,0 +1,38 @@
+// Module 6 multi-dimensional array (matrix) kernels.
+pub mod convolve;
+pub mod curve_layout;
+pub mod display;
+pub mod flatten;
+pub mod gemm;
+pub mod linalg;
+pub mod max_submatrix;
+pub mod multiply;
+pub mod op_multiply;
+pub mod prefix_sum;
+pub mod rotate;
+pub mod sparse;
+pub mod strassen;
+pub mod sum;
+pub mod transpose;
+pub mod traversal;
+
+/// Creates a `row_count` x `col_count` matrix, either filled with
+/// `default_value` or, when `default_value` is 0, numbered by cell index.
+pub fn make_matrix(row_count: usize, col_count: usize, default_value: i32) -> Vec<Vec<i32>> {
+    //
+    let mut rows: Vec<Vec<i32>> = Vec::with_capacity(row_count);
+    let mut cell_number = 0;
+    for _ in 0..row_count {
+        let mut cols = Vec::with_capacity(col_count);
+        for _ in 0..col_count {
+            if default_value == 0 {
+                cols.push(cell_number);
+            } else {
+                cols.push(default_value);
+            }
+            cell_number += 1;
+        }
+        rows.push(cols);
+    }
+    rows
+}
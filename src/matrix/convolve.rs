@@ -0,0 +1,232 @@
+// 2d convolution of a single channel row-major image with a square, odd sided kernel.
+// Pixels past the edge take the value of the nearest edge pixel (clamp to edge), so the
+// output is the same size as the input. The kernel is applied as is, not flipped, which is
+// the same thing for the symmetric kernels used here (box and gaussian blur).
+use crate::parallel::{map_row_bands, pool::ThreadPool};
+use std::ops::Range;
+
+// side x side kernel of equal weights that sum to 1
+pub fn box_blur_kernel(side: usize) -> Vec<f32> {
+    if side.is_multiple_of(2) {
+        panic!("Kernel side must be odd.");
+    }
+    vec![1.0 / (side * side) as f32; side * side]
+}
+
+pub fn convolve_2d_clamped(
+    image: &[f32],
+    row_count: usize,
+    col_count: usize,
+    kernel: &[f32],
+    kernel_side: usize,
+) -> Vec<f32> {
+    validate_convolution(image, row_count, col_count, kernel, kernel_side);
+    let mut output = vec![0.0; row_count * col_count];
+    convolve_rows_clamped(
+        image,
+        row_count,
+        col_count,
+        kernel,
+        kernel_side,
+        0..row_count,
+        &mut output,
+    );
+    output
+}
+
+// convolve_2d_clamped with the output rows split into one band per thread. Every output
+// pixel is summed in the same order as the single threaded version, so the results match
+// bit for bit.
+pub fn convolve_2d_clamped_parallel(
+    image: &[f32],
+    row_count: usize,
+    col_count: usize,
+    kernel: &[f32],
+    kernel_side: usize,
+    thread_count: usize,
+) -> Vec<f32> {
+    validate_convolution(image, row_count, col_count, kernel, kernel_side);
+    let mut output = vec![0.0; row_count * col_count];
+    map_row_bands(&mut output, col_count, thread_count, |_, rows, band| {
+        convolve_rows_clamped(image, row_count, col_count, kernel, kernel_side, rows, band);
+    });
+    output
+}
+
+// convolve_2d_clamped on the work-stealing pool, one task per tile x tile block of output
+pub fn convolve_2d_clamped_pooled(
+    image: &[f32],
+    row_count: usize,
+    col_count: usize,
+    kernel: &[f32],
+    kernel_side: usize,
+    tile: usize,
+    pool: &ThreadPool,
+) -> Vec<f32> {
+    validate_convolution(image, row_count, col_count, kernel, kernel_side);
+    let mut output = vec![0.0; row_count * col_count];
+    pool.par_for_each_tile_mut(&mut output, col_count, row_count, tile, |mut view| {
+        let tile = view.tile();
+        for row_idx in tile.rows() {
+            for (col_idx, pixel) in tile.cols().zip(view.row_mut(row_idx)) {
+                *pixel = convolve_pixel_clamped(
+                    image,
+                    row_count,
+                    col_count,
+                    kernel,
+                    kernel_side,
+                    row_idx,
+                    col_idx,
+                );
+            }
+        }
+    });
+    output
+}
+
+fn validate_convolution(
+    image: &[f32],
+    row_count: usize,
+    col_count: usize,
+    kernel: &[f32],
+    kernel_side: usize,
+) {
+    if image.len() != row_count * col_count {
+        panic!("Image dimensions do not match the pixel count.");
+    }
+    if kernel_side.is_multiple_of(2) || kernel.len() != kernel_side * kernel_side {
+        panic!("Kernel must be square with an odd side.");
+    }
+}
+
+// Convolves the output rows in `rows` into `output_band`, which holds just those rows
+pub(crate) fn convolve_rows_clamped(
+    image: &[f32],
+    row_count: usize,
+    col_count: usize,
+    kernel: &[f32],
+    kernel_side: usize,
+    rows: Range<usize>,
+    output_band: &mut [f32],
+) {
+    for (band_row_idx, row_idx) in rows.enumerate() {
+        let output_row = &mut output_band[band_row_idx * col_count..(band_row_idx + 1) * col_count];
+        for (col_idx, pixel) in output_row.iter_mut().enumerate() {
+            *pixel = convolve_pixel_clamped(
+                image,
+                row_count,
+                col_count,
+                kernel,
+                kernel_side,
+                row_idx,
+                col_idx,
+            );
+        }
+    }
+}
+
+// One output pixel, the kernel rows and columns are always summed in the same order
+fn convolve_pixel_clamped(
+    image: &[f32],
+    row_count: usize,
+    col_count: usize,
+    kernel: &[f32],
+    kernel_side: usize,
+    row_idx: usize,
+    col_idx: usize,
+) -> f32 {
+    let radius = (kernel_side / 2) as isize;
+    let clamp = |idx: isize, len: usize| idx.clamp(0, len as isize - 1) as usize;
+    let mut sum = 0.0;
+    for kernel_row_idx in 0..kernel_side {
+        let image_row_idx = clamp(
+            row_idx as isize + kernel_row_idx as isize - radius,
+            row_count,
+        );
+        let image_row = &image[image_row_idx * col_count..(image_row_idx + 1) * col_count];
+        let kernel_row = &kernel[kernel_row_idx * kernel_side..(kernel_row_idx + 1) * kernel_side];
+        for (kernel_col_idx, &weight) in kernel_row.iter().enumerate() {
+            let image_col_idx = clamp(
+                col_idx as isize + kernel_col_idx as isize - radius,
+                col_count,
+            );
+            sum += weight * image_row[image_col_idx];
+        }
+    }
+    sum
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn box_blur_averages_the_neighbourhood() {
+        // 3x3 image, the centre pixel sees every pixel once
+        let image: Vec<f32> = (1..=9).map(|v| v as f32).collect();
+        let blurred = convolve_2d_clamped(&image, 3, 3, &box_blur_kernel(3), 3);
+        assert!((blurred[4] - 5.0).abs() < 1e-6);
+        // corner (0,0) clamps to rows/cols 0,0,1: (1+1+2)*2 + (4+4+5) = 21 over 9
+        assert!((blurred[0] - 21.0 / 9.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn identity_kernel_returns_the_image() {
+        let image: Vec<f32> = (0..35).map(|v| v as f32).collect();
+        let mut kernel = vec![0.0; 25];
+        kernel[12] = 1.0;
+        assert_eq!(convolve_2d_clamped(&image, 5, 7, &kernel, 5), image);
+    }
+
+    #[test]
+    fn parallel_matches_single_threaded_bit_for_bit() {
+        let (row_count, col_count) = (37, 23);
+        let image: Vec<f32> = (0..row_count * col_count)
+            .map(|v| (v % 17) as f32 / 7.0)
+            .collect();
+        let kernel = box_blur_kernel(5);
+        let expected = convolve_2d_clamped(&image, row_count, col_count, &kernel, 5);
+        for thread_count in [1, 2, 5, 64] {
+            let output = convolve_2d_clamped_parallel(
+                &image,
+                row_count,
+                col_count,
+                &kernel,
+                5,
+                thread_count,
+            );
+            assert!(
+                output
+                    .iter()
+                    .zip(&expected)
+                    .all(|(a, b)| a.to_bits() == b.to_bits())
+            );
+        }
+    }
+
+    #[test]
+    fn pooled_matches_single_threaded_bit_for_bit() {
+        let (row_count, col_count) = (41, 29);
+        let image: Vec<f32> = (0..row_count * col_count)
+            .map(|v| (v % 19) as f32 / 3.0)
+            .collect();
+        let kernel = box_blur_kernel(3);
+        let expected = convolve_2d_clamped(&image, row_count, col_count, &kernel, 3);
+        for pool in [ThreadPool::new(3), ThreadPool::deterministic()] {
+            let output =
+                convolve_2d_clamped_pooled(&image, row_count, col_count, &kernel, 3, 8, &pool);
+            assert!(
+                output
+                    .iter()
+                    .zip(&expected)
+                    .all(|(a, b)| a.to_bits() == b.to_bits())
+            );
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "odd side")]
+    fn even_kernels_are_rejected() {
+        convolve_2d_clamped(&[0.0; 4], 2, 2, &[0.25; 4], 2);
+    }
+}
@@ -0,0 +1,631 @@
+// Morton (Z-order) and Hilbert curve layouts, the alternatives to flatten_row_major and
+// flatten_col_major.
+//
+// Both curves store every aligned 2^k x 2^k quadrant of the matrix as one contiguous run, so
+// the recursive kernels below get the blocking of the loop tiled kernels at every block size at
+// once instead of one hand picked size. The curves are defined on power of two squares, other
+// shapes are padded with T::default() up to the enclosing square.
+//
+// Morton codes interleave the bits of the row and column. Hilbert codes never jump between
+// neighbouring cells, but each quadrant is stored in one of four orientations (identity,
+// transposed, flipped, or both), which the recursion has to keep track of.
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CurveLayout {
+    Morton,
+    Hilbert,
+}
+
+impl CurveLayout {
+    pub fn name(&self) -> &'static str {
+        match self {
+            CurveLayout::Morton => "morton",
+            CurveLayout::Hilbert => "hilbert",
+        }
+    }
+
+    // Position of (row, col) along the curve over a side x side square
+    pub fn encode(&self, side: usize, row: usize, col: usize) -> usize {
+        match self {
+            CurveLayout::Morton => morton_encode_2d(row, col),
+            CurveLayout::Hilbert => hilbert_encode_2d(side, row, col),
+        }
+    }
+
+    pub fn decode(&self, side: usize, code: usize) -> (usize, usize) {
+        match self {
+            CurveLayout::Morton => morton_decode_2d(code),
+            CurveLayout::Hilbert => hilbert_decode_2d(side, code),
+        }
+    }
+}
+
+// 0b1011 -> 0b1000101, the bits of v move to the even positions
+fn spread_bits(v: usize) -> u64 {
+    let mut v = v as u64 & 0x0000_0000_ffff_ffff;
+    v = (v | (v << 16)) & 0x0000_ffff_0000_ffff;
+    v = (v | (v << 8)) & 0x00ff_00ff_00ff_00ff;
+    v = (v | (v << 4)) & 0x0f0f_0f0f_0f0f_0f0f;
+    v = (v | (v << 2)) & 0x3333_3333_3333_3333;
+    v = (v | (v << 1)) & 0x5555_5555_5555_5555;
+    v
+}
+
+// Inverse of spread_bits, the odd bits are dropped
+fn compact_bits(v: u64) -> usize {
+    let mut v = v & 0x5555_5555_5555_5555;
+    v = (v | (v >> 1)) & 0x3333_3333_3333_3333;
+    v = (v | (v >> 2)) & 0x0f0f_0f0f_0f0f_0f0f;
+    v = (v | (v >> 4)) & 0x00ff_00ff_00ff_00ff;
+    v = (v | (v >> 8)) & 0x0000_ffff_0000_ffff;
+    v = (v | (v >> 16)) & 0x0000_0000_ffff_ffff;
+    v as usize
+}
+
+// Bits of the column go to the even positions, bits of the row to the odd ones. Codes don't
+// depend on the size of the square, (row, col) has the same code in any square containing it.
+pub fn morton_encode_2d(row: usize, col: usize) -> usize {
+    ((spread_bits(row) << 1) | spread_bits(col)) as usize
+}
+
+pub fn morton_decode_2d(code: usize) -> (usize, usize) {
+    let code = code as u64;
+    (compact_bits(code >> 1), compact_bits(code))
+}
+
+// The classic xy2d walk (x = col, y = row): pick the quadrant at each level, then rotate the
+// remaining low bits into the orientation that quadrant's sub-curve is drawn in.
+pub fn hilbert_encode_2d(side: usize, row: usize, col: usize) -> usize {
+    debug_assert!(side.is_power_of_two() && row < side && col < side);
+    let (mut x, mut y) = (col, row);
+    let mut code = 0;
+    let mut half = side / 2;
+    while half > 0 {
+        let rx = usize::from(x & half != 0);
+        let ry = usize::from(y & half != 0);
+        code += half * half * ((3 * rx) ^ ry);
+        if ry == 0 {
+            if rx == 1 {
+                x = side - 1 - x;
+                y = side - 1 - y;
+            }
+            std::mem::swap(&mut x, &mut y);
+        }
+        half /= 2;
+    }
+    code
+}
+
+pub fn hilbert_decode_2d(side: usize, code: usize) -> (usize, usize) {
+    debug_assert!(side.is_power_of_two() && code < side * side);
+    let (mut x, mut y) = (0, 0);
+    let mut remaining = code;
+    let mut size = 1;
+    while size < side {
+        let rx = 1 & (remaining / 2);
+        let ry = 1 & (remaining ^ rx);
+        if ry == 0 {
+            if rx == 1 {
+                x = size - 1 - x;
+                y = size - 1 - y;
+            }
+            std::mem::swap(&mut x, &mut y);
+        }
+        x += size * rx;
+        y += size * ry;
+        remaining /= 4;
+        size *= 2;
+    }
+    (y, x)
+}
+
+// Side of the power of two square a row_count x col_count matrix is padded to
+pub fn enclosing_side(row_count: usize, col_count: usize) -> usize {
+    row_count.max(col_count).max(1).next_power_of_two()
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct CurveMatrix<T> {
+    pub layout: CurveLayout,
+    pub row_count: usize,
+    pub col_count: usize,
+    pub side: usize,
+    // side * side cells in curve order, the padding cells hold T::default()
+    pub data: Vec<T>,
+}
+
+impl<T: Copy + Default> CurveMatrix<T> {
+    pub fn from_row_major(
+        flat_matrix_row_major_1d: &[T],
+        row_count: usize,
+        col_count: usize,
+        layout: CurveLayout,
+    ) -> CurveMatrix<T> {
+        CurveMatrix::from_row_major_with_side(
+            flat_matrix_row_major_1d,
+            row_count,
+            col_count,
+            layout,
+            enclosing_side(row_count, col_count),
+        )
+    }
+
+    // For operands that have to share a side, e.g. the A and B of a multiplication
+    pub fn from_row_major_with_side(
+        flat_matrix_row_major_1d: &[T],
+        row_count: usize,
+        col_count: usize,
+        layout: CurveLayout,
+        side: usize,
+    ) -> CurveMatrix<T> {
+        if flat_matrix_row_major_1d.len() != row_count * col_count {
+            panic!("Matrix dimensions do not match the number of elements.");
+        }
+        if !side.is_power_of_two() || side < row_count.max(col_count) {
+            panic!("Side must be a power of two enclosing the matrix.");
+        }
+        let mut data = vec![T::default(); side * side];
+        for row_idx in 0..row_count {
+            for col_idx in 0..col_count {
+                data[layout.encode(side, row_idx, col_idx)] =
+                    flat_matrix_row_major_1d[row_idx * col_count + col_idx];
+            }
+        }
+        CurveMatrix {
+            layout,
+            row_count,
+            col_count,
+            side,
+            data,
+        }
+    }
+
+    pub fn to_row_major(&self) -> Vec<T> {
+        let mut flat_matrix_row_major_1d = Vec::with_capacity(self.row_count * self.col_count);
+        for row_idx in 0..self.row_count {
+            for col_idx in 0..self.col_count {
+                flat_matrix_row_major_1d.push(self.get(row_idx, col_idx));
+            }
+        }
+        flat_matrix_row_major_1d
+    }
+
+    pub fn index_of(&self, row: usize, col: usize) -> usize {
+        self.layout.encode(self.side, row, col)
+    }
+
+    pub fn get(&self, row: usize, col: usize) -> T {
+        self.data[self.index_of(row, col)]
+    }
+}
+
+// How a quadrant's sub-curve is drawn relative to the canonical curve of its size: the local
+// (row, col) is flipped to (side - 1 - row, side - 1 - col) and/or swapped to (col, row) before
+// encoding. Composing two orientations is an xor of the flags. Morton quadrants are always
+// drawn the canonical way.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+struct Orientation {
+    swap: bool,
+    flip: bool,
+}
+
+impl Orientation {
+    const ALL: [Orientation; 4] = [
+        Orientation {
+            swap: false,
+            flip: false,
+        },
+        Orientation {
+            swap: false,
+            flip: true,
+        },
+        Orientation {
+            swap: true,
+            flip: false,
+        },
+        Orientation {
+            swap: true,
+            flip: true,
+        },
+    ];
+
+    fn index(&self) -> usize {
+        usize::from(self.swap) * 2 + usize::from(self.flip)
+    }
+
+    fn compose(&self, other: Orientation) -> Orientation {
+        Orientation {
+            swap: self.swap ^ other.swap,
+            flip: self.flip ^ other.flip,
+        }
+    }
+
+    fn apply(&self, side: usize, row: usize, col: usize) -> (usize, usize) {
+        let (row, col) = if self.flip {
+            (side - 1 - row, side - 1 - col)
+        } else {
+            (row, col)
+        };
+        if self.swap { (col, row) } else { (row, col) }
+    }
+}
+
+// Which of the 4 contiguous quarter runs of a block holds its quadrant (quadrant_row,
+// quadrant_col), and the orientation that quarter is drawn in
+fn quadrant_chunk(
+    layout: CurveLayout,
+    orientation: Orientation,
+    quadrant_row: usize,
+    quadrant_col: usize,
+) -> (usize, Orientation) {
+    match layout {
+        CurveLayout::Morton => ((quadrant_row << 1) | quadrant_col, orientation),
+        CurveLayout::Hilbert => {
+            let (row, col) = orientation.apply(2, quadrant_row, quadrant_col);
+            // canonical quadrant order is top left, bottom left, bottom right, top right; the
+            // two top quadrants are drawn transposed, the top right one also flipped
+            let (chunk, sub_curve) = match (row, col) {
+                (0, 0) => (0, Orientation::ALL[2]),
+                (1, 0) => (1, Orientation::ALL[0]),
+                (1, 1) => (2, Orientation::ALL[0]),
+                _ => (3, Orientation::ALL[3]),
+            };
+            (chunk, orientation.compose(sub_curve))
+        }
+    }
+}
+
+// Below this side the recursion stops and the kernels loop over the block through lookup
+// tables, 3 blocks of 32x32 i32 fit in L1 with room for the tables
+const LEAF_SIDE: usize = 32;
+
+// Curve position of every local (row, col) of a leaf block, row-major, per orientation
+struct LeafTables {
+    side: usize,
+    index: Vec<Vec<u32>>,
+}
+
+impl LeafTables {
+    fn new(layout: CurveLayout, side: usize) -> LeafTables {
+        let index = Orientation::ALL
+            .iter()
+            .map(|orientation| {
+                let mut table = Vec::with_capacity(side * side);
+                for row_idx in 0..side {
+                    for col_idx in 0..side {
+                        let (row, col) = orientation.apply(side, row_idx, col_idx);
+                        table.push(layout.encode(side, row, col) as u32);
+                    }
+                }
+                table
+            })
+            .collect();
+        LeafTables { side, index }
+    }
+
+    fn table(&self, orientation: Orientation) -> &[u32] {
+        &self.index[orientation.index()]
+    }
+}
+
+fn transpose_block<T: Copy>(
+    layout: CurveLayout,
+    tables: &LeafTables,
+    source: &[T],
+    source_orientation: Orientation,
+    target: &mut [T],
+    target_orientation: Orientation,
+    side: usize,
+) {
+    if side <= tables.side {
+        let source_table = tables.table(source_orientation);
+        let target_table = tables.table(target_orientation);
+        for row_idx in 0..side {
+            for col_idx in 0..side {
+                target[target_table[col_idx * side + row_idx] as usize] =
+                    source[source_table[row_idx * side + col_idx] as usize];
+            }
+        }
+        return;
+    }
+    let quarter = side * side / 4;
+    for quadrant_row in 0..2 {
+        for quadrant_col in 0..2 {
+            // quadrant (r, c) of the source becomes quadrant (c, r) of the target
+            let (source_chunk, source_sub) =
+                quadrant_chunk(layout, source_orientation, quadrant_row, quadrant_col);
+            let (target_chunk, target_sub) =
+                quadrant_chunk(layout, target_orientation, quadrant_col, quadrant_row);
+            transpose_block(
+                layout,
+                tables,
+                &source[source_chunk * quarter..(source_chunk + 1) * quarter],
+                source_sub,
+                &mut target[target_chunk * quarter..(target_chunk + 1) * quarter],
+                target_sub,
+                side / 2,
+            );
+        }
+    }
+}
+
+// Out of place transpose that stays in the curve layout. Quadrants are swapped across the
+// diagonal recursively, so both the reads and the writes stay inside one contiguous run.
+pub fn transpose_curve_matrix<T: Copy + Default>(matrix: &CurveMatrix<T>) -> CurveMatrix<T> {
+    let side = matrix.side;
+    let mut data = vec![T::default(); side * side];
+    let tables = LeafTables::new(matrix.layout, side.min(LEAF_SIDE));
+    transpose_block(
+        matrix.layout,
+        &tables,
+        &matrix.data,
+        Orientation::default(),
+        &mut data,
+        Orientation::default(),
+        side,
+    );
+    CurveMatrix {
+        layout: matrix.layout,
+        row_count: matrix.col_count,
+        col_count: matrix.row_count,
+        side,
+        data,
+    }
+}
+
+struct Operand<'a> {
+    data: &'a [i32],
+    orientation: Orientation,
+}
+
+fn multiply_add_block(
+    layout: CurveLayout,
+    tables: &LeafTables,
+    a: Operand,
+    b: Operand,
+    c: &mut [i32],
+    c_orientation: Orientation,
+    side: usize,
+) {
+    if side <= tables.side {
+        let a_table = tables.table(a.orientation);
+        let b_table = tables.table(b.orientation);
+        let c_table = tables.table(c_orientation);
+        // i-k-j so the inner loop walks a row of B and C
+        for row_idx in 0..side {
+            for k_idx in 0..side {
+                let a_val = a.data[a_table[row_idx * side + k_idx] as usize];
+                for col_idx in 0..side {
+                    let b_val = b.data[b_table[k_idx * side + col_idx] as usize];
+                    c[c_table[row_idx * side + col_idx] as usize] += a_val * b_val;
+                }
+            }
+        }
+        return;
+    }
+    let quarter = side * side / 4;
+    // C(r, c) += A(r, 0) * B(0, c) + A(r, 1) * B(1, c)
+    for quadrant_row in 0..2 {
+        for quadrant_col in 0..2 {
+            let (c_chunk, c_sub) =
+                quadrant_chunk(layout, c_orientation, quadrant_row, quadrant_col);
+            for quadrant_k in 0..2 {
+                let (a_chunk, a_sub) =
+                    quadrant_chunk(layout, a.orientation, quadrant_row, quadrant_k);
+                let (b_chunk, b_sub) =
+                    quadrant_chunk(layout, b.orientation, quadrant_k, quadrant_col);
+                multiply_add_block(
+                    layout,
+                    tables,
+                    Operand {
+                        data: &a.data[a_chunk * quarter..(a_chunk + 1) * quarter],
+                        orientation: a_sub,
+                    },
+                    Operand {
+                        data: &b.data[b_chunk * quarter..(b_chunk + 1) * quarter],
+                        orientation: b_sub,
+                    },
+                    &mut c[c_chunk * quarter..(c_chunk + 1) * quarter],
+                    c_sub,
+                    side / 2,
+                );
+            }
+        }
+    }
+}
+
+// Recursive quadrant multiplication of two matrices in the same curve layout and side (build
+// them with CurveMatrix::from_row_major_with_side when their natural sides differ). The padding
+// is all zeros so it doesn't change the product.
+pub fn matrix_multiply_curve_matrix(
+    matrix_a: &CurveMatrix<i32>,
+    matrix_b: &CurveMatrix<i32>,
+) -> CurveMatrix<i32> {
+    if matrix_a.col_count != matrix_b.row_count {
+        panic!("Matrix B dimensions do not match multiplication requirements.");
+    }
+    if matrix_a.layout != matrix_b.layout || matrix_a.side != matrix_b.side {
+        panic!("Matrices must share the same curve layout and side.");
+    }
+    let layout = matrix_a.layout;
+    let side = matrix_a.side;
+    let mut data = vec![0; side * side];
+    let tables = LeafTables::new(layout, side.min(LEAF_SIDE));
+    multiply_add_block(
+        layout,
+        &tables,
+        Operand {
+            data: &matrix_a.data,
+            orientation: Orientation::default(),
+        },
+        Operand {
+            data: &matrix_b.data,
+            orientation: Orientation::default(),
+        },
+        &mut data,
+        Orientation::default(),
+        side,
+    );
+    CurveMatrix {
+        layout,
+        row_count: matrix_a.row_count,
+        col_count: matrix_b.col_count,
+        side,
+        data,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::matrix::multiply::matrix_multiply_loop_tiling_flat_row_major;
+    use crate::matrix::transpose::transpose_1d_row_major_matrix_loop_tiling_aka_block_tiling;
+
+    const LAYOUTS: [CurveLayout; 2] = [CurveLayout::Morton, CurveLayout::Hilbert];
+
+    fn numbered(len: usize) -> Vec<i32> {
+        (0..len as i32).map(|v| v % 7 - 3).collect()
+    }
+
+    #[test]
+    fn morton_codes_interleave_bits() {
+        assert_eq!(morton_encode_2d(0, 1), 1);
+        assert_eq!(morton_encode_2d(1, 0), 2);
+        assert_eq!(morton_encode_2d(0b11, 0b01), 0b1011);
+        assert_eq!(morton_encode_2d(5, 3), 0b100111);
+        let big = (1 << 31) + 12345;
+        assert_eq!(morton_decode_2d(morton_encode_2d(big, 7)), (big, 7));
+        for code in 0..256 {
+            let (row, col) = morton_decode_2d(code);
+            assert_eq!(morton_encode_2d(row, col), code);
+        }
+    }
+
+    #[test]
+    fn hilbert_codes_are_a_continuous_walk() {
+        assert_eq!(
+            (0..4)
+                .map(|code| hilbert_decode_2d(2, code))
+                .collect::<Vec<_>>(),
+            vec![(0, 0), (1, 0), (1, 1), (0, 1)]
+        );
+        for side in [1, 2, 4, 16, 64] {
+            for code in 0..side * side {
+                let (row, col) = hilbert_decode_2d(side, code);
+                assert_eq!(hilbert_encode_2d(side, row, col), code);
+                if code > 0 {
+                    let (prev_row, prev_col) = hilbert_decode_2d(side, code - 1);
+                    assert_eq!(prev_row.abs_diff(row) + prev_col.abs_diff(col), 1);
+                }
+            }
+        }
+    }
+
+    // Following quadrant_chunk down to single cells has to land on the encoded position
+    #[test]
+    fn quadrant_chunks_match_the_codes() {
+        let side = 32;
+        for layout in LAYOUTS {
+            for row in 0..side {
+                for col in 0..side {
+                    let mut offset = 0;
+                    let mut orientation = Orientation::default();
+                    let mut half = side / 2;
+                    while half > 0 {
+                        let (chunk, sub) = quadrant_chunk(
+                            layout,
+                            orientation,
+                            usize::from(row & half != 0),
+                            usize::from(col & half != 0),
+                        );
+                        offset += chunk * half * half;
+                        orientation = sub;
+                        half /= 2;
+                    }
+                    assert_eq!(offset, layout.encode(side, row, col));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn row_major_round_trip_with_padding() {
+        let flat = numbered(5 * 3);
+        for layout in LAYOUTS {
+            let curve = CurveMatrix::from_row_major(&flat, 5, 3, layout);
+            assert_eq!(curve.side, 8);
+            assert_eq!(curve.data.len(), 64);
+            assert_eq!(curve.get(4, 2), flat[4 * 3 + 2]);
+            assert_eq!(curve.to_row_major(), flat);
+        }
+        let morton = CurveMatrix::from_row_major(&[1, 2, 3, 4], 2, 2, CurveLayout::Morton);
+        assert_eq!(morton.data, vec![1, 2, 3, 4]);
+        let hilbert = CurveMatrix::from_row_major(&[1, 2, 3, 4], 2, 2, CurveLayout::Hilbert);
+        assert_eq!(hilbert.data, vec![1, 3, 4, 2]);
+    }
+
+    #[test]
+    fn transpose_matches_row_major() {
+        for (row_count, col_count) in [(1, 1), (3, 5), (64, 64), (100, 37)] {
+            let flat = numbered(row_count * col_count);
+            let expected = transpose_1d_row_major_matrix_loop_tiling_aka_block_tiling(
+                flat.clone(),
+                col_count,
+                16,
+            );
+            for layout in LAYOUTS {
+                let curve = CurveMatrix::from_row_major(&flat, row_count, col_count, layout);
+                let transposed = transpose_curve_matrix(&curve);
+                assert_eq!(
+                    (transposed.row_count, transposed.col_count),
+                    (col_count, row_count)
+                );
+                assert_eq!(transposed.to_row_major(), expected);
+            }
+        }
+    }
+
+    #[test]
+    fn multiply_matches_loop_tiling() {
+        for (a_row_count, a_col_count, b_col_count) in [(1, 1, 1), (5, 10, 6), (64, 128, 64)] {
+            let a = numbered(a_row_count * a_col_count);
+            let b = numbered(a_col_count * b_col_count);
+            let expected = matrix_multiply_loop_tiling_flat_row_major(
+                &a,
+                &b,
+                a_row_count,
+                a_col_count,
+                b_col_count,
+                32,
+            );
+            let side = enclosing_side(a_row_count, a_col_count)
+                .max(enclosing_side(a_col_count, b_col_count));
+            for layout in LAYOUTS {
+                let curve_a = CurveMatrix::from_row_major_with_side(
+                    &a,
+                    a_row_count,
+                    a_col_count,
+                    layout,
+                    side,
+                );
+                let curve_b = CurveMatrix::from_row_major_with_side(
+                    &b,
+                    a_col_count,
+                    b_col_count,
+                    layout,
+                    side,
+                );
+                let product = matrix_multiply_curve_matrix(&curve_a, &curve_b);
+                assert_eq!(product.to_row_major(), expected);
+            }
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "same curve layout and side")]
+    fn multiply_rejects_mixed_layouts() {
+        let a = CurveMatrix::from_row_major(&[1, 2, 3, 4], 2, 2, CurveLayout::Morton);
+        let b = CurveMatrix::from_row_major(&[1, 2, 3, 4], 2, 2, CurveLayout::Hilbert);
+        matrix_multiply_curve_matrix(&a, &b);
+    }
+}
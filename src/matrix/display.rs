@@ -0,0 +1,45 @@
+use crate::matrix::flatten::get_row_major;
+
+pub fn print_matrix_1d_vec_matrix(flat_row_major_matrix: &[i32], col_count: usize) {
+    print!(" {:>5} |", "idx");
+    for col_idx in 0..col_count {
+        print!(" {:>5} |", col_idx);
+    }
+    println!();
+    for _ in 0..=col_count {
+        print!("-------|");
+    }
+    println!();
+    for row_idx in 0..(flat_row_major_matrix.len() / col_count) {
+        print!(" {:>5} |", row_idx);
+        for col_idx in 0..col_count {
+            print!(
+                " {:>5} |",
+                get_row_major(flat_row_major_matrix, row_idx, col_idx, col_count)
+            );
+        }
+        println!();
+    }
+    println!("\n");
+}
+
+pub fn print_matrix_2d_vec_matrix(matrix: &[Vec<i32>]) {
+    print!(" {:>5} |", "idx");
+    for col_idx in 0..matrix[0].len() {
+        print!(" {:>5} |", col_idx);
+    }
+    println!();
+    for _ in 0..=matrix[0].len() {
+        print!("-------|");
+    }
+    println!();
+    for row_idx in 0..matrix.len() {
+        let row = &*matrix[row_idx];
+        print!(" {:>5} |", row_idx);
+        row.iter().for_each(|column_value| {
+            print!(" {:>5} |", column_value);
+        });
+        println!();
+    }
+    println!("\n");
+}
@@ -0,0 +1,39 @@
+/// Flatten a 2D vector into 1D row-major order.
+pub fn flatten_row_major<T: Clone>(matrix: &[Vec<T>]) -> Vec<T> {
+    let row_count = matrix.len();
+    let col_count = matrix[0].len();
+    let mut result = Vec::with_capacity(row_count * col_count);
+    for row_idx in 0..row_count {
+        for col_idx in 0..col_count {
+            let value = matrix[row_idx][col_idx].clone();
+            result.push(value);
+        }
+    }
+    result
+}
+
+/// Flatten a 2D vector into 1D column-major order.
+pub fn flatten_col_major<T: Clone>(matrix: &[Vec<T>]) -> Vec<T> {
+    let row_count = matrix.len();
+    let col_count = matrix[0].len();
+    let mut result = Vec::with_capacity(row_count * col_count);
+    for col_idx in 0..col_count {
+        for row_idx in 0..row_count {
+            let value = matrix[row_idx][col_idx].clone();
+            result.push(value);
+        }
+    }
+    result
+}
+
+/// Access element in row-major flattened array.
+/// rows = number of rows in the original matrix
+/// cols = number of columns in the original matrix
+pub fn get_row_major<T>(flat: &[T], row: usize, col: usize, cols: usize) -> &T {
+    &flat[row * cols + col]
+}
+
+/// Access element in column-major flattened array.
+pub fn get_col_major<T>(flat: &[T], row: usize, col: usize, rows: usize) -> &T {
+    &flat[col * rows + row]
+}
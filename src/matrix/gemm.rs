@@ -0,0 +1,455 @@
+// Packed GEMM (C = alpha * A * B + beta * C) for f32 and f64, structured like GotoBLAS/BLIS.
+//
+// The tiled kernels in multiply.rs block the loops but still read A and B straight out of
+// the row-major matrices, so every tile row is a separate stream and the inner loop keeps
+// reloading C. Here the five loops around the micro-kernel are:
+//
+//   for jc in 0..n step nc          B panel (kc x nc) packed once, lives in L3
+//     for pc in 0..k step kc
+//       for ic in 0..m step mc      A block (mc x kc) packed once, lives in L2
+//         for jr in 0..nc step NR   one NR wide micro-panel of B, lives in L1
+//           for ir in 0..mc step MR micro-kernel: MR x NR block of C kept in registers
+//
+// Packing copies the A and B blocks into the exact order the micro-kernel reads them, so
+// it streams through both with unit stride, and pads the fringes with zeros so the
+// micro-kernel never has to check bounds. Only the final write to C handles the fringe.
+pub mod micro_kernel;
+pub mod pack;
+
+use crate::simd;
+use crate::tuning::cache::{CacheLevel, data_cache_size, read_cache_levels};
+use micro_kernel::{MR, NR, micro_kernel};
+use pack::{pack_a, pack_b};
+use std::fmt::Debug;
+use std::ops::{Add, Mul};
+use std::sync::OnceLock;
+
+pub trait GemmScalar:
+    Copy + Default + Debug + PartialEq + Add<Output = Self> + Mul<Output = Self> + Send + Sync
+{
+    const ZERO: Self;
+    const ONE: Self;
+
+    // The MR x NR block product, f32 and f64 hand it to the SSE2/AVX2 kernels in simd.rs
+    fn micro_kernel(k_len: usize, a_panel: &[Self], b_panel: &[Self]) -> [[Self; NR]; MR] {
+        micro_kernel(k_len, a_panel, b_panel)
+    }
+}
+
+impl GemmScalar for f32 {
+    const ZERO: Self = 0.0;
+    const ONE: Self = 1.0;
+
+    fn micro_kernel(k_len: usize, a_panel: &[Self], b_panel: &[Self]) -> [[Self; NR]; MR] {
+        simd::micro_kernel_f32(k_len, a_panel, b_panel)
+    }
+}
+
+impl GemmScalar for f64 {
+    const ZERO: Self = 0.0;
+    const ONE: Self = 1.0;
+
+    fn micro_kernel(k_len: usize, a_panel: &[Self], b_panel: &[Self]) -> [[Self; NR]; MR] {
+        simd::micro_kernel_f64(k_len, a_panel, b_panel)
+    }
+}
+
+// (a_row_count x a_col_count) * (a_col_count x b_col_count), all three matrices row-major
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GemmShape {
+    pub a_row_count: usize,
+    pub a_col_count: usize,
+    pub b_col_count: usize,
+}
+
+impl GemmShape {
+    pub fn new(a_row_count: usize, a_col_count: usize, b_col_count: usize) -> GemmShape {
+        GemmShape {
+            a_row_count,
+            a_col_count,
+            b_col_count,
+        }
+    }
+
+    pub fn flop_count(&self) -> usize {
+        2 * self.a_row_count * self.a_col_count * self.b_col_count
+    }
+
+    fn validate<T>(&self, matrix_a: &[T], matrix_b: &[T], matrix_c: &[T]) {
+        if matrix_a.len() != self.a_row_count * self.a_col_count {
+            panic!("Matrix A dimensions do not match the gemm shape.");
+        }
+        if matrix_b.len() != self.a_col_count * self.b_col_count {
+            panic!(
+                "Matrix B dimensions do not match: A is {}x{} but B is {}.",
+                self.a_row_count,
+                self.a_col_count,
+                flat_shape(matrix_b.len(), self.b_col_count)
+            );
+        }
+        if matrix_c.len() != self.a_row_count * self.b_col_count {
+            panic!(
+                "Matrix C dimensions do not match: A * B is {}x{} but C is {}.",
+                self.a_row_count,
+                self.b_col_count,
+                flat_shape(matrix_c.len(), self.b_col_count)
+            );
+        }
+    }
+}
+
+// rows x cols of a row-major slice with `col_count` columns, or its length when that does
+// not come out whole
+fn flat_shape(len: usize, col_count: usize) -> String {
+    if col_count != 0 && len.is_multiple_of(col_count) {
+        format!("{}x{}", len / col_count, col_count)
+    } else {
+        format!("{} elements with {} columns", len, col_count)
+    }
+}
+
+// Cache blocking: A blocks are mc x kc, B panels kc x nc. mc and nc are multiples of MR/NR
+// so the packed buffers are made of whole micro-panels, gemm_with_blocking rounds them up
+// when a hand-built blocking isn't.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GemmBlocking {
+    pub mc: usize,
+    pub kc: usize,
+    pub nc: usize,
+}
+
+// Used for any level sysfs doesn't report
+const FALLBACK_CACHE_SIZES: [usize; 3] = [32 * 1024, 256 * 1024, 8 * 1024 * 1024];
+
+impl GemmBlocking {
+    // Half of each level for our block, the other half is left for C and whatever else is
+    // passing through: one A and one B micro-panel in L1, the packed A block in L2, the
+    // packed B panel in L3.
+    pub fn for_caches(caches: &[CacheLevel], element_size: usize) -> GemmBlocking {
+        let cache = |level: u32| {
+            data_cache_size(caches, level).unwrap_or(FALLBACK_CACHE_SIZES[level as usize - 1])
+        };
+        let kc = (cache(1) / 2 / ((MR + NR) * element_size)).clamp(16, 1024);
+        let mc = (cache(2) / 2 / (kc * element_size)).clamp(MR, 4096) / MR * MR;
+        let nc = (cache(3) / 2 / (kc * element_size)).clamp(NR, 8192) / NR * NR;
+        GemmBlocking { mc, kc, nc }
+    }
+
+    // Blocking for this machine's caches, read once
+    pub fn host<T>() -> GemmBlocking {
+        static CACHES: OnceLock<Vec<CacheLevel>> = OnceLock::new();
+        GemmBlocking::for_caches(CACHES.get_or_init(read_cache_levels), size_of::<T>())
+    }
+}
+
+// The reference every faster version is checked against
+pub fn gemm_triple_loop<T: GemmScalar>(
+    shape: GemmShape,
+    alpha: T,
+    matrix_a: &[T],
+    matrix_b: &[T],
+    beta: T,
+    matrix_c: &mut [T],
+) {
+    shape.validate(matrix_a, matrix_b, matrix_c);
+    let GemmShape {
+        a_row_count,
+        a_col_count,
+        b_col_count,
+    } = shape;
+    for row_idx in 0..a_row_count {
+        for col_idx in 0..b_col_count {
+            let mut total = T::ZERO;
+            for k_idx in 0..a_col_count {
+                total = total
+                    + matrix_a[row_idx * a_col_count + k_idx]
+                        * matrix_b[k_idx * b_col_count + col_idx];
+            }
+            let c = &mut matrix_c[row_idx * b_col_count + col_idx];
+            *c = scale_add(alpha, total, beta, *c);
+        }
+    }
+}
+
+// alpha * product + beta * c, with beta == 0 meaning "overwrite" as in BLAS, so whatever was
+// in C (NaN included) doesn't leak into the result
+#[inline(always)]
+fn scale_add<T: GemmScalar>(alpha: T, product: T, beta: T, c: T) -> T {
+    if beta == T::ZERO {
+        alpha * product
+    } else {
+        alpha * product + beta * c
+    }
+}
+
+// C = alpha * A * B + beta * C with the blocking for this machine's caches
+pub fn gemm<T: GemmScalar>(
+    shape: GemmShape,
+    alpha: T,
+    matrix_a: &[T],
+    matrix_b: &[T],
+    beta: T,
+    matrix_c: &mut [T],
+) {
+    gemm_with_blocking(
+        shape,
+        alpha,
+        matrix_a,
+        matrix_b,
+        beta,
+        matrix_c,
+        GemmBlocking::host::<T>(),
+    );
+}
+
+pub fn gemm_with_blocking<T: GemmScalar>(
+    shape: GemmShape,
+    alpha: T,
+    matrix_a: &[T],
+    matrix_b: &[T],
+    beta: T,
+    matrix_c: &mut [T],
+    blocking: GemmBlocking,
+) {
+    shape.validate(matrix_a, matrix_b, matrix_c);
+    let GemmBlocking { mc, kc, nc } = blocking;
+    if mc == 0 || kc == 0 || nc == 0 {
+        panic!("Gemm blocking sizes must be > 0.");
+    }
+    let GemmShape {
+        a_row_count: m,
+        a_col_count: k,
+        b_col_count: n,
+    } = shape;
+    if m == 0 || n == 0 {
+        return;
+    }
+    // Nothing to multiply, only the beta * C part is left
+    if k == 0 || alpha == T::ZERO {
+        for c in matrix_c.iter_mut() {
+            *c = scale_add(T::ZERO, T::ZERO, beta, *c);
+        }
+        return;
+    }
+
+    let mc = mc.next_multiple_of(MR);
+    let nc = nc.next_multiple_of(NR);
+    let mut a_packed = vec![T::ZERO; mc.min(m).div_ceil(MR) * MR * kc.min(k)];
+    let mut b_packed = vec![T::ZERO; nc.min(n).div_ceil(NR) * NR * kc.min(k)];
+
+    for jc in (0..n).step_by(nc) {
+        let nc_len = nc.min(n - jc);
+        for pc in (0..k).step_by(kc) {
+            let kc_len = kc.min(k - pc);
+            pack_b(matrix_b, n, pc, kc_len, jc, nc_len, &mut b_packed);
+            // beta applies once, the later k blocks add onto what the first one wrote
+            let beta_for_block = if pc == 0 { beta } else { T::ONE };
+            for ic in (0..m).step_by(mc) {
+                let mc_len = mc.min(m - ic);
+                pack_a(matrix_a, k, ic, mc_len, pc, kc_len, &mut a_packed);
+                for jr in (0..nc_len).step_by(NR) {
+                    let b_panel = &b_packed[jr * kc_len..(jr + NR) * kc_len];
+                    for ir in (0..mc_len).step_by(MR) {
+                        let a_panel = &a_packed[ir * kc_len..(ir + MR) * kc_len];
+                        let product = T::micro_kernel(kc_len, a_panel, b_panel);
+                        // write back only the part of the MR x NR block inside C
+                        let row_end = MR.min(mc_len - ir);
+                        let col_end = NR.min(nc_len - jr);
+                        for (block_row, product_row) in product.iter().enumerate().take(row_end) {
+                            let c_row_start = (ic + ir + block_row) * n + jc + jr;
+                            let c_row = &mut matrix_c[c_row_start..c_row_start + col_end];
+                            for (c, &value) in c_row.iter_mut().zip(product_row) {
+                                *c = scale_add(alpha, value, beta_for_block, *c);
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bench::data::XorShift64;
+
+    // Small integers keep every sum exact, so the blocked and naive results must be identical
+    fn random_matrix<T: GemmScalar + From<i8>>(seed: u64, len: usize) -> Vec<T> {
+        let mut rng = XorShift64::new(seed);
+        (0..len)
+            .map(|_| T::from((rng.next_u64() % 9) as i8 - 4))
+            .collect()
+    }
+
+    fn check_against_triple_loop<T: GemmScalar + From<i8>>(
+        shape: GemmShape,
+        alpha: T,
+        beta: T,
+        blocking: GemmBlocking,
+    ) {
+        let matrix_a = random_matrix::<T>(1, shape.a_row_count * shape.a_col_count);
+        let matrix_b = random_matrix::<T>(2, shape.a_col_count * shape.b_col_count);
+        let mut expected = random_matrix::<T>(3, shape.a_row_count * shape.b_col_count);
+        let mut matrix_c = expected.clone();
+        gemm_triple_loop(shape, alpha, &matrix_a, &matrix_b, beta, &mut expected);
+        gemm_with_blocking(
+            shape,
+            alpha,
+            &matrix_a,
+            &matrix_b,
+            beta,
+            &mut matrix_c,
+            blocking,
+        );
+        assert_eq!(matrix_c, expected, "{:?} {:?}", shape, blocking);
+    }
+
+    const SHAPES: [(usize, usize, usize); 7] = [
+        (1, 1, 1),
+        (4, 8, 8),
+        (5, 3, 9),
+        (37, 29, 41),
+        (64, 64, 64),
+        (100, 7, 3),
+        (3, 130, 70),
+    ];
+
+    #[test]
+    fn matches_triple_loop_f64() {
+        let small = GemmBlocking {
+            mc: 8,
+            kc: 16,
+            nc: 16,
+        };
+        for (m, k, n) in SHAPES {
+            let shape = GemmShape::new(m, k, n);
+            check_against_triple_loop::<f64>(shape, 1.0, 0.0, small);
+            check_against_triple_loop::<f64>(shape, 2.0, -1.0, small);
+            check_against_triple_loop::<f64>(shape, 0.5, 3.0, GemmBlocking::host::<f64>());
+        }
+    }
+
+    #[test]
+    fn matches_triple_loop_f32() {
+        let odd = GemmBlocking {
+            mc: 12,
+            kc: 5,
+            nc: 24,
+        };
+        for (m, k, n) in SHAPES {
+            let shape = GemmShape::new(m, k, n);
+            check_against_triple_loop::<f32>(shape, 1.0, 1.0, odd);
+            check_against_triple_loop::<f32>(shape, -1.0, 0.0, GemmBlocking::host::<f32>());
+        }
+    }
+
+    #[test]
+    fn beta_zero_overwrites_and_empty_k_scales() {
+        let shape = GemmShape::new(2, 2, 2);
+        let mut matrix_c = vec![f64::NAN; 4];
+        gemm(
+            shape,
+            1.0,
+            &[1.0, 2.0, 3.0, 4.0],
+            &[5.0, 6.0, 7.0, 8.0],
+            0.0,
+            &mut matrix_c,
+        );
+        assert_eq!(matrix_c, vec![19.0, 22.0, 43.0, 50.0]);
+
+        let mut matrix_c = vec![1.0f32, 2.0, 3.0, 4.0];
+        gemm(GemmShape::new(2, 0, 2), 1.0, &[], &[], 2.0, &mut matrix_c);
+        assert_eq!(matrix_c, vec![2.0, 4.0, 6.0, 8.0]);
+    }
+
+    #[test]
+    fn blocking_follows_the_caches() {
+        use crate::tuning::cache::CacheKind;
+        let level = |level, size_bytes| CacheLevel {
+            level,
+            kind: CacheKind::Unified,
+            size_bytes,
+            line_size: 64,
+            ways: 8,
+        };
+        let caches = [
+            level(1, 48 * 1024),
+            level(2, 2048 * 1024),
+            level(3, 32 * 1024 * 1024),
+        ];
+        let blocking = GemmBlocking::for_caches(&caches, size_of::<f64>());
+        // 24K / (12 * 8) = 256, 1M / (256 * 8) = 512, 16M / (256 * 8) capped at 8192
+        assert_eq!(
+            blocking,
+            GemmBlocking {
+                mc: 512,
+                kc: 256,
+                nc: 8192
+            }
+        );
+        let fallback = GemmBlocking::for_caches(&[], size_of::<f32>());
+        assert_eq!(fallback.kc, 16 * 1024 / (12 * 4));
+        assert_eq!(fallback.mc % MR, 0);
+        assert_eq!(fallback.nc % NR, 0);
+    }
+
+    #[test]
+    fn rounds_hand_built_blocking_to_whole_micro_panels() {
+        let ragged = GemmBlocking {
+            mc: 5,
+            kc: 3,
+            nc: 9,
+        };
+        for (m, k, n) in SHAPES {
+            check_against_triple_loop::<f64>(GemmShape::new(m, k, n), 1.5, 0.5, ragged);
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "Gemm blocking sizes must be > 0.")]
+    fn rejects_zero_blocking() {
+        let mut matrix_c = vec![0.0; 4];
+        gemm_with_blocking(
+            GemmShape::new(2, 2, 2),
+            1.0,
+            &[0.0; 4],
+            &[0.0; 4],
+            0.0,
+            &mut matrix_c,
+            GemmBlocking {
+                mc: 0,
+                kc: 16,
+                nc: 16,
+            },
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "A is 2x2 but B is 3 elements with 2 columns")]
+    fn rejects_mismatched_shapes() {
+        let mut matrix_c = vec![0.0; 4];
+        gemm(
+            GemmShape::new(2, 2, 2),
+            1.0,
+            &[0.0; 4],
+            &[0.0; 3],
+            0.0,
+            &mut matrix_c,
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "A is 2x3 but B is 2x2")]
+    fn names_both_shapes_when_a_columns_and_b_rows_differ() {
+        let mut matrix_c = vec![0.0; 4];
+        gemm(
+            GemmShape::new(2, 3, 2),
+            1.0,
+            &[0.0; 6],
+            &[0.0; 4],
+            0.0,
+            &mut matrix_c,
+        );
+    }
+}
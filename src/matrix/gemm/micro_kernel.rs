@@ -0,0 +1,48 @@
+// The innermost loop of the packed GEMM: an MR x NR block of C accumulated in registers over
+// kc steps, each step one column of the A micro-panel times one row of the B micro-panel
+// (an outer product). Fixed size arrays and no bounds checks in the loop body let LLVM keep
+// the accumulators in vector registers and vectorize the NR wide rows.
+use super::GemmScalar;
+
+pub const MR: usize = 4;
+pub const NR: usize = 8;
+
+#[inline(always)]
+pub fn micro_kernel<T: GemmScalar>(k_len: usize, a_panel: &[T], b_panel: &[T]) -> [[T; NR]; MR] {
+    let mut accumulators = [[T::ZERO; NR]; MR];
+    for (a_column, b_row) in a_panel
+        .chunks_exact(MR)
+        .zip(b_panel.chunks_exact(NR))
+        .take(k_len)
+    {
+        let a_column: &[T; MR] = a_column.try_into().unwrap();
+        let b_row: &[T; NR] = b_row.try_into().unwrap();
+        for (accumulator_row, &a_val) in accumulators.iter_mut().zip(a_column) {
+            for (accumulator, &b_val) in accumulator_row.iter_mut().zip(b_row) {
+                *accumulator = *accumulator + a_val * b_val;
+            }
+        }
+    }
+    accumulators
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sums_outer_products() {
+        // two steps: a columns (1, 2, 3, 4) and (1, 1, 1, 1), b rows 0..8 and all 10s
+        let a_panel = [1.0, 2.0, 3.0, 4.0, 1.0, 1.0, 1.0, 1.0];
+        let mut b_panel = [10.0f64; 2 * NR];
+        for (col_idx, b_val) in b_panel[..NR].iter_mut().enumerate() {
+            *b_val = col_idx as f64;
+        }
+        let block = micro_kernel(2, &a_panel, &b_panel);
+        for (row_idx, row) in block.iter().enumerate() {
+            for (col_idx, &value) in row.iter().enumerate() {
+                assert_eq!(value, (row_idx + 1) as f64 * col_idx as f64 + 10.0);
+            }
+        }
+    }
+}
@@ -0,0 +1,94 @@
+// Copies blocks of A and B into the order the micro-kernel reads them.
+//
+// A block (mc x kc) -> micro-panels of MR rows, each stored column by column:
+//   panel 0: a[0][0] a[1][0] .. a[MR-1][0]  a[0][1] a[1][1] ..  (MR * kc values)
+// B panel (kc x nc) -> micro-panels of NR columns, each stored row by row:
+//   panel 0: b[0][0] b[0][1] .. b[0][NR-1]  b[1][0] b[1][1] ..  (NR * kc values)
+// Rows/columns past the edge of the matrix are written as zeros so every micro-panel is full.
+use super::GemmScalar;
+use super::micro_kernel::{MR, NR};
+
+// A[row_start..row_start + row_len, k_start..k_start + k_len] of a row-major A with
+// `a_col_count` columns
+pub fn pack_a<T: GemmScalar>(
+    matrix_a: &[T],
+    a_col_count: usize,
+    row_start: usize,
+    row_len: usize,
+    k_start: usize,
+    k_len: usize,
+    packed: &mut [T],
+) {
+    for (panel_idx, panel) in packed
+        .chunks_exact_mut(MR * k_len)
+        .take(row_len.div_ceil(MR))
+        .enumerate()
+    {
+        let panel_row = panel_idx * MR;
+        let rows_in_panel = MR.min(row_len - panel_row);
+        for k_idx in 0..k_len {
+            let column = &mut panel[k_idx * MR..(k_idx + 1) * MR];
+            for (row_in_panel, value) in column.iter_mut().enumerate() {
+                *value = if row_in_panel < rows_in_panel {
+                    matrix_a[(row_start + panel_row + row_in_panel) * a_col_count + k_start + k_idx]
+                } else {
+                    T::ZERO
+                };
+            }
+        }
+    }
+}
+
+// B[k_start..k_start + k_len, col_start..col_start + col_len] of a row-major B with
+// `b_col_count` columns
+pub fn pack_b<T: GemmScalar>(
+    matrix_b: &[T],
+    b_col_count: usize,
+    k_start: usize,
+    k_len: usize,
+    col_start: usize,
+    col_len: usize,
+    packed: &mut [T],
+) {
+    for (panel_idx, panel) in packed
+        .chunks_exact_mut(NR * k_len)
+        .take(col_len.div_ceil(NR))
+        .enumerate()
+    {
+        let panel_col = panel_idx * NR;
+        let cols_in_panel = NR.min(col_len - panel_col);
+        for k_idx in 0..k_len {
+            let row_start = (k_start + k_idx) * b_col_count + col_start + panel_col;
+            let row = &mut panel[k_idx * NR..(k_idx + 1) * NR];
+            row[..cols_in_panel].copy_from_slice(&matrix_b[row_start..row_start + cols_in_panel]);
+            row[cols_in_panel..].fill(T::ZERO);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn packs_panels_with_zero_fringes() {
+        // 5 x 3 A, cols 1..3 packed: one full MR panel and one with a single real row
+        let matrix_a: Vec<f64> = (0..15).map(f64::from).collect();
+        let mut packed = vec![-1.0; 2 * MR * 2];
+        pack_a(&matrix_a, 3, 0, 5, 1, 2, &mut packed);
+        assert_eq!(
+            packed,
+            vec![
+                1.0, 4.0, 7.0, 10.0, 2.0, 5.0, 8.0, 11.0, 13.0, 0.0, 0.0, 0.0, 14.0, 0.0, 0.0, 0.0
+            ]
+        );
+
+        // 2 x 3 B, all of it: one panel with 3 real columns and NR - 3 zeros per row
+        let matrix_b: Vec<f32> = (1..=6).map(|v| v as f32).collect();
+        let mut packed = vec![-1.0; NR * 2];
+        pack_b(&matrix_b, 3, 0, 2, 0, 3, &mut packed);
+        assert_eq!(&packed[..3], &[1.0, 2.0, 3.0]);
+        assert_eq!(&packed[NR..NR + 3], &[4.0, 5.0, 6.0]);
+        assert_eq!(packed.iter().filter(|&&v| v == 0.0).count(), 2 * (NR - 3));
+    }
+}
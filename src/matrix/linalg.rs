@@ -0,0 +1,479 @@
+// Dense linear algebra on flat row-major f64 matrices: LU with partial pivoting, Householder
+// QR, determinant, inverse, linear solve and least squares.
+//
+// LU is the cheap one (n^3 / 3 multiply-adds) and is what solve, inverse and determinant use
+// for square systems. QR costs about twice that but never squares the condition number the
+// way the normal equations AᵀA·x = Aᵀb do, so it is what over-determined least-squares fits
+// (homographies from more than 4 point pairs) go through.
+//
+// A pivot counts as zero when it is within n * f64::EPSILON of the largest entry of the
+// input, anything that small is rounding noise rather than information. Singular and rank
+// deficient matrices come back as Err instead of a vector full of inf and NaN.
+
+#[derive(Debug, Clone)]
+pub struct LuDecomposition {
+    pub size: usize,
+    // L below the diagonal (its unit diagonal is implied) and U on and above it
+    pub lu: Vec<f64>,
+    // row i of L·U is row permutation[i] of the input
+    pub permutation: Vec<usize>,
+    pub swap_count: usize,
+}
+
+#[derive(Debug, Clone)]
+pub struct QrDecomposition {
+    pub row_count: usize,
+    pub col_count: usize,
+    // R in the upper triangle of the first col_count rows, zeros below
+    pub r: Vec<f64>,
+    // Householder vector of step k acts on rows k.., an empty one means the step was skipped
+    pub reflectors: Vec<Vec<f64>>,
+    tolerance: f64,
+}
+
+fn singular_tolerance(matrix: &[f64], dimension: usize) -> f64 {
+    let scale = matrix
+        .iter()
+        .fold(0.0_f64, |largest, v| largest.max(v.abs()));
+    scale * dimension as f64 * f64::EPSILON
+}
+
+pub fn lu_decompose(matrix: &[f64], size: usize) -> Result<LuDecomposition, String> {
+    if matrix.len() != size * size {
+        panic!("Matrix dimensions do not match a square matrix of the given size.");
+    }
+    let tolerance = singular_tolerance(matrix, size);
+    let mut lu = matrix.to_vec();
+    let mut permutation: Vec<usize> = (0..size).collect();
+    let mut swap_count = 0;
+
+    for col in 0..size {
+        // partial pivoting: the largest magnitude in the column keeps the multipliers <= 1
+        let pivot_row = (col..size)
+            .max_by(|&a, &b| {
+                lu[a * size + col]
+                    .abs()
+                    .total_cmp(&lu[b * size + col].abs())
+            })
+            .unwrap();
+        if lu[pivot_row * size + col].abs() <= tolerance {
+            return Err(format!(
+                "Matrix is singular: column {} has no non-zero pivot.",
+                col
+            ));
+        }
+        if pivot_row != col {
+            for j in 0..size {
+                lu.swap(col * size + j, pivot_row * size + j);
+            }
+            permutation.swap(col, pivot_row);
+            swap_count += 1;
+        }
+
+        let (upper_rows, lower_rows) = lu.split_at_mut((col + 1) * size);
+        let pivot_row = &upper_rows[col * size..];
+        for row in lower_rows.chunks_mut(size) {
+            let factor = row[col] / pivot_row[col];
+            row[col] = factor;
+            for j in col + 1..size {
+                row[j] -= factor * pivot_row[j];
+            }
+        }
+    }
+
+    Ok(LuDecomposition {
+        size,
+        lu,
+        permutation,
+        swap_count,
+    })
+}
+
+impl LuDecomposition {
+    // det(A) = (-1)^swaps * product of U's diagonal
+    pub fn determinant(&self) -> f64 {
+        let diagonal_product: f64 = (0..self.size).map(|i| self.lu[i * self.size + i]).product();
+        if self.swap_count.is_multiple_of(2) {
+            diagonal_product
+        } else {
+            -diagonal_product
+        }
+    }
+
+    // Forward substitution with L on the permuted b, then back substitution with U
+    pub fn solve(&self, vector_b: &[f64]) -> Vec<f64> {
+        let size = self.size;
+        if vector_b.len() != size {
+            panic!("Vector b length does not match the matrix size.");
+        }
+        let mut vector_x: Vec<f64> = self.permutation.iter().map(|&row| vector_b[row]).collect();
+        for i in 0..size {
+            for j in 0..i {
+                vector_x[i] -= self.lu[i * size + j] * vector_x[j];
+            }
+        }
+        for i in (0..size).rev() {
+            for j in i + 1..size {
+                vector_x[i] -= self.lu[i * size + j] * vector_x[j];
+            }
+            vector_x[i] /= self.lu[i * size + i];
+        }
+        vector_x
+    }
+
+    // One solve per column of the identity
+    pub fn inverse(&self) -> Vec<f64> {
+        let size = self.size;
+        let mut inverse = vec![0.0; size * size];
+        let mut unit = vec![0.0; size];
+        for col in 0..size {
+            unit[col] = 1.0;
+            for (row, value) in self.solve(&unit).into_iter().enumerate() {
+                inverse[row * size + col] = value;
+            }
+            unit[col] = 0.0;
+        }
+        inverse
+    }
+}
+
+// Zero for singular matrices rather than an error, that is the determinant after all
+pub fn determinant(matrix: &[f64], size: usize) -> f64 {
+    match lu_decompose(matrix, size) {
+        Ok(decomposition) => decomposition.determinant(),
+        Err(_) => 0.0,
+    }
+}
+
+pub fn solve(matrix: &[f64], size: usize, vector_b: &[f64]) -> Result<Vec<f64>, String> {
+    if vector_b.len() != size {
+        panic!("Vector b length does not match the matrix size.");
+    }
+    Ok(lu_decompose(matrix, size)?.solve(vector_b))
+}
+
+pub fn inverse(matrix: &[f64], size: usize) -> Result<Vec<f64>, String> {
+    Ok(lu_decompose(matrix, size)?.inverse())
+}
+
+// H = I - 2·v·vᵀ / vᵀv applied to rows first_row.. and columns first_col.. of a row-major
+// matrix. vᵀ·M is accumulated row by row so every pass over M stays on unit stride.
+fn apply_householder(
+    reflector: &[f64],
+    matrix: &mut [f64],
+    col_count: usize,
+    first_row: usize,
+    first_col: usize,
+) {
+    let reflector_norm_sq: f64 = reflector.iter().map(|v| v * v).sum();
+    if reflector_norm_sq == 0.0 {
+        return;
+    }
+    let rows = matrix[first_row * col_count..].chunks_mut(col_count);
+    let mut projections = vec![0.0; col_count - first_col];
+    for (&v, row) in reflector.iter().zip(rows) {
+        for (projection, &value) in projections.iter_mut().zip(&row[first_col..]) {
+            *projection += v * value;
+        }
+    }
+    let rows = matrix[first_row * col_count..].chunks_mut(col_count);
+    for (&v, row) in reflector.iter().zip(rows) {
+        let factor = 2.0 * v / reflector_norm_sq;
+        for (value, &projection) in row[first_col..].iter_mut().zip(&projections) {
+            *value -= factor * projection;
+        }
+    }
+}
+
+pub fn qr_decompose(matrix: &[f64], row_count: usize, col_count: usize) -> QrDecomposition {
+    if matrix.len() != row_count * col_count {
+        panic!("Matrix dimensions do not match the row and column counts.");
+    }
+    if row_count < col_count {
+        panic!("QR decomposition needs at least as many rows as columns.");
+    }
+    let tolerance = singular_tolerance(matrix, row_count);
+    let mut r = matrix.to_vec();
+    let mut reflectors = Vec::with_capacity(col_count);
+
+    for col in 0..col_count {
+        let mut reflector: Vec<f64> = (col..row_count)
+            .map(|row| r[row * col_count + col])
+            .collect();
+        let norm = reflector.iter().map(|v| v * v).sum::<f64>().sqrt();
+        if norm == 0.0 {
+            reflectors.push(Vec::new());
+            continue;
+        }
+        // reflect onto -sign(x0)·|x|·e0 so v0 = x0 - alpha never cancels
+        let alpha = if reflector[0] >= 0.0 { -norm } else { norm };
+        reflector[0] -= alpha;
+        apply_householder(&reflector, &mut r, col_count, col, col);
+        r[col * col_count + col] = alpha;
+        for row in col + 1..row_count {
+            r[row * col_count + col] = 0.0;
+        }
+        reflectors.push(reflector);
+    }
+
+    QrDecomposition {
+        row_count,
+        col_count,
+        r,
+        reflectors,
+        tolerance,
+    }
+}
+
+impl QrDecomposition {
+    // Qᵀ·b, the reflectors in the order they were made
+    pub fn apply_q_transpose(&self, vector_b: &[f64]) -> Vec<f64> {
+        if vector_b.len() != self.row_count {
+            panic!("Vector b length does not match the matrix row count.");
+        }
+        let mut vector = vector_b.to_vec();
+        for (step, reflector) in self.reflectors.iter().enumerate() {
+            apply_householder(reflector, &mut vector, 1, step, 0);
+        }
+        vector
+    }
+
+    // The thin row_count x col_count Q: H0·H1·...·I, built from the last reflector back
+    pub fn q(&self) -> Vec<f64> {
+        let (row_count, col_count) = (self.row_count, self.col_count);
+        let mut q = vec![0.0; row_count * col_count];
+        for i in 0..col_count {
+            q[i * col_count + i] = 1.0;
+        }
+        for (step, reflector) in self.reflectors.iter().enumerate().rev() {
+            apply_householder(reflector, &mut q, col_count, step, 0);
+        }
+        q
+    }
+
+    // The square col_count x col_count upper triangle of R
+    pub fn r_square(&self) -> Vec<f64> {
+        self.r[..self.col_count * self.col_count].to_vec()
+    }
+
+    // x minimizing |A·x - b|: R·x = (Qᵀ·b)[..col_count] by back substitution
+    pub fn solve_least_squares(&self, vector_b: &[f64]) -> Result<Vec<f64>, String> {
+        let col_count = self.col_count;
+        let mut vector_x = self.apply_q_transpose(vector_b);
+        vector_x.truncate(col_count);
+        for i in (0..col_count).rev() {
+            let diagonal = self.r[i * col_count + i];
+            if diagonal.abs() <= self.tolerance {
+                return Err(format!(
+                    "Matrix is rank deficient: column {} depends on the columns before it.",
+                    i
+                ));
+            }
+            for j in i + 1..col_count {
+                vector_x[i] -= self.r[i * col_count + j] * vector_x[j];
+            }
+            vector_x[i] /= diagonal;
+        }
+        Ok(vector_x)
+    }
+}
+
+pub fn solve_least_squares(
+    matrix: &[f64],
+    row_count: usize,
+    col_count: usize,
+    vector_b: &[f64],
+) -> Result<Vec<f64>, String> {
+    if vector_b.len() != row_count {
+        panic!("Vector b length does not match the matrix row count.");
+    }
+    qr_decompose(matrix, row_count, col_count).solve_least_squares(vector_b)
+}
+
+// The 3x3 row-major homography H (with H[2][2] = 1) mapping each source point onto its
+// target point, least squares when there are more than 4 pairs. Each pair gives two rows:
+//   x' = (h0·x + h1·y + h2) / (h6·x + h7·y + 1)  =>  h0·x + h1·y + h2 - h6·x·x' - h7·y·x' = x'
+// and the same for y' with h3, h4, h5.
+pub fn fit_homography(
+    source_points: &[(f64, f64)],
+    target_points: &[(f64, f64)],
+) -> Result<[f64; 9], String> {
+    if source_points.len() != target_points.len() {
+        panic!("Source and target point counts do not match.");
+    }
+    if source_points.len() < 4 {
+        return Err("A homography needs at least 4 point pairs.".to_string());
+    }
+    let mut matrix = Vec::with_capacity(source_points.len() * 16);
+    let mut vector_b = Vec::with_capacity(source_points.len() * 2);
+    for (&(x, y), &(target_x, target_y)) in source_points.iter().zip(target_points) {
+        matrix.extend_from_slice(&[x, y, 1.0, 0.0, 0.0, 0.0, -x * target_x, -y * target_x]);
+        matrix.extend_from_slice(&[0.0, 0.0, 0.0, x, y, 1.0, -x * target_y, -y * target_y]);
+        vector_b.push(target_x);
+        vector_b.push(target_y);
+    }
+    let h = solve_least_squares(&matrix, vector_b.len(), 8, &vector_b).map_err(|_| {
+        "Point pairs are degenerate (3 or more source points on a line).".to_string()
+    })?;
+    Ok([h[0], h[1], h[2], h[3], h[4], h[5], h[6], h[7], 1.0])
+}
+
+pub fn apply_homography(homography: &[f64; 9], point: (f64, f64)) -> (f64, f64) {
+    let (x, y) = point;
+    let h = homography;
+    let w = h[6] * x + h[7] * y + h[8];
+    (
+        (h[0] * x + h[1] * y + h[2]) / w,
+        (h[3] * x + h[4] * y + h[5]) / w,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::matrix::op_multiply::{MatrixOp, matrix_multiply_op_flat_row_major};
+
+    fn assert_close(actual: &[f64], expected: &[f64], tolerance: f64) {
+        assert_eq!(actual.len(), expected.len());
+        for (a, e) in actual.iter().zip(expected) {
+            assert!((a - e).abs() <= tolerance, "{:?} != {:?}", actual, expected);
+        }
+    }
+
+    fn identity(size: usize) -> Vec<f64> {
+        let mut matrix = vec![0.0; size * size];
+        for i in 0..size {
+            matrix[i * size + i] = 1.0;
+        }
+        matrix
+    }
+
+    // diagonally dominant so it is comfortably invertible
+    fn test_matrix(size: usize) -> Vec<f64> {
+        (0..size * size)
+            .map(|idx| {
+                let (row, col) = (idx / size, idx % size);
+                let noise = ((row * 7 + col * 3) % 11) as f64 - 5.0;
+                if row == col { noise + 20.0 } else { noise }
+            })
+            .collect()
+    }
+
+    #[test]
+    fn lu_solves_a_system_that_needs_pivoting() {
+        // a zero in the top left corner fails without a row swap
+        let matrix = [0.0, 2.0, 1.0, 1.0, 1.0, 1.0, 2.0, 1.0, 3.0];
+        let x = solve(&matrix, 3, &[5.0, 5.0, 12.0]).unwrap();
+        assert_close(&x, &[1.0, 1.0, 3.0], 1e-12);
+    }
+
+    #[test]
+    fn determinant_tracks_row_swaps() {
+        assert!((determinant(&[0.0, 1.0, 1.0, 0.0], 2) + 1.0).abs() < 1e-12);
+        assert!((determinant(&[2.0, 1.0, 4.0, 3.0], 2) - 2.0).abs() < 1e-12);
+        let matrix = [0.0, 2.0, 1.0, 1.0, 1.0, 1.0, 2.0, 1.0, 3.0];
+        assert!((determinant(&matrix, 3) + 3.0).abs() < 1e-12);
+        assert_eq!(determinant(&[], 0), 1.0);
+    }
+
+    #[test]
+    fn inverse_times_matrix_is_identity() {
+        for size in [1, 2, 5, 17] {
+            let matrix = test_matrix(size);
+            let matrix_inverse = inverse(&matrix, size).unwrap();
+            let product = matrix_multiply_op_flat_row_major(
+                MatrixOp::Normal,
+                MatrixOp::Normal,
+                &matrix,
+                &matrix_inverse,
+                size,
+                size,
+                size,
+            );
+            assert_close(&product, &identity(size), 1e-12);
+        }
+    }
+
+    #[test]
+    fn singular_matrices_are_errors() {
+        // row 2 = row 0 + row 1
+        let matrix = [1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 5.0, 7.0, 9.0];
+        assert!(lu_decompose(&matrix, 3).unwrap_err().contains("singular"));
+        assert!(inverse(&matrix, 3).is_err());
+        assert!(solve(&[0.0; 4], 2, &[1.0, 1.0]).is_err());
+        assert_eq!(determinant(&matrix, 3), 0.0);
+    }
+
+    #[test]
+    fn qr_is_orthogonal_times_upper_triangular() {
+        for (row_count, col_count) in [(4, 4), (7, 3), (5, 1)] {
+            let matrix = test_matrix(row_count)[..row_count * col_count].to_vec();
+            let qr = qr_decompose(&matrix, row_count, col_count);
+            let q = qr.q();
+            let r = qr.r_square();
+            for i in 0..col_count {
+                for j in 0..i {
+                    assert_eq!(r[i * col_count + j], 0.0);
+                }
+            }
+            let product = matrix_multiply_op_flat_row_major(
+                MatrixOp::Normal,
+                MatrixOp::Normal,
+                &q,
+                &r,
+                row_count,
+                col_count,
+                col_count,
+            );
+            assert_close(&product, &matrix, 1e-12);
+            let q_transpose_q = matrix_multiply_op_flat_row_major(
+                MatrixOp::Transposed,
+                MatrixOp::Normal,
+                &q,
+                &q,
+                col_count,
+                row_count,
+                col_count,
+            );
+            assert_close(&q_transpose_q, &identity(col_count), 1e-12);
+        }
+    }
+
+    #[test]
+    fn least_squares_fits_a_line() {
+        // y = 2x + 1 with +-0.5 noise orthogonal to both columns, so the fit ignores it
+        let xs = [0.0, 1.0, 2.0, 3.0];
+        let ys = [1.5, 2.5, 4.5, 7.5];
+        let matrix: Vec<f64> = xs.iter().flat_map(|&x| [x, 1.0]).collect();
+        let fit = solve_least_squares(&matrix, 4, 2, &ys).unwrap();
+        assert_close(&fit, &[2.0, 1.0], 1e-12);
+
+        let rank_deficient = [1.0, 2.0, 2.0, 4.0, 3.0, 6.0];
+        assert!(solve_least_squares(&rank_deficient, 3, 2, &[1.0, 2.0, 3.0]).is_err());
+    }
+
+    #[test]
+    fn homography_is_recovered_from_point_pairs() {
+        let homography = [1.2, 0.1, 30.0, -0.05, 0.9, 12.0, 0.0005, -0.0002, 1.0];
+        let source_points = [
+            (0.0, 0.0),
+            (640.0, 0.0),
+            (640.0, 480.0),
+            (0.0, 480.0),
+            (320.0, 200.0),
+        ];
+        let target_points: Vec<(f64, f64)> = source_points
+            .iter()
+            .map(|&point| apply_homography(&homography, point))
+            .collect();
+        for point_count in [4, 5] {
+            let fitted =
+                fit_homography(&source_points[..point_count], &target_points[..point_count])
+                    .unwrap();
+            assert_close(&fitted, &homography, 1e-9);
+        }
+        let collinear = [(0.0, 0.0), (1.0, 1.0), (2.0, 2.0), (3.0, 3.0)];
+        assert!(fit_homography(&collinear, &collinear).is_err());
+        assert!(fit_homography(&collinear[..3], &collinear[..3]).is_err());
+    }
+}
@@ -0,0 +1,295 @@
+// Maximum sum submatrix (Kadane's 2D extension) and the largest all-ones rectangle in a mask.
+//
+// 2D Kadane fixes a top and a bottom row, squashes the rows between them into one array of
+// column sums, and runs 1D Kadane over that: the best rectangle spanning exactly those rows.
+// Moving the bottom row down one only adds that row to the column sums, so every (top, bottom)
+// pair costs O(cols), O(rows^2 · cols) in total. The prefix matrix variant reads each column
+// strip sum straight out of make_prefix_sum_matrix instead of keeping running column sums.
+//
+// The largest all-ones rectangle is the same row squashing with a different 1D problem: for
+// each row, the number of consecutive ones ending at it in every column is a histogram, and
+// the biggest rectangle under a histogram comes out of one pass with a stack.
+use super::prefix_sum::{make_prefix_sum_matrix, sub_matrix_sum_using_prefix_matrix};
+use crate::array::max_subarray::max_subarray_sum_kadane;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SubmatrixSum {
+    pub sum: i32,
+    // inclusive corners, like sub_matrix_sum_using_prefix_matrix
+    pub l_row: usize,
+    pub l_col: usize,
+    pub r_row: usize,
+    pub r_col: usize,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct OnesRectangle {
+    pub area: usize,
+    pub l_row: usize,
+    pub l_col: usize,
+    pub r_row: usize,
+    pub r_col: usize,
+}
+
+fn matrix_shape<T>(matrix: &[Vec<T>]) -> (usize, usize) {
+    let col_count = matrix.first().map_or(0, |row| row.len());
+    if matrix.iter().any(|row| row.len() != col_count) {
+        panic!("Matrix rows do not all have the same length.");
+    }
+    (matrix.len(), col_count)
+}
+
+fn keep_best(best: &mut Option<SubmatrixSum>, candidate: SubmatrixSum) {
+    if best.is_none_or(|best| candidate.sum > best.sum) {
+        *best = Some(candidate);
+    }
+}
+
+// O(rows^2 · cols). None only for an empty matrix, an all negative one gives its largest cell.
+pub fn max_submatrix_sum_kadane(matrix: &[Vec<i32>]) -> Option<SubmatrixSum> {
+    let (row_count, col_count) = matrix_shape(matrix);
+    let mut best = None;
+    let mut col_sums = vec![0; col_count];
+    for l_row in 0..row_count {
+        col_sums.iter_mut().for_each(|col_sum| *col_sum = 0);
+        for r_row in l_row..row_count {
+            for (col_sum, &value) in col_sums.iter_mut().zip(&matrix[r_row]) {
+                *col_sum += value;
+            }
+            if let Some(strip_best) = max_subarray_sum_kadane(&col_sums) {
+                keep_best(
+                    &mut best,
+                    SubmatrixSum {
+                        sum: strip_best.sum,
+                        l_row,
+                        l_col: strip_best.l,
+                        r_row,
+                        r_col: strip_best.r,
+                    },
+                );
+            }
+        }
+    }
+    best
+}
+
+// Same search, each column strip sum is an O(1) inclusion-exclusion lookup
+pub fn max_submatrix_sum_using_prefix_matrix(matrix: &[Vec<i32>]) -> Option<SubmatrixSum> {
+    let (row_count, col_count) = matrix_shape(matrix);
+    let prefix_matrix = make_prefix_sum_matrix(matrix);
+    let mut best = None;
+    let mut col_sums = vec![0; col_count];
+    for l_row in 0..row_count {
+        for r_row in l_row..row_count {
+            for (col, col_sum) in col_sums.iter_mut().enumerate() {
+                *col_sum =
+                    sub_matrix_sum_using_prefix_matrix(&prefix_matrix, l_row, col, r_row, col);
+            }
+            if let Some(strip_best) = max_subarray_sum_kadane(&col_sums) {
+                keep_best(
+                    &mut best,
+                    SubmatrixSum {
+                        sum: strip_best.sum,
+                        l_row,
+                        l_col: strip_best.l,
+                        r_row,
+                        r_col: strip_best.r,
+                    },
+                );
+            }
+        }
+    }
+    best
+}
+
+// Largest rectangle under a histogram, as (area, first bar, last bar). The stack holds bars
+// of increasing height whose rectangle can still grow to the right. A lower bar ends them:
+// each popped bar's rectangle spans from just after the bar below it on the stack up to just
+// before the bar that popped it.
+pub fn largest_rectangle_in_histogram(heights: &[usize]) -> Option<(usize, usize, usize)> {
+    let mut best: Option<(usize, usize, usize)> = None;
+    let mut stack: Vec<usize> = Vec::with_capacity(heights.len());
+    // a zero height bar past the end pops everything that is left
+    for idx in 0..=heights.len() {
+        let height = heights.get(idx).copied().unwrap_or(0);
+        while let Some(&top) = stack.last() {
+            if heights[top] < height {
+                break;
+            }
+            stack.pop();
+            let l = stack.last().map_or(0, |&below| below + 1);
+            let area = heights[top] * (idx - l);
+            if best.is_none_or(|(best_area, _, _)| area > best_area) {
+                best = Some((area, l, idx - 1));
+            }
+        }
+        stack.push(idx);
+    }
+    best.filter(|&(area, _, _)| area > 0)
+}
+
+// O(rows · cols). None when the mask has no true cell at all.
+pub fn largest_all_ones_rectangle(mask: &[Vec<bool>]) -> Option<OnesRectangle> {
+    let (_, col_count) = matrix_shape(mask);
+    let mut heights = vec![0; col_count];
+    let mut best: Option<OnesRectangle> = None;
+    for (r_row, row) in mask.iter().enumerate() {
+        for (height, &is_one) in heights.iter_mut().zip(row) {
+            *height = if is_one { *height + 1 } else { 0 };
+        }
+        if let Some((area, l_col, r_col)) = largest_rectangle_in_histogram(&heights)
+            && best.is_none_or(|best| area > best.area)
+        {
+            let rect_height = area / (r_col - l_col + 1);
+            best = Some(OnesRectangle {
+                area,
+                l_row: r_row + 1 - rect_height,
+                l_col,
+                r_row,
+                r_col,
+            });
+        }
+    }
+    best
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bench::data::XorShift64;
+
+    fn random_matrix(rng: &mut XorShift64, row_count: usize, col_count: usize) -> Vec<Vec<i32>> {
+        (0..row_count)
+            .map(|_| {
+                (0..col_count)
+                    .map(|_| (rng.next_u64() % 21) as i32 - 10)
+                    .collect()
+            })
+            .collect()
+    }
+
+    fn region_sum(matrix: &[Vec<i32>], result: &SubmatrixSum) -> i32 {
+        matrix[result.l_row..=result.r_row]
+            .iter()
+            .flat_map(|row| &row[result.l_col..=result.r_col])
+            .sum()
+    }
+
+    // every rectangle, summed cell by cell
+    fn max_submatrix_sum_brute_force(matrix: &[Vec<i32>]) -> i32 {
+        let (row_count, col_count) = matrix_shape(matrix);
+        let mut best = i32::MIN;
+        for l_row in 0..row_count {
+            for r_row in l_row..row_count {
+                for l_col in 0..col_count {
+                    for r_col in l_col..col_count {
+                        let candidate = SubmatrixSum {
+                            sum: 0,
+                            l_row,
+                            l_col,
+                            r_row,
+                            r_col,
+                        };
+                        best = best.max(region_sum(matrix, &candidate));
+                    }
+                }
+            }
+        }
+        best
+    }
+
+    #[test]
+    fn test_max_submatrix_sum_example() {
+        let matrix = vec![
+            vec![1, 2, -1, -4, -20],
+            vec![-8, -3, 4, 2, 1],
+            vec![3, 8, 10, 1, 3],
+            vec![-4, -1, 1, 7, -6],
+        ];
+        let expected = Some(SubmatrixSum {
+            sum: 29,
+            l_row: 1,
+            l_col: 1,
+            r_row: 3,
+            r_col: 3,
+        });
+        assert_eq!(max_submatrix_sum_kadane(&matrix), expected);
+        assert_eq!(max_submatrix_sum_using_prefix_matrix(&matrix), expected);
+        assert_eq!(max_submatrix_sum_kadane(&[]), None);
+    }
+
+    #[test]
+    fn test_max_submatrix_sum_against_brute_force() {
+        let mut rng = XorShift64::new(2);
+        for (row_count, col_count) in [(1, 1), (1, 8), (6, 1), (5, 7), (9, 9)] {
+            for _ in 0..10 {
+                let matrix = random_matrix(&mut rng, row_count, col_count);
+                let expected = max_submatrix_sum_brute_force(&matrix);
+                for result in [
+                    max_submatrix_sum_kadane(&matrix).unwrap(),
+                    max_submatrix_sum_using_prefix_matrix(&matrix).unwrap(),
+                ] {
+                    assert_eq!(result.sum, expected);
+                    assert_eq!(region_sum(&matrix, &result), expected);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_largest_rectangle_in_histogram() {
+        assert_eq!(
+            largest_rectangle_in_histogram(&[2, 1, 5, 6, 2, 3]),
+            Some((10, 2, 3))
+        );
+        assert_eq!(largest_rectangle_in_histogram(&[3, 3, 3]), Some((9, 0, 2)));
+        assert_eq!(largest_rectangle_in_histogram(&[0, 0]), None);
+    }
+
+    #[test]
+    fn test_largest_all_ones_rectangle_against_brute_force() {
+        let mut rng = XorShift64::new(3);
+        for (row_count, col_count) in [(1, 1), (1, 9), (7, 1), (6, 8), (10, 10)] {
+            for _ in 0..10 {
+                let mask: Vec<Vec<bool>> = (0..row_count)
+                    .map(|_| {
+                        (0..col_count)
+                            .map(|_| !rng.next_u64().is_multiple_of(4))
+                            .collect()
+                    })
+                    .collect();
+                let mut expected = 0;
+                for l_row in 0..row_count {
+                    for r_row in l_row..row_count {
+                        for l_col in 0..col_count {
+                            for r_col in l_col..col_count {
+                                if mask[l_row..=r_row]
+                                    .iter()
+                                    .all(|row| row[l_col..=r_col].iter().all(|&v| v))
+                                {
+                                    expected =
+                                        expected.max((r_row - l_row + 1) * (r_col - l_col + 1));
+                                }
+                            }
+                        }
+                    }
+                }
+                match largest_all_ones_rectangle(&mask) {
+                    None => assert_eq!(expected, 0),
+                    Some(rect) => {
+                        assert_eq!(rect.area, expected);
+                        assert_eq!(
+                            rect.area,
+                            (rect.r_row - rect.l_row + 1) * (rect.r_col - rect.l_col + 1)
+                        );
+                        assert!(
+                            mask[rect.l_row..=rect.r_row]
+                                .iter()
+                                .all(|row| row[rect.l_col..=rect.r_col].iter().all(|&v| v))
+                        );
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,468 @@
+use crate::parallel::map_row_bands;
+use crate::tuning::{TiledKernel, TuningShape, tuned_block_size};
+use std::ops::{AddAssign, Mul, Range};
+
+pub fn matrix_multiply_triple_loop_vec_2d(
+    matrix_a: &[Vec<i32>],
+    matrix_b: &[Vec<i32>],
+) -> Vec<Vec<i32>> {
+    let a_col_count = matrix_a[0].len();
+    let b_row_count = matrix_b.len();
+    if a_col_count != b_row_count {
+        panic!("Can not multiply matrices [A]'s columns do not match [B]'s rows.")
+    }
+    let a_row_count = matrix_a.len();
+    let b_col_count = matrix_b[0].len();
+
+    // a_col_count == b_row_count == k
+    let k = a_col_count;
+    let mut matrix_c = vec![vec![0; b_col_count]; a_row_count];
+
+    for a_row_idx in 0..a_row_count {
+        for b_col_idx in 0..b_col_count {
+            let mut total = 0;
+            for k_idx in 0..k {
+                total += matrix_a[a_row_idx][k_idx] * matrix_b[k_idx][b_col_idx];
+            }
+            matrix_c[a_row_idx][b_col_idx] = total;
+        }
+    }
+    matrix_c
+}
+
+pub fn matrix_multiply_triple_loop_vec_2d_with_transposed_b(
+    matrix_a: &[Vec<i32>],
+    matrix_b_transposed: &[Vec<i32>],
+) -> Vec<Vec<i32>> {
+    let a_col_count = matrix_a[0].len();
+    let b_row_count = matrix_b_transposed[0].len();
+    if a_col_count != b_row_count {
+        panic!("Can not multiply matrices [A]'s columns do not match [B]'s rows.")
+    }
+    let a_row_count = matrix_a.len();
+    let b_col_count = matrix_b_transposed.len();
+
+    // a_col_count == b_row_count == k
+    let k = a_col_count;
+    let mut matrix_c = vec![vec![0; b_col_count]; a_row_count];
+
+    for a_row_idx in 0..a_row_count {
+        let a_row = &matrix_a[a_row_idx];
+        for b_col_idx in 0..b_col_count {
+            // Since b is transposed, the row is a the column.
+            let b_column = &matrix_b_transposed[b_col_idx];
+            for k_idx in 0..k {
+                matrix_c[a_row_idx][b_col_idx] += a_row[k_idx] * b_column[k_idx];
+            }
+        }
+    }
+    matrix_c
+}
+
+// Just got this straight out of a article, need to ponder this to understand it.
+// A good way would be to make a matrix A/B/C visuallizer highlighting the parts
+// being worked on to see it visually: I think the main thing tripping me up here
+// is understanding the for block_k and for k_in_block loops.
+pub fn matrix_multiply_loop_tiling(
+    matrix_a: &[Vec<i32>],
+    matrix_b: &[Vec<i32>],
+    block_size: usize,
+) -> Vec<Vec<i32>> {
+    let a_col_count = matrix_a[0].len();
+    let b_row_count = matrix_b.len();
+    if a_col_count != b_row_count {
+        panic!("Can not multiply matrices [A]'s columns do not match [B]'s rows.")
+    }
+    let a_row_count = matrix_a.len();
+    let b_col_count = matrix_b[0].len();
+
+    // a_col_count == b_row_count == k
+    let k = a_col_count;
+    let mut matrix_c = vec![vec![0; b_col_count]; a_row_count];
+
+    // Iterate over blocks of rows in C (and rows in A)
+    for block_row_c in (0..a_row_count).step_by(block_size) {
+        // Iterate over blocks of columns in C (and columns in B)
+        for block_col_c in (0..b_col_count).step_by(block_size) {
+            // Iterate over blocks along the inner dimension (columns of A / rows of B)
+            for block_k in (0..k).step_by(block_size) {
+                // Iterate over rows inside the current C block
+                for row_in_c_block in block_row_c..(block_row_c + block_size).min(a_row_count) {
+                    // Iterate over columns inside the current C block
+                    for col_in_c_block in block_col_c..(block_col_c + block_size).min(b_col_count) {
+                        // Iterate along the inner dimension inside the block
+                        for k_in_block in block_k..(block_k + block_size).min(a_col_count) {
+                            // Multiply and accumulate the corresponding elements
+                            matrix_c[row_in_c_block][col_in_c_block] += matrix_a[row_in_c_block]
+                                [k_in_block]
+                                * matrix_b[k_in_block][col_in_c_block];
+                        }
+                    }
+                }
+            }
+        }
+    }
+    matrix_c
+}
+
+// Just got this straight out of a article, need to ponder this to understand it.
+/// Matrix multiplication with loop tiling (blocking),
+/// using flat row-major Vec<T> for storage (i32 in the tutorial, Strassen also
+/// falls back to it for floats).
+pub fn matrix_multiply_loop_tiling_flat_row_major<T>(
+    matrix_a: &[T], // row-major [a_row_count × a_col_count]
+    matrix_b: &[T], // row-major [b_row_count × b_col_count]
+    a_row_count: usize,
+    a_col_count: usize,
+    b_col_count: usize,
+    block_size: usize,
+) -> Vec<T>
+where
+    T: Copy + Default + AddAssign + Mul<Output = T>,
+{
+    let b_row_count = a_col_count;
+    if matrix_b.len() != b_row_count * b_col_count {
+        panic!("Matrix B dimensions do not match multiplication requirements.");
+    }
+
+    // Output matrix C [a_row_count × b_col_count], row-major
+    let mut matrix_c = vec![T::default(); a_row_count * b_col_count];
+
+    // Iterate over blocks of rows in C (and rows in A)
+    for block_row_c in (0..a_row_count).step_by(block_size) {
+        // Iterate over blocks of columns in C (and columns in B)
+        for block_col_c in (0..b_col_count).step_by(block_size) {
+            // Iterate over blocks along the inner dimension (columns of A / rows of B)
+            for block_k in (0..a_col_count).step_by(block_size) {
+                // Rows inside the current C block
+                for row_in_c_block in block_row_c..(block_row_c + block_size).min(a_row_count) {
+                    // Cols inside the current C block
+                    for col_in_c_block in block_col_c..(block_col_c + block_size).min(b_col_count) {
+                        let mut sum = matrix_c[row_in_c_block * b_col_count + col_in_c_block];
+                        // Inner dimension inside the block
+                        for k_in_block in block_k..(block_k + block_size).min(a_col_count) {
+                            let a_val = matrix_a[row_in_c_block * a_col_count + k_in_block];
+                            let b_val = matrix_b[k_in_block * b_col_count + col_in_c_block];
+                            sum += a_val * b_val;
+                        }
+                        matrix_c[row_in_c_block * b_col_count + col_in_c_block] = sum;
+                    }
+                }
+            }
+        }
+    }
+
+    matrix_c
+}
+
+/// Matrix multiplication with loop tiling (blocking),
+/// using flat row-major Vec<i32> for A and C,
+/// and flat column-major Vec<i32> for B.
+pub fn matrix_multiply_loop_tiling_flat_row_major_a_col_major_b(
+    matrix_a: &[i32], // row-major [a_row_count × a_col_count]
+    matrix_b: &[i32], // column-major [b_row_count × b_col_count]
+    a_row_count: usize,
+    a_col_count: usize,
+    b_col_count: usize,
+    block_size: usize,
+) -> Vec<i32> {
+    let b_row_count = a_col_count;
+    if matrix_b.len() != b_row_count * b_col_count {
+        panic!("Matrix B dimensions do not match multiplication requirements.");
+    }
+
+    // Output matrix C [a_row_count × b_col_count], row-major
+    let mut matrix_c = vec![0; a_row_count * b_col_count];
+
+    // Iterate over blocks of rows in C (and rows in A)
+    for block_row_c in (0..a_row_count).step_by(block_size) {
+        // Iterate over blocks of columns in C (and columns in B)
+        for block_col_c in (0..b_col_count).step_by(block_size) {
+            // Iterate over blocks along the inner dimension (columns of A / rows of B)
+            for block_k in (0..a_col_count).step_by(block_size) {
+                // Rows inside the current C block
+                for row_in_c_block in block_row_c..(block_row_c + block_size).min(a_row_count) {
+                    // Cols inside the current C block
+                    for col_in_c_block in block_col_c..(block_col_c + block_size).min(b_col_count) {
+                        let mut sum = matrix_c[row_in_c_block * b_col_count + col_in_c_block];
+                        // Inner dimension inside the block
+                        for k_in_block in block_k..(block_k + block_size).min(a_col_count) {
+                            let a_val = matrix_a[row_in_c_block * a_col_count + k_in_block];
+                            // <-- difference: column-major indexing
+                            let b_val = matrix_b[col_in_c_block * b_row_count + k_in_block];
+                            sum += a_val * b_val;
+                        }
+                        matrix_c[row_in_c_block * b_col_count + col_in_c_block] = sum;
+                    }
+                }
+            }
+        }
+    }
+
+    matrix_c
+}
+
+// The tiled kernels above with the block size taken from the tuning file (see crate::tuning),
+// falling back to a block that fits L1 when this shape hasn't been tuned.
+pub fn matrix_multiply_loop_tiling_tuned(
+    matrix_a: &[Vec<i32>],
+    matrix_b: &[Vec<i32>],
+) -> Vec<Vec<i32>> {
+    let shape = TuningShape::matrix_multiply(matrix_a.len(), matrix_a[0].len(), matrix_b[0].len());
+    let block_size = tuned_block_size(TiledKernel::MatrixMultiplyLoopTiling, shape);
+    matrix_multiply_loop_tiling(matrix_a, matrix_b, block_size)
+}
+
+pub fn matrix_multiply_loop_tiling_flat_row_major_tuned(
+    matrix_a: &[i32],
+    matrix_b: &[i32],
+    a_row_count: usize,
+    a_col_count: usize,
+    b_col_count: usize,
+) -> Vec<i32> {
+    let shape = TuningShape::matrix_multiply(a_row_count, a_col_count, b_col_count);
+    let block_size = tuned_block_size(TiledKernel::MatrixMultiplyLoopTilingFlatRowMajor, shape);
+    matrix_multiply_loop_tiling_flat_row_major(
+        matrix_a,
+        matrix_b,
+        a_row_count,
+        a_col_count,
+        b_col_count,
+        block_size,
+    )
+}
+
+pub fn matrix_multiply_loop_tiling_flat_row_major_a_col_major_b_tuned(
+    matrix_a: &[i32],
+    matrix_b: &[i32],
+    a_row_count: usize,
+    a_col_count: usize,
+    b_col_count: usize,
+) -> Vec<i32> {
+    let shape = TuningShape::matrix_multiply(a_row_count, a_col_count, b_col_count);
+    let block_size = tuned_block_size(
+        TiledKernel::MatrixMultiplyLoopTilingFlatRowMajorAColMajorB,
+        shape,
+    );
+    matrix_multiply_loop_tiling_flat_row_major_a_col_major_b(
+        matrix_a,
+        matrix_b,
+        a_row_count,
+        a_col_count,
+        b_col_count,
+        block_size,
+    )
+}
+
+// Below this many multiply-adds the recursion stops and runs a plain loop, about three
+// 32x32 tiles of i32 which sit comfortably in L1.
+pub const CACHE_OBLIVIOUS_MULTIPLY_CUTOFF: usize = 32 * 32 * 32;
+
+// Cache-oblivious matrix multiplication, no block size to tune: keep halving the largest of
+// the three dimensions (rows of A, the shared k, cols of B) until the sub-problem is small.
+// Somewhere along the way every sub-problem fits in each cache level, whatever its size.
+pub fn matrix_multiply_cache_oblivious_flat_row_major(
+    matrix_a: &[i32], // row-major [a_row_count × a_col_count]
+    matrix_b: &[i32], // row-major [b_row_count × b_col_count]
+    a_row_count: usize,
+    a_col_count: usize,
+    b_col_count: usize,
+) -> Vec<i32> {
+    let b_row_count = a_col_count;
+    if matrix_a.len() != a_row_count * a_col_count || matrix_b.len() != b_row_count * b_col_count {
+        panic!("Matrix B dimensions do not match multiplication requirements.");
+    }
+
+    let mut matrix_c = vec![0; a_row_count * b_col_count];
+    multiply_add_cache_oblivious(
+        matrix_a,
+        matrix_b,
+        &mut matrix_c,
+        a_col_count,
+        b_col_count,
+        0..a_row_count,
+        0..a_col_count,
+        0..b_col_count,
+    );
+    matrix_c
+}
+
+// C[rows, cols] += A[rows, ks] * B[ks, cols]
+#[allow(clippy::too_many_arguments)]
+fn multiply_add_cache_oblivious(
+    matrix_a: &[i32],
+    matrix_b: &[i32],
+    matrix_c: &mut [i32],
+    a_col_count: usize,
+    b_col_count: usize,
+    rows: Range<usize>,
+    ks: Range<usize>,
+    cols: Range<usize>,
+) {
+    let (row_len, k_len, col_len) = (rows.len(), ks.len(), cols.len());
+    if row_len * k_len * col_len <= CACHE_OBLIVIOUS_MULTIPLY_CUTOFF {
+        // i-k-j so the inner loop walks a row of B and C
+        for row_idx in rows {
+            for k_idx in ks.clone() {
+                let a_val = matrix_a[row_idx * a_col_count + k_idx];
+                for col_idx in cols.clone() {
+                    matrix_c[row_idx * b_col_count + col_idx] +=
+                        a_val * matrix_b[k_idx * b_col_count + col_idx];
+                }
+            }
+        }
+        return;
+    }
+
+    if row_len >= k_len && row_len >= col_len {
+        // top and bottom halves of A and C
+        let mid = rows.start + row_len / 2;
+        for half in [rows.start..mid, mid..rows.end] {
+            multiply_add_cache_oblivious(
+                matrix_a,
+                matrix_b,
+                matrix_c,
+                a_col_count,
+                b_col_count,
+                half,
+                ks.clone(),
+                cols.clone(),
+            );
+        }
+    } else if col_len >= k_len {
+        // left and right halves of B and C
+        let mid = cols.start + col_len / 2;
+        for half in [cols.start..mid, mid..cols.end] {
+            multiply_add_cache_oblivious(
+                matrix_a,
+                matrix_b,
+                matrix_c,
+                a_col_count,
+                b_col_count,
+                rows.clone(),
+                ks.clone(),
+                half,
+            );
+        }
+    } else {
+        // both halves of k add into the same block of C, one after the other
+        let mid = ks.start + k_len / 2;
+        for half in [ks.start..mid, mid..ks.end] {
+            multiply_add_cache_oblivious(
+                matrix_a,
+                matrix_b,
+                matrix_c,
+                a_col_count,
+                b_col_count,
+                rows.clone(),
+                half,
+                cols.clone(),
+            );
+        }
+    }
+}
+
+// matrix_multiply_cache_oblivious_flat_row_major with the rows of C split into one band per
+// thread: each thread multiplies its band of A's rows by all of B into its own band of C.
+pub fn matrix_multiply_cache_oblivious_flat_row_major_parallel(
+    matrix_a: &[i32], // row-major [a_row_count × a_col_count]
+    matrix_b: &[i32], // row-major [b_row_count × b_col_count]
+    a_row_count: usize,
+    a_col_count: usize,
+    b_col_count: usize,
+    thread_count: usize,
+) -> Vec<i32> {
+    let b_row_count = a_col_count;
+    if matrix_a.len() != a_row_count * a_col_count || matrix_b.len() != b_row_count * b_col_count {
+        panic!("Matrix B dimensions do not match multiplication requirements.");
+    }
+
+    let mut matrix_c = vec![0; a_row_count * b_col_count];
+    map_row_bands(&mut matrix_c, b_col_count, thread_count, |_, rows, band| {
+        let a_band = &matrix_a[rows.start * a_col_count..rows.end * a_col_count];
+        multiply_add_cache_oblivious(
+            a_band,
+            matrix_b,
+            band,
+            a_col_count,
+            b_col_count,
+            0..rows.len(),
+            0..a_col_count,
+            0..b_col_count,
+        );
+    });
+    matrix_c
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn numbered_flat(len: usize) -> Vec<i32> {
+        (0..len as i32).map(|v| v % 11 - 5).collect()
+    }
+
+    fn to_2d(flat: &[i32], col_count: usize) -> Vec<Vec<i32>> {
+        flat.chunks(col_count).map(|row| row.to_vec()).collect()
+    }
+
+    #[test]
+    fn cache_oblivious_matches_triple_loop() {
+        // square, tall, wide, deep, and below the cutoff
+        for (a_row_count, a_col_count, b_col_count) in [
+            (64, 64, 64),
+            (129, 40, 33),
+            (7, 50, 200),
+            (20, 300, 9),
+            (3, 4, 5),
+            (1, 1, 1),
+        ] {
+            let matrix_a = numbered_flat(a_row_count * a_col_count);
+            let matrix_b = numbered_flat(a_col_count * b_col_count);
+            let expected = matrix_multiply_triple_loop_vec_2d(
+                &to_2d(&matrix_a, a_col_count),
+                &to_2d(&matrix_b, b_col_count),
+            );
+            let matrix_c = matrix_multiply_cache_oblivious_flat_row_major(
+                &matrix_a,
+                &matrix_b,
+                a_row_count,
+                a_col_count,
+                b_col_count,
+            );
+            assert_eq!(to_2d(&matrix_c, b_col_count), expected);
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "do not match")]
+    fn cache_oblivious_rejects_mismatched_shapes() {
+        matrix_multiply_cache_oblivious_flat_row_major(&[1, 2, 3, 4], &[1, 2, 3], 2, 2, 2);
+    }
+
+    #[test]
+    fn parallel_matches_single_threaded() {
+        for (a_row_count, a_col_count, b_col_count) in [(129, 40, 33), (3, 70, 5), (64, 64, 64)] {
+            let matrix_a = numbered_flat(a_row_count * a_col_count);
+            let matrix_b = numbered_flat(a_col_count * b_col_count);
+            let expected = matrix_multiply_cache_oblivious_flat_row_major(
+                &matrix_a,
+                &matrix_b,
+                a_row_count,
+                a_col_count,
+                b_col_count,
+            );
+            for thread_count in [1, 2, 4, 7] {
+                let matrix_c = matrix_multiply_cache_oblivious_flat_row_major_parallel(
+                    &matrix_a,
+                    &matrix_b,
+                    a_row_count,
+                    a_col_count,
+                    b_col_count,
+                    thread_count,
+                );
+                assert_eq!(matrix_c, expected);
+            }
+        }
+    }
+}
@@ -0,0 +1,391 @@
+// Matrix-vector and matrix-matrix multiply with BLAS style operand flags (transa/transb):
+// y = op(A)·x and C = op(A)·op(B), where op(X) is X or Xᵀ, all over flat row-major storage.
+//
+// A transposed operand is never materialized. Instead each of the four flag combinations
+// gets the loop order that keeps the inner loop on unit stride:
+//
+//   A·B    i-p-j  C row i += A[i][p] * B row p
+//   Aᵀ·B   p-i-j  C row i += A[p][i] * B row p  (rows of A and B are both read once per p)
+//   A·Bᵀ   i-j-p  C[i][j] = row i of A · row j of B
+//   Aᵀ·Bᵀ  j-p-i  column j of C gathered in a buffer from row j of B and rows of A
+use crate::parallel::map_row_bands;
+use std::ops::{AddAssign, Mul};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MatrixOp {
+    Normal,
+    Transposed,
+}
+
+impl MatrixOp {
+    pub fn name(&self) -> &'static str {
+        match self {
+            MatrixOp::Normal => "N",
+            MatrixOp::Transposed => "T",
+        }
+    }
+
+    // (row_count, col_count) of the stored matrix whose op is row_count x col_count
+    pub fn stored_shape(&self, row_count: usize, col_count: usize) -> (usize, usize) {
+        match self {
+            MatrixOp::Normal => (row_count, col_count),
+            MatrixOp::Transposed => (col_count, row_count),
+        }
+    }
+}
+
+// y = op(A)·x, where op(A) is row_count x col_count (so A itself is col_count x row_count
+// when transposed)
+pub fn matrix_vector_multiply<T>(
+    op_a: MatrixOp,
+    matrix_a: &[T],
+    row_count: usize,
+    col_count: usize,
+    vector_x: &[T],
+) -> Vec<T>
+where
+    T: Copy + Default + AddAssign + Mul<Output = T>,
+{
+    if matrix_a.len() != row_count * col_count {
+        panic!("Matrix A dimensions do not match multiplication requirements.");
+    }
+    if vector_x.len() != col_count {
+        panic!("Vector x length does not match the columns of op(A).");
+    }
+
+    let mut vector_y = vec![T::default(); row_count];
+    match op_a {
+        // one dot product per row of A
+        MatrixOp::Normal => {
+            for (y, a_row) in vector_y.iter_mut().zip(matrix_a.chunks(col_count.max(1))) {
+                for (&a_val, &x_val) in a_row.iter().zip(vector_x) {
+                    *y += a_val * x_val;
+                }
+            }
+        }
+        // row p of A is column p of Aᵀ: add x[p] times it onto y
+        MatrixOp::Transposed => {
+            for (a_row, &x_val) in matrix_a.chunks(row_count.max(1)).zip(vector_x) {
+                for (y, &a_val) in vector_y.iter_mut().zip(a_row) {
+                    *y += a_val * x_val;
+                }
+            }
+        }
+    }
+    vector_y
+}
+
+// C = op(A)·op(B) with op(A) a_row_count x a_col_count and op(B) a_col_count x b_col_count
+pub fn matrix_multiply_op_flat_row_major<T>(
+    op_a: MatrixOp,
+    op_b: MatrixOp,
+    matrix_a: &[T],
+    matrix_b: &[T],
+    a_row_count: usize,
+    a_col_count: usize,
+    b_col_count: usize,
+) -> Vec<T>
+where
+    T: Copy + Default + AddAssign + Mul<Output = T>,
+{
+    let mut matrix_c = vec![T::default(); a_row_count * b_col_count];
+    multiply_op_into(
+        op_a,
+        op_b,
+        matrix_a,
+        matrix_b,
+        a_row_count,
+        a_col_count,
+        b_col_count,
+        &mut matrix_c,
+    );
+    matrix_c
+}
+
+#[allow(clippy::too_many_arguments)]
+fn multiply_op_into<T>(
+    op_a: MatrixOp,
+    op_b: MatrixOp,
+    matrix_a: &[T],
+    matrix_b: &[T],
+    a_row_count: usize,
+    a_col_count: usize,
+    b_col_count: usize,
+    matrix_c: &mut [T],
+) where
+    T: Copy + Default + AddAssign + Mul<Output = T>,
+{
+    let (m, k, n) = (a_row_count, a_col_count, b_col_count);
+    if matrix_a.len() != m * k {
+        panic!("Matrix A dimensions do not match multiplication requirements.");
+    }
+    if matrix_b.len() != k * n {
+        panic!("Matrix B dimensions do not match multiplication requirements.");
+    }
+    if m == 0 || n == 0 {
+        return;
+    }
+
+    match (op_a, op_b) {
+        (MatrixOp::Normal, MatrixOp::Normal) => {
+            for (a_row, c_row) in matrix_a.chunks(k.max(1)).zip(matrix_c.chunks_mut(n)) {
+                for (&a_val, b_row) in a_row.iter().zip(matrix_b.chunks(n)) {
+                    for (c, &b_val) in c_row.iter_mut().zip(b_row) {
+                        *c += a_val * b_val;
+                    }
+                }
+            }
+        }
+        // A is stored k x m
+        (MatrixOp::Transposed, MatrixOp::Normal) => {
+            for (a_row, b_row) in matrix_a.chunks(m).zip(matrix_b.chunks(n)) {
+                for (&a_val, c_row) in a_row.iter().zip(matrix_c.chunks_mut(n)) {
+                    for (c, &b_val) in c_row.iter_mut().zip(b_row) {
+                        *c += a_val * b_val;
+                    }
+                }
+            }
+        }
+        // B is stored n x k
+        (MatrixOp::Normal, MatrixOp::Transposed) => {
+            for (a_row, c_row) in matrix_a.chunks(k.max(1)).zip(matrix_c.chunks_mut(n)) {
+                for (c, b_row) in c_row.iter_mut().zip(matrix_b.chunks(k.max(1))) {
+                    for (&a_val, &b_val) in a_row.iter().zip(b_row) {
+                        *c += a_val * b_val;
+                    }
+                }
+            }
+        }
+        // A is stored k x m, B is stored n x k: column j of C is Aᵀ·(row j of B), built
+        // with the Aᵀ·x loop then scattered into C
+        (MatrixOp::Transposed, MatrixOp::Transposed) => {
+            let mut c_col = vec![T::default(); m];
+            for (col_idx, b_row) in matrix_b.chunks(k.max(1)).enumerate() {
+                c_col.fill(T::default());
+                for (a_row, &b_val) in matrix_a.chunks(m).zip(b_row) {
+                    for (c, &a_val) in c_col.iter_mut().zip(a_row) {
+                        *c += a_val * b_val;
+                    }
+                }
+                for (row_idx, &c) in c_col.iter().enumerate() {
+                    matrix_c[row_idx * n + col_idx] = c;
+                }
+            }
+        }
+    }
+}
+
+// Many small products of the same shape, each matrix stored one after the other:
+// C[i] = op(A[i])·op(B[i]) for i in 0..batch_count
+#[allow(clippy::too_many_arguments)]
+pub fn matrix_multiply_batched_flat_row_major<T>(
+    op_a: MatrixOp,
+    op_b: MatrixOp,
+    matrices_a: &[T],
+    matrices_b: &[T],
+    batch_count: usize,
+    a_row_count: usize,
+    a_col_count: usize,
+    b_col_count: usize,
+) -> Vec<T>
+where
+    T: Copy + Default + AddAssign + Mul<Output = T>,
+{
+    let (a_len, b_len, c_len) = batch_lens(
+        batch_count,
+        matrices_a,
+        matrices_b,
+        a_row_count,
+        a_col_count,
+        b_col_count,
+    );
+    let mut matrices_c = vec![T::default(); batch_count * c_len];
+    for batch_idx in 0..batch_count {
+        multiply_op_into(
+            op_a,
+            op_b,
+            &matrices_a[batch_idx * a_len..(batch_idx + 1) * a_len],
+            &matrices_b[batch_idx * b_len..(batch_idx + 1) * b_len],
+            a_row_count,
+            a_col_count,
+            b_col_count,
+            &mut matrices_c[batch_idx * c_len..(batch_idx + 1) * c_len],
+        );
+    }
+    matrices_c
+}
+
+// matrix_multiply_batched_flat_row_major with the batch split into one band of products per
+// thread (see crate::parallel), each product is too small to be worth splitting on its own
+#[allow(clippy::too_many_arguments)]
+pub fn matrix_multiply_batched_flat_row_major_parallel<T>(
+    op_a: MatrixOp,
+    op_b: MatrixOp,
+    matrices_a: &[T],
+    matrices_b: &[T],
+    batch_count: usize,
+    a_row_count: usize,
+    a_col_count: usize,
+    b_col_count: usize,
+    thread_count: usize,
+) -> Vec<T>
+where
+    T: Copy + Default + AddAssign + Mul<Output = T> + Send + Sync,
+{
+    let (a_len, b_len, c_len) = batch_lens(
+        batch_count,
+        matrices_a,
+        matrices_b,
+        a_row_count,
+        a_col_count,
+        b_col_count,
+    );
+    let mut matrices_c = vec![T::default(); batch_count * c_len];
+    if c_len == 0 {
+        return matrices_c;
+    }
+    // each band "row" is one whole C matrix
+    map_row_bands(&mut matrices_c, c_len, thread_count, |_, batch, band| {
+        for (batch_idx, matrix_c) in batch.zip(band.chunks_mut(c_len)) {
+            multiply_op_into(
+                op_a,
+                op_b,
+                &matrices_a[batch_idx * a_len..(batch_idx + 1) * a_len],
+                &matrices_b[batch_idx * b_len..(batch_idx + 1) * b_len],
+                a_row_count,
+                a_col_count,
+                b_col_count,
+                matrix_c,
+            );
+        }
+    });
+    matrices_c
+}
+
+// Element counts of one A, B and C matrix of the batch
+fn batch_lens<T>(
+    batch_count: usize,
+    matrices_a: &[T],
+    matrices_b: &[T],
+    a_row_count: usize,
+    a_col_count: usize,
+    b_col_count: usize,
+) -> (usize, usize, usize) {
+    let a_len = a_row_count * a_col_count;
+    let b_len = a_col_count * b_col_count;
+    if matrices_a.len() != batch_count * a_len || matrices_b.len() != batch_count * b_len {
+        panic!("Batch dimensions do not match multiplication requirements.");
+    }
+    (a_len, b_len, a_row_count * b_col_count)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::matrix::multiply::matrix_multiply_cache_oblivious_flat_row_major;
+    use crate::matrix::transpose::transpose_1d_row_major_matrix;
+
+    const OPS: [MatrixOp; 2] = [MatrixOp::Normal, MatrixOp::Transposed];
+
+    fn numbered(len: usize, seed: i32) -> Vec<i32> {
+        (0..len as i32).map(|v| (v * 7 + seed) % 13 - 6).collect()
+    }
+
+    // the stored form of a matrix whose op is row_count x col_count
+    fn stored(op: MatrixOp, matrix: &[i32], col_count: usize) -> Vec<i32> {
+        match op {
+            MatrixOp::Normal => matrix.to_vec(),
+            MatrixOp::Transposed => transpose_1d_row_major_matrix(matrix.to_vec(), col_count),
+        }
+    }
+
+    #[test]
+    fn every_flag_combination_matches_the_plain_product() {
+        for (m, k, n) in [(5, 7, 3), (1, 9, 4), (6, 1, 6), (8, 8, 8)] {
+            let matrix_a = numbered(m * k, 1);
+            let matrix_b = numbered(k * n, 2);
+            let expected =
+                matrix_multiply_cache_oblivious_flat_row_major(&matrix_a, &matrix_b, m, k, n);
+            for op_a in OPS {
+                for op_b in OPS {
+                    let matrix_c = matrix_multiply_op_flat_row_major(
+                        op_a,
+                        op_b,
+                        &stored(op_a, &matrix_a, k),
+                        &stored(op_b, &matrix_b, n),
+                        m,
+                        k,
+                        n,
+                    );
+                    assert_eq!(matrix_c, expected, "op(A)={:?} op(B)={:?}", op_a, op_b);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn matrix_vector_matches_the_one_column_product() {
+        let (m, k) = (6, 9);
+        let matrix_a = numbered(m * k, 3);
+        let vector_x = numbered(k, 4);
+        let expected =
+            matrix_multiply_cache_oblivious_flat_row_major(&matrix_a, &vector_x, m, k, 1);
+        for op_a in OPS {
+            let vector_y =
+                matrix_vector_multiply(op_a, &stored(op_a, &matrix_a, k), m, k, &vector_x);
+            assert_eq!(vector_y, expected);
+        }
+        assert_eq!(MatrixOp::Transposed.stored_shape(m, k), (k, m));
+    }
+
+    #[test]
+    fn batched_matches_one_product_at_a_time() {
+        let (batch_count, m, k, n) = (11, 3, 4, 2);
+        let matrices_a = numbered(batch_count * m * k, 5);
+        let matrices_b = numbered(batch_count * k * n, 6);
+        let expected: Vec<i32> = (0..batch_count)
+            .flat_map(|batch_idx| {
+                matrix_multiply_op_flat_row_major(
+                    MatrixOp::Transposed,
+                    MatrixOp::Normal,
+                    &matrices_a[batch_idx * m * k..(batch_idx + 1) * m * k],
+                    &matrices_b[batch_idx * k * n..(batch_idx + 1) * k * n],
+                    m,
+                    k,
+                    n,
+                )
+            })
+            .collect();
+        for thread_count in [1, 3, 16] {
+            let matrices_c = matrix_multiply_batched_flat_row_major_parallel(
+                MatrixOp::Transposed,
+                MatrixOp::Normal,
+                &matrices_a,
+                &matrices_b,
+                batch_count,
+                m,
+                k,
+                n,
+                thread_count,
+            );
+            assert_eq!(matrices_c, expected);
+        }
+        let matrices_c = matrix_multiply_batched_flat_row_major(
+            MatrixOp::Transposed,
+            MatrixOp::Normal,
+            &matrices_a,
+            &matrices_b,
+            batch_count,
+            m,
+            k,
+            n,
+        );
+        assert_eq!(matrices_c, expected);
+    }
+
+    #[test]
+    #[should_panic(expected = "Vector x length")]
+    fn matrix_vector_rejects_short_vectors() {
+        matrix_vector_multiply(MatrixOp::Normal, &[1, 2, 3, 4], 2, 2, &[1]);
+    }
+}
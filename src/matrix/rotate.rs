@@ -0,0 +1,92 @@
+use crate::parallel::pool::ThreadPool;
+use crate::tuning::{TiledKernel, TuningShape, tuned_block_size};
+
+pub fn rotate_2d_vec<T: Copy>(matrix: Vec<Vec<T>>, block_size: usize) -> Vec<Vec<T>> {
+    let row_count = matrix.len();
+
+    if row_count < 1 || matrix[0].is_empty() {
+        panic!("Matrix must be a 2d matrix with at least 1 element.");
+    }
+    let col_count = matrix[0].len();
+
+    // Make new rows vector based on amount of columns
+    let mut new_matrix = vec![vec![matrix[0][0]; row_count]; col_count];
+
+    for block_start_row_idx in (0..row_count).step_by(block_size) {
+        for block_start_col_idx in (0..col_count).step_by(block_size) {
+            // Determine the row/cell ends (some blocks get chopped off)
+            let row_end = (block_start_row_idx + block_size).min(row_count);
+            let col_end = (block_start_col_idx + block_size).min(col_count);
+
+            // Transpose the block
+            for cell_row_idx in block_start_row_idx..row_end {
+                for cell_col_idx in block_start_col_idx..col_end {
+                    new_matrix[cell_col_idx][row_count - cell_row_idx - 1] =
+                        matrix[cell_row_idx][cell_col_idx];
+                }
+            }
+        }
+    }
+    new_matrix
+}
+
+// rotate_2d_vec with the tuned block size for the matrix shape (see crate::tuning)
+pub fn rotate_2d_vec_tuned<T: Copy>(matrix: Vec<Vec<T>>) -> Vec<Vec<T>> {
+    let shape = TuningShape::matrix(matrix.len(), matrix.first().map_or(0, |row| row.len()));
+    let block_size = tuned_block_size(TiledKernel::Rotate2dVec, shape);
+    rotate_2d_vec(matrix, block_size)
+}
+
+// 90 degrees clockwise rotation of a 1d row-major matrix on the work-stealing pool. The
+// rotated matrix is row_count wide and col_count high, and its cell (r, c) comes from
+// (row_count - 1 - c, r) of the source.
+pub fn rotate_1d_row_major_matrix_pooled<T: Copy + Send + Sync>(
+    flat_matrix_row_major_1d: Vec<T>,
+    col_count: usize,
+    tile: usize,
+    pool: &ThreadPool,
+) -> Vec<T> {
+    let row_count = flat_matrix_row_major_1d.len() / col_count.max(1);
+
+    if col_count < 1 || row_count < 1 {
+        panic!("Matrix must be a 2d matrix with at least 1 element.");
+    }
+
+    let source = &flat_matrix_row_major_1d;
+    let mut new_flat_matrix_row_major_1d = vec![source[0]; col_count * row_count];
+    pool.par_for_each_tile_mut(
+        &mut new_flat_matrix_row_major_1d,
+        row_count,
+        col_count,
+        tile,
+        |mut view| {
+            let tile = view.tile();
+            for target_row_idx in tile.rows() {
+                for (target_col_idx, cell) in tile.cols().zip(view.row_mut(target_row_idx)) {
+                    let source_row_idx = row_count - 1 - target_col_idx;
+                    *cell = source[source_row_idx * col_count + target_row_idx];
+                }
+            }
+        },
+    );
+    new_flat_matrix_row_major_1d
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pooled_matches_rotate_2d_vec() {
+        let pool = ThreadPool::new(2);
+        for (row_count, col_count) in [(1, 1), (4, 9), (33, 20)] {
+            let flat: Vec<i32> = (0..(row_count * col_count) as i32).collect();
+            let matrix: Vec<Vec<i32>> = flat.chunks(col_count).map(|row| row.to_vec()).collect();
+            let expected: Vec<i32> = rotate_2d_vec(matrix, 4).concat();
+            assert_eq!(
+                rotate_1d_row_major_matrix_pooled(flat, col_count, 8, &pool),
+                expected
+            );
+        }
+    }
+}
@@ -0,0 +1,503 @@
+// Sparse matrices: COO, CSR and CSC, conversions between them and dense Vec<Vec<T>>, sparse
+// times dense vector / matrix products and sparse transpose.
+//
+// COO is a bag of (row, col, value) triples, easy to build in any order but slow to use. CSR
+// sorts them by row and replaces the row index with row_offsets, so row i is entries
+// row_offsets[i]..row_offsets[i + 1]: A·x reads each row's values and column indices front to
+// back. CSC is the same thing by column, and CSR of A has exactly the arrays of CSC of Aᵀ,
+// which is what makes transposing cheap.
+//
+// Only values != T::default() are stored, and duplicate COO entries are added together.
+use std::mem::size_of;
+use std::ops::{AddAssign, Mul};
+
+pub trait SparseScalar: Copy + Default + PartialEq + AddAssign + Mul<Output = Self> {}
+
+impl<T: Copy + Default + PartialEq + AddAssign + Mul<Output = T>> SparseScalar for T {}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct CooMatrix<T> {
+    pub row_count: usize,
+    pub col_count: usize,
+    pub row_indices: Vec<usize>,
+    pub col_indices: Vec<usize>,
+    pub values: Vec<T>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct CsrMatrix<T> {
+    pub row_count: usize,
+    pub col_count: usize,
+    pub row_offsets: Vec<usize>,
+    pub col_indices: Vec<usize>,
+    pub values: Vec<T>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct CscMatrix<T> {
+    pub row_count: usize,
+    pub col_count: usize,
+    pub col_offsets: Vec<usize>,
+    pub row_indices: Vec<usize>,
+    pub values: Vec<T>,
+}
+
+// Bytes a dense row_count x col_count matrix of T needs, to compare memory_bytes against
+pub fn dense_memory_bytes<T>(row_count: usize, col_count: usize) -> usize {
+    row_count * col_count * size_of::<T>()
+}
+
+fn check_dense_shape<T>(dense: &[Vec<T>]) -> (usize, usize) {
+    let col_count = dense.first().map_or(0, |row| row.len());
+    if dense.iter().any(|row| row.len() != col_count) {
+        panic!("Dense matrix rows do not all have the same length.");
+    }
+    (dense.len(), col_count)
+}
+
+// Stable counting sort of (major, minor, value) triples by major. Returns the offsets of each
+// major index and the minor indices and values in that order, entries with the same major keep
+// their input order.
+fn compress_by_major<T: Copy>(
+    major_count: usize,
+    majors: &[usize],
+    minors: &[usize],
+    values: &[T],
+) -> (Vec<usize>, Vec<usize>, Vec<T>) {
+    let mut offsets = vec![0; major_count + 1];
+    for &major in majors {
+        offsets[major + 1] += 1;
+    }
+    for i in 0..major_count {
+        offsets[i + 1] += offsets[i];
+    }
+    let mut next = offsets.clone();
+    let mut sorted_minors = vec![0; minors.len()];
+    let mut sorted_values = values.to_vec();
+    for ((&major, &minor), &value) in majors.iter().zip(minors).zip(values) {
+        sorted_minors[next[major]] = minor;
+        sorted_values[next[major]] = value;
+        next[major] += 1;
+    }
+    (offsets, sorted_minors, sorted_values)
+}
+
+// Expand offsets back to one major index per entry
+fn expand_offsets(offsets: &[usize]) -> Vec<usize> {
+    let mut majors = Vec::with_capacity(offsets.last().copied().unwrap_or(0));
+    for (major, window) in offsets.windows(2).enumerate() {
+        majors.extend(std::iter::repeat_n(major, window[1] - window[0]));
+    }
+    majors
+}
+
+impl<T: SparseScalar> CooMatrix<T> {
+    pub fn new(row_count: usize, col_count: usize) -> Self {
+        CooMatrix {
+            row_count,
+            col_count,
+            row_indices: Vec::new(),
+            col_indices: Vec::new(),
+            values: Vec::new(),
+        }
+    }
+
+    pub fn push(&mut self, row: usize, col: usize, value: T) {
+        if row >= self.row_count || col >= self.col_count {
+            panic!("Sparse entry is outside the matrix.");
+        }
+        self.row_indices.push(row);
+        self.col_indices.push(col);
+        self.values.push(value);
+    }
+
+    pub fn from_dense(dense: &[Vec<T>]) -> Self {
+        let (row_count, col_count) = check_dense_shape(dense);
+        let mut coo = CooMatrix::new(row_count, col_count);
+        for (row_idx, row) in dense.iter().enumerate() {
+            for (col_idx, &value) in row.iter().enumerate() {
+                if value != T::default() {
+                    coo.push(row_idx, col_idx, value);
+                }
+            }
+        }
+        coo
+    }
+
+    pub fn nnz(&self) -> usize {
+        self.values.len()
+    }
+
+    // Two stable counting sorts, by column and then by row, leave the triples sorted by
+    // (row, col) so duplicates end up next to each other and get added together
+    pub fn to_csr(&self) -> CsrMatrix<T> {
+        let (col_offsets, rows_by_col, values_by_col) = compress_by_major(
+            self.col_count,
+            &self.col_indices,
+            &self.row_indices,
+            &self.values,
+        );
+        let cols_by_col = expand_offsets(&col_offsets);
+        let (row_offsets, col_indices, values) =
+            compress_by_major(self.row_count, &rows_by_col, &cols_by_col, &values_by_col);
+
+        let mut csr = CsrMatrix {
+            row_count: self.row_count,
+            col_count: self.col_count,
+            row_offsets: vec![0; self.row_count + 1],
+            col_indices: Vec::with_capacity(col_indices.len()),
+            values: Vec::with_capacity(values.len()),
+        };
+        for row in 0..self.row_count {
+            let row_start = csr.values.len();
+            for idx in row_offsets[row]..row_offsets[row + 1] {
+                if csr.values.len() > row_start
+                    && *csr.col_indices.last().unwrap() == col_indices[idx]
+                {
+                    *csr.values.last_mut().unwrap() += values[idx];
+                } else {
+                    csr.col_indices.push(col_indices[idx]);
+                    csr.values.push(values[idx]);
+                }
+            }
+            csr.row_offsets[row + 1] = csr.values.len();
+        }
+        csr.drop_zeros()
+    }
+
+    pub fn to_csc(&self) -> CscMatrix<T> {
+        self.to_csr().to_csc()
+    }
+}
+
+impl<T: SparseScalar> CsrMatrix<T> {
+    pub fn from_dense(dense: &[Vec<T>]) -> Self {
+        let (row_count, col_count) = check_dense_shape(dense);
+        let mut csr = CsrMatrix {
+            row_count,
+            col_count,
+            row_offsets: Vec::with_capacity(row_count + 1),
+            col_indices: Vec::new(),
+            values: Vec::new(),
+        };
+        csr.row_offsets.push(0);
+        for row in dense {
+            for (col_idx, &value) in row.iter().enumerate() {
+                if value != T::default() {
+                    csr.col_indices.push(col_idx);
+                    csr.values.push(value);
+                }
+            }
+            csr.row_offsets.push(csr.values.len());
+        }
+        csr
+    }
+
+    pub fn to_dense(&self) -> Vec<Vec<T>> {
+        let mut dense = vec![vec![T::default(); self.col_count]; self.row_count];
+        for (row_idx, row) in dense.iter_mut().enumerate() {
+            for idx in self.row_offsets[row_idx]..self.row_offsets[row_idx + 1] {
+                row[self.col_indices[idx]] = self.values[idx];
+            }
+        }
+        dense
+    }
+
+    pub fn to_coo(&self) -> CooMatrix<T> {
+        CooMatrix {
+            row_count: self.row_count,
+            col_count: self.col_count,
+            row_indices: expand_offsets(&self.row_offsets),
+            col_indices: self.col_indices.clone(),
+            values: self.values.clone(),
+        }
+    }
+
+    // One counting sort by column, rows stay sorted inside each column since they went in
+    // in row order
+    pub fn to_csc(&self) -> CscMatrix<T> {
+        let (col_offsets, row_indices, values) = compress_by_major(
+            self.col_count,
+            &self.col_indices,
+            &expand_offsets(&self.row_offsets),
+            &self.values,
+        );
+        CscMatrix {
+            row_count: self.row_count,
+            col_count: self.col_count,
+            col_offsets,
+            row_indices,
+            values,
+        }
+    }
+
+    // The CSC arrays of A read as CSR are Aᵀ
+    pub fn transpose(&self) -> CsrMatrix<T> {
+        let csc = self.to_csc();
+        CsrMatrix {
+            row_count: csc.col_count,
+            col_count: csc.row_count,
+            row_offsets: csc.col_offsets,
+            col_indices: csc.row_indices,
+            values: csc.values,
+        }
+    }
+
+    pub fn nnz(&self) -> usize {
+        self.values.len()
+    }
+
+    pub fn density(&self) -> f64 {
+        self.nnz() as f64 / (self.row_count * self.col_count).max(1) as f64
+    }
+
+    pub fn memory_bytes(&self) -> usize {
+        (self.row_offsets.len() + self.col_indices.len()) * size_of::<usize>()
+            + self.values.len() * size_of::<T>()
+    }
+
+    // y = A·x, one sparse dot product per row
+    pub fn multiply_vector(&self, vector_x: &[T]) -> Vec<T> {
+        if vector_x.len() != self.col_count {
+            panic!("Vector x length does not match the sparse matrix columns.");
+        }
+        let mut vector_y = vec![T::default(); self.row_count];
+        for (row_idx, y) in vector_y.iter_mut().enumerate() {
+            for idx in self.row_offsets[row_idx]..self.row_offsets[row_idx + 1] {
+                *y += self.values[idx] * vector_x[self.col_indices[idx]];
+            }
+        }
+        vector_y
+    }
+
+    // C = A·B with B dense flat row-major (col_count x b_col_count): each nonzero A[i][p] adds
+    // a scaled row p of B onto row i of C
+    pub fn multiply_dense(&self, matrix_b: &[T], b_col_count: usize) -> Vec<T> {
+        if matrix_b.len() != self.col_count * b_col_count {
+            panic!("Matrix B dimensions do not match multiplication requirements.");
+        }
+        let mut matrix_c = vec![T::default(); self.row_count * b_col_count];
+        if b_col_count == 0 {
+            return matrix_c;
+        }
+        for (row_idx, c_row) in matrix_c.chunks_mut(b_col_count).enumerate() {
+            for idx in self.row_offsets[row_idx]..self.row_offsets[row_idx + 1] {
+                let a_val = self.values[idx];
+                let b_start = self.col_indices[idx] * b_col_count;
+                for (c_val, &b_val) in c_row
+                    .iter_mut()
+                    .zip(&matrix_b[b_start..b_start + b_col_count])
+                {
+                    *c_val += a_val * b_val;
+                }
+            }
+        }
+        matrix_c
+    }
+
+    // Duplicates can add up to zero, keep only real nonzeros
+    fn drop_zeros(mut self) -> Self {
+        if !self.values.iter().any(|&value| value == T::default()) {
+            return self;
+        }
+        let mut kept = 0;
+        let mut row_start = 0;
+        for row in 0..self.row_count {
+            let row_end = self.row_offsets[row + 1];
+            for idx in row_start..row_end {
+                if self.values[idx] != T::default() {
+                    self.col_indices[kept] = self.col_indices[idx];
+                    self.values[kept] = self.values[idx];
+                    kept += 1;
+                }
+            }
+            row_start = row_end;
+            self.row_offsets[row + 1] = kept;
+        }
+        self.col_indices.truncate(kept);
+        self.values.truncate(kept);
+        self
+    }
+}
+
+impl<T: SparseScalar> CscMatrix<T> {
+    pub fn from_dense(dense: &[Vec<T>]) -> Self {
+        CsrMatrix::from_dense(dense).to_csc()
+    }
+
+    pub fn to_dense(&self) -> Vec<Vec<T>> {
+        let mut dense = vec![vec![T::default(); self.col_count]; self.row_count];
+        for col in 0..self.col_count {
+            for idx in self.col_offsets[col]..self.col_offsets[col + 1] {
+                dense[self.row_indices[idx]][col] = self.values[idx];
+            }
+        }
+        dense
+    }
+
+    pub fn to_csr(&self) -> CsrMatrix<T> {
+        let (row_offsets, col_indices, values) = compress_by_major(
+            self.row_count,
+            &self.row_indices,
+            &expand_offsets(&self.col_offsets),
+            &self.values,
+        );
+        CsrMatrix {
+            row_count: self.row_count,
+            col_count: self.col_count,
+            row_offsets,
+            col_indices,
+            values,
+        }
+    }
+
+    // The CSR arrays of A read as CSC are Aᵀ
+    pub fn transpose(&self) -> CscMatrix<T> {
+        let csr = self.to_csr();
+        CscMatrix {
+            row_count: csr.col_count,
+            col_count: csr.row_count,
+            col_offsets: csr.row_offsets,
+            row_indices: csr.col_indices,
+            values: csr.values,
+        }
+    }
+
+    pub fn nnz(&self) -> usize {
+        self.values.len()
+    }
+
+    pub fn memory_bytes(&self) -> usize {
+        (self.col_offsets.len() + self.row_indices.len()) * size_of::<usize>()
+            + self.values.len() * size_of::<T>()
+    }
+
+    // y = A·x by columns: column j scaled by x[j] is scattered into y, so the reads are
+    // sequential and the writes jump around
+    pub fn multiply_vector(&self, vector_x: &[T]) -> Vec<T> {
+        if vector_x.len() != self.col_count {
+            panic!("Vector x length does not match the sparse matrix columns.");
+        }
+        let mut vector_y = vec![T::default(); self.row_count];
+        for (col, &x_val) in vector_x.iter().enumerate() {
+            for idx in self.col_offsets[col]..self.col_offsets[col + 1] {
+                vector_y[self.row_indices[idx]] += self.values[idx] * x_val;
+            }
+        }
+        vector_y
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::matrix::flatten::flatten_row_major;
+    use crate::matrix::op_multiply::{MatrixOp, matrix_multiply_op_flat_row_major};
+    use crate::matrix::transpose::transpose_2d_vec;
+
+    // mostly zeros, with a fully empty row and column
+    fn sparse_dense(row_count: usize, col_count: usize) -> Vec<Vec<i32>> {
+        (0..row_count)
+            .map(|row| {
+                (0..col_count)
+                    .map(|col| {
+                        if row == 2 || col == 1 || (row * 5 + col * 3) % 4 != 0 {
+                            0
+                        } else {
+                            (row * col_count + col) as i32 - 7
+                        }
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn dense_round_trips_through_every_format() {
+        let dense = sparse_dense(6, 9);
+        let csr = CsrMatrix::from_dense(&dense);
+        assert_eq!(
+            csr.nnz(),
+            dense.iter().flatten().filter(|&&v| v != 0).count()
+        );
+        assert_eq!(csr.to_dense(), dense);
+        assert_eq!(CscMatrix::from_dense(&dense).to_dense(), dense);
+        assert_eq!(csr.to_csc().to_csr(), csr);
+        assert_eq!(csr.to_coo().to_csr(), csr);
+        assert_eq!(CooMatrix::from_dense(&dense).to_csc().to_dense(), dense);
+    }
+
+    #[test]
+    fn coo_in_any_order_sums_duplicates() {
+        let mut coo = CooMatrix::new(3, 3);
+        coo.push(2, 0, 4);
+        coo.push(0, 2, 1);
+        coo.push(2, 0, 3);
+        coo.push(1, 1, 5);
+        coo.push(1, 1, -5);
+        coo.push(0, 0, 2);
+        let csr = coo.to_csr();
+        assert_eq!(csr.row_offsets, vec![0, 2, 2, 3]);
+        assert_eq!(csr.col_indices, vec![0, 2, 0]);
+        assert_eq!(csr.values, vec![2, 1, 7]);
+    }
+
+    #[test]
+    fn transpose_matches_the_dense_transpose() {
+        let dense = sparse_dense(5, 8);
+        let expected = transpose_2d_vec(dense.clone());
+        assert_eq!(
+            CsrMatrix::from_dense(&dense).transpose().to_dense(),
+            expected
+        );
+        assert_eq!(
+            CscMatrix::from_dense(&dense).transpose().to_dense(),
+            expected
+        );
+    }
+
+    #[test]
+    fn products_match_the_dense_kernels() {
+        let (m, k, n) = (7, 6, 4);
+        let dense = sparse_dense(m, k);
+        let flat_a = flatten_row_major(&dense);
+        let vector_x: Vec<i32> = (0..k as i32).map(|v| v * 2 - 3).collect();
+        let matrix_b: Vec<i32> = (0..(k * n) as i32).map(|v| v % 5 - 2).collect();
+
+        let expected_y = matrix_multiply_op_flat_row_major(
+            MatrixOp::Normal,
+            MatrixOp::Normal,
+            &flat_a,
+            &vector_x,
+            m,
+            k,
+            1,
+        );
+        let csr = CsrMatrix::from_dense(&dense);
+        assert_eq!(csr.multiply_vector(&vector_x), expected_y);
+        assert_eq!(csr.to_csc().multiply_vector(&vector_x), expected_y);
+
+        let expected_c = matrix_multiply_op_flat_row_major(
+            MatrixOp::Normal,
+            MatrixOp::Normal,
+            &flat_a,
+            &matrix_b,
+            m,
+            k,
+            n,
+        );
+        assert_eq!(csr.multiply_dense(&matrix_b, n), expected_c);
+    }
+
+    #[test]
+    fn memory_is_proportional_to_nonzeros() {
+        let mut dense = vec![vec![0.0_f64; 100]; 100];
+        for i in 0..100 {
+            dense[i][i] = 1.0;
+        }
+        let csr = CsrMatrix::from_dense(&dense);
+        assert_eq!(csr.density(), 0.01);
+        assert_eq!(csr.memory_bytes(), (101 + 100) * 8 + 100 * 8);
+        assert!(csr.memory_bytes() < dense_memory_bytes::<f64>(100, 100) / 20);
+    }
+}
@@ -0,0 +1,338 @@
+// Strassen and Strassen-Winograd fast matrix multiplication.
+//
+// Splitting A, B and C into 2x2 quadrants needs 8 quadrant products. Strassen gets away with
+// 7 by multiplying sums and differences of quadrants, so each halving of the side costs 7
+// instead of 8 products: O(n^2.807) instead of O(n^3). Winograd's variant of it needs the same
+// 7 products but 15 quadrant additions instead of 18.
+//
+// The extra additions and temporaries make it slower than the tiled kernel on small matrices,
+// so the recursion stops at the crossover side and hands the rest to
+// matrix_multiply_loop_tiling_flat_row_major. Non-square and odd sized matrices are zero
+// padded to a square whose side halves evenly down to the crossover.
+use super::multiply::matrix_multiply_loop_tiling_flat_row_major;
+use std::fmt::Debug;
+use std::ops::{Add, AddAssign, Mul, Sub};
+
+// Around this side one level of Strassen and the tiled kernel break even on the machine the
+// tutorial was written on, tuning::tuner::tune_strassen_crossover finds it for another one
+pub const STRASSEN_DEFAULT_CROSSOVER: usize = 128;
+// Block size of the tiled kernel used for the leaves
+pub const STRASSEN_LEAF_BLOCK_SIZE: usize = 64;
+
+pub trait StrassenScalar:
+    Copy
+    + Default
+    + Debug
+    + Add<Output = Self>
+    + Sub<Output = Self>
+    + Mul<Output = Self>
+    + AddAssign
+    + Send
+    + Sync
+{
+}
+
+impl StrassenScalar for i32 {}
+impl StrassenScalar for i64 {}
+impl StrassenScalar for f32 {}
+impl StrassenScalar for f64 {}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StrassenVariant {
+    Strassen,
+    Winograd,
+}
+
+impl StrassenVariant {
+    pub fn name(&self) -> &'static str {
+        match self {
+            StrassenVariant::Strassen => "Strassen",
+            StrassenVariant::Winograd => "Winograd",
+        }
+    }
+}
+
+// The side the matrices get padded to: the largest dimension rounded up so that halving it
+// `levels` times lands at or below the crossover, e.g. 301 with crossover 128 pads to 304
+// (304 -> 152 -> 76), not to 512
+pub fn strassen_padded_side(largest_dimension: usize, crossover: usize) -> usize {
+    if crossover < 1 {
+        panic!("Strassen crossover must be at least 1.");
+    }
+    let mut leaf_side = largest_dimension;
+    let mut levels = 0;
+    while leaf_side > crossover {
+        leaf_side = leaf_side.div_ceil(2);
+        levels += 1;
+    }
+    leaf_side << levels
+}
+
+pub fn matrix_multiply_strassen_flat_row_major<T: StrassenScalar>(
+    matrix_a: &[T], // row-major [a_row_count × a_col_count]
+    matrix_b: &[T], // row-major [b_row_count × b_col_count]
+    a_row_count: usize,
+    a_col_count: usize,
+    b_col_count: usize,
+    variant: StrassenVariant,
+    crossover: usize,
+) -> Vec<T> {
+    let b_row_count = a_col_count;
+    if matrix_a.len() != a_row_count * a_col_count || matrix_b.len() != b_row_count * b_col_count {
+        panic!("Matrix B dimensions do not match multiplication requirements.");
+    }
+
+    let largest_dimension = a_row_count.max(a_col_count).max(b_col_count);
+    if largest_dimension <= crossover {
+        return matrix_multiply_loop_tiling_flat_row_major(
+            matrix_a,
+            matrix_b,
+            a_row_count,
+            a_col_count,
+            b_col_count,
+            STRASSEN_LEAF_BLOCK_SIZE,
+        );
+    }
+
+    let side = strassen_padded_side(largest_dimension, crossover);
+    let padded_a = pad_to_square(matrix_a, a_row_count, a_col_count, side);
+    let padded_b = pad_to_square(matrix_b, b_row_count, b_col_count, side);
+    let padded_c = multiply_square(&padded_a, &padded_b, side, variant, crossover);
+    let mut matrix_c = Vec::with_capacity(a_row_count * b_col_count);
+    for row in padded_c.chunks(side).take(a_row_count) {
+        matrix_c.extend_from_slice(&row[..b_col_count]);
+    }
+    matrix_c
+}
+
+fn pad_to_square<T: StrassenScalar>(
+    matrix: &[T],
+    row_count: usize,
+    col_count: usize,
+    side: usize,
+) -> Vec<T> {
+    let mut padded = vec![T::default(); side * side];
+    for (row_idx, row) in matrix.chunks(col_count.max(1)).take(row_count).enumerate() {
+        padded[row_idx * side..row_idx * side + col_count].copy_from_slice(row);
+    }
+    padded
+}
+
+// The four half x half quadrants of a side x side matrix: 11, 12, 21, 22
+fn split_quadrants<T: StrassenScalar>(matrix: &[T], side: usize) -> [Vec<T>; 4] {
+    let half = side / 2;
+    let quadrant = |row_start: usize, col_start: usize| {
+        let mut block = Vec::with_capacity(half * half);
+        for row_idx in row_start..row_start + half {
+            let start = row_idx * side + col_start;
+            block.extend_from_slice(&matrix[start..start + half]);
+        }
+        block
+    };
+    [
+        quadrant(0, 0),
+        quadrant(0, half),
+        quadrant(half, 0),
+        quadrant(half, half),
+    ]
+}
+
+fn join_quadrants<T: StrassenScalar>(quadrants: [Vec<T>; 4], half: usize) -> Vec<T> {
+    let side = half * 2;
+    let mut matrix = vec![T::default(); side * side];
+    for (quadrant_idx, quadrant) in quadrants.iter().enumerate() {
+        let row_start = (quadrant_idx / 2) * half;
+        let col_start = (quadrant_idx % 2) * half;
+        for (row_idx, row) in quadrant.chunks(half).enumerate() {
+            let start = (row_start + row_idx) * side + col_start;
+            matrix[start..start + half].copy_from_slice(row);
+        }
+    }
+    matrix
+}
+
+fn add<T: StrassenScalar>(a: &[T], b: &[T]) -> Vec<T> {
+    a.iter()
+        .zip(b)
+        .map(|(&a_val, &b_val)| a_val + b_val)
+        .collect()
+}
+
+fn sub<T: StrassenScalar>(a: &[T], b: &[T]) -> Vec<T> {
+    a.iter()
+        .zip(b)
+        .map(|(&a_val, &b_val)| a_val - b_val)
+        .collect()
+}
+
+fn multiply_square<T: StrassenScalar>(
+    matrix_a: &[T],
+    matrix_b: &[T],
+    side: usize,
+    variant: StrassenVariant,
+    crossover: usize,
+) -> Vec<T> {
+    // strassen_padded_side makes every side above the crossover even
+    if side <= crossover {
+        return matrix_multiply_loop_tiling_flat_row_major(
+            matrix_a,
+            matrix_b,
+            side,
+            side,
+            side,
+            STRASSEN_LEAF_BLOCK_SIZE,
+        );
+    }
+    let half = side / 2;
+    let [a11, a12, a21, a22] = split_quadrants(matrix_a, side);
+    let [b11, b12, b21, b22] = split_quadrants(matrix_b, side);
+    let multiply = |a: &[T], b: &[T]| multiply_square(a, b, half, variant, crossover);
+
+    let quadrants = match variant {
+        StrassenVariant::Strassen => {
+            let m1 = multiply(&add(&a11, &a22), &add(&b11, &b22));
+            let m2 = multiply(&add(&a21, &a22), &b11);
+            let m3 = multiply(&a11, &sub(&b12, &b22));
+            let m4 = multiply(&a22, &sub(&b21, &b11));
+            let m5 = multiply(&add(&a11, &a12), &b22);
+            let m6 = multiply(&sub(&a21, &a11), &add(&b11, &b12));
+            let m7 = multiply(&sub(&a12, &a22), &add(&b21, &b22));
+            [
+                // C11 = M1 + M4 - M5 + M7
+                add(&sub(&add(&m1, &m4), &m5), &m7),
+                // C12 = M3 + M5
+                add(&m3, &m5),
+                // C21 = M2 + M4
+                add(&m2, &m4),
+                // C22 = M1 - M2 + M3 + M6
+                add(&add(&sub(&m1, &m2), &m3), &m6),
+            ]
+        }
+        StrassenVariant::Winograd => {
+            // 8 additions on the operands...
+            let s1 = add(&a21, &a22);
+            let s2 = sub(&s1, &a11);
+            let s3 = sub(&a11, &a21);
+            let s4 = sub(&a12, &s2);
+            let t1 = sub(&b12, &b11);
+            let t2 = sub(&b22, &t1);
+            let t3 = sub(&b22, &b12);
+            let t4 = sub(&t2, &b21);
+            let p1 = multiply(&a11, &b11);
+            let p2 = multiply(&a12, &b21);
+            let p3 = multiply(&s4, &b22);
+            let p4 = multiply(&a22, &t4);
+            let p5 = multiply(&s1, &t1);
+            let p6 = multiply(&s2, &t2);
+            let p7 = multiply(&s3, &t3);
+            // ...and 7 on the products, U2 and U3 are shared between quadrants
+            let u2 = add(&p1, &p6);
+            let u3 = add(&u2, &p7);
+            let u4 = add(&u2, &p5);
+            [add(&p1, &p2), add(&u4, &p3), sub(&u3, &p4), add(&u3, &p5)]
+        }
+    };
+    join_quadrants(quadrants, half)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bench::data::XorShift64;
+    use crate::matrix::multiply::matrix_multiply_cache_oblivious_flat_row_major;
+
+    const VARIANTS: [StrassenVariant; 2] = [StrassenVariant::Strassen, StrassenVariant::Winograd];
+
+    fn random_i32(seed: u64, len: usize) -> Vec<i32> {
+        let mut rng = XorShift64::new(seed);
+        (0..len)
+            .map(|_| (rng.next_u64() % 21) as i32 - 10)
+            .collect()
+    }
+
+    #[test]
+    fn padded_side_halves_down_to_the_crossover() {
+        assert_eq!(strassen_padded_side(100, 128), 100);
+        assert_eq!(strassen_padded_side(301, 128), 304);
+        assert_eq!(strassen_padded_side(300, 128), 300);
+        assert_eq!(strassen_padded_side(256, 64), 256);
+        assert_eq!(strassen_padded_side(17, 4), 24);
+    }
+
+    #[test]
+    fn matches_the_exact_product_on_integers() {
+        // square power of two, odd, and non-square shapes, with tiny crossovers so the
+        // recursion goes several levels deep
+        for (a_row_count, a_col_count, b_col_count) in [
+            (64, 64, 64),
+            (37, 37, 37),
+            (20, 45, 9),
+            (3, 70, 33),
+            (1, 1, 1),
+        ] {
+            let matrix_a = random_i32(1, a_row_count * a_col_count);
+            let matrix_b = random_i32(2, a_col_count * b_col_count);
+            let expected = matrix_multiply_cache_oblivious_flat_row_major(
+                &matrix_a,
+                &matrix_b,
+                a_row_count,
+                a_col_count,
+                b_col_count,
+            );
+            for variant in VARIANTS {
+                for crossover in [2, 8, 128] {
+                    let matrix_c = matrix_multiply_strassen_flat_row_major(
+                        &matrix_a,
+                        &matrix_b,
+                        a_row_count,
+                        a_col_count,
+                        b_col_count,
+                        variant,
+                        crossover,
+                    );
+                    assert_eq!(matrix_c, expected, "{:?} crossover {}", variant, crossover);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn floating_point_error_stays_small() {
+        let side = 96;
+        let mut rng = XorShift64::new(7);
+        let mut random = || {
+            (0..side * side)
+                .map(|_| (rng.next_u64() % 2001) as f64 / 1000.0 - 1.0)
+                .collect::<Vec<f64>>()
+        };
+        let (matrix_a, matrix_b) = (random(), random());
+        let exact =
+            matrix_multiply_loop_tiling_flat_row_major(&matrix_a, &matrix_b, side, side, side, 32);
+        for variant in VARIANTS {
+            let matrix_c = matrix_multiply_strassen_flat_row_major(
+                &matrix_a, &matrix_b, side, side, side, variant, 8,
+            );
+            let max_error = matrix_c
+                .iter()
+                .zip(&exact)
+                .map(|(c, e)| (c - e).abs())
+                .fold(0.0, f64::max);
+            // entries are sums of 96 products in [-1, 1], so this is ~1e-12 relative to them
+            assert!(max_error < 1e-10, "{:?} max error {}", variant, max_error);
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "do not match")]
+    fn rejects_mismatched_shapes() {
+        matrix_multiply_strassen_flat_row_major(
+            &[1, 2, 3, 4],
+            &[1, 2, 3],
+            2,
+            2,
+            2,
+            StrassenVariant::Strassen,
+            1,
+        );
+    }
+}
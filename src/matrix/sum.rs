@@ -0,0 +1,46 @@
+use crate::simd;
+
+pub fn row_sum_matrix_iterator(matrix: &[Vec<i32>]) -> i32 {
+    matrix.iter().map(|row| row.iter().sum::<i32>()).sum()
+}
+
+pub fn row_sum_matrix(matrix: &[Vec<i32>]) -> i32 {
+    let row_count = matrix.len();
+    let col_count = matrix[0].len();
+    let mut sum = 0;
+    for row_idx in 0..row_count {
+        for col_idx in 0..col_count {
+            sum += matrix[row_idx][col_idx];
+        }
+    }
+    sum
+}
+
+pub fn row_sum_matrix_trying_to_coax_llvm_to_optimize_using_simd(matrix: &[Vec<i32>]) -> i32 {
+    let mut sum = 0;
+    for row in matrix {
+        for value in row {
+            sum += value;
+        }
+    }
+    sum
+}
+
+// Each row handed to the explicit SSE2/AVX2 sum, wrapping like the vector adds do
+pub fn row_sum_matrix_simd(matrix: &[Vec<i32>]) -> i32 {
+    matrix
+        .iter()
+        .fold(0i32, |sum, row| sum.wrapping_add(simd::sum_i32(row)))
+}
+
+pub fn col_sum_matrix(matrix: &[Vec<i32>]) -> i32 {
+    let row_count = matrix.len();
+    let col_count = matrix[0].len();
+    let mut sum = 0;
+    for col_idx in 0..col_count {
+        for row_idx in 0..row_count {
+            sum += matrix[row_idx][col_idx];
+        }
+    }
+    sum
+}
@@ -0,0 +1,576 @@
+use crate::parallel::{map_row_bands, pool::ThreadPool};
+use crate::simd::{TRANSPOSE_BLOCK, transpose_8x8_i32};
+use crate::tuning::{TiledKernel, TuningShape, tuned_block_size};
+use std::mem::MaybeUninit;
+use std::ops::Range;
+
+// This implementation of transpose has poor cache locality due to striding memory access,
+// Is really only fast up to ~32x32 after which the whole matrix can no longer fit in cache.
+// Block-Tiling the matrix is the better approach, and Block-Tiling with 1d row major arrays
+// holding the matrix is the even bettter than that approach.
+pub fn transpose_2d_vec<T: Clone>(mut matrix: Vec<Vec<T>>) -> Vec<Vec<T>> {
+    let input_row_count = matrix.len();
+
+    if input_row_count < 1 || matrix[0].is_empty() {
+        panic!("Matrix must be a 2d matrix with at least 1 element.");
+    }
+
+    let input_col_count = matrix[0].len();
+
+    if matrix.len() == matrix[0].len() {
+        // This is an inplace swap so excluding the diagonals we only need to swap
+        // on a half and it will auto swap the other half as a result.
+
+        // for each row
+        for idx_row in 0..input_row_count {
+            // Split the matrix into two mutable slices so we can memory swap later
+            let (top, bottom) = matrix.split_at_mut(idx_row + 1);
+
+            // start indexing after the diagonal + 1, +1 since the first element is on the diagonal
+            for idx_col in idx_row + 1..input_col_count {
+                //std::mem::swap(&mut matrix[idx_row][idx_col], &mut matrix[idx_col][idx_row]);
+                std::mem::swap(
+                    &mut top[idx_row][idx_col],
+                    &mut bottom[idx_col - (idx_row + 1)][idx_row],
+                );
+            }
+        }
+        matrix
+    } else {
+        // Make new rows vector based on amount of columns
+        let mut new_matrix = Vec::with_capacity(input_col_count);
+        // Make new column vectors based on amount of rows.
+        for _ in 0..input_col_count {
+            new_matrix.push(Vec::with_capacity(input_row_count));
+        }
+        for input_row in matrix {
+            // moves ownership
+            //let drained_row = input_row.drain(..).collect::<Vec<T>>();
+            //for (input_col_idx, val) in drained_row.into_iter().enumerate() {
+            for (input_col_idx, val) in input_row.into_iter().enumerate() {
+                new_matrix[input_col_idx].push(val);
+            }
+        }
+        new_matrix
+    }
+}
+
+pub fn transpose_1d_row_major_matrix<T: Copy>(
+    mut flat_matrix_row_major_1d: Vec<T>,
+    col_count: usize,
+) -> Vec<T> {
+    let row_count = flat_matrix_row_major_1d.len() / col_count;
+
+    if row_count < 1 || col_count < 1 {
+        panic!("Matrix must be a 2d matrix with at least 1 element.");
+    }
+
+    if row_count == col_count {
+        // This is an inplace swap so excluding the diagonals we only need to swap
+        // on a half and it will auto swap the other half as a result.
+
+        // for each row
+        for idx_row in 0..row_count {
+            for idx_col in idx_row + 1..col_count {
+                let source_idx = idx_row * col_count + idx_col;
+                let target_idx = idx_col * col_count + idx_row;
+                flat_matrix_row_major_1d.swap(source_idx, target_idx);
+            }
+        }
+        flat_matrix_row_major_1d
+    } else {
+        // Make new rows vector based on amount of columns
+        let mut new_flat_matrix_row_major_1d =
+            vec![flat_matrix_row_major_1d[0]; col_count * row_count];
+        for idx_row in 0..row_count {
+            for idx_col in 0..col_count {
+                // &flat[row * cols + col]
+                let source_idx = idx_row * col_count + idx_col;
+                let target_idx = idx_col * row_count + idx_row;
+                new_flat_matrix_row_major_1d[target_idx] = flat_matrix_row_major_1d[source_idx];
+            }
+        }
+        new_flat_matrix_row_major_1d
+    }
+}
+
+// This is the optimal solution for matrices that need transposing.
+// Vec<Vec<T>> is the best structure it wins because of the strided
+// access on the writes. The sub Vec<T> instances play better with
+// the cache when writing.
+// Best block size on my processor (128K L1) means a square
+// matrix performs best with a 64 block size, while a
+// rectangle performs better with a 32 block size. The difference
+// is due to the square matrix doing in place swapping, while the
+// rectangle has to copy to a destination matrix.
+pub fn transpose_2d_vec_loop_tiling_aka_block_tiling<T: Copy>(
+    mut matrix: Vec<Vec<T>>,
+    block_size: usize,
+) -> Vec<Vec<T>> {
+    let input_row_count = matrix.len();
+
+    if input_row_count < 1 || matrix[0].is_empty() {
+        panic!("Matrix must be a 2d matrix with at least 1 element.");
+    }
+
+    let input_col_count = matrix[0].len();
+
+    if matrix.len() == matrix[0].len() {
+        // This is an inplace swap so excluding the diagonals we only need to swap
+        // on a half and it will auto swap the other half as a result.
+
+        // Break the matrix into blocks first with block row/col index co-ordinates
+
+        for block_start_row_idx in (0..input_row_count).step_by(block_size) {
+            for block_start_col_idx in (block_start_row_idx..input_col_count).step_by(block_size) {
+                // Determine the row/cell ends (some blocks get chopped off)
+                let row_end = (block_start_row_idx + block_size).min(input_row_count);
+                let col_end = (block_start_col_idx + block_size).min(input_col_count);
+
+                // Determine if the block being processed is on the diagonal
+                if block_start_col_idx == block_start_row_idx {
+                    // We are on the diagonal so only swap half the cells.
+                    // Transpose top right half
+                    for cell_row_idx in block_start_row_idx..row_end {
+                        let (top, bottom) = matrix.split_at_mut(cell_row_idx + 1);
+                        for cell_col_idx in (cell_row_idx + 1)..col_end {
+                            std::mem::swap(
+                                &mut top[cell_row_idx][cell_col_idx],
+                                &mut bottom[cell_col_idx - (cell_row_idx + 1)][cell_row_idx],
+                            );
+                        }
+                    }
+                } else {
+                    // We are not on the diagonal so swap all cells
+                    // Transpose the entire block
+                    for cell_row_idx in block_start_row_idx..row_end {
+                        let (top, bottom) = matrix.split_at_mut(cell_row_idx + 1);
+                        for cell_col_idx in block_start_col_idx..col_end {
+                            std::mem::swap(
+                                &mut top[cell_row_idx][cell_col_idx],
+                                &mut bottom[cell_col_idx - (cell_row_idx + 1)][cell_row_idx],
+                            );
+                        }
+                    }
+                }
+            }
+        }
+
+        matrix
+    } else {
+        // Make new rows vector based on amount of columns
+        let mut new_matrix = vec![vec![matrix[0][0]; input_row_count]; input_col_count];
+
+        for block_start_row_idx in (0..input_row_count).step_by(block_size) {
+            for block_start_col_idx in (0..input_col_count).step_by(block_size) {
+                // Determine the row/cell ends (some blocks get chopped off)
+                let row_end = (block_start_row_idx + block_size).min(input_row_count);
+                let col_end = (block_start_col_idx + block_size).min(input_col_count);
+
+                // Transpose the block
+                for cell_row_idx in block_start_row_idx..row_end {
+                    for cell_col_idx in block_start_col_idx..col_end {
+                        new_matrix[cell_col_idx][cell_row_idx] = matrix[cell_row_idx][cell_col_idx];
+                    }
+                }
+            }
+        }
+        new_matrix
+    }
+}
+
+pub fn transpose_1d_row_major_matrix_loop_tiling_aka_block_tiling<T: Copy>(
+    mut flat_matrix_row_major_1d: Vec<T>,
+    col_count: usize,
+    block_size: usize,
+) -> Vec<T> {
+    let row_count = flat_matrix_row_major_1d.len() / col_count;
+
+    if col_count < 1 || row_count < 1 {
+        panic!("Matrix must be a 2d matrix with at least 1 element.");
+    }
+
+    if row_count == col_count {
+        // This is an inplace swap so excluding the diagonals we only need to swap
+        // on a half and it will auto swap the other half as a result.
+        // Break the matrix into blocks first with block row/col index co-ordinates
+
+        for block_start_row_idx in (0..row_count).step_by(block_size) {
+            for block_start_col_idx in (block_start_row_idx..col_count).step_by(block_size) {
+                // Determine the row/cell ends (some blocks get chopped off)
+                let row_end = (block_start_row_idx + block_size).min(row_count);
+                let col_end = (block_start_col_idx + block_size).min(col_count);
+
+                // Determine if the block being processed is on the diagonal
+                if block_start_col_idx == block_start_row_idx {
+                    // We are on the diagonal so only swap half the cells.
+                    // Transpose top right half
+                    for cell_row_idx in block_start_row_idx..row_end {
+                        for cell_col_idx in (cell_row_idx + 1)..col_end {
+                            let source_idx = cell_row_idx * col_count + cell_col_idx;
+                            let target_idx = cell_col_idx * col_count + cell_row_idx;
+                            flat_matrix_row_major_1d.swap(source_idx, target_idx);
+                        }
+                    }
+                } else {
+                    // We are not on the diagonal so swap all cells
+                    // Transpose the entire block
+                    for cell_row_idx in block_start_row_idx..row_end {
+                        for cell_col_idx in block_start_col_idx..col_end {
+                            let source_idx = cell_row_idx * col_count + cell_col_idx;
+                            let target_idx = cell_col_idx * col_count + cell_row_idx;
+                            flat_matrix_row_major_1d.swap(source_idx, target_idx);
+                        }
+                    }
+                }
+            }
+        }
+        flat_matrix_row_major_1d
+    } else {
+        // Make new rows vector based on amount of columns
+        let mut new_flat_matrix_row_major_1d =
+            vec![flat_matrix_row_major_1d[0]; col_count * row_count];
+
+        for block_start_row_idx in (0..row_count).step_by(block_size) {
+            for block_start_col_idx in (0..col_count).step_by(block_size) {
+                // Determine the row/cell ends (some blocks get chopped off)
+                let row_end = (block_start_row_idx + block_size).min(row_count);
+                let col_end = (block_start_col_idx + block_size).min(col_count);
+
+                // Transpose the block
+                for cell_row_idx in block_start_row_idx..row_end {
+                    for cell_col_idx in block_start_col_idx..col_end {
+                        let source_idx = cell_row_idx * col_count + cell_col_idx;
+                        let target_idx = cell_col_idx * row_count + cell_row_idx;
+                        new_flat_matrix_row_major_1d[target_idx] =
+                            flat_matrix_row_major_1d[source_idx];
+                    }
+                }
+            }
+        }
+        new_flat_matrix_row_major_1d
+    }
+}
+
+// Enhanced version square tiled transpose found
+// I need to study these differences still.
+// Looks like its using an intermediate buffer to write to before writing that buffer into the strided destination...
+// I'm not so sure this is going to be much faster - but what do I know I just test and observe the speed.
+//
+// Ok I did the benchmarks it is a lie that this is faster it is the same performance.
+pub fn transpose_square_tiled<T: Copy>(mut mat: Vec<T>, n: usize, block: usize) -> Vec<T> {
+    let mut buf = vec![MaybeUninit::<T>::uninit(); block * block];
+
+    for i in (0..n).step_by(block) {
+        for j in (0..n).step_by(block) {
+            let row_end = (i + block).min(n);
+            let col_end = (j + block).min(n);
+
+            if i == j {
+                // 🔹 Diagonal block: transpose inside a scratch buffer
+                for r in i..row_end {
+                    for c in j..col_end {
+                        buf[(r - i) * block + (c - j)].write(mat[r * n + c]);
+                    }
+                }
+                for r in i..row_end {
+                    for c in j..col_end {
+                        unsafe {
+                            mat[r * n + c] = buf[(c - j) * block + (r - i)].assume_init();
+                        }
+                    }
+                }
+            } else if i < j {
+                // 🔹 Off-diagonal tile pair (i,j) and (j,i)
+                for r in i..row_end {
+                    for c in j..col_end {
+                        buf[(r - i) * block + (c - j)].write(mat[r * n + c]); // copy tile (i,j)
+                    }
+                }
+
+                for r in j..col_end {
+                    for c in i..row_end {
+                        let tmp = mat[r * n + c]; // (j,i) tile element
+                        unsafe {
+                            mat[r * n + c] = buf[(c - i) * block + (r - j)].assume_init(); // from (i,j)
+                        }
+                        mat[c * n + r] = tmp; // write into (i,j)
+                    }
+                }
+            }
+        }
+    }
+    mat
+}
+
+// Tiled transposes using the tuned block size for the matrix shape (see crate::tuning)
+pub fn transpose_2d_vec_loop_tiling_tuned<T: Copy>(matrix: Vec<Vec<T>>) -> Vec<Vec<T>> {
+    let shape = TuningShape::matrix(matrix.len(), matrix.first().map_or(0, |row| row.len()));
+    let block_size = tuned_block_size(TiledKernel::Transpose2dVecLoopTiling, shape);
+    transpose_2d_vec_loop_tiling_aka_block_tiling(matrix, block_size)
+}
+
+pub fn transpose_1d_row_major_matrix_loop_tiling_tuned<T: Copy>(
+    flat_matrix_row_major_1d: Vec<T>,
+    col_count: usize,
+) -> Vec<T> {
+    let shape = TuningShape::matrix(flat_matrix_row_major_1d.len() / col_count.max(1), col_count);
+    let block_size = tuned_block_size(TiledKernel::Transpose1dRowMajorLoopTiling, shape);
+    transpose_1d_row_major_matrix_loop_tiling_aka_block_tiling(
+        flat_matrix_row_major_1d,
+        col_count,
+        block_size,
+    )
+}
+
+pub fn transpose_square_tiled_tuned<T: Copy>(mat: Vec<T>, n: usize) -> Vec<T> {
+    let block_size = tuned_block_size(TiledKernel::TransposeSquareTiled, TuningShape::matrix(n, n));
+    transpose_square_tiled(mat, n, block_size)
+}
+
+// Recursion stops once both sides of the sub-matrix are at most this long: a 16x16 tile of
+// i32 is 16 cache lines to read and 16 to write.
+pub const CACHE_OBLIVIOUS_TRANSPOSE_CUTOFF: usize = 16;
+
+// Cache-oblivious transpose, no block size to tune: split the longer side of the sub-matrix
+// in half until both sides are under the cutoff. Always out of place, so rectangles and
+// squares take the same path.
+pub fn transpose_1d_row_major_matrix_cache_oblivious<T: Copy>(
+    flat_matrix_row_major_1d: Vec<T>,
+    col_count: usize,
+) -> Vec<T> {
+    let row_count = flat_matrix_row_major_1d.len() / col_count.max(1);
+
+    if col_count < 1 || row_count < 1 {
+        panic!("Matrix must be a 2d matrix with at least 1 element.");
+    }
+
+    let mut new_flat_matrix_row_major_1d = vec![flat_matrix_row_major_1d[0]; col_count * row_count];
+    transpose_cache_oblivious_block(
+        &flat_matrix_row_major_1d,
+        &mut new_flat_matrix_row_major_1d,
+        row_count,
+        col_count,
+        0..row_count,
+        0..col_count,
+    );
+    new_flat_matrix_row_major_1d
+}
+
+fn transpose_cache_oblivious_block<T: Copy>(
+    source: &[T],
+    target: &mut [T],
+    row_count: usize,
+    col_count: usize,
+    rows: Range<usize>,
+    cols: Range<usize>,
+) {
+    if rows.len() <= CACHE_OBLIVIOUS_TRANSPOSE_CUTOFF
+        && cols.len() <= CACHE_OBLIVIOUS_TRANSPOSE_CUTOFF
+    {
+        for cell_row_idx in rows {
+            for cell_col_idx in cols.clone() {
+                target[cell_col_idx * row_count + cell_row_idx] =
+                    source[cell_row_idx * col_count + cell_col_idx];
+            }
+        }
+    } else if rows.len() >= cols.len() {
+        let mid = rows.start + rows.len() / 2;
+        for half in [rows.start..mid, mid..rows.end] {
+            transpose_cache_oblivious_block(
+                source,
+                target,
+                row_count,
+                col_count,
+                half,
+                cols.clone(),
+            );
+        }
+    } else {
+        let mid = cols.start + cols.len() / 2;
+        for half in [cols.start..mid, mid..cols.end] {
+            transpose_cache_oblivious_block(
+                source,
+                target,
+                row_count,
+                col_count,
+                rows.clone(),
+                half,
+            );
+        }
+    }
+}
+
+// 8x8 blocks transposed in registers by the SSE2/AVX2 kernel in simd.rs, the fringe rows
+// and columns that do not fill a block fall back to the plain loop. i32 only, the kernel
+// shuffles 32 bit lanes.
+pub fn transpose_1d_row_major_matrix_simd(
+    flat_matrix_row_major_1d: Vec<i32>,
+    col_count: usize,
+) -> Vec<i32> {
+    let row_count = flat_matrix_row_major_1d.len() / col_count.max(1);
+
+    if col_count < 1 || row_count < 1 {
+        panic!("Matrix must be a 2d matrix with at least 1 element.");
+    }
+
+    let mut new_flat_matrix_row_major_1d = vec![0; col_count * row_count];
+    let block_row_end = row_count - row_count % TRANSPOSE_BLOCK;
+    let block_col_end = col_count - col_count % TRANSPOSE_BLOCK;
+    for block_row_idx in (0..block_row_end).step_by(TRANSPOSE_BLOCK) {
+        for block_col_idx in (0..block_col_end).step_by(TRANSPOSE_BLOCK) {
+            transpose_8x8_i32(
+                &flat_matrix_row_major_1d[block_row_idx * col_count + block_col_idx..],
+                col_count,
+                &mut new_flat_matrix_row_major_1d[block_col_idx * row_count + block_row_idx..],
+                row_count,
+            );
+        }
+    }
+    for row_idx in 0..row_count {
+        let fringe_start = if row_idx < block_row_end {
+            block_col_end
+        } else {
+            0
+        };
+        for col_idx in fringe_start..col_count {
+            new_flat_matrix_row_major_1d[col_idx * row_count + row_idx] =
+                flat_matrix_row_major_1d[row_idx * col_count + col_idx];
+        }
+    }
+    new_flat_matrix_row_major_1d
+}
+
+// Tiled transpose with the rows of the transposed matrix (the columns of the source) split
+// into one band per thread. Each thread reads a column strip of the source and writes its
+// own contiguous band of the target.
+pub fn transpose_1d_row_major_matrix_parallel<T: Copy + Send + Sync>(
+    flat_matrix_row_major_1d: Vec<T>,
+    col_count: usize,
+    block_size: usize,
+    thread_count: usize,
+) -> Vec<T> {
+    let row_count = flat_matrix_row_major_1d.len() / col_count.max(1);
+
+    if col_count < 1 || row_count < 1 {
+        panic!("Matrix must be a 2d matrix with at least 1 element.");
+    }
+
+    let source = &flat_matrix_row_major_1d;
+    let mut new_flat_matrix_row_major_1d = vec![source[0]; col_count * row_count];
+    // band rows are source columns, each band row is row_count long
+    map_row_bands(
+        &mut new_flat_matrix_row_major_1d,
+        row_count,
+        thread_count,
+        |_, cols, band| {
+            for block_start_row_idx in (0..row_count).step_by(block_size) {
+                let row_end = (block_start_row_idx + block_size).min(row_count);
+                for block_start_col_idx in cols.clone().step_by(block_size) {
+                    let col_end = (block_start_col_idx + block_size).min(cols.end);
+                    for cell_row_idx in block_start_row_idx..row_end {
+                        for cell_col_idx in block_start_col_idx..col_end {
+                            band[(cell_col_idx - cols.start) * row_count + cell_row_idx] =
+                                source[cell_row_idx * col_count + cell_col_idx];
+                        }
+                    }
+                }
+            }
+        },
+    );
+    new_flat_matrix_row_major_1d
+}
+
+// Transpose on the work-stealing pool: the transposed matrix is cut into tile x tile tiles
+// and each tile gathers its cells from the matching column strip of the source
+pub fn transpose_1d_row_major_matrix_pooled<T: Copy + Send + Sync>(
+    flat_matrix_row_major_1d: Vec<T>,
+    col_count: usize,
+    tile: usize,
+    pool: &ThreadPool,
+) -> Vec<T> {
+    let row_count = flat_matrix_row_major_1d.len() / col_count.max(1);
+
+    if col_count < 1 || row_count < 1 {
+        panic!("Matrix must be a 2d matrix with at least 1 element.");
+    }
+
+    let source = &flat_matrix_row_major_1d;
+    let mut new_flat_matrix_row_major_1d = vec![source[0]; col_count * row_count];
+    // the target is row_count wide and col_count high
+    pool.par_for_each_tile_mut(
+        &mut new_flat_matrix_row_major_1d,
+        row_count,
+        col_count,
+        tile,
+        |mut view| {
+            let tile = view.tile();
+            for target_row_idx in tile.rows() {
+                for (source_row_idx, cell) in tile.cols().zip(view.row_mut(target_row_idx)) {
+                    *cell = source[source_row_idx * col_count + target_row_idx];
+                }
+            }
+        },
+    );
+    new_flat_matrix_row_major_1d
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cache_oblivious_matches_naive_transpose() {
+        for (row_count, col_count) in [(1, 1), (1, 40), (40, 1), (16, 16), (100, 37), (64, 256)] {
+            let flat: Vec<i32> = (0..(row_count * col_count) as i32).collect();
+            let expected = transpose_1d_row_major_matrix(flat.clone(), col_count);
+            assert_eq!(
+                transpose_1d_row_major_matrix_cache_oblivious(flat, col_count),
+                expected
+            );
+        }
+    }
+
+    #[test]
+    fn simd_matches_naive_transpose() {
+        for (row_count, col_count) in [(1, 1), (3, 40), (8, 8), (16, 24), (100, 37), (67, 9)] {
+            let flat: Vec<i32> = (0..(row_count * col_count) as i32).collect();
+            let expected = transpose_1d_row_major_matrix(flat.clone(), col_count);
+            assert_eq!(
+                transpose_1d_row_major_matrix_simd(flat, col_count),
+                expected
+            );
+        }
+    }
+
+    #[test]
+    fn parallel_matches_naive_transpose() {
+        for (row_count, col_count) in [(1, 1), (3, 40), (100, 37), (67, 9)] {
+            let flat: Vec<i32> = (0..(row_count * col_count) as i32).collect();
+            let expected = transpose_1d_row_major_matrix(flat.clone(), col_count);
+            for thread_count in [1, 2, 3, 8] {
+                let transposed = transpose_1d_row_major_matrix_parallel(
+                    flat.clone(),
+                    col_count,
+                    16,
+                    thread_count,
+                );
+                assert_eq!(transposed, expected);
+            }
+        }
+    }
+
+    #[test]
+    fn pooled_matches_naive_transpose() {
+        let pools = [ThreadPool::new(3), ThreadPool::deterministic()];
+        for (row_count, col_count) in [(1, 1), (3, 40), (100, 37)] {
+            let flat: Vec<i32> = (0..(row_count * col_count) as i32).collect();
+            let expected = transpose_1d_row_major_matrix(flat.clone(), col_count);
+            for pool in &pools {
+                let transposed =
+                    transpose_1d_row_major_matrix_pooled(flat.clone(), col_count, 8, pool);
+                assert_eq!(transposed, expected);
+            }
+        }
+    }
+}
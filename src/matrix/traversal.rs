@@ -0,0 +1,452 @@
+// Traversal orders as iterators: spiral (clockwise and counter-clockwise), JPEG zig-zag,
+// diagonals, snake (boustrophedon) and the border rings the spiral is made of.
+//
+// Every order is an iterator of (row, col) coordinates that only needs the shape, so the same
+// order works for Vec<Vec<T>> (traverse_2d_vec) and flat row-major storage
+// (traverse_1d_row_major), or anything else that can be indexed by row and column. The
+// iterators are lazy and know their exact length.
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Traversal {
+    // outer ring first, each ring starting at its top-left corner going right
+    SpiralClockwise,
+    // outer ring first, each ring starting at its top-left corner going down
+    SpiralCounterClockwise,
+    // JPEG coefficient order: anti-diagonals (row + col constant) alternating direction,
+    // (0, 0), (0, 1), (1, 0), (2, 0), (1, 1), (0, 2), ...
+    ZigZag,
+    // diagonals (col - row constant) from the bottom-left corner to the top-right one, each
+    // walked top-left to bottom-right
+    Diagonal,
+    // rows alternating left to right and right to left
+    Snake,
+}
+
+impl Traversal {
+    pub const ALL: [Traversal; 5] = [
+        Traversal::SpiralClockwise,
+        Traversal::SpiralCounterClockwise,
+        Traversal::ZigZag,
+        Traversal::Diagonal,
+        Traversal::Snake,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Traversal::SpiralClockwise => "spiral clockwise",
+            Traversal::SpiralCounterClockwise => "spiral counter-clockwise",
+            Traversal::ZigZag => "zig-zag",
+            Traversal::Diagonal => "diagonal",
+            Traversal::Snake => "snake",
+        }
+    }
+
+    pub fn coords(&self, row_count: usize, col_count: usize) -> TraversalIter {
+        let cell_count = row_count * col_count;
+        match self {
+            Traversal::SpiralClockwise => {
+                TraversalIter::Spiral(SpiralIter::new(row_count, col_count, true))
+            }
+            Traversal::SpiralCounterClockwise => {
+                TraversalIter::Spiral(SpiralIter::new(row_count, col_count, false))
+            }
+            Traversal::ZigZag | Traversal::Diagonal | Traversal::Snake => {
+                TraversalIter::Lines(LineIter {
+                    traversal: *self,
+                    row_count,
+                    col_count,
+                    line: 0,
+                    step: 0,
+                    remaining: cell_count,
+                })
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum TraversalIter {
+    Spiral(SpiralIter),
+    Lines(LineIter),
+}
+
+impl Iterator for TraversalIter {
+    type Item = (usize, usize);
+
+    fn next(&mut self) -> Option<(usize, usize)> {
+        match self {
+            TraversalIter::Spiral(iter) => iter.next(),
+            TraversalIter::Lines(iter) => iter.next(),
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        match self {
+            TraversalIter::Spiral(iter) => iter.size_hint(),
+            TraversalIter::Lines(iter) => iter.size_hint(),
+        }
+    }
+}
+
+impl ExactSizeIterator for TraversalIter {}
+
+// Walks along the current side until the next step would leave the bounds, then turns and
+// pulls in the bound of the side it just finished. Clockwise turns right, down, left, up;
+// counter-clockwise down, right, up, left.
+#[derive(Debug, Clone)]
+pub struct SpiralIter {
+    top: usize,
+    bottom: usize,
+    left: usize,
+    right: usize,
+    row: usize,
+    col: usize,
+    // 0..4 into the turn order
+    side: usize,
+    clockwise: bool,
+    remaining: usize,
+}
+
+impl SpiralIter {
+    fn new(row_count: usize, col_count: usize, clockwise: bool) -> SpiralIter {
+        SpiralIter {
+            top: 0,
+            bottom: row_count.saturating_sub(1),
+            left: 0,
+            right: col_count.saturating_sub(1),
+            row: 0,
+            col: 0,
+            side: 0,
+            clockwise,
+            remaining: row_count * col_count,
+        }
+    }
+
+    // Heading of the current side: 0 right, 1 down, 2 left, 3 up
+    fn direction(&self) -> usize {
+        if self.clockwise {
+            self.side
+        } else {
+            [1, 0, 3, 2][self.side]
+        }
+    }
+
+    fn try_step(&mut self) -> bool {
+        let (row, col) = (self.row, self.col);
+        let next = match self.direction() {
+            0 if col < self.right => (row, col + 1),
+            1 if row < self.bottom => (row + 1, col),
+            2 if col > self.left => (row, col - 1),
+            3 if row > self.top => (row - 1, col),
+            _ => return false,
+        };
+        (self.row, self.col) = next;
+        true
+    }
+
+    fn finish_side(&mut self) {
+        match self.direction() {
+            0 if self.clockwise => self.top += 1,
+            0 => self.bottom -= 1,
+            1 if self.clockwise => self.right -= 1,
+            1 => self.left += 1,
+            2 if self.clockwise => self.bottom -= 1,
+            2 => self.top += 1,
+            3 if self.clockwise => self.left += 1,
+            _ => self.right -= 1,
+        }
+        self.side = (self.side + 1) % 4;
+    }
+}
+
+impl Iterator for SpiralIter {
+    type Item = (usize, usize);
+
+    fn next(&mut self) -> Option<(usize, usize)> {
+        if self.remaining == 0 {
+            return None;
+        }
+        let current = (self.row, self.col);
+        self.remaining -= 1;
+        if self.remaining > 0 {
+            while !self.try_step() {
+                self.finish_side();
+            }
+        }
+        Some(current)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl ExactSizeIterator for SpiralIter {}
+
+// The orders made of straight lines: anti-diagonals, diagonals or rows, `step` cells into
+// line number `line`
+#[derive(Debug, Clone)]
+pub struct LineIter {
+    traversal: Traversal,
+    row_count: usize,
+    col_count: usize,
+    line: usize,
+    step: usize,
+    remaining: usize,
+}
+
+impl LineIter {
+    // First cell and length of the current line
+    fn line_cells(&self) -> ((usize, usize), usize) {
+        let (row_count, col_count, line) = (self.row_count, self.col_count, self.line);
+        match self.traversal {
+            Traversal::ZigZag => {
+                // row + col = line, rows first_row..=last_row
+                let first_row = line.saturating_sub(col_count - 1);
+                let last_row = line.min(row_count - 1);
+                let len = last_row - first_row + 1;
+                if line.is_multiple_of(2) {
+                    // up-right, starting from the bottom-left end
+                    ((last_row, line - last_row), len)
+                } else {
+                    ((first_row, line - first_row), len)
+                }
+            }
+            Traversal::Diagonal => {
+                // line 0 is the bottom-left corner, line row_count - 1 the main diagonal
+                let (start_row, start_col) = if line < row_count {
+                    (row_count - 1 - line, 0)
+                } else {
+                    (0, line + 1 - row_count)
+                };
+                let len = (row_count - start_row).min(col_count - start_col);
+                ((start_row, start_col), len)
+            }
+            _ => {
+                let start_col = if line.is_multiple_of(2) {
+                    0
+                } else {
+                    col_count - 1
+                };
+                ((line, start_col), col_count)
+            }
+        }
+    }
+}
+
+impl Iterator for LineIter {
+    type Item = (usize, usize);
+
+    fn next(&mut self) -> Option<(usize, usize)> {
+        if self.remaining == 0 {
+            return None;
+        }
+        let ((start_row, start_col), len) = self.line_cells();
+        let step = self.step;
+        let cell = match self.traversal {
+            Traversal::ZigZag if self.line.is_multiple_of(2) => {
+                (start_row - step, start_col + step)
+            }
+            Traversal::ZigZag => (start_row + step, start_col - step),
+            Traversal::Diagonal => (start_row + step, start_col + step),
+            _ if self.line.is_multiple_of(2) => (start_row, start_col + step),
+            _ => (start_row, start_col - step),
+        };
+        self.remaining -= 1;
+        self.step += 1;
+        if self.step == len {
+            self.line += 1;
+            self.step = 0;
+        }
+        Some(cell)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl ExactSizeIterator for LineIter {}
+
+pub fn border_ring_count(row_count: usize, col_count: usize) -> usize {
+    row_count.min(col_count).div_ceil(2)
+}
+
+// Ring 0 is the outer border, clockwise from its top-left corner. A ring that is a single row
+// or column (the middle of a non-square matrix) is just that line.
+pub fn border_ring_coords(row_count: usize, col_count: usize, ring: usize) -> SpiralIter {
+    if ring >= border_ring_count(row_count, col_count) {
+        panic!("Border ring is outside the matrix.");
+    }
+    let (top, bottom) = (ring, row_count - 1 - ring);
+    let (left, right) = (ring, col_count - 1 - ring);
+    let (height, width) = (bottom - top + 1, right - left + 1);
+    let cell_count = if height == 1 || width == 1 {
+        height * width
+    } else {
+        2 * (height + width) - 4
+    };
+    SpiralIter {
+        top,
+        bottom,
+        left,
+        right,
+        row: top,
+        col: left,
+        side: 0,
+        clockwise: true,
+        remaining: cell_count,
+    }
+}
+
+pub fn border_rings(row_count: usize, col_count: usize) -> impl Iterator<Item = SpiralIter> {
+    (0..border_ring_count(row_count, col_count))
+        .map(move |ring| border_ring_coords(row_count, col_count, ring))
+}
+
+fn shape_2d_vec<T>(matrix: &[Vec<T>]) -> (usize, usize) {
+    let col_count = matrix.first().map_or(0, |row| row.len());
+    if matrix.iter().any(|row| row.len() != col_count) {
+        panic!("Matrix rows do not all have the same length.");
+    }
+    (matrix.len(), col_count)
+}
+
+// Element references in the order of any coordinate iterator
+pub fn elements_2d_vec<'a, T>(
+    matrix: &'a [Vec<T>],
+    coords: impl Iterator<Item = (usize, usize)> + 'a,
+) -> impl Iterator<Item = &'a T> + 'a {
+    coords.map(move |(row, col)| &matrix[row][col])
+}
+
+pub fn elements_1d_row_major<'a, T>(
+    flat: &'a [T],
+    col_count: usize,
+    coords: impl Iterator<Item = (usize, usize)> + 'a,
+) -> impl Iterator<Item = &'a T> + 'a {
+    coords.map(move |(row, col)| &flat[row * col_count + col])
+}
+
+pub fn traverse_2d_vec<T>(matrix: &[Vec<T>], traversal: Traversal) -> impl Iterator<Item = &T> {
+    let (row_count, col_count) = shape_2d_vec(matrix);
+    elements_2d_vec(matrix, traversal.coords(row_count, col_count))
+}
+
+pub fn traverse_1d_row_major<T>(
+    flat: &[T],
+    col_count: usize,
+    traversal: Traversal,
+) -> impl Iterator<Item = &T> {
+    if col_count == 0 || !flat.len().is_multiple_of(col_count) {
+        panic!("Flat matrix length is not a multiple of the column count.");
+    }
+    elements_1d_row_major(
+        flat,
+        col_count,
+        traversal.coords(flat.len() / col_count, col_count),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::matrix::flatten::flatten_row_major;
+    use crate::matrix::make_matrix;
+
+    const SHAPES: [(usize, usize); 8] = [
+        (1, 1),
+        (1, 5),
+        (5, 1),
+        (2, 2),
+        (3, 4),
+        (4, 3),
+        (6, 6),
+        (5, 8),
+    ];
+
+    // cell numbers (row * col_count + col) of a 3x4 matrix in traversal order
+    fn order_3x4(traversal: Traversal) -> Vec<usize> {
+        traversal
+            .coords(3, 4)
+            .map(|(row, col)| row * 4 + col)
+            .collect()
+    }
+
+    #[test]
+    fn orders_on_a_3x4_matrix() {
+        //  0  1  2  3
+        //  4  5  6  7
+        //  8  9 10 11
+        assert_eq!(
+            order_3x4(Traversal::SpiralClockwise),
+            [0, 1, 2, 3, 7, 11, 10, 9, 8, 4, 5, 6]
+        );
+        assert_eq!(
+            order_3x4(Traversal::SpiralCounterClockwise),
+            [0, 4, 8, 9, 10, 11, 7, 3, 2, 1, 5, 6]
+        );
+        assert_eq!(
+            order_3x4(Traversal::ZigZag),
+            [0, 1, 4, 8, 5, 2, 3, 6, 9, 10, 7, 11]
+        );
+        assert_eq!(
+            order_3x4(Traversal::Diagonal),
+            [8, 4, 9, 0, 5, 10, 1, 6, 11, 2, 7, 3]
+        );
+        assert_eq!(
+            order_3x4(Traversal::Snake),
+            [0, 1, 2, 3, 7, 6, 5, 4, 8, 9, 10, 11]
+        );
+    }
+
+    #[test]
+    fn every_order_visits_every_cell_once() {
+        for traversal in Traversal::ALL {
+            for (row_count, col_count) in SHAPES {
+                let iter = traversal.coords(row_count, col_count);
+                assert_eq!(iter.len(), row_count * col_count);
+                let mut seen = vec![false; row_count * col_count];
+                for (row, col) in iter {
+                    assert!(
+                        !seen[row * col_count + col],
+                        "{} revisits {:?}",
+                        traversal.name(),
+                        (row, col)
+                    );
+                    seen[row * col_count + col] = true;
+                }
+                assert!(seen.iter().all(|&v| v), "{} misses cells", traversal.name());
+            }
+            assert_eq!(traversal.coords(0, 4).count(), 0);
+        }
+    }
+
+    #[test]
+    fn border_rings_make_up_the_clockwise_spiral() {
+        for (row_count, col_count) in SHAPES {
+            let rings: Vec<(usize, usize)> = border_rings(row_count, col_count).flatten().collect();
+            let spiral: Vec<(usize, usize)> = Traversal::SpiralClockwise
+                .coords(row_count, col_count)
+                .collect();
+            assert_eq!(rings, spiral);
+        }
+        assert_eq!(
+            border_ring_coords(4, 5, 1).collect::<Vec<_>>(),
+            [(1, 1), (1, 2), (1, 3), (2, 3), (2, 2), (2, 1)]
+        );
+    }
+
+    #[test]
+    fn both_layouts_yield_the_same_elements() {
+        let matrix = make_matrix(5, 7, 0);
+        let flat = flatten_row_major(&matrix);
+        for traversal in Traversal::ALL {
+            let from_2d_vec: Vec<i32> = traverse_2d_vec(&matrix, traversal).copied().collect();
+            let from_flat: Vec<i32> = traverse_1d_row_major(&flat, 7, traversal)
+                .copied()
+                .collect();
+            assert_eq!(from_2d_vec, from_flat);
+        }
+    }
+}
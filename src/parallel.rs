@@ -0,0 +1,159 @@
+// Scoped-thread helpers behind the *_parallel kernel variants.
+//
+// Every parallel kernel splits the rows of its output into one contiguous band per thread,
+// so each thread owns a disjoint &mut slice of the output and nothing needs a lock. The
+// threads are spawned with std::thread::scope, which lets them borrow the inputs and joins
+// them (re-raising any panic) before the kernel returns. The tile kernels use the
+// persistent work-stealing pool in pool.rs instead.
+pub mod pool;
+
+use std::env;
+use std::ops::Range;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::thread;
+
+pub const THREAD_COUNT_ENV: &str = "DSA_THREADS";
+
+// 0 until set_thread_count is called
+static THREAD_COUNT: AtomicUsize = AtomicUsize::new(0);
+
+// Overrides the thread count used by the benchmarks (the --threads option)
+pub fn set_thread_count(thread_count: usize) {
+    if thread_count < 1 {
+        panic!("Thread count must be at least 1.");
+    }
+    THREAD_COUNT.store(thread_count, Ordering::Relaxed);
+}
+
+// set_thread_count if called, otherwise $DSA_THREADS, otherwise the available parallelism
+pub fn thread_count() -> usize {
+    match THREAD_COUNT.load(Ordering::Relaxed) {
+        0 => env::var(THREAD_COUNT_ENV)
+            .ok()
+            .and_then(|value| value.parse().ok())
+            .filter(|&thread_count| thread_count > 0)
+            .unwrap_or_else(|| thread::available_parallelism().map_or(1, |count| count.get())),
+        thread_count => thread_count,
+    }
+}
+
+// 1, 2, 4, ... up to and including max_thread_count, for the speedup tables
+pub fn thread_counts_up_to(max_thread_count: usize) -> Vec<usize> {
+    let mut thread_counts: Vec<usize> = (0..)
+        .map(|shift| 1 << shift)
+        .take_while(|&thread_count| thread_count < max_thread_count)
+        .collect();
+    thread_counts.push(max_thread_count.max(1));
+    thread_counts
+}
+
+// Splits 0..row_count into at most thread_count bands whose lengths differ by at most one.
+// Never returns an empty band, so fewer rows than threads means fewer bands.
+pub fn row_bands(row_count: usize, thread_count: usize) -> Vec<Range<usize>> {
+    if thread_count < 1 {
+        panic!("Thread count must be at least 1.");
+    }
+    let band_count = thread_count.min(row_count);
+    let mut bands = Vec::with_capacity(band_count);
+    let mut row_start = 0;
+    for band_idx in 0..band_count {
+        // the first row_count % band_count bands get one extra row
+        let band_len = row_count / band_count + usize::from(band_idx < row_count % band_count);
+        bands.push(row_start..row_start + band_len);
+        row_start += band_len;
+    }
+    bands
+}
+
+// Runs band_fn(band_idx, rows, band) for every row band of output, where band is the
+// &mut slice holding those rows (row_len elements each). The first band runs on the calling
+// thread, the others on scoped threads. Returns the band results in band order.
+pub fn map_row_bands<T, R, F>(
+    output: &mut [T],
+    row_len: usize,
+    thread_count: usize,
+    band_fn: F,
+) -> Vec<R>
+where
+    T: Send,
+    R: Send,
+    F: Fn(usize, Range<usize>, &mut [T]) -> R + Sync,
+{
+    let row_count = output.len().checked_div(row_len).unwrap_or(0);
+    let bands = row_bands(row_count, thread_count);
+    let band_fn = &band_fn;
+    thread::scope(|scope| {
+        let mut rest = output;
+        let mut handles = Vec::with_capacity(bands.len());
+        let mut first = None;
+        for (band_idx, rows) in bands.into_iter().enumerate() {
+            let (band, tail) = rest.split_at_mut(rows.len() * row_len);
+            rest = tail;
+            if band_idx == 0 {
+                first = Some((rows, band));
+            } else {
+                handles.push(scope.spawn(move || band_fn(band_idx, rows, band)));
+            }
+        }
+        let mut results = Vec::with_capacity(handles.len() + 1);
+        if let Some((rows, band)) = first {
+            results.push(band_fn(0, rows, band));
+        }
+        for handle in handles {
+            match handle.join() {
+                Ok(result) => results.push(result),
+                Err(payload) => std::panic::resume_unwind(payload),
+            }
+        }
+        results
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn row_bands_cover_every_row_once() {
+        for (row_count, thread_count) in [(0, 4), (3, 8), (10, 3), (64, 4), (7, 1)] {
+            let bands = row_bands(row_count, thread_count);
+            assert!(bands.len() <= thread_count);
+            assert!(bands.iter().all(|band| !band.is_empty()));
+            let rows: Vec<usize> = bands.into_iter().flatten().collect();
+            assert_eq!(rows, (0..row_count).collect::<Vec<usize>>());
+        }
+        let lens: Vec<usize> = row_bands(10, 3).iter().map(|band| band.len()).collect();
+        assert_eq!(lens, vec![4, 3, 3]);
+    }
+
+    #[test]
+    fn map_row_bands_hands_out_disjoint_bands_in_order() {
+        let mut output = vec![0usize; 9 * 5];
+        let starts = map_row_bands(&mut output, 5, 4, |band_idx, rows, band| {
+            assert_eq!(band.len(), rows.len() * 5);
+            band.fill(band_idx + 1);
+            rows.start
+        });
+        assert_eq!(starts, vec![0, 3, 5, 7]);
+        assert_eq!(&output[..15], &[1; 15]);
+        assert_eq!(&output[40..], &[4; 5]);
+    }
+
+    #[test]
+    #[should_panic(expected = "band 2 failed")]
+    fn map_row_bands_propagates_panics() {
+        let mut output = vec![0; 4];
+        map_row_bands(&mut output, 1, 4, |band_idx, _, _| {
+            if band_idx == 2 {
+                panic!("band 2 failed");
+            }
+        });
+    }
+
+    #[test]
+    fn thread_counts_double_up_to_the_max() {
+        assert_eq!(thread_counts_up_to(1), vec![1]);
+        assert_eq!(thread_counts_up_to(4), vec![1, 2, 4]);
+        assert_eq!(thread_counts_up_to(6), vec![1, 2, 4, 6]);
+    }
+}
@@ -0,0 +1,457 @@
+// A small persistent work-stealing thread pool for tile-granular image kernels.
+//
+// map_row_bands spawns its scoped threads on every call, which costs more than a whole small
+// image takes to process. The pool keeps its workers around instead. Each worker owns a deque
+// of tiles: par_for_each_tile deals the tiles out to the deques in contiguous runs, a worker
+// pops from the front of its own deque (neighbouring tiles, warm cache) and once that is
+// empty steals from the back of the others. The calling thread steals too while it waits,
+// so a tile callback can itself call par_for_each_tile without deadlocking.
+use super::thread_count;
+use std::any::Any;
+use std::collections::VecDeque;
+use std::marker::PhantomData;
+use std::ops::Range;
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::{Arc, Condvar, Mutex, OnceLock};
+use std::thread::{self, JoinHandle};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Tile {
+    pub x: usize,
+    pub y: usize,
+    pub width: usize,
+    pub height: usize,
+}
+
+impl Tile {
+    pub fn rows(&self) -> Range<usize> {
+        self.y..self.y + self.height
+    }
+
+    pub fn cols(&self) -> Range<usize> {
+        self.x..self.x + self.width
+    }
+}
+
+// tile x tile squares covering width x height in row-major order, clipped at the right and
+// bottom edges
+pub fn tile_grid(width: usize, height: usize, tile: usize) -> Vec<Tile> {
+    if tile < 1 {
+        panic!("Tile size must be at least 1.");
+    }
+    let mut tiles = Vec::with_capacity(width.div_ceil(tile) * height.div_ceil(tile));
+    for y in (0..height).step_by(tile) {
+        for x in (0..width).step_by(tile) {
+            tiles.push(Tile {
+                x,
+                y,
+                width: tile.min(width - x),
+                height: tile.min(height - y),
+            });
+        }
+    }
+    tiles
+}
+
+type TileFn<'a> = dyn Fn(Tile) + Sync + 'a;
+
+// One par_for_each_tile call. tile_fn borrows from the caller's stack with the lifetime
+// erased: the caller does not return until `remaining` reaches zero, and no tile of the job
+// is left to call it after that.
+struct Job {
+    tile_fn: *const TileFn<'static>,
+    remaining: AtomicUsize,
+    panicked: AtomicBool,
+    panic_payload: Mutex<Option<Box<dyn Any + Send>>>,
+    done: Mutex<bool>,
+    done_cv: Condvar,
+}
+
+// tile_fn is Sync, and only dereferenced while the caller is blocked waiting for the job
+unsafe impl Send for Job {}
+unsafe impl Sync for Job {}
+
+impl Job {
+    fn run(&self, tile: Tile) {
+        // once a tile has panicked the rest of the job is skipped, only counted down
+        if !self.panicked.load(Ordering::Relaxed) {
+            let tile_fn = unsafe { &*self.tile_fn };
+            if let Err(payload) = panic::catch_unwind(AssertUnwindSafe(|| tile_fn(tile))) {
+                self.panicked.store(true, Ordering::Relaxed);
+                self.panic_payload.lock().unwrap().get_or_insert(payload);
+            }
+        }
+        if self.remaining.fetch_sub(1, Ordering::AcqRel) == 1 {
+            *self.done.lock().unwrap() = true;
+            self.done_cv.notify_all();
+        }
+    }
+}
+
+type Task = (Arc<Job>, Tile);
+
+struct PoolState {
+    // tasks pushed and not yet taken, counted up before they are pushed
+    pending: usize,
+    shutdown: bool,
+}
+
+struct Shared {
+    queues: Vec<Mutex<VecDeque<Task>>>,
+    state: Mutex<PoolState>,
+    work_cv: Condvar,
+}
+
+impl Shared {
+    // Own deque from the front, then steal from the back of the others, nearest first
+    fn take_task(&self, home: usize) -> Option<Task> {
+        let queue_count = self.queues.len();
+        let own = self.queues[home].lock().unwrap().pop_front();
+        let task = own.or_else(|| {
+            (1..queue_count).find_map(|offset| {
+                self.queues[(home + offset) % queue_count]
+                    .lock()
+                    .unwrap()
+                    .pop_back()
+            })
+        })?;
+        self.state.lock().unwrap().pending -= 1;
+        Some(task)
+    }
+}
+
+fn worker_loop(shared: Arc<Shared>, home: usize) {
+    loop {
+        if let Some((job, tile)) = shared.take_task(home) {
+            job.run(tile);
+            continue;
+        }
+        let state = shared.state.lock().unwrap();
+        // pending but nothing found: a push or a steal is half way done, look again
+        if state.pending > 0 {
+            continue;
+        }
+        // graceful: only leave once every queued tile has been taken
+        if state.shutdown {
+            return;
+        }
+        drop(shared.work_cv.wait(state).unwrap());
+    }
+}
+
+pub struct ThreadPool {
+    shared: Arc<Shared>,
+    workers: Vec<JoinHandle<()>>,
+    deterministic: bool,
+}
+
+impl ThreadPool {
+    pub fn new(worker_count: usize) -> ThreadPool {
+        if worker_count < 1 {
+            panic!("Thread pool needs at least 1 worker.");
+        }
+        let shared = Arc::new(Shared {
+            queues: (0..worker_count)
+                .map(|_| Mutex::new(VecDeque::new()))
+                .collect(),
+            state: Mutex::new(PoolState {
+                pending: 0,
+                shutdown: false,
+            }),
+            work_cv: Condvar::new(),
+        });
+        let workers = (0..worker_count)
+            .map(|home| {
+                let shared = Arc::clone(&shared);
+                thread::Builder::new()
+                    .name(format!("tile-worker-{}", home))
+                    .spawn(move || worker_loop(shared, home))
+                    .unwrap()
+            })
+            .collect();
+        ThreadPool {
+            shared,
+            workers,
+            deterministic: false,
+        }
+    }
+
+    // No workers: every tile runs on the calling thread in tile_grid order, for tests and
+    // for comparing against the parallel output step by step
+    pub fn deterministic() -> ThreadPool {
+        ThreadPool {
+            shared: Arc::new(Shared {
+                queues: Vec::new(),
+                state: Mutex::new(PoolState {
+                    pending: 0,
+                    shutdown: false,
+                }),
+                work_cv: Condvar::new(),
+            }),
+            workers: Vec::new(),
+            deterministic: true,
+        }
+    }
+
+    // Shared pool sized by crate::parallel::thread_count, started on first use
+    pub fn global() -> &'static ThreadPool {
+        static GLOBAL: OnceLock<ThreadPool> = OnceLock::new();
+        GLOBAL.get_or_init(|| ThreadPool::new(thread_count()))
+    }
+
+    pub fn worker_count(&self) -> usize {
+        self.workers.len()
+    }
+
+    pub fn is_deterministic(&self) -> bool {
+        self.deterministic
+    }
+
+    // Calls tile_fn once for every tile of tile_grid(width, height, tile) and returns when
+    // all of them are done. If any call panics the remaining tiles are skipped and the
+    // panic is resumed on the calling thread.
+    pub fn par_for_each_tile<F>(&self, width: usize, height: usize, tile: usize, tile_fn: F)
+    where
+        F: Fn(Tile) + Sync,
+    {
+        let tiles = tile_grid(width, height, tile);
+        if self.deterministic {
+            tiles.into_iter().for_each(tile_fn);
+            return;
+        }
+        if tiles.is_empty() {
+            return;
+        }
+
+        let tile_fn: &TileFn<'_> = &tile_fn;
+        // Safety: see Job, this function does not return before the job is done
+        let tile_fn: *const TileFn<'static> = unsafe { std::mem::transmute(tile_fn) };
+        let job = Arc::new(Job {
+            tile_fn,
+            remaining: AtomicUsize::new(tiles.len()),
+            panicked: AtomicBool::new(false),
+            panic_payload: Mutex::new(None),
+            done: Mutex::new(false),
+            done_cv: Condvar::new(),
+        });
+
+        self.shared.state.lock().unwrap().pending += tiles.len();
+        let run_len = tiles.len().div_ceil(self.shared.queues.len());
+        for (queue, run) in self.shared.queues.iter().zip(tiles.chunks(run_len)) {
+            let mut queue = queue.lock().unwrap();
+            queue.extend(run.iter().map(|&tile| (Arc::clone(&job), tile)));
+        }
+        self.shared.work_cv.notify_all();
+
+        // help out until there is nothing left to steal, then wait for the tiles in flight
+        while job.remaining.load(Ordering::Acquire) > 0 {
+            match self.shared.take_task(0) {
+                Some((task_job, tile)) => task_job.run(tile),
+                None => {
+                    let done = job.done.lock().unwrap();
+                    drop(job.done_cv.wait_while(done, |done| !*done).unwrap());
+                }
+            }
+        }
+
+        if let Some(payload) = job.panic_payload.lock().unwrap().take() {
+            panic::resume_unwind(payload);
+        }
+    }
+
+    // par_for_each_tile over a row-major width x height output, handing each call a view
+    // that can only write the tile's own pixels
+    pub fn par_for_each_tile_mut<T, F>(
+        &self,
+        output: &mut [T],
+        width: usize,
+        height: usize,
+        tile: usize,
+        tile_fn: F,
+    ) where
+        T: Send,
+        F: Fn(TileMut<'_, T>) + Sync,
+    {
+        if output.len() != width * height {
+            panic!("Output dimensions do not match the tile grid.");
+        }
+        let output = OutputPtr(output.as_mut_ptr());
+        self.par_for_each_tile(width, height, tile, |tile| {
+            tile_fn(TileMut {
+                data: output.get(),
+                width,
+                tile,
+                marker: PhantomData,
+            })
+        });
+    }
+
+    // Waits for the workers to drain the queues and exit (dropping the pool does the same)
+    pub fn shutdown(self) {}
+
+    fn shutdown_and_join(&mut self) {
+        self.shared.state.lock().unwrap().shutdown = true;
+        self.shared.work_cv.notify_all();
+        for worker in self.workers.drain(..) {
+            // tile panics are caught inside Job::run, so the workers themselves never panic
+            let _ = worker.join();
+        }
+    }
+}
+
+impl Drop for ThreadPool {
+    fn drop(&mut self) {
+        self.shutdown_and_join();
+    }
+}
+
+struct OutputPtr<T>(*mut T);
+
+impl<T> OutputPtr<T> {
+    // a method rather than field access so closures capture the Sync wrapper, not the pointer
+    fn get(&self) -> *mut T {
+        self.0
+    }
+}
+
+// Tiles never overlap, so every TileMut handed out points at different pixels
+unsafe impl<T: Send> Sync for OutputPtr<T> {}
+
+// Mutable access to one tile of the output passed to par_for_each_tile_mut
+pub struct TileMut<'a, T> {
+    data: *mut T,
+    width: usize,
+    tile: Tile,
+    marker: PhantomData<&'a mut [T]>,
+}
+
+impl<T> TileMut<'_, T> {
+    pub fn tile(&self) -> Tile {
+        self.tile
+    }
+
+    // The tile's part of output row `y` (y is a row of the whole output, not of the tile)
+    pub fn row_mut(&mut self, y: usize) -> &mut [T] {
+        if !self.tile.rows().contains(&y) {
+            panic!("Row {} is outside of the tile {:?}.", y, self.tile);
+        }
+        let start = y * self.width + self.tile.x;
+        unsafe { std::slice::from_raw_parts_mut(self.data.add(start), self.tile.width) }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tile_grid_clips_the_edges() {
+        let tiles = tile_grid(5, 3, 2);
+        assert_eq!(tiles.len(), 6);
+        assert_eq!(
+            tiles[2],
+            Tile {
+                x: 4,
+                y: 0,
+                width: 1,
+                height: 2
+            }
+        );
+        assert_eq!(
+            tiles[5],
+            Tile {
+                x: 4,
+                y: 2,
+                width: 1,
+                height: 1
+            }
+        );
+        let covered: usize = tiles.iter().map(|tile| tile.width * tile.height).sum();
+        assert_eq!(covered, 15);
+    }
+
+    #[test]
+    fn every_tile_runs_exactly_once() {
+        let pool = ThreadPool::new(3);
+        for (width, height, tile) in [(100, 37, 8), (1, 1, 4), (0, 10, 4), (64, 64, 64)] {
+            let counts: Vec<AtomicUsize> =
+                (0..width * height).map(|_| AtomicUsize::new(0)).collect();
+            pool.par_for_each_tile(width, height, tile, |tile| {
+                for y in tile.rows() {
+                    for x in tile.cols() {
+                        counts[y * width + x].fetch_add(1, Ordering::Relaxed);
+                    }
+                }
+            });
+            assert!(
+                counts
+                    .iter()
+                    .all(|count| count.load(Ordering::Relaxed) == 1)
+            );
+        }
+    }
+
+    #[test]
+    fn deterministic_mode_runs_in_grid_order() {
+        let pool = ThreadPool::deterministic();
+        let order = Mutex::new(Vec::new());
+        pool.par_for_each_tile(10, 7, 4, |tile| order.lock().unwrap().push(tile));
+        assert_eq!(order.into_inner().unwrap(), tile_grid(10, 7, 4));
+    }
+
+    #[test]
+    fn tile_mut_writes_land_in_place() {
+        let pool = ThreadPool::new(2);
+        let mut output = vec![0; 13 * 9];
+        pool.par_for_each_tile_mut(&mut output, 13, 9, 4, |mut view| {
+            for y in view.tile().rows() {
+                let x_start = view.tile().x;
+                for (x, pixel) in view.row_mut(y).iter_mut().enumerate() {
+                    *pixel = y * 13 + x_start + x;
+                }
+            }
+        });
+        assert_eq!(output, (0..13 * 9).collect::<Vec<usize>>());
+    }
+
+    #[test]
+    fn panics_reach_the_caller_and_the_pool_keeps_working() {
+        let pool = ThreadPool::new(2);
+        let result = panic::catch_unwind(AssertUnwindSafe(|| {
+            pool.par_for_each_tile(16, 16, 4, |tile| {
+                if tile.x == 8 && tile.y == 4 {
+                    panic!("bad tile");
+                }
+            })
+        }));
+        let payload = result.unwrap_err();
+        assert_eq!(payload.downcast_ref::<&str>(), Some(&"bad tile"));
+
+        let ran = AtomicUsize::new(0);
+        pool.par_for_each_tile(16, 16, 4, |_| {
+            ran.fetch_add(1, Ordering::Relaxed);
+        });
+        assert_eq!(ran.load(Ordering::Relaxed), 16);
+    }
+
+    #[test]
+    fn nested_calls_do_not_deadlock() {
+        let pool = ThreadPool::new(1);
+        let ran = AtomicUsize::new(0);
+        pool.par_for_each_tile(4, 4, 2, |_| {
+            pool.par_for_each_tile(4, 4, 2, |_| {
+                ran.fetch_add(1, Ordering::Relaxed);
+            });
+        });
+        assert_eq!(ran.load(Ordering::Relaxed), 16);
+    }
+
+    #[test]
+    fn shutdown_joins_the_workers() {
+        let pool = ThreadPool::new(4);
+        assert_eq!(pool.worker_count(), 4);
+        pool.par_for_each_tile(8, 8, 1, |_| {});
+        pool.shutdown();
+    }
+}
@@ -0,0 +1,4 @@
+// Range queries over arrays and matrices that keep working while the data changes.
+pub mod fenwick;
+pub mod segment_tree;
+pub mod sparse_table;
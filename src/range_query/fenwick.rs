@@ -0,0 +1,313 @@
+// Fenwick trees (binary indexed trees): prefix sums that survive point updates.
+//
+// make_prefix_sum_array and make_prefix_sum_matrix answer a range sum in O(1), but one changed
+// value means rebuilding all of it. A Fenwick tree stores, at 1-based index i, the sum of the
+// lowbit(i) = i & -i values ending at i. A prefix sum walks i -= lowbit(i) and an update walks
+// i += lowbit(i), both touching O(log n) nodes. The 2D tree nests the same walk over rows and
+// columns, so point update and rectangle sum are O(log n · log m).
+//
+// The queries mirror the static versions: range_sum(l, r) is range_sum_prefix_sum_arr and
+// sub_matrix_sum(l_row, l_col, r_row, r_col) is sub_matrix_sum_using_prefix_matrix, all
+// bounds inclusive.
+
+fn lowbit(idx: usize) -> usize {
+    idx & idx.wrapping_neg()
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FenwickTree {
+    // tree[0] is unused so the lowbit walk can stop at 0
+    tree: Vec<i32>,
+    values: Vec<i32>,
+}
+
+impl FenwickTree {
+    pub fn new(len: usize) -> Self {
+        FenwickTree {
+            tree: vec![0; len + 1],
+            values: vec![0; len],
+        }
+    }
+
+    // O(n): every node pushes its total onto its parent once instead of n O(log n) adds
+    pub fn from_slice(arr: &[i32]) -> Self {
+        let mut tree = vec![0; arr.len() + 1];
+        tree[1..].copy_from_slice(arr);
+        for idx in 1..tree.len() {
+            let parent = idx + lowbit(idx);
+            if parent < tree.len() {
+                tree[parent] += tree[idx];
+            }
+        }
+        FenwickTree {
+            tree,
+            values: arr.to_vec(),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    pub fn get(&self, idx: usize) -> i32 {
+        self.values[idx]
+    }
+
+    pub fn add(&mut self, idx: usize, delta: i32) {
+        self.values[idx] += delta;
+        let mut node = idx + 1;
+        while node < self.tree.len() {
+            self.tree[node] += delta;
+            node += lowbit(node);
+        }
+    }
+
+    pub fn set(&mut self, idx: usize, value: i32) {
+        self.add(idx, value - self.values[idx]);
+    }
+
+    // Sum of arr[0..=idx], what prefix_arr[idx] would hold
+    pub fn prefix_sum(&self, idx: usize) -> i32 {
+        if idx >= self.len() {
+            panic!("Prefix sum index is outside the array.");
+        }
+        let mut total = 0;
+        let mut node = idx + 1;
+        while node > 0 {
+            total += self.tree[node];
+            node -= lowbit(node);
+        }
+        total
+    }
+
+    // Sum of arr[l..=r]
+    pub fn range_sum(&self, l: usize, r: usize) -> i32 {
+        if l == 0 {
+            self.prefix_sum(r)
+        } else {
+            self.prefix_sum(r) - self.prefix_sum(l - 1)
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FenwickTree2d {
+    row_count: usize,
+    col_count: usize,
+    // (row_count + 1) x (col_count + 1) flat row-major, row 0 and column 0 unused
+    tree: Vec<i32>,
+    values: Vec<Vec<i32>>,
+}
+
+impl FenwickTree2d {
+    pub fn new(row_count: usize, col_count: usize) -> Self {
+        FenwickTree2d {
+            row_count,
+            col_count,
+            tree: vec![0; (row_count + 1) * (col_count + 1)],
+            values: vec![vec![0; col_count]; row_count],
+        }
+    }
+
+    // The 1D O(n) build along every row, then along every column of the result
+    pub fn from_matrix(matrix: &[Vec<i32>]) -> Self {
+        let row_count = matrix.len();
+        let col_count = matrix.first().map_or(0, |row| row.len());
+        if matrix.iter().any(|row| row.len() != col_count) {
+            panic!("Matrix rows do not all have the same length.");
+        }
+        let width = col_count + 1;
+        let mut tree = vec![0; (row_count + 1) * width];
+        for (row_idx, row) in matrix.iter().enumerate() {
+            tree[(row_idx + 1) * width + 1..(row_idx + 2) * width].copy_from_slice(row);
+        }
+        for row in 1..=row_count {
+            for col in 1..=col_count {
+                let parent = col + lowbit(col);
+                if parent <= col_count {
+                    tree[row * width + parent] += tree[row * width + col];
+                }
+            }
+        }
+        for row in 1..=row_count {
+            let parent = row + lowbit(row);
+            if parent <= row_count {
+                for col in 1..=col_count {
+                    tree[parent * width + col] += tree[row * width + col];
+                }
+            }
+        }
+        FenwickTree2d {
+            row_count,
+            col_count,
+            tree,
+            values: matrix.to_vec(),
+        }
+    }
+
+    pub fn row_count(&self) -> usize {
+        self.row_count
+    }
+
+    pub fn col_count(&self) -> usize {
+        self.col_count
+    }
+
+    pub fn get(&self, row: usize, col: usize) -> i32 {
+        self.values[row][col]
+    }
+
+    pub fn add(&mut self, row: usize, col: usize, delta: i32) {
+        self.values[row][col] += delta;
+        let width = self.col_count + 1;
+        let mut row_node = row + 1;
+        while row_node <= self.row_count {
+            let mut col_node = col + 1;
+            while col_node <= self.col_count {
+                self.tree[row_node * width + col_node] += delta;
+                col_node += lowbit(col_node);
+            }
+            row_node += lowbit(row_node);
+        }
+    }
+
+    pub fn set(&mut self, row: usize, col: usize, value: i32) {
+        self.add(row, col, value - self.values[row][col]);
+    }
+
+    // Sum of the rectangle (0, 0)..=(row, col), what prefix_matrix[row][col] would hold
+    pub fn prefix_sum(&self, row: usize, col: usize) -> i32 {
+        if row >= self.row_count || col >= self.col_count {
+            panic!("Prefix sum cell is outside the matrix.");
+        }
+        let width = self.col_count + 1;
+        let mut total = 0;
+        let mut row_node = row + 1;
+        while row_node > 0 {
+            let mut col_node = col + 1;
+            while col_node > 0 {
+                total += self.tree[row_node * width + col_node];
+                col_node -= lowbit(col_node);
+            }
+            row_node -= lowbit(row_node);
+        }
+        total
+    }
+
+    // Same inclusion-exclusion as sub_matrix_sum_using_prefix_matrix
+    pub fn sub_matrix_sum(&self, l_row: usize, l_col: usize, r_row: usize, r_col: usize) -> i32 {
+        let mut total = self.prefix_sum(r_row, r_col);
+        if l_row > 0 {
+            total -= self.prefix_sum(l_row - 1, r_col);
+        }
+        if l_col > 0 {
+            total -= self.prefix_sum(r_row, l_col - 1);
+        }
+        if l_row > 0 && l_col > 0 {
+            total += self.prefix_sum(l_row - 1, l_col - 1);
+        }
+        total
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::array::prefix_sum::{make_prefix_sum_array, range_sum_prefix_sum_arr};
+    use crate::bench::data::XorShift64;
+    use crate::matrix::prefix_sum::{make_prefix_sum_matrix, sub_matrix_sum_using_prefix_matrix};
+
+    fn random_value(rng: &mut XorShift64) -> i32 {
+        (rng.next_u64() % 201) as i32 - 100
+    }
+
+    fn random_range(rng: &mut XorShift64, len: usize) -> (usize, usize) {
+        let a = rng.next_u64() as usize % len;
+        let b = rng.next_u64() as usize % len;
+        (a.min(b), a.max(b))
+    }
+
+    #[test]
+    fn from_slice_matches_adding_one_at_a_time() {
+        let arr: Vec<i32> = (1..=37).collect();
+        let mut added = FenwickTree::new(arr.len());
+        for (idx, &value) in arr.iter().enumerate() {
+            added.add(idx, value);
+        }
+        assert_eq!(FenwickTree::from_slice(&arr), added);
+        assert!(FenwickTree::from_slice(&[]).is_empty());
+    }
+
+    #[test]
+    fn random_updates_match_a_rebuilt_prefix_array() {
+        let mut rng = XorShift64::new(43);
+        for len in [1, 2, 7, 64, 100] {
+            let mut arr: Vec<i32> = (0..len).map(|_| random_value(&mut rng)).collect();
+            let mut tree = FenwickTree::from_slice(&arr);
+            for step in 0..200 {
+                let idx = rng.next_u64() as usize % len;
+                let value = random_value(&mut rng);
+                if step % 2 == 0 {
+                    tree.set(idx, value);
+                    arr[idx] = value;
+                } else {
+                    tree.add(idx, value);
+                    arr[idx] += value;
+                }
+                let prefix_arr = make_prefix_sum_array(&arr);
+                let (l, r) = random_range(&mut rng, len);
+                assert_eq!(
+                    tree.range_sum(l, r),
+                    range_sum_prefix_sum_arr(&prefix_arr, l, r)
+                );
+                assert_eq!(tree.get(idx), arr[idx]);
+            }
+        }
+    }
+
+    #[test]
+    fn from_matrix_matches_adding_one_at_a_time() {
+        let matrix: Vec<Vec<i32>> = (0..9)
+            .map(|row| (0..13).map(|col| row * 13 + col).collect())
+            .collect();
+        let mut added = FenwickTree2d::new(9, 13);
+        for (row_idx, row) in matrix.iter().enumerate() {
+            for (col_idx, &value) in row.iter().enumerate() {
+                added.add(row_idx, col_idx, value);
+            }
+        }
+        assert_eq!(FenwickTree2d::from_matrix(&matrix), added);
+    }
+
+    #[test]
+    fn random_updates_match_a_rebuilt_prefix_matrix() {
+        let mut rng = XorShift64::new(7);
+        for (row_count, col_count) in [(1, 1), (1, 9), (8, 1), (6, 11), (16, 16)] {
+            let mut matrix: Vec<Vec<i32>> = (0..row_count)
+                .map(|_| (0..col_count).map(|_| random_value(&mut rng)).collect())
+                .collect();
+            let mut tree = FenwickTree2d::from_matrix(&matrix);
+            for _ in 0..200 {
+                let (row, col) = (
+                    rng.next_u64() as usize % row_count,
+                    rng.next_u64() as usize % col_count,
+                );
+                let value = random_value(&mut rng);
+                tree.set(row, col, value);
+                matrix[row][col] = value;
+
+                let prefix_matrix = make_prefix_sum_matrix(&matrix);
+                let (l_row, r_row) = random_range(&mut rng, row_count);
+                let (l_col, r_col) = random_range(&mut rng, col_count);
+                assert_eq!(
+                    tree.sub_matrix_sum(l_row, l_col, r_row, r_col),
+                    sub_matrix_sum_using_prefix_matrix(&prefix_matrix, l_row, l_col, r_row, r_col)
+                );
+            }
+        }
+    }
+}
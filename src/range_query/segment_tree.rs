@@ -0,0 +1,525 @@
+// Segment trees: range queries for any associative operation, with updates.
+//
+// SegmentTree is the bottom-up array form: leaves at tree[n..2n], node i combines nodes 2i and
+// 2i + 1. A query climbs from both ends of the range, keeping a left and a right accumulator
+// so a non-commutative op (matrix product, string concat) still combines in order. Point
+// updates and queries are O(log n), and unlike the sparse table op doesn't need to be
+// idempotent, only associative with an identity.
+//
+// LazySegmentTree is the recursive form with pending updates parked on the highest node they
+// cover and pushed to the children only when a later call has to go below it. That makes
+// range add and range assign O(log n) too. It keeps sum, min and max per node, so one tree
+// answers all three.
+//
+// SegmentTree2d nests the bottom-up tree: a tree over rows whose nodes are column trees.
+// Point update and rectangle query are O(log n · log m). Lazy range updates don't carry
+// over to 2D (a pending row update can't be pushed down column trees cheaply), so it only
+// takes point updates.
+
+#[derive(Debug, Clone)]
+pub struct SegmentTree<T> {
+    len: usize,
+    identity: T,
+    op: fn(T, T) -> T,
+    tree: Vec<T>,
+}
+
+impl<T: Copy> SegmentTree<T> {
+    pub fn new(arr: &[T], identity: T, op: fn(T, T) -> T) -> Self {
+        let len = arr.len();
+        let mut tree = vec![identity; 2 * len];
+        tree[len..].copy_from_slice(arr);
+        for node in (1..len).rev() {
+            tree[node] = op(tree[2 * node], tree[2 * node + 1]);
+        }
+        SegmentTree {
+            len,
+            identity,
+            op,
+            tree,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn get(&self, idx: usize) -> T {
+        self.tree[self.len + idx]
+    }
+
+    pub fn set(&mut self, idx: usize, value: T) {
+        let mut node = self.len + idx;
+        self.tree[node] = value;
+        while node > 1 {
+            node /= 2;
+            self.tree[node] = (self.op)(self.tree[2 * node], self.tree[2 * node + 1]);
+        }
+    }
+
+    // op over arr[l..=r], in order
+    pub fn query(&self, l: usize, r: usize) -> T {
+        if l > r || r >= self.len {
+            panic!("Range query bounds are outside the array.");
+        }
+        let (mut left_acc, mut right_acc) = (self.identity, self.identity);
+        let (mut lo, mut hi) = (l + self.len, r + self.len + 1);
+        while lo < hi {
+            if lo % 2 == 1 {
+                left_acc = (self.op)(left_acc, self.tree[lo]);
+                lo += 1;
+            }
+            if hi % 2 == 1 {
+                hi -= 1;
+                right_acc = (self.op)(self.tree[hi], right_acc);
+            }
+            lo /= 2;
+            hi /= 2;
+        }
+        (self.op)(left_acc, right_acc)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct RangeSummary {
+    pub sum: i64,
+    pub min: i64,
+    pub max: i64,
+}
+
+impl RangeSummary {
+    fn combine(self, other: RangeSummary) -> RangeSummary {
+        RangeSummary {
+            sum: self.sum + other.sum,
+            min: self.min.min(other.min),
+            max: self.max.max(other.max),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+enum RangeUpdate {
+    Add(i64),
+    Assign(i64),
+}
+
+#[derive(Debug, Clone)]
+pub struct LazySegmentTree {
+    len: usize,
+    summaries: Vec<RangeSummary>,
+    // pending updates for the children of a node, already applied to the node itself. An
+    // assign followed by an add is folded into one assign, so one slot is enough.
+    pending: Vec<Option<RangeUpdate>>,
+}
+
+impl LazySegmentTree {
+    pub fn new(arr: &[i64]) -> Self {
+        let mut tree = LazySegmentTree {
+            len: arr.len(),
+            summaries: vec![RangeSummary::default(); 4 * arr.len().max(1)],
+            pending: vec![None; 4 * arr.len().max(1)],
+        };
+        if !arr.is_empty() {
+            tree.build(1, 0, arr.len() - 1, arr);
+        }
+        tree
+    }
+
+    fn build(&mut self, node: usize, node_l: usize, node_r: usize, arr: &[i64]) {
+        if node_l == node_r {
+            let value = arr[node_l];
+            self.summaries[node] = RangeSummary {
+                sum: value,
+                min: value,
+                max: value,
+            };
+            return;
+        }
+        let mid = (node_l + node_r) / 2;
+        self.build(2 * node, node_l, mid, arr);
+        self.build(2 * node + 1, mid + 1, node_r, arr);
+        self.summaries[node] = self.summaries[2 * node].combine(self.summaries[2 * node + 1]);
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    fn apply(&mut self, node: usize, node_len: usize, update: RangeUpdate) {
+        let summary = &mut self.summaries[node];
+        match update {
+            RangeUpdate::Add(delta) => {
+                summary.sum += delta * node_len as i64;
+                summary.min += delta;
+                summary.max += delta;
+                self.pending[node] = match self.pending[node] {
+                    None => Some(RangeUpdate::Add(delta)),
+                    Some(RangeUpdate::Add(previous)) => Some(RangeUpdate::Add(previous + delta)),
+                    Some(RangeUpdate::Assign(value)) => Some(RangeUpdate::Assign(value + delta)),
+                };
+            }
+            RangeUpdate::Assign(value) => {
+                *summary = RangeSummary {
+                    sum: value * node_len as i64,
+                    min: value,
+                    max: value,
+                };
+                self.pending[node] = Some(RangeUpdate::Assign(value));
+            }
+        }
+    }
+
+    fn push_down(&mut self, node: usize, node_l: usize, node_r: usize) {
+        if let Some(update) = self.pending[node].take() {
+            let mid = (node_l + node_r) / 2;
+            self.apply(2 * node, mid + 1 - node_l, update);
+            self.apply(2 * node + 1, node_r - mid, update);
+        }
+    }
+
+    fn update(
+        &mut self,
+        node: usize,
+        node_l: usize,
+        node_r: usize,
+        l: usize,
+        r: usize,
+        update: RangeUpdate,
+    ) {
+        if r < node_l || node_r < l {
+            return;
+        }
+        if l <= node_l && node_r <= r {
+            self.apply(node, node_r + 1 - node_l, update);
+            return;
+        }
+        self.push_down(node, node_l, node_r);
+        let mid = (node_l + node_r) / 2;
+        self.update(2 * node, node_l, mid, l, r, update);
+        self.update(2 * node + 1, mid + 1, node_r, l, r, update);
+        self.summaries[node] = self.summaries[2 * node].combine(self.summaries[2 * node + 1]);
+    }
+
+    fn query(
+        &mut self,
+        node: usize,
+        node_l: usize,
+        node_r: usize,
+        l: usize,
+        r: usize,
+    ) -> Option<RangeSummary> {
+        if r < node_l || node_r < l {
+            return None;
+        }
+        if l <= node_l && node_r <= r {
+            return Some(self.summaries[node]);
+        }
+        self.push_down(node, node_l, node_r);
+        let mid = (node_l + node_r) / 2;
+        match (
+            self.query(2 * node, node_l, mid, l, r),
+            self.query(2 * node + 1, mid + 1, node_r, l, r),
+        ) {
+            (Some(left), Some(right)) => Some(left.combine(right)),
+            (left, right) => left.or(right),
+        }
+    }
+
+    fn check_range(&self, l: usize, r: usize) {
+        if l > r || r >= self.len {
+            panic!("Range bounds are outside the array.");
+        }
+    }
+
+    // arr[l..=r] += delta
+    pub fn range_add(&mut self, l: usize, r: usize, delta: i64) {
+        self.check_range(l, r);
+        self.update(1, 0, self.len - 1, l, r, RangeUpdate::Add(delta));
+    }
+
+    // arr[l..=r] = value
+    pub fn range_assign(&mut self, l: usize, r: usize, value: i64) {
+        self.check_range(l, r);
+        self.update(1, 0, self.len - 1, l, r, RangeUpdate::Assign(value));
+    }
+
+    // Sum, min and max of arr[l..=r] in one walk. Takes &mut self since the walk pushes
+    // pending updates down on its way.
+    pub fn range_summary(&mut self, l: usize, r: usize) -> RangeSummary {
+        self.check_range(l, r);
+        self.query(1, 0, self.len - 1, l, r).unwrap()
+    }
+
+    pub fn range_sum(&mut self, l: usize, r: usize) -> i64 {
+        self.range_summary(l, r).sum
+    }
+
+    pub fn range_min(&mut self, l: usize, r: usize) -> i64 {
+        self.range_summary(l, r).min
+    }
+
+    pub fn range_max(&mut self, l: usize, r: usize) -> i64 {
+        self.range_summary(l, r).max
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct SegmentTree2d<T> {
+    row_count: usize,
+    col_count: usize,
+    identity: T,
+    // should be commutative as well, a rectangle has no single order to combine in
+    op: fn(T, T) -> T,
+    // 2·row_count x 2·col_count flat row-major: row node i, column node j
+    tree: Vec<T>,
+}
+
+impl<T: Copy> SegmentTree2d<T> {
+    pub fn new(matrix: &[Vec<T>], identity: T, op: fn(T, T) -> T) -> Self {
+        let row_count = matrix.len();
+        let col_count = matrix.first().map_or(0, |row| row.len());
+        if matrix.iter().any(|row| row.len() != col_count) {
+            panic!("Matrix rows do not all have the same length.");
+        }
+        let width = 2 * col_count;
+        let mut tree = vec![identity; 2 * row_count * width];
+        // column trees of the leaf rows
+        for (row_idx, row) in matrix.iter().enumerate() {
+            let tree_row =
+                &mut tree[(row_count + row_idx) * width..(row_count + row_idx + 1) * width];
+            tree_row[col_count..].copy_from_slice(row);
+            for col_node in (1..col_count).rev() {
+                tree_row[col_node] = op(tree_row[2 * col_node], tree_row[2 * col_node + 1]);
+            }
+        }
+        // every column node of an inner row node combines the same column node of its children
+        for row_node in (1..row_count).rev() {
+            for col_node in 1..width {
+                tree[row_node * width + col_node] = op(
+                    tree[2 * row_node * width + col_node],
+                    tree[(2 * row_node + 1) * width + col_node],
+                );
+            }
+        }
+        SegmentTree2d {
+            row_count,
+            col_count,
+            identity,
+            op,
+            tree,
+        }
+    }
+
+    pub fn row_count(&self) -> usize {
+        self.row_count
+    }
+
+    pub fn col_count(&self) -> usize {
+        self.col_count
+    }
+
+    pub fn get(&self, row: usize, col: usize) -> T {
+        self.tree[(self.row_count + row) * 2 * self.col_count + self.col_count + col]
+    }
+
+    pub fn set(&mut self, row: usize, col: usize, value: T) {
+        let width = 2 * self.col_count;
+        let op = self.op;
+        let mut row_node = self.row_count + row;
+        let leaf_col = self.col_count + col;
+        // the leaf row's column tree
+        self.tree[row_node * width + leaf_col] = value;
+        let mut col_node = leaf_col;
+        while col_node > 1 {
+            col_node /= 2;
+            self.tree[row_node * width + col_node] = op(
+                self.tree[row_node * width + 2 * col_node],
+                self.tree[row_node * width + 2 * col_node + 1],
+            );
+        }
+        // then the same column path in every ancestor row node
+        while row_node > 1 {
+            row_node /= 2;
+            let mut col_node = leaf_col;
+            while col_node >= 1 {
+                self.tree[row_node * width + col_node] = op(
+                    self.tree[2 * row_node * width + col_node],
+                    self.tree[(2 * row_node + 1) * width + col_node],
+                );
+                col_node /= 2;
+            }
+        }
+    }
+
+    fn query_row_node(&self, row_node: usize, l_col: usize, r_col: usize) -> T {
+        let tree_row =
+            &self.tree[row_node * 2 * self.col_count..(row_node + 1) * 2 * self.col_count];
+        let mut acc = self.identity;
+        let (mut lo, mut hi) = (l_col + self.col_count, r_col + self.col_count + 1);
+        while lo < hi {
+            if lo % 2 == 1 {
+                acc = (self.op)(acc, tree_row[lo]);
+                lo += 1;
+            }
+            if hi % 2 == 1 {
+                hi -= 1;
+                acc = (self.op)(acc, tree_row[hi]);
+            }
+            lo /= 2;
+            hi /= 2;
+        }
+        acc
+    }
+
+    // op over the rectangle (l_row, l_col)..=(r_row, r_col)
+    pub fn query(&self, l_row: usize, l_col: usize, r_row: usize, r_col: usize) -> T {
+        if l_row > r_row || l_col > r_col || r_row >= self.row_count || r_col >= self.col_count {
+            panic!("Range query bounds are outside the matrix.");
+        }
+        let mut acc = self.identity;
+        let (mut lo, mut hi) = (l_row + self.row_count, r_row + self.row_count + 1);
+        while lo < hi {
+            if lo % 2 == 1 {
+                acc = (self.op)(acc, self.query_row_node(lo, l_col, r_col));
+                lo += 1;
+            }
+            if hi % 2 == 1 {
+                hi -= 1;
+                acc = (self.op)(acc, self.query_row_node(hi, l_col, r_col));
+            }
+            lo /= 2;
+            hi /= 2;
+        }
+        acc
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bench::data::XorShift64;
+    use std::cmp::{max, min};
+
+    fn random_range(rng: &mut XorShift64, len: usize) -> (usize, usize) {
+        let a = rng.next_u64() as usize % len;
+        let b = rng.next_u64() as usize % len;
+        (a.min(b), a.max(b))
+    }
+
+    #[test]
+    fn non_commutative_op_keeps_the_order() {
+        // 2x2 matrix products, [a, b, c, d] row-major
+        let mul = |x: [i64; 4], y: [i64; 4]| {
+            [
+                x[0] * y[0] + x[1] * y[2],
+                x[0] * y[1] + x[1] * y[3],
+                x[2] * y[0] + x[3] * y[2],
+                x[2] * y[1] + x[3] * y[3],
+            ]
+        };
+        let arr: Vec<[i64; 4]> = (0..13).map(|v| [1, v % 3, (v % 2) - 1, 1]).collect();
+        let mut tree = SegmentTree::new(&arr, [1, 0, 0, 1], mul);
+        tree.set(4, [2, 1, 0, 1]);
+        let mut expected_arr = arr.clone();
+        expected_arr[4] = [2, 1, 0, 1];
+        for l in 0..arr.len() {
+            for r in l..arr.len() {
+                let expected = expected_arr[l..=r]
+                    .iter()
+                    .fold([1, 0, 0, 1], |acc, &m| mul(acc, m));
+                assert_eq!(tree.query(l, r), expected);
+            }
+        }
+    }
+
+    #[test]
+    fn lazy_updates_match_a_plain_array() {
+        let mut rng = XorShift64::new(12);
+        for len in [1, 2, 5, 16, 37] {
+            let mut arr: Vec<i64> = (0..len)
+                .map(|_| (rng.next_u64() % 100) as i64 - 50)
+                .collect();
+            let mut tree = LazySegmentTree::new(&arr);
+            for step in 0..300 {
+                let (l, r) = random_range(&mut rng, len);
+                let value = (rng.next_u64() % 100) as i64 - 50;
+                match step % 3 {
+                    0 => {
+                        tree.range_add(l, r, value);
+                        arr[l..=r].iter_mut().for_each(|v| *v += value);
+                    }
+                    1 => {
+                        tree.range_assign(l, r, value);
+                        arr[l..=r].iter_mut().for_each(|v| *v = value);
+                    }
+                    _ => {}
+                }
+                let (l, r) = random_range(&mut rng, len);
+                let window = &arr[l..=r];
+                assert_eq!(
+                    tree.range_summary(l, r),
+                    RangeSummary {
+                        sum: window.iter().sum(),
+                        min: *window.iter().min().unwrap(),
+                        max: *window.iter().max().unwrap(),
+                    }
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn region_queries_follow_point_updates() {
+        let mut rng = XorShift64::new(5);
+        for (row_count, col_count) in [(1, 1), (1, 6), (7, 1), (5, 9), (16, 11)] {
+            let mut matrix: Vec<Vec<i32>> = (0..row_count)
+                .map(|_| {
+                    (0..col_count)
+                        .map(|_| (rng.next_u64() % 256) as i32)
+                        .collect()
+                })
+                .collect();
+            let mut max_tree = SegmentTree2d::new(&matrix, i32::MIN, max);
+            let mut sum_tree = SegmentTree2d::new(&matrix, 0, |a, b| a + b);
+            let mut min_tree = SegmentTree2d::new(&matrix, i32::MAX, min);
+            for _ in 0..200 {
+                let (row, col) = (
+                    rng.next_u64() as usize % row_count,
+                    rng.next_u64() as usize % col_count,
+                );
+                let value = (rng.next_u64() % 256) as i32;
+                matrix[row][col] = value;
+                max_tree.set(row, col, value);
+                sum_tree.set(row, col, value);
+                min_tree.set(row, col, value);
+                assert_eq!(max_tree.get(row, col), value);
+
+                let (l_row, r_row) = random_range(&mut rng, row_count);
+                let (l_col, r_col) = random_range(&mut rng, col_count);
+                let region = matrix[l_row..=r_row]
+                    .iter()
+                    .flat_map(|row| &row[l_col..=r_col]);
+                assert_eq!(
+                    max_tree.query(l_row, l_col, r_row, r_col),
+                    *region.clone().max().unwrap()
+                );
+                assert_eq!(
+                    min_tree.query(l_row, l_col, r_row, r_col),
+                    *region.clone().min().unwrap()
+                );
+                assert_eq!(
+                    sum_tree.query(l_row, l_col, r_row, r_col),
+                    region.sum::<i32>()
+                );
+            }
+        }
+    }
+}
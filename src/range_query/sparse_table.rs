@@ -0,0 +1,233 @@
+// Sparse tables: O(1) range queries for idempotent operations (min, max, gcd, bitwise and/or).
+//
+// Level k holds op over every window of 2^k values, built from two windows of level k - 1,
+// so the table costs O(n log n) to build and store. Any range l..=r is covered by the two
+// (overlapping) level k windows starting at l and ending at r, where 2^k is the largest
+// power of two that fits. Counting the overlap twice is what limits this to idempotent ops:
+// min(x, x) = x, but x + x != x, so sums belong in a Fenwick or segment tree instead.
+//
+// The 2D table does the same along both axes, a rectangle is covered by four overlapping
+// windows. It needs O(n·m·log n·log m) memory, which is fine for region min/max over
+// tiles and small images but not for whole 4K frames.
+//
+// The data is static, any change means building the table again.
+
+// floor(log2(len)) for len >= 1
+fn floor_log2(len: usize) -> usize {
+    (usize::BITS - 1 - len.leading_zeros()) as usize
+}
+
+pub fn gcd(a: u64, b: u64) -> u64 {
+    if b == 0 { a } else { gcd(b, a % b) }
+}
+
+#[derive(Debug, Clone)]
+pub struct SparseTable<T> {
+    op: fn(T, T) -> T,
+    // levels[k][i] = op over arr[i..i + 2^k]
+    levels: Vec<Vec<T>>,
+}
+
+impl<T: Copy> SparseTable<T> {
+    pub fn new(arr: &[T], op: fn(T, T) -> T) -> Self {
+        let mut levels = vec![arr.to_vec()];
+        let mut width = 1;
+        while width * 2 <= arr.len() {
+            let previous = levels.last().unwrap();
+            let level = (0..=arr.len() - width * 2)
+                .map(|idx| op(previous[idx], previous[idx + width]))
+                .collect();
+            levels.push(level);
+            width *= 2;
+        }
+        SparseTable { op, levels }
+    }
+
+    pub fn len(&self) -> usize {
+        self.levels[0].len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.levels[0].is_empty()
+    }
+
+    // op over arr[l..=r]
+    pub fn query(&self, l: usize, r: usize) -> T {
+        if l > r || r >= self.len() {
+            panic!("Range query bounds are outside the array.");
+        }
+        let level = floor_log2(r - l + 1);
+        let width = 1 << level;
+        (self.op)(self.levels[level][l], self.levels[level][r + 1 - width])
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct SparseTable2d<T> {
+    op: fn(T, T) -> T,
+    row_count: usize,
+    col_count: usize,
+    // levels[row_level][col_level] is flat row-major with (col_count - 2^col_level + 1)
+    // columns, cell (i, j) = op over the 2^row_level x 2^col_level window at (i, j)
+    levels: Vec<Vec<Vec<T>>>,
+}
+
+impl<T: Copy> SparseTable2d<T> {
+    pub fn new(matrix: &[Vec<T>], op: fn(T, T) -> T) -> Self {
+        let row_count = matrix.len();
+        let col_count = matrix.first().map_or(0, |row| row.len());
+        if matrix.iter().any(|row| row.len() != col_count) {
+            panic!("Matrix rows do not all have the same length.");
+        }
+        if row_count == 0 || col_count == 0 {
+            panic!("Sparse table needs a non-empty matrix.");
+        }
+        let row_levels = floor_log2(row_count) + 1;
+        let col_levels = floor_log2(col_count) + 1;
+        let mut levels: Vec<Vec<Vec<T>>> = Vec::with_capacity(row_levels);
+
+        for row_level in 0..row_levels {
+            let window_rows = row_count + 1 - (1 << row_level);
+            let mut row_level_tables: Vec<Vec<T>> = Vec::with_capacity(col_levels);
+            for col_level in 0..col_levels {
+                let window_cols = col_count + 1 - (1 << col_level);
+                let mut table = Vec::with_capacity(window_rows * window_cols);
+                if row_level == 0 && col_level == 0 {
+                    for row in matrix {
+                        table.extend_from_slice(row);
+                    }
+                } else if row_level == 0 {
+                    // two windows side by side from the previous column level
+                    let previous = &row_level_tables[col_level - 1];
+                    let previous_cols = col_count + 1 - (1 << (col_level - 1));
+                    let half = 1 << (col_level - 1);
+                    for row in 0..window_rows {
+                        let previous_row = &previous[row * previous_cols..];
+                        table.extend(
+                            (0..window_cols)
+                                .map(|col| op(previous_row[col], previous_row[col + half])),
+                        );
+                    }
+                } else {
+                    // two windows stacked from the previous row level
+                    let previous = &levels[row_level - 1][col_level];
+                    let half = 1 << (row_level - 1);
+                    for row in 0..window_rows {
+                        let top = &previous[row * window_cols..(row + 1) * window_cols];
+                        let bottom =
+                            &previous[(row + half) * window_cols..(row + half + 1) * window_cols];
+                        table.extend(top.iter().zip(bottom).map(|(&a, &b)| op(a, b)));
+                    }
+                }
+                row_level_tables.push(table);
+            }
+            levels.push(row_level_tables);
+        }
+
+        SparseTable2d {
+            op,
+            row_count,
+            col_count,
+            levels,
+        }
+    }
+
+    pub fn row_count(&self) -> usize {
+        self.row_count
+    }
+
+    pub fn col_count(&self) -> usize {
+        self.col_count
+    }
+
+    // op over the rectangle (l_row, l_col)..=(r_row, r_col), four overlapping windows
+    pub fn query(&self, l_row: usize, l_col: usize, r_row: usize, r_col: usize) -> T {
+        if l_row > r_row || l_col > r_col || r_row >= self.row_count || r_col >= self.col_count {
+            panic!("Range query bounds are outside the matrix.");
+        }
+        let row_level = floor_log2(r_row - l_row + 1);
+        let col_level = floor_log2(r_col - l_col + 1);
+        let table = &self.levels[row_level][col_level];
+        let window_cols = self.col_count + 1 - (1 << col_level);
+        let bottom_row = r_row + 1 - (1 << row_level);
+        let right_col = r_col + 1 - (1 << col_level);
+        let op = self.op;
+        op(
+            op(
+                table[l_row * window_cols + l_col],
+                table[l_row * window_cols + right_col],
+            ),
+            op(
+                table[bottom_row * window_cols + l_col],
+                table[bottom_row * window_cols + right_col],
+            ),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bench::data::XorShift64;
+    use std::cmp::{max, min};
+
+    #[test]
+    fn min_max_and_gcd_match_a_scan() {
+        let mut rng = XorShift64::new(44);
+        for len in [1, 2, 3, 8, 31, 100] {
+            let arr: Vec<u64> = (0..len).map(|_| (rng.next_u64() % 60 + 1) * 6).collect();
+            let min_table = SparseTable::new(&arr, min);
+            let max_table = SparseTable::new(&arr, max);
+            let gcd_table = SparseTable::new(&arr, gcd);
+            for l in 0..len {
+                for r in l..len {
+                    let window = &arr[l..=r];
+                    assert_eq!(min_table.query(l, r), *window.iter().min().unwrap());
+                    assert_eq!(max_table.query(l, r), *window.iter().max().unwrap());
+                    assert_eq!(
+                        gcd_table.query(l, r),
+                        window.iter().fold(0, |a, &b| gcd(a, b))
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn region_min_max_match_a_scan() {
+        let mut rng = XorShift64::new(9);
+        for (row_count, col_count) in [(1, 1), (1, 7), (5, 1), (6, 9), (16, 13)] {
+            let matrix: Vec<Vec<i32>> = (0..row_count)
+                .map(|_| {
+                    (0..col_count)
+                        .map(|_| (rng.next_u64() % 256) as i32)
+                        .collect()
+                })
+                .collect();
+            let min_table = SparseTable2d::new(&matrix, min);
+            let max_table = SparseTable2d::new(&matrix, max);
+            for _ in 0..200 {
+                let (a, b) = (
+                    rng.next_u64() as usize % row_count,
+                    rng.next_u64() as usize % row_count,
+                );
+                let (c, d) = (
+                    rng.next_u64() as usize % col_count,
+                    rng.next_u64() as usize % col_count,
+                );
+                let (l_row, r_row, l_col, r_col) = (a.min(b), a.max(b), c.min(d), c.max(d));
+                let region = matrix[l_row..=r_row]
+                    .iter()
+                    .flat_map(|row| &row[l_col..=r_col]);
+                assert_eq!(
+                    min_table.query(l_row, l_col, r_row, r_col),
+                    *region.clone().min().unwrap()
+                );
+                assert_eq!(
+                    max_table.query(l_row, l_col, r_row, r_col),
+                    *region.max().unwrap()
+                );
+            }
+        }
+    }
+}
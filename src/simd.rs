@@ -0,0 +1,350 @@
+// Explicit SIMD kernels through std::arch, for when coaxing the auto-vectorizer isn't enough
+// (see row_sum_matrix_trying_to_coax_llvm_to_optimize_using_simd).
+//
+// Every kernel has a portable scalar version and SSE2/AVX2 versions on x86_64. The public
+// functions pick the best level the CPU supports at runtime (is_x86_feature_detected!), the
+// `*_using` variants take the level explicitly so the tests can run every path.
+//
+// All paths give bit-identical results. For the integer kernels that's free; for the float
+// ones the scalar code does the additions in exactly the order the vector code does: 8 lane
+// accumulators reduced pairwise, and separate multiply and add (no FMA, which rounds once
+// instead of twice).
+mod scalar;
+#[cfg(target_arch = "x86_64")]
+mod x86;
+
+use crate::matrix::gemm::micro_kernel::{MR, NR};
+use std::sync::OnceLock;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum SimdLevel {
+    Scalar,
+    Sse2,
+    Avx2,
+}
+
+impl SimdLevel {
+    pub fn name(&self) -> &'static str {
+        match self {
+            SimdLevel::Scalar => "scalar",
+            SimdLevel::Sse2 => "sse2",
+            SimdLevel::Avx2 => "avx2",
+        }
+    }
+
+    pub fn is_supported(&self) -> bool {
+        match self {
+            SimdLevel::Scalar => true,
+            #[cfg(target_arch = "x86_64")]
+            SimdLevel::Sse2 => is_x86_feature_detected!("sse2"),
+            #[cfg(target_arch = "x86_64")]
+            SimdLevel::Avx2 => is_x86_feature_detected!("avx2"),
+            #[cfg(not(target_arch = "x86_64"))]
+            _ => false,
+        }
+    }
+
+    // Every level this CPU can run, scalar first
+    pub fn supported() -> Vec<SimdLevel> {
+        [SimdLevel::Scalar, SimdLevel::Sse2, SimdLevel::Avx2]
+            .into_iter()
+            .filter(SimdLevel::is_supported)
+            .collect()
+    }
+
+    // The best supported level, detected once
+    pub fn detect() -> SimdLevel {
+        static LEVEL: OnceLock<SimdLevel> = OnceLock::new();
+        *LEVEL.get_or_init(|| *SimdLevel::supported().last().unwrap())
+    }
+
+    fn assert_supported(&self) {
+        if !self.is_supported() {
+            panic!("SIMD level {} is not supported on this CPU.", self.name());
+        }
+    }
+}
+
+// Each public kernel matches on the level; the x86 arms are only compiled on x86_64, and the
+// unsafe calls are sound because assert_supported checked the target feature first.
+macro_rules! dispatch {
+    ($level:expr, $scalar:expr, $sse2:expr, $avx2:expr) => {{
+        let level: SimdLevel = $level;
+        level.assert_supported();
+        match level {
+            SimdLevel::Scalar => $scalar,
+            #[cfg(target_arch = "x86_64")]
+            SimdLevel::Sse2 => unsafe { $sse2 },
+            #[cfg(target_arch = "x86_64")]
+            SimdLevel::Avx2 => unsafe { $avx2 },
+            #[cfg(not(target_arch = "x86_64"))]
+            _ => unreachable!(),
+        }
+    }};
+}
+
+// Wrapping sum, overflow wraps around instead of panicking in debug builds
+pub fn sum_i32(values: &[i32]) -> i32 {
+    sum_i32_using(SimdLevel::detect(), values)
+}
+
+pub fn sum_i32_using(level: SimdLevel, values: &[i32]) -> i32 {
+    dispatch!(
+        level,
+        scalar::sum_i32(values),
+        x86::sum_i32_sse2(values),
+        x86::sum_i32_avx2(values)
+    )
+}
+
+pub fn dot_f32(a: &[f32], b: &[f32]) -> f32 {
+    dot_f32_using(SimdLevel::detect(), a, b)
+}
+
+pub fn dot_f32_using(level: SimdLevel, a: &[f32], b: &[f32]) -> f32 {
+    if a.len() != b.len() {
+        panic!("Vectors must have the same length for a dot product.");
+    }
+    dispatch!(
+        level,
+        scalar::dot_f32(a, b),
+        x86::dot_f32_sse2(a, b),
+        x86::dot_f32_avx2(a, b)
+    )
+}
+
+// The packed GEMM micro-kernel (see matrix::gemm::micro_kernel) for f32 and f64
+pub fn micro_kernel_f32(k_len: usize, a_panel: &[f32], b_panel: &[f32]) -> [[f32; NR]; MR] {
+    micro_kernel_f32_using(SimdLevel::detect(), k_len, a_panel, b_panel)
+}
+
+pub fn micro_kernel_f32_using(
+    level: SimdLevel,
+    k_len: usize,
+    a_panel: &[f32],
+    b_panel: &[f32],
+) -> [[f32; NR]; MR] {
+    assert_micro_panels(k_len, a_panel.len(), b_panel.len());
+    dispatch!(
+        level,
+        scalar::micro_kernel(k_len, a_panel, b_panel),
+        x86::micro_kernel_f32_sse2(k_len, a_panel, b_panel),
+        x86::micro_kernel_f32_avx2(k_len, a_panel, b_panel)
+    )
+}
+
+pub fn micro_kernel_f64(k_len: usize, a_panel: &[f64], b_panel: &[f64]) -> [[f64; NR]; MR] {
+    micro_kernel_f64_using(SimdLevel::detect(), k_len, a_panel, b_panel)
+}
+
+pub fn micro_kernel_f64_using(
+    level: SimdLevel,
+    k_len: usize,
+    a_panel: &[f64],
+    b_panel: &[f64],
+) -> [[f64; NR]; MR] {
+    assert_micro_panels(k_len, a_panel.len(), b_panel.len());
+    dispatch!(
+        level,
+        scalar::micro_kernel(k_len, a_panel, b_panel),
+        x86::micro_kernel_f64_sse2(k_len, a_panel, b_panel),
+        x86::micro_kernel_f64_avx2(k_len, a_panel, b_panel)
+    )
+}
+
+fn assert_micro_panels(k_len: usize, a_panel_len: usize, b_panel_len: usize) {
+    if a_panel_len < k_len * MR || b_panel_len < k_len * NR {
+        panic!("Micro-panels are shorter than k_len steps.");
+    }
+}
+
+pub const TRANSPOSE_BLOCK: usize = 8;
+
+// Transposes the 8x8 block at the start of `source` (rows `source_stride` apart) into the
+// start of `target` (rows `target_stride` apart)
+pub fn transpose_8x8_i32(
+    source: &[i32],
+    source_stride: usize,
+    target: &mut [i32],
+    target_stride: usize,
+) {
+    transpose_8x8_i32_using(
+        SimdLevel::detect(),
+        source,
+        source_stride,
+        target,
+        target_stride,
+    );
+}
+
+pub fn transpose_8x8_i32_using(
+    level: SimdLevel,
+    source: &[i32],
+    source_stride: usize,
+    target: &mut [i32],
+    target_stride: usize,
+) {
+    let span = |stride: usize| (TRANSPOSE_BLOCK - 1) * stride + TRANSPOSE_BLOCK;
+    if source_stride < TRANSPOSE_BLOCK
+        || target_stride < TRANSPOSE_BLOCK
+        || source.len() < span(source_stride)
+        || target.len() < span(target_stride)
+    {
+        panic!("An 8x8 block does not fit in the source or target.");
+    }
+    dispatch!(
+        level,
+        scalar::transpose_8x8_i32(source, source_stride, target, target_stride),
+        x86::transpose_8x8_i32_sse2(source, source_stride, target, target_stride),
+        x86::transpose_8x8_i32_avx2(source, source_stride, target, target_stride)
+    )
+}
+
+// Adds `delta` to every pixel, clamping at 0 and 255
+pub fn adjust_brightness_u8(pixels: &mut [u8], delta: i16) {
+    adjust_brightness_u8_using(SimdLevel::detect(), pixels, delta);
+}
+
+pub fn adjust_brightness_u8_using(level: SimdLevel, pixels: &mut [u8], delta: i16) {
+    // anything past +-255 saturates every pixel the same way
+    let amount = delta.unsigned_abs().min(255) as u8;
+    let brighten = delta >= 0;
+    dispatch!(
+        level,
+        scalar::adjust_brightness_u8(pixels, amount, brighten),
+        x86::adjust_brightness_u8_sse2(pixels, amount, brighten),
+        x86::adjust_brightness_u8_avx2(pixels, amount, brighten)
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bench::data::XorShift64;
+
+    // Lengths around the vector widths so every tail length is hit
+    const LENGTHS: [usize; 10] = [0, 1, 3, 7, 8, 9, 16, 31, 33, 1000];
+
+    fn random_f32(rng: &mut XorShift64, len: usize) -> Vec<f32> {
+        (0..len)
+            .map(|_| (rng.next_u64() % 20001) as f32 / 977.0 - 10.0)
+            .collect()
+    }
+
+    #[test]
+    fn detect_picks_a_supported_level() {
+        let levels = SimdLevel::supported();
+        assert_eq!(levels[0], SimdLevel::Scalar);
+        assert!(SimdLevel::detect().is_supported());
+        assert_eq!(SimdLevel::detect(), *levels.last().unwrap());
+    }
+
+    #[test]
+    fn sum_i32_identical_on_every_level() {
+        let mut rng = XorShift64::new(7);
+        for len in LENGTHS {
+            // large values so the sum wraps
+            let values: Vec<i32> = (0..len).map(|_| rng.next_u64() as i32).collect();
+            let expected = values.iter().fold(0i32, |sum, &v| sum.wrapping_add(v));
+            for level in SimdLevel::supported() {
+                assert_eq!(sum_i32_using(level, &values), expected, "{:?}", level);
+            }
+        }
+    }
+
+    #[test]
+    fn dot_f32_bit_identical_on_every_level() {
+        let mut rng = XorShift64::new(11);
+        for len in LENGTHS {
+            let a = random_f32(&mut rng, len);
+            let b = random_f32(&mut rng, len);
+            let expected = dot_f32_using(SimdLevel::Scalar, &a, &b);
+            let naive: f64 = a.iter().zip(&b).map(|(&x, &y)| x as f64 * y as f64).sum();
+            assert!((expected as f64 - naive).abs() < 1e-3 * (1.0 + naive.abs()));
+            for level in SimdLevel::supported() {
+                assert_eq!(
+                    dot_f32_using(level, &a, &b).to_bits(),
+                    expected.to_bits(),
+                    "{:?} len {}",
+                    level,
+                    len
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn micro_kernels_bit_identical_on_every_level() {
+        let mut rng = XorShift64::new(13);
+        for k_len in [0, 1, 5, 64] {
+            let a = random_f32(&mut rng, k_len * MR);
+            let b = random_f32(&mut rng, k_len * NR);
+            let expected = micro_kernel_f32_using(SimdLevel::Scalar, k_len, &a, &b);
+            let a64: Vec<f64> = a.iter().map(|&v| v as f64 / 3.0).collect();
+            let b64: Vec<f64> = b.iter().map(|&v| v as f64 / 7.0).collect();
+            let expected64 = micro_kernel_f64_using(SimdLevel::Scalar, k_len, &a64, &b64);
+            for level in SimdLevel::supported() {
+                let block = micro_kernel_f32_using(level, k_len, &a, &b);
+                let block64 = micro_kernel_f64_using(level, k_len, &a64, &b64);
+                for row_idx in 0..MR {
+                    for col_idx in 0..NR {
+                        assert_eq!(
+                            block[row_idx][col_idx].to_bits(),
+                            expected[row_idx][col_idx].to_bits()
+                        );
+                        assert_eq!(
+                            block64[row_idx][col_idx].to_bits(),
+                            expected64[row_idx][col_idx].to_bits()
+                        );
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn transpose_8x8_identical_on_every_level() {
+        // an 8x8 block inside a 10 wide source, written into a 12 wide target
+        let source: Vec<i32> = (0..80).collect();
+        for level in SimdLevel::supported() {
+            let mut target = vec![-1; 8 * 12];
+            transpose_8x8_i32_using(level, &source[1..], 10, &mut target[2..], 12);
+            for row_idx in 0..8 {
+                for col_idx in 0..8 {
+                    assert_eq!(
+                        target[2 + col_idx * 12 + row_idx],
+                        source[1 + row_idx * 10 + col_idx]
+                    );
+                }
+                // columns outside the block are untouched
+                assert_eq!(target[row_idx * 12 + 10], -1);
+                assert_eq!(target[row_idx * 12], -1);
+            }
+        }
+    }
+
+    #[test]
+    fn brightness_saturates_identically_on_every_level() {
+        let mut rng = XorShift64::new(17);
+        for len in LENGTHS.into_iter().chain([64, 65]) {
+            let pixels: Vec<u8> = (0..len).map(|_| rng.next_u64() as u8).collect();
+            for delta in [0, 1, -1, 100, -100, 255, -255, 1000, -1000] {
+                let expected: Vec<u8> = pixels
+                    .iter()
+                    .map(|&p| (p as i32 + delta as i32).clamp(0, 255) as u8)
+                    .collect();
+                for level in SimdLevel::supported() {
+                    let mut adjusted = pixels.clone();
+                    adjust_brightness_u8_using(level, &mut adjusted, delta);
+                    assert_eq!(adjusted, expected, "{:?} delta {}", level, delta);
+                }
+            }
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "same length")]
+    fn dot_rejects_mismatched_lengths() {
+        dot_f32(&[1.0, 2.0], &[1.0]);
+    }
+}
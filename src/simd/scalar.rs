@@ -0,0 +1,64 @@
+// Portable versions of the SIMD kernels, written to produce exactly what the vector code does
+pub use crate::matrix::gemm::micro_kernel::micro_kernel;
+
+// Accumulator lanes of the widest (AVX2, 8 x f32) path, the narrower paths emulate it
+pub const F32_LANES: usize = 8;
+
+pub fn sum_i32(values: &[i32]) -> i32 {
+    values
+        .iter()
+        .fold(0i32, |sum, &value| sum.wrapping_add(value))
+}
+
+// Lane l of the accumulator sums a[i] * b[i] for i = l mod 8, then the lanes are reduced
+// the way the vector code does: high half onto low half, then pairs, then the last two.
+// Whatever doesn't fill a whole 8 is added on at the end, one at a time.
+pub fn dot_f32(a: &[f32], b: &[f32]) -> f32 {
+    let mut lanes = [0.0f32; F32_LANES];
+    let mut a_chunks = a.chunks_exact(F32_LANES);
+    let mut b_chunks = b.chunks_exact(F32_LANES);
+    for (a_chunk, b_chunk) in a_chunks.by_ref().zip(b_chunks.by_ref()) {
+        for lane in 0..F32_LANES {
+            lanes[lane] += a_chunk[lane] * b_chunk[lane];
+        }
+    }
+    let mut total = reduce_f32_lanes(lanes);
+    for (&a_val, &b_val) in a_chunks.remainder().iter().zip(b_chunks.remainder()) {
+        total += a_val * b_val;
+    }
+    total
+}
+
+pub fn reduce_f32_lanes(lanes: [f32; F32_LANES]) -> f32 {
+    let quads = [
+        lanes[0] + lanes[4],
+        lanes[1] + lanes[5],
+        lanes[2] + lanes[6],
+        lanes[3] + lanes[7],
+    ];
+    let pairs = [quads[0] + quads[2], quads[1] + quads[3]];
+    pairs[0] + pairs[1]
+}
+
+pub fn transpose_8x8_i32(
+    source: &[i32],
+    source_stride: usize,
+    target: &mut [i32],
+    target_stride: usize,
+) {
+    for row_idx in 0..8 {
+        for col_idx in 0..8 {
+            target[col_idx * target_stride + row_idx] = source[row_idx * source_stride + col_idx];
+        }
+    }
+}
+
+pub fn adjust_brightness_u8(pixels: &mut [u8], amount: u8, brighten: bool) {
+    for pixel in pixels {
+        *pixel = if brighten {
+            pixel.saturating_add(amount)
+        } else {
+            pixel.saturating_sub(amount)
+        };
+    }
+}
@@ -0,0 +1,315 @@
+// SSE2 and AVX2 versions of the kernels. Only called through the dispatch in simd.rs, which
+// checks the CPU supports the target feature first; the slice lengths are checked there too.
+use super::scalar::{self, F32_LANES};
+use crate::matrix::gemm::micro_kernel::{MR, NR};
+use std::arch::x86_64::*;
+
+// The micro-kernels below hold one 4 x 8 block: a row of C is one __m256 (f32, AVX2)
+const _: () = assert!(MR == 4 && NR == 8 && F32_LANES == 8);
+
+#[target_feature(enable = "sse2")]
+pub unsafe fn sum_i32_sse2(values: &[i32]) -> i32 {
+    let mut accumulator = _mm_setzero_si128();
+    let chunks = values.chunks_exact(4);
+    let rest = chunks.remainder();
+    for chunk in chunks {
+        let vector = unsafe { _mm_loadu_si128(chunk.as_ptr().cast()) };
+        accumulator = _mm_add_epi32(accumulator, vector);
+    }
+    let mut lanes = [0i32; 4];
+    unsafe { _mm_storeu_si128(lanes.as_mut_ptr().cast(), accumulator) };
+    scalar::sum_i32(&lanes).wrapping_add(scalar::sum_i32(rest))
+}
+
+#[target_feature(enable = "avx2")]
+pub unsafe fn sum_i32_avx2(values: &[i32]) -> i32 {
+    let mut accumulator = _mm256_setzero_si256();
+    let chunks = values.chunks_exact(8);
+    let rest = chunks.remainder();
+    for chunk in chunks {
+        let vector = unsafe { _mm256_loadu_si256(chunk.as_ptr().cast()) };
+        accumulator = _mm256_add_epi32(accumulator, vector);
+    }
+    let mut lanes = [0i32; 8];
+    unsafe { _mm256_storeu_si256(lanes.as_mut_ptr().cast(), accumulator) };
+    scalar::sum_i32(&lanes).wrapping_add(scalar::sum_i32(rest))
+}
+
+// The 8 lanes are finished off by the scalar reduction and tail, so the result can only
+// match the scalar version if the lanes do, which they do: same products, same order.
+fn finish_dot_f32(lanes: [f32; F32_LANES], a: &[f32], b: &[f32]) -> f32 {
+    let done = a.len() / F32_LANES * F32_LANES;
+    let mut total = scalar::reduce_f32_lanes(lanes);
+    for (&a_val, &b_val) in a[done..].iter().zip(&b[done..]) {
+        total += a_val * b_val;
+    }
+    total
+}
+
+// Two 4 lane registers standing in for one 8 lane one
+#[target_feature(enable = "sse2")]
+pub unsafe fn dot_f32_sse2(a: &[f32], b: &[f32]) -> f32 {
+    let mut low = _mm_setzero_ps();
+    let mut high = _mm_setzero_ps();
+    for (a_chunk, b_chunk) in a.chunks_exact(F32_LANES).zip(b.chunks_exact(F32_LANES)) {
+        let (a_low, a_high, b_low, b_high) = unsafe {
+            (
+                _mm_loadu_ps(a_chunk.as_ptr()),
+                _mm_loadu_ps(a_chunk.as_ptr().add(4)),
+                _mm_loadu_ps(b_chunk.as_ptr()),
+                _mm_loadu_ps(b_chunk.as_ptr().add(4)),
+            )
+        };
+        low = _mm_add_ps(low, _mm_mul_ps(a_low, b_low));
+        high = _mm_add_ps(high, _mm_mul_ps(a_high, b_high));
+    }
+    let mut lanes = [0.0f32; F32_LANES];
+    unsafe {
+        _mm_storeu_ps(lanes.as_mut_ptr(), low);
+        _mm_storeu_ps(lanes.as_mut_ptr().add(4), high);
+    }
+    finish_dot_f32(lanes, a, b)
+}
+
+#[target_feature(enable = "avx2")]
+pub unsafe fn dot_f32_avx2(a: &[f32], b: &[f32]) -> f32 {
+    let mut accumulator = _mm256_setzero_ps();
+    for (a_chunk, b_chunk) in a.chunks_exact(F32_LANES).zip(b.chunks_exact(F32_LANES)) {
+        let (a_vector, b_vector) = unsafe {
+            (
+                _mm256_loadu_ps(a_chunk.as_ptr()),
+                _mm256_loadu_ps(b_chunk.as_ptr()),
+            )
+        };
+        accumulator = _mm256_add_ps(accumulator, _mm256_mul_ps(a_vector, b_vector));
+    }
+    let mut lanes = [0.0f32; F32_LANES];
+    unsafe { _mm256_storeu_ps(lanes.as_mut_ptr(), accumulator) };
+    finish_dot_f32(lanes, a, b)
+}
+
+// Micro-kernels: per step broadcast each of the MR values of the A column and add its
+// product with the B row to that row of C. Rows of C are split into as many registers as
+// the level needs (f32: 1 x AVX2 or 2 x SSE2, f64: 2 x AVX2 or 4 x SSE2).
+#[target_feature(enable = "sse2")]
+pub unsafe fn micro_kernel_f32_sse2(
+    k_len: usize,
+    a_panel: &[f32],
+    b_panel: &[f32],
+) -> [[f32; NR]; MR] {
+    let mut c = [[_mm_setzero_ps(); 2]; MR];
+    for k_idx in 0..k_len {
+        let b_row = unsafe {
+            [
+                _mm_loadu_ps(b_panel.as_ptr().add(k_idx * NR)),
+                _mm_loadu_ps(b_panel.as_ptr().add(k_idx * NR + 4)),
+            ]
+        };
+        for row_idx in 0..MR {
+            let a_val = _mm_set1_ps(a_panel[k_idx * MR + row_idx]);
+            for part in 0..2 {
+                c[row_idx][part] = _mm_add_ps(c[row_idx][part], _mm_mul_ps(a_val, b_row[part]));
+            }
+        }
+    }
+    let mut block = [[0.0f32; NR]; MR];
+    for row_idx in 0..MR {
+        for part in 0..2 {
+            unsafe { _mm_storeu_ps(block[row_idx].as_mut_ptr().add(part * 4), c[row_idx][part]) };
+        }
+    }
+    block
+}
+
+#[target_feature(enable = "avx2")]
+pub unsafe fn micro_kernel_f32_avx2(
+    k_len: usize,
+    a_panel: &[f32],
+    b_panel: &[f32],
+) -> [[f32; NR]; MR] {
+    let mut c = [_mm256_setzero_ps(); MR];
+    for k_idx in 0..k_len {
+        let b_row = unsafe { _mm256_loadu_ps(b_panel.as_ptr().add(k_idx * NR)) };
+        for row_idx in 0..MR {
+            let a_val = _mm256_set1_ps(a_panel[k_idx * MR + row_idx]);
+            c[row_idx] = _mm256_add_ps(c[row_idx], _mm256_mul_ps(a_val, b_row));
+        }
+    }
+    let mut block = [[0.0f32; NR]; MR];
+    for row_idx in 0..MR {
+        unsafe { _mm256_storeu_ps(block[row_idx].as_mut_ptr(), c[row_idx]) };
+    }
+    block
+}
+
+#[target_feature(enable = "sse2")]
+pub unsafe fn micro_kernel_f64_sse2(
+    k_len: usize,
+    a_panel: &[f64],
+    b_panel: &[f64],
+) -> [[f64; NR]; MR] {
+    let mut c = [[_mm_setzero_pd(); 4]; MR];
+    for k_idx in 0..k_len {
+        let mut b_row = [_mm_setzero_pd(); 4];
+        for part in 0..4 {
+            b_row[part] = unsafe { _mm_loadu_pd(b_panel.as_ptr().add(k_idx * NR + part * 2)) };
+        }
+        for row_idx in 0..MR {
+            let a_val = _mm_set1_pd(a_panel[k_idx * MR + row_idx]);
+            for part in 0..4 {
+                c[row_idx][part] = _mm_add_pd(c[row_idx][part], _mm_mul_pd(a_val, b_row[part]));
+            }
+        }
+    }
+    let mut block = [[0.0f64; NR]; MR];
+    for row_idx in 0..MR {
+        for part in 0..4 {
+            unsafe { _mm_storeu_pd(block[row_idx].as_mut_ptr().add(part * 2), c[row_idx][part]) };
+        }
+    }
+    block
+}
+
+#[target_feature(enable = "avx2")]
+pub unsafe fn micro_kernel_f64_avx2(
+    k_len: usize,
+    a_panel: &[f64],
+    b_panel: &[f64],
+) -> [[f64; NR]; MR] {
+    let mut c = [[_mm256_setzero_pd(); 2]; MR];
+    for k_idx in 0..k_len {
+        let b_row = unsafe {
+            [
+                _mm256_loadu_pd(b_panel.as_ptr().add(k_idx * NR)),
+                _mm256_loadu_pd(b_panel.as_ptr().add(k_idx * NR + 4)),
+            ]
+        };
+        for row_idx in 0..MR {
+            let a_val = _mm256_set1_pd(a_panel[k_idx * MR + row_idx]);
+            for part in 0..2 {
+                c[row_idx][part] =
+                    _mm256_add_pd(c[row_idx][part], _mm256_mul_pd(a_val, b_row[part]));
+            }
+        }
+    }
+    let mut block = [[0.0f64; NR]; MR];
+    for row_idx in 0..MR {
+        for part in 0..2 {
+            unsafe {
+                _mm256_storeu_pd(block[row_idx].as_mut_ptr().add(part * 4), c[row_idx][part])
+            };
+        }
+    }
+    block
+}
+
+// 4x4 transpose of 32 bit lanes: interleave pairs of rows, then pairs of pairs
+#[target_feature(enable = "sse2")]
+fn transpose_4x4_epi32(rows: [__m128i; 4]) -> [__m128i; 4] {
+    let t0 = _mm_unpacklo_epi32(rows[0], rows[1]);
+    let t1 = _mm_unpacklo_epi32(rows[2], rows[3]);
+    let t2 = _mm_unpackhi_epi32(rows[0], rows[1]);
+    let t3 = _mm_unpackhi_epi32(rows[2], rows[3]);
+    [
+        _mm_unpacklo_epi64(t0, t1),
+        _mm_unpackhi_epi64(t0, t1),
+        _mm_unpacklo_epi64(t2, t3),
+        _mm_unpackhi_epi64(t2, t3),
+    ]
+}
+
+// Four 4x4 quadrants, quadrant (r, c) of the source lands on quadrant (c, r) of the target
+#[target_feature(enable = "sse2")]
+pub unsafe fn transpose_8x8_i32_sse2(
+    source: &[i32],
+    source_stride: usize,
+    target: &mut [i32],
+    target_stride: usize,
+) {
+    for quadrant_row in 0..2 {
+        for quadrant_col in 0..2 {
+            let mut rows = [_mm_setzero_si128(); 4];
+            for (row_idx, row) in rows.iter_mut().enumerate() {
+                let offset = (quadrant_row * 4 + row_idx) * source_stride + quadrant_col * 4;
+                *row = unsafe { _mm_loadu_si128(source.as_ptr().add(offset).cast()) };
+            }
+            for (col_idx, col) in transpose_4x4_epi32(rows).into_iter().enumerate() {
+                let offset = (quadrant_col * 4 + col_idx) * target_stride + quadrant_row * 4;
+                unsafe { _mm_storeu_si128(target.as_mut_ptr().add(offset).cast(), col) };
+            }
+        }
+    }
+}
+
+// Same interleaving within each 128 bit half, then the halves are swapped across with
+// permute2x128: low halves give columns 0-3, high halves columns 4-7
+#[target_feature(enable = "avx2")]
+pub unsafe fn transpose_8x8_i32_avx2(
+    source: &[i32],
+    source_stride: usize,
+    target: &mut [i32],
+    target_stride: usize,
+) {
+    let mut rows = [_mm256_setzero_si256(); 8];
+    for (row_idx, row) in rows.iter_mut().enumerate() {
+        *row = unsafe { _mm256_loadu_si256(source.as_ptr().add(row_idx * source_stride).cast()) };
+    }
+    let mut pairs = [_mm256_setzero_si256(); 8];
+    for half in 0..2 {
+        let r = &rows[half * 4..half * 4 + 4];
+        let t0 = _mm256_unpacklo_epi32(r[0], r[1]);
+        let t1 = _mm256_unpackhi_epi32(r[0], r[1]);
+        let t2 = _mm256_unpacklo_epi32(r[2], r[3]);
+        let t3 = _mm256_unpackhi_epi32(r[2], r[3]);
+        pairs[half * 4] = _mm256_unpacklo_epi64(t0, t2);
+        pairs[half * 4 + 1] = _mm256_unpackhi_epi64(t0, t2);
+        pairs[half * 4 + 2] = _mm256_unpacklo_epi64(t1, t3);
+        pairs[half * 4 + 3] = _mm256_unpackhi_epi64(t1, t3);
+    }
+    for col_idx in 0..4 {
+        let low = _mm256_permute2x128_si256::<0x20>(pairs[col_idx], pairs[col_idx + 4]);
+        let high = _mm256_permute2x128_si256::<0x31>(pairs[col_idx], pairs[col_idx + 4]);
+        unsafe {
+            _mm256_storeu_si256(target.as_mut_ptr().add(col_idx * target_stride).cast(), low);
+            _mm256_storeu_si256(
+                target
+                    .as_mut_ptr()
+                    .add((col_idx + 4) * target_stride)
+                    .cast(),
+                high,
+            );
+        }
+    }
+}
+
+#[target_feature(enable = "sse2")]
+pub unsafe fn adjust_brightness_u8_sse2(pixels: &mut [u8], amount: u8, brighten: bool) {
+    let amount_vector = _mm_set1_epi8(amount as i8);
+    let mut chunks = pixels.chunks_exact_mut(16);
+    for chunk in chunks.by_ref() {
+        let vector = unsafe { _mm_loadu_si128(chunk.as_ptr().cast()) };
+        let adjusted = if brighten {
+            _mm_adds_epu8(vector, amount_vector)
+        } else {
+            _mm_subs_epu8(vector, amount_vector)
+        };
+        unsafe { _mm_storeu_si128(chunk.as_mut_ptr().cast(), adjusted) };
+    }
+    scalar::adjust_brightness_u8(chunks.into_remainder(), amount, brighten);
+}
+
+#[target_feature(enable = "avx2")]
+pub unsafe fn adjust_brightness_u8_avx2(pixels: &mut [u8], amount: u8, brighten: bool) {
+    let amount_vector = _mm256_set1_epi8(amount as i8);
+    let mut chunks = pixels.chunks_exact_mut(32);
+    for chunk in chunks.by_ref() {
+        let vector = unsafe { _mm256_loadu_si256(chunk.as_ptr().cast()) };
+        let adjusted = if brighten {
+            _mm256_adds_epu8(vector, amount_vector)
+        } else {
+            _mm256_subs_epu8(vector, amount_vector)
+        };
+        unsafe { _mm256_storeu_si256(chunk.as_mut_ptr().cast(), adjusted) };
+    }
+    scalar::adjust_brightness_u8(chunks.into_remainder(), amount, brighten);
+}
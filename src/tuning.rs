@@ -0,0 +1,292 @@
+// Block size auto-tuning for the tiled matrix kernels.
+//
+// Module 6 hard-coded block sizes of 32/64/128 and left a note to experiment with them.
+// Here the candidates come from the cache sizes in sysfs, the tuner benchmarks each candidate
+// per kernel and (bucketed) matrix shape, and the winners are persisted in a tuning file so the
+// `*_tuned` kernel variants can pick them up. Without a tuning entry we fall back to the largest
+// power of two whose working set fits in L1.
+pub mod cache;
+pub mod store;
+pub mod tuner;
+
+use cache::{CacheLevel, data_cache_size, read_cache_levels};
+use std::sync::{Mutex, OnceLock};
+use store::TuningStore;
+
+pub const DEFAULT_BLOCK_SIZE: usize = 64;
+const MIN_BLOCK_SIZE: usize = 8;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TiledKernel {
+    MatrixMultiplyLoopTiling,
+    MatrixMultiplyLoopTilingFlatRowMajor,
+    MatrixMultiplyLoopTilingFlatRowMajorAColMajorB,
+    Transpose2dVecLoopTiling,
+    Transpose1dRowMajorLoopTiling,
+    TransposeSquareTiled,
+    Rotate2dVec,
+}
+
+impl TiledKernel {
+    pub const ALL: [TiledKernel; 7] = [
+        TiledKernel::MatrixMultiplyLoopTiling,
+        TiledKernel::MatrixMultiplyLoopTilingFlatRowMajor,
+        TiledKernel::MatrixMultiplyLoopTilingFlatRowMajorAColMajorB,
+        TiledKernel::Transpose2dVecLoopTiling,
+        TiledKernel::Transpose1dRowMajorLoopTiling,
+        TiledKernel::TransposeSquareTiled,
+        TiledKernel::Rotate2dVec,
+    ];
+
+    // The name of the kernel function, also used as the key in the tuning file
+    pub fn name(&self) -> &'static str {
+        match self {
+            TiledKernel::MatrixMultiplyLoopTiling => "matrix_multiply_loop_tiling",
+            TiledKernel::MatrixMultiplyLoopTilingFlatRowMajor => {
+                "matrix_multiply_loop_tiling_flat_row_major"
+            }
+            TiledKernel::MatrixMultiplyLoopTilingFlatRowMajorAColMajorB => {
+                "matrix_multiply_loop_tiling_flat_row_major_a_col_major_b"
+            }
+            TiledKernel::Transpose2dVecLoopTiling => {
+                "transpose_2d_vec_loop_tiling_aka_block_tiling"
+            }
+            TiledKernel::Transpose1dRowMajorLoopTiling => {
+                "transpose_1d_row_major_matrix_loop_tiling_aka_block_tiling"
+            }
+            TiledKernel::TransposeSquareTiled => "transpose_square_tiled",
+            TiledKernel::Rotate2dVec => "rotate_2d_vec",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<TiledKernel> {
+        TiledKernel::ALL
+            .into_iter()
+            .find(|kernel| kernel.name() == name)
+    }
+
+    // How many block x block tiles are live at once: A, B and C for matmul, source and
+    // destination for the transposes/rotate (transpose_square_tiled also has its buffer).
+    pub fn live_tiles(&self) -> usize {
+        match self {
+            TiledKernel::MatrixMultiplyLoopTiling
+            | TiledKernel::MatrixMultiplyLoopTilingFlatRowMajor
+            | TiledKernel::MatrixMultiplyLoopTilingFlatRowMajorAColMajorB
+            | TiledKernel::TransposeSquareTiled => 3,
+            TiledKernel::Transpose2dVecLoopTiling
+            | TiledKernel::Transpose1dRowMajorLoopTiling
+            | TiledKernel::Rotate2dVec => 2,
+        }
+    }
+
+    pub fn is_matrix_multiply(&self) -> bool {
+        self.live_tiles() == 3 && *self != TiledKernel::TransposeSquareTiled
+    }
+}
+
+// rows x cols for the 2d kernels, (rows x depth) * (depth x cols) for matmul, depth = 1 otherwise
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct TuningShape {
+    pub rows: usize,
+    pub cols: usize,
+    pub depth: usize,
+}
+
+impl TuningShape {
+    pub fn matrix(rows: usize, cols: usize) -> TuningShape {
+        TuningShape {
+            rows,
+            cols,
+            depth: 1,
+        }
+    }
+
+    pub fn matrix_multiply(a_row_count: usize, a_col_count: usize, b_col_count: usize) -> Self {
+        TuningShape {
+            rows: a_row_count,
+            cols: b_col_count,
+            depth: a_col_count,
+        }
+    }
+
+    // Shapes are tuned per power of two bucket, a 500x500 matrix uses the 512x512 result
+    pub fn bucket(&self) -> TuningShape {
+        TuningShape {
+            rows: self.rows.max(1).next_power_of_two(),
+            cols: self.cols.max(1).next_power_of_two(),
+            depth: self.depth.max(1).next_power_of_two(),
+        }
+    }
+
+    pub fn largest_dimension(&self) -> usize {
+        self.rows.max(self.cols).max(self.depth)
+    }
+}
+
+// Largest power of two block whose live tiles fit in `cache_bytes`
+fn largest_block_fitting(cache_bytes: usize, live_tiles: usize, element_size: usize) -> usize {
+    let mut block = MIN_BLOCK_SIZE;
+    while live_tiles * (block * 2) * (block * 2) * element_size <= cache_bytes {
+        block *= 2;
+    }
+    block
+}
+
+// Static guess used when nothing has been tuned: fill L1 with the live tiles
+pub fn heuristic_block_size(
+    kernel: TiledKernel,
+    caches: &[CacheLevel],
+    element_size: usize,
+) -> usize {
+    match data_cache_size(caches, 1) {
+        Some(l1) => largest_block_fitting(l1, kernel.live_tiles(), element_size),
+        None => DEFAULT_BLOCK_SIZE,
+    }
+}
+
+// Powers of two from 8 up to whatever still fits in L2 (or 256 without cache info),
+// never larger than the matrix itself. The L1 heuristic is always part of the search.
+pub fn candidate_block_sizes(
+    kernel: TiledKernel,
+    shape: TuningShape,
+    caches: &[CacheLevel],
+    element_size: usize,
+) -> Vec<usize> {
+    let upper = match data_cache_size(caches, 2).or(data_cache_size(caches, 1)) {
+        Some(cache_bytes) => largest_block_fitting(cache_bytes, kernel.live_tiles(), element_size),
+        None => 256,
+    }
+    .min(
+        shape
+            .largest_dimension()
+            .max(MIN_BLOCK_SIZE)
+            .next_power_of_two(),
+    );
+
+    let mut candidates: Vec<usize> = std::iter::successors(Some(MIN_BLOCK_SIZE), |b| Some(b * 2))
+        .take_while(|&b| b <= upper)
+        .collect();
+    let heuristic = heuristic_block_size(kernel, caches, element_size).min(upper);
+    if !candidates.contains(&heuristic) {
+        candidates.push(heuristic);
+        candidates.sort_unstable();
+    }
+    candidates
+}
+
+fn caches() -> &'static [CacheLevel] {
+    static CACHES: OnceLock<Vec<CacheLevel>> = OnceLock::new();
+    CACHES.get_or_init(read_cache_levels)
+}
+
+// Loaded from the tuning file on first use, updated in place when the tuner runs
+static STORE: Mutex<Option<TuningStore>> = Mutex::new(None);
+
+pub(crate) fn with_store<R>(f: impl FnOnce(&mut TuningStore) -> R) -> R {
+    let mut guard = STORE.lock().unwrap();
+    let store = guard
+        .get_or_insert_with(|| TuningStore::load(&store::tuning_file_path()).unwrap_or_default());
+    f(store)
+}
+
+// Block size the *_tuned kernels use: the tuning file entry for this kernel and shape bucket,
+// otherwise the L1 heuristic for 4 byte (i32) elements.
+pub fn tuned_block_size(kernel: TiledKernel, shape: TuningShape) -> usize {
+    with_store(|store| store.get(kernel, shape))
+        .unwrap_or_else(|| heuristic_block_size(kernel, caches(), size_of::<i32>()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::cache::CacheKind;
+    use super::*;
+
+    fn caches(l1: usize, l2: usize) -> Vec<CacheLevel> {
+        let level = |level, kind, size_bytes| CacheLevel {
+            level,
+            kind,
+            size_bytes,
+            line_size: 64,
+            ways: 8,
+        };
+        vec![
+            level(1, CacheKind::Data, l1),
+            level(1, CacheKind::Instruction, l1),
+            level(2, CacheKind::Unified, l2),
+        ]
+    }
+
+    #[test]
+    fn kernel_names_round_trip() {
+        for kernel in TiledKernel::ALL {
+            assert_eq!(TiledKernel::from_name(kernel.name()), Some(kernel));
+        }
+        assert_eq!(TiledKernel::from_name("nope"), None);
+        assert!(TiledKernel::MatrixMultiplyLoopTiling.is_matrix_multiply());
+        assert!(!TiledKernel::TransposeSquareTiled.is_matrix_multiply());
+    }
+
+    #[test]
+    fn heuristic_fills_l1() {
+        let caches = caches(48 * 1024, 2048 * 1024);
+        // 3 * 64 * 64 * 4 = 48K fits, 128 would not
+        assert_eq!(
+            heuristic_block_size(TiledKernel::MatrixMultiplyLoopTiling, &caches, 4),
+            64
+        );
+        // 2 * 64 * 64 * 4 = 32K fits, 2 * 128 * 128 * 4 = 128K does not
+        assert_eq!(
+            heuristic_block_size(TiledKernel::Rotate2dVec, &caches, 4),
+            64
+        );
+        assert_eq!(
+            heuristic_block_size(TiledKernel::Rotate2dVec, &caches, 1),
+            128
+        );
+        assert_eq!(
+            heuristic_block_size(TiledKernel::Rotate2dVec, &[], 4),
+            DEFAULT_BLOCK_SIZE
+        );
+    }
+
+    #[test]
+    fn candidates_bounded_by_l2_and_shape() {
+        let caches = caches(32 * 1024, 256 * 1024);
+        let big = TuningShape::matrix_multiply(4096, 4096, 4096);
+        // 3 * 128 * 128 * 4 = 192K fits in 256K, 256 does not
+        assert_eq!(
+            candidate_block_sizes(TiledKernel::MatrixMultiplyLoopTiling, big, &caches, 4),
+            vec![8, 16, 32, 64, 128]
+        );
+        let small = TuningShape::matrix(20, 10);
+        assert_eq!(
+            candidate_block_sizes(TiledKernel::Transpose2dVecLoopTiling, small, &caches, 4),
+            vec![8, 16, 32]
+        );
+        assert_eq!(
+            candidate_block_sizes(TiledKernel::Transpose2dVecLoopTiling, big, &[], 4),
+            vec![8, 16, 32, 64, 128, 256]
+        );
+    }
+
+    #[test]
+    fn shapes_bucket_to_powers_of_two() {
+        let shape = TuningShape::matrix_multiply(500, 300, 1000).bucket();
+        assert_eq!(
+            shape,
+            TuningShape {
+                rows: 512,
+                cols: 1024,
+                depth: 512
+            }
+        );
+        assert_eq!(
+            TuningShape::matrix(0, 3).bucket(),
+            TuningShape {
+                rows: 1,
+                cols: 4,
+                depth: 1
+            }
+        );
+    }
+}
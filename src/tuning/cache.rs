@@ -0,0 +1,154 @@
+// CPU cache geometry as reported by Linux under /sys/devices/system/cpu/cpu0/cache/index*/
+use std::fs;
+use std::path::Path;
+
+pub const SYSFS_CACHE_DIR: &str = "/sys/devices/system/cpu/cpu0/cache";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CacheKind {
+    Data,
+    Instruction,
+    Unified,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CacheLevel {
+    pub level: u32,
+    pub kind: CacheKind,
+    pub size_bytes: usize,
+    pub line_size: usize,
+    pub ways: usize,
+}
+
+impl CacheLevel {
+    // Instruction caches don't hold our matrices
+    pub fn holds_data(&self) -> bool {
+        self.kind != CacheKind::Instruction
+    }
+}
+
+// sysfs sizes look like "48K", "2048K" or "105M"
+pub fn parse_cache_size(text: &str) -> Option<usize> {
+    let text = text.trim();
+    let (digits, multiplier) = match text.chars().last()? {
+        'K' | 'k' => (&text[..text.len() - 1], 1024),
+        'M' | 'm' => (&text[..text.len() - 1], 1024 * 1024),
+        'G' | 'g' => (&text[..text.len() - 1], 1024 * 1024 * 1024),
+        _ => (text, 1),
+    };
+    digits.trim().parse::<usize>().ok().map(|n| n * multiplier)
+}
+
+fn read_trimmed(dir: &Path, file: &str) -> Option<String> {
+    fs::read_to_string(dir.join(file))
+        .ok()
+        .map(|s| s.trim().to_string())
+}
+
+fn read_cache_level(dir: &Path) -> Option<CacheLevel> {
+    let kind = match read_trimmed(dir, "type")?.as_str() {
+        "Data" => CacheKind::Data,
+        "Instruction" => CacheKind::Instruction,
+        "Unified" => CacheKind::Unified,
+        _ => return None,
+    };
+    Some(CacheLevel {
+        level: read_trimmed(dir, "level")?.parse().ok()?,
+        kind,
+        size_bytes: parse_cache_size(&read_trimmed(dir, "size")?)?,
+        line_size: read_trimmed(dir, "coherency_line_size")
+            .and_then(|s| s.parse().ok())
+            .unwrap_or(64),
+        ways: read_trimmed(dir, "ways_of_associativity")
+            .and_then(|s| s.parse().ok())
+            .unwrap_or(1),
+    })
+}
+
+// Reads every index*/ directory below `cache_dir`, sorted by level (data before instruction).
+// Returns an empty Vec when the directory is missing, e.g. not on Linux.
+pub fn read_cache_levels_from(cache_dir: &Path) -> Vec<CacheLevel> {
+    let Ok(entries) = fs::read_dir(cache_dir) else {
+        return Vec::new();
+    };
+    let mut levels: Vec<CacheLevel> = entries
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| entry.file_name().to_string_lossy().starts_with("index"))
+        .filter_map(|entry| read_cache_level(&entry.path()))
+        .collect();
+    levels.sort_by_key(|cache| (cache.level, cache.kind == CacheKind::Instruction));
+    levels
+}
+
+pub fn read_cache_levels() -> Vec<CacheLevel> {
+    read_cache_levels_from(Path::new(SYSFS_CACHE_DIR))
+}
+
+// Size of the data cache at `level`, if the machine told us about one
+pub fn data_cache_size(levels: &[CacheLevel], level: u32) -> Option<usize> {
+    levels
+        .iter()
+        .find(|cache| cache.level == level && cache.holds_data())
+        .map(|cache| cache.size_bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    fn fake_sysfs(name: &str, caches: &[(&str, &str, &str, &str)]) -> PathBuf {
+        let root = std::env::temp_dir().join(format!("dsa-cache-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        for (idx, (level, kind, size, ways)) in caches.iter().enumerate() {
+            let dir = root.join(format!("index{}", idx));
+            fs::create_dir_all(&dir).unwrap();
+            fs::write(dir.join("level"), format!("{}\n", level)).unwrap();
+            fs::write(dir.join("type"), format!("{}\n", kind)).unwrap();
+            fs::write(dir.join("size"), format!("{}\n", size)).unwrap();
+            fs::write(dir.join("coherency_line_size"), "64\n").unwrap();
+            fs::write(dir.join("ways_of_associativity"), format!("{}\n", ways)).unwrap();
+        }
+        fs::write(root.join("uevent"), "").unwrap();
+        root
+    }
+
+    #[test]
+    fn parses_sizes() {
+        assert_eq!(parse_cache_size("48K"), Some(48 * 1024));
+        assert_eq!(parse_cache_size("2048K\n"), Some(2048 * 1024));
+        assert_eq!(parse_cache_size("105M"), Some(105 * 1024 * 1024));
+        assert_eq!(parse_cache_size("512"), Some(512));
+        assert_eq!(parse_cache_size("lots"), None);
+        assert_eq!(parse_cache_size(""), None);
+    }
+
+    #[test]
+    fn reads_levels_from_directory() {
+        let root = fake_sysfs(
+            "levels",
+            &[
+                ("2", "Unified", "2048K", "16"),
+                ("1", "Instruction", "32K", "8"),
+                ("1", "Data", "48K", "12"),
+            ],
+        );
+        let levels = read_cache_levels_from(&root);
+        fs::remove_dir_all(&root).unwrap();
+
+        assert_eq!(levels.len(), 3);
+        assert_eq!(levels[0].kind, CacheKind::Data);
+        assert_eq!(levels[0].size_bytes, 48 * 1024);
+        assert_eq!(levels[0].ways, 12);
+        assert_eq!(levels[1].kind, CacheKind::Instruction);
+        assert_eq!(levels[2].level, 2);
+        assert_eq!(data_cache_size(&levels, 1), Some(48 * 1024));
+        assert_eq!(data_cache_size(&levels, 2), Some(2048 * 1024));
+        assert_eq!(data_cache_size(&levels, 3), None);
+    }
+
+    #[test]
+    fn missing_directory_is_empty() {
+        assert!(read_cache_levels_from(Path::new("/definitely/not/here")).is_empty());
+    }
+}
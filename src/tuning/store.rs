@@ -0,0 +1,238 @@
+// The tuning file: best block size per kernel and shape bucket, kept as JSON next to the
+// benchmark exports so it can be read back with the same small parser.
+//
+// {"version":1,"cpu_model":"...","entries":[{"kernel":"matrix_multiply_loop_tiling",
+//   "rows":512,"cols":512,"depth":512,"block_size":64,"median_ns":123456}]}
+use super::{TiledKernel, TuningShape};
+use crate::bench::json::{self, Json};
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+pub const TUNING_FILE_ENV: &str = "DSA_TUNING_FILE";
+pub const DEFAULT_TUNING_FILE: &str = "dsa-tuning.json";
+const TUNING_FILE_VERSION: u64 = 1;
+
+// $DSA_TUNING_FILE if set, otherwise dsa-tuning.json in the working directory
+pub fn tuning_file_path() -> PathBuf {
+    env::var_os(TUNING_FILE_ENV)
+        .map(PathBuf::from)
+        .unwrap_or_else(|| PathBuf::from(DEFAULT_TUNING_FILE))
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct TuningEntry {
+    pub kernel: TiledKernel,
+    pub shape: TuningShape,
+    pub block_size: usize,
+    pub median_ns: u64,
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TuningStore {
+    pub cpu_model: Option<String>,
+    pub entries: Vec<TuningEntry>,
+}
+
+impl TuningStore {
+    // Shapes are looked up by bucket, see TuningShape::bucket
+    pub fn get(&self, kernel: TiledKernel, shape: TuningShape) -> Option<usize> {
+        let shape = shape.bucket();
+        self.entries
+            .iter()
+            .find(|entry| entry.kernel == kernel && entry.shape == shape)
+            .map(|entry| entry.block_size)
+    }
+
+    // Replaces any previous result for the same kernel and shape bucket
+    pub fn insert(&mut self, mut entry: TuningEntry) {
+        entry.shape = entry.shape.bucket();
+        match self
+            .entries
+            .iter_mut()
+            .find(|existing| existing.kernel == entry.kernel && existing.shape == entry.shape)
+        {
+            Some(existing) => *existing = entry,
+            None => self.entries.push(entry),
+        }
+    }
+
+    pub fn to_json(&self) -> String {
+        let entries = self
+            .entries
+            .iter()
+            .map(|entry| {
+                Json::Object(vec![
+                    ("kernel".to_string(), entry.kernel.name().into()),
+                    ("rows".to_string(), entry.shape.rows.into()),
+                    ("cols".to_string(), entry.shape.cols.into()),
+                    ("depth".to_string(), entry.shape.depth.into()),
+                    ("block_size".to_string(), entry.block_size.into()),
+                    ("median_ns".to_string(), entry.median_ns.into()),
+                ])
+            })
+            .collect();
+        let document = Json::Object(vec![
+            ("version".to_string(), TUNING_FILE_VERSION.into()),
+            ("cpu_model".to_string(), self.cpu_model.as_deref().into()),
+            ("entries".to_string(), Json::Array(entries)),
+        ]);
+        format!("{}\n", document)
+    }
+
+    // Entries for kernels this build doesn't know about are skipped rather than rejected,
+    // so an older binary can still read a newer tuning file.
+    pub fn parse(text: &str) -> Result<TuningStore, String> {
+        let document = json::parse(text)?;
+        match document.get("version").and_then(Json::as_u64) {
+            Some(TUNING_FILE_VERSION) => {}
+            Some(other) => return Err(format!("unsupported tuning file version {}", other)),
+            None => return Err("tuning file has no version".to_string()),
+        }
+        let entries = document
+            .get("entries")
+            .and_then(Json::as_array)
+            .ok_or("tuning file has no entries array")?;
+
+        let mut store = TuningStore {
+            cpu_model: document
+                .get("cpu_model")
+                .and_then(Json::as_str)
+                .map(str::to_string),
+            entries: Vec::new(),
+        };
+        for (idx, entry) in entries.iter().enumerate() {
+            let number = |key: &str| {
+                entry
+                    .get(key)
+                    .and_then(Json::as_u64)
+                    .ok_or(format!("entry {} is missing '{}'", idx, key))
+            };
+            let name = entry
+                .get("kernel")
+                .and_then(Json::as_str)
+                .ok_or(format!("entry {} is missing 'kernel'", idx))?;
+            let Some(kernel) = TiledKernel::from_name(name) else {
+                continue;
+            };
+            let block_size = number("block_size")? as usize;
+            if block_size == 0 {
+                return Err(format!("entry {} has a zero block size", idx));
+            }
+            store.insert(TuningEntry {
+                kernel,
+                shape: TuningShape {
+                    rows: number("rows")? as usize,
+                    cols: number("cols")? as usize,
+                    depth: number("depth")? as usize,
+                },
+                block_size,
+                median_ns: number("median_ns")?,
+            });
+        }
+        Ok(store)
+    }
+
+    // A missing file is an empty store, a malformed one is an error
+    pub fn load(path: &Path) -> Result<TuningStore, String> {
+        match fs::read_to_string(path) {
+            Ok(text) => TuningStore::parse(&text).map_err(|e| format!("{}: {}", path.display(), e)),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(TuningStore::default()),
+            Err(e) => Err(format!("{}: {}", path.display(), e)),
+        }
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), String> {
+        fs::write(path, self.to_json())
+            .map_err(|e| format!("could not write {}: {}", path.display(), e))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(kernel: TiledKernel, size: usize, block_size: usize) -> TuningEntry {
+        TuningEntry {
+            kernel,
+            shape: TuningShape::matrix_multiply(size, size, size),
+            block_size,
+            median_ns: 1000,
+        }
+    }
+
+    #[test]
+    fn lookups_use_the_shape_bucket() {
+        let mut store = TuningStore::default();
+        store.insert(entry(TiledKernel::MatrixMultiplyLoopTiling, 512, 32));
+        assert_eq!(
+            store.get(
+                TiledKernel::MatrixMultiplyLoopTiling,
+                TuningShape::matrix_multiply(300, 500, 400)
+            ),
+            Some(32)
+        );
+        assert_eq!(
+            store.get(
+                TiledKernel::MatrixMultiplyLoopTilingFlatRowMajor,
+                TuningShape::matrix_multiply(512, 512, 512)
+            ),
+            None
+        );
+
+        store.insert(entry(TiledKernel::MatrixMultiplyLoopTiling, 500, 128));
+        assert_eq!(store.entries.len(), 1);
+        assert_eq!(store.entries[0].block_size, 128);
+    }
+
+    #[test]
+    fn file_round_trip() {
+        let mut store = TuningStore {
+            cpu_model: Some("Test CPU".to_string()),
+            entries: Vec::new(),
+        };
+        store.insert(entry(TiledKernel::MatrixMultiplyLoopTiling, 256, 64));
+        store.insert(TuningEntry {
+            kernel: TiledKernel::Transpose2dVecLoopTiling,
+            shape: TuningShape::matrix(1024, 512),
+            block_size: 32,
+            median_ns: 42,
+        });
+
+        let path = env::temp_dir().join(format!("dsa-tuning-test-{}.json", std::process::id()));
+        store.save(&path).unwrap();
+        let loaded = TuningStore::load(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+        assert_eq!(loaded, store);
+
+        assert_eq!(
+            TuningStore::load(Path::new("/definitely/not/here.json")).unwrap(),
+            TuningStore::default()
+        );
+    }
+
+    #[test]
+    fn parse_skips_unknown_kernels_and_rejects_bad_files() {
+        let store = TuningStore::parse(
+            r#"{"version":1,"cpu_model":null,"entries":[
+                {"kernel":"from_the_future","rows":8,"cols":8,"depth":1,"block_size":8,"median_ns":1},
+                {"kernel":"rotate_2d_vec","rows":8,"cols":8,"depth":1,"block_size":8,"median_ns":1}]}"#,
+        )
+        .unwrap();
+        assert_eq!(store.cpu_model, None);
+        assert_eq!(store.entries.len(), 1);
+        assert_eq!(store.entries[0].kernel, TiledKernel::Rotate2dVec);
+
+        assert!(TuningStore::parse(r#"{"version":2,"entries":[]}"#).is_err());
+        assert!(TuningStore::parse(r#"{"entries":[]}"#).is_err());
+        assert!(
+            TuningStore::parse(
+                r#"{"version":1,"entries":[{"kernel":"rotate_2d_vec","rows":8,"cols":8,"depth":1,"block_size":0,"median_ns":1}]}"#
+            )
+            .is_err()
+        );
+        assert!(
+            TuningStore::parse(r#"{"version":1,"entries":[{"kernel":"rotate_2d_vec"}]}"#).is_err()
+        );
+    }
+}
@@ -0,0 +1,284 @@
+// Runs every candidate block size for a kernel and shape through the bench harness
+// and keeps the one with the lowest median.
+use super::cache::read_cache_levels;
+use super::store::{TuningEntry, TuningStore, tuning_file_path};
+use super::{TiledKernel, TuningShape, candidate_block_sizes, with_store};
+use crate::bench::data::i32s_from_seed;
+use crate::bench::export::cpu_model;
+use crate::bench::harness::{Bench, BenchConfig, BenchReport};
+use crate::matrix::multiply::{
+    matrix_multiply_loop_tiling, matrix_multiply_loop_tiling_flat_row_major,
+    matrix_multiply_loop_tiling_flat_row_major_a_col_major_b,
+};
+use crate::matrix::rotate::rotate_2d_vec;
+use crate::matrix::strassen::{StrassenVariant, matrix_multiply_strassen_flat_row_major};
+use crate::matrix::transpose::{
+    transpose_1d_row_major_matrix_loop_tiling_aka_block_tiling,
+    transpose_2d_vec_loop_tiling_aka_block_tiling, transpose_square_tiled,
+};
+use std::time::Duration;
+
+#[derive(Debug, Clone)]
+pub struct TuningResult {
+    pub kernel: TiledKernel,
+    pub shape: TuningShape,
+    pub block_size: usize,
+    pub median: Duration,
+    pub report: BenchReport,
+}
+
+impl TuningResult {
+    pub fn entry(&self) -> TuningEntry {
+        TuningEntry {
+            kernel: self.kernel,
+            shape: self.shape,
+            block_size: self.block_size,
+            median_ns: self.median.as_nanos() as u64,
+        }
+    }
+}
+
+fn random_matrix(seed: u64, row_count: usize, col_count: usize) -> Vec<Vec<i32>> {
+    i32s_from_seed(seed, row_count * col_count)
+        .chunks(col_count)
+        .map(|row| row.to_vec())
+        .collect()
+}
+
+fn block_case_name(block_size: usize) -> String {
+    format!("block {}", block_size)
+}
+
+// One bench per kernel, one case per candidate. The size column holds the largest dimension,
+// the exact shape is in the title.
+fn bench_candidates(
+    kernel: TiledKernel,
+    shape: TuningShape,
+    candidates: &[usize],
+    config: BenchConfig,
+) -> BenchReport {
+    let TuningShape { rows, cols, depth } = shape;
+    let title = format!("Tuning {} ({}x{}x{})", kernel.name(), rows, depth, cols);
+    let sizes = vec![shape.largest_dimension()];
+    match kernel {
+        TiledKernel::MatrixMultiplyLoopTiling => {
+            let mut bench = Bench::new(&title, sizes, |_| {
+                (random_matrix(1, rows, depth), random_matrix(2, depth, cols))
+            })
+            .config(config);
+            for &block_size in candidates {
+                bench = bench.case(&block_case_name(block_size), move |(a, b)| {
+                    matrix_multiply_loop_tiling(a, b, block_size)
+                });
+            }
+            bench.run()
+        }
+        TiledKernel::MatrixMultiplyLoopTilingFlatRowMajor
+        | TiledKernel::MatrixMultiplyLoopTilingFlatRowMajorAColMajorB => {
+            let multiply = if kernel == TiledKernel::MatrixMultiplyLoopTilingFlatRowMajor {
+                matrix_multiply_loop_tiling_flat_row_major
+            } else {
+                matrix_multiply_loop_tiling_flat_row_major_a_col_major_b
+            };
+            let mut bench = Bench::new(&title, sizes, |_| {
+                (
+                    i32s_from_seed(1, rows * depth),
+                    i32s_from_seed(2, depth * cols),
+                )
+            })
+            .config(config);
+            for &block_size in candidates {
+                bench = bench.case(&block_case_name(block_size), move |(a, b)| {
+                    multiply(a, b, rows, depth, cols, block_size)
+                });
+            }
+            bench.run()
+        }
+        TiledKernel::Transpose2dVecLoopTiling | TiledKernel::Rotate2dVec => {
+            let routine = if kernel == TiledKernel::Rotate2dVec {
+                rotate_2d_vec::<i32>
+            } else {
+                transpose_2d_vec_loop_tiling_aka_block_tiling::<i32>
+            };
+            let mut bench =
+                Bench::new(&title, sizes, |_| random_matrix(1, rows, cols)).config(config);
+            for &block_size in candidates {
+                bench = bench.case_owned(&block_case_name(block_size), move |matrix| {
+                    routine(matrix, block_size)
+                });
+            }
+            bench.run()
+        }
+        TiledKernel::Transpose1dRowMajorLoopTiling => {
+            let mut bench =
+                Bench::new(&title, sizes, |_| i32s_from_seed(1, rows * cols)).config(config);
+            for &block_size in candidates {
+                bench = bench.case_owned(&block_case_name(block_size), move |flat| {
+                    transpose_1d_row_major_matrix_loop_tiling_aka_block_tiling(
+                        flat, cols, block_size,
+                    )
+                });
+            }
+            bench.run()
+        }
+        TiledKernel::TransposeSquareTiled => {
+            // in place on a square matrix, the column count is ignored
+            let mut bench =
+                Bench::new(&title, sizes, |_| i32s_from_seed(1, rows * rows)).config(config);
+            for &block_size in candidates {
+                bench = bench.case_owned(&block_case_name(block_size), move |flat| {
+                    transpose_square_tiled(flat, rows, block_size)
+                });
+            }
+            bench.run()
+        }
+    }
+}
+
+// Benchmarks all candidate block sizes for 4 byte elements and returns the fastest by median
+pub fn tune_kernel(kernel: TiledKernel, shape: TuningShape, config: BenchConfig) -> TuningResult {
+    let shape = match kernel {
+        TiledKernel::TransposeSquareTiled => TuningShape::matrix(shape.rows, shape.rows),
+        _ if !kernel.is_matrix_multiply() => TuningShape::matrix(shape.rows, shape.cols),
+        _ => shape,
+    };
+    let candidates = candidate_block_sizes(kernel, shape, &read_cache_levels(), size_of::<i32>());
+    let report = bench_candidates(kernel, shape, &candidates, config);
+    let (block_size, median) = candidates
+        .iter()
+        .zip(&report.rows[0].stats)
+        .map(|(&block_size, stats)| (block_size, stats.median))
+        .min_by_key(|&(_, median)| median)
+        .unwrap();
+    TuningResult {
+        kernel,
+        shape,
+        block_size,
+        median,
+        report,
+    }
+}
+
+// Stores the results in the in memory store used by tuned_block_size and writes it to the
+// tuning file. Entries for other kernels and shapes already in the file are kept.
+pub fn save_tuning_results(results: &[TuningResult]) -> Result<(), String> {
+    with_store(|store: &mut TuningStore| {
+        store.cpu_model = cpu_model();
+        for result in results {
+            store.insert(result.entry());
+        }
+        store.save(&tuning_file_path())
+    })
+}
+
+#[derive(Debug, Clone)]
+pub struct CrossoverResult {
+    pub variant: StrassenVariant,
+    pub side: usize,
+    pub crossover: usize,
+    pub median: Duration,
+    pub report: BenchReport,
+}
+
+fn crossover_case_name(crossover: usize) -> String {
+    format!("crossover {}", crossover)
+}
+
+// Runs Strassen on a side x side product once per candidate crossover and keeps the fastest.
+// A crossover at or above the side means no recursion at all, i.e. just the tiled kernel.
+pub fn tune_strassen_crossover(
+    variant: StrassenVariant,
+    side: usize,
+    candidates: &[usize],
+    config: BenchConfig,
+) -> CrossoverResult {
+    if candidates.is_empty() {
+        panic!("Need at least one crossover candidate.");
+    }
+    let title = format!("Tuning {} crossover ({}x{})", variant.name(), side, side);
+    let mut bench = Bench::new(&title, vec![side], |_| {
+        (
+            i32s_from_seed(1, side * side),
+            i32s_from_seed(2, side * side),
+        )
+    })
+    .config(config);
+    for &crossover in candidates {
+        bench = bench.case(&crossover_case_name(crossover), move |(a, b)| {
+            matrix_multiply_strassen_flat_row_major(a, b, side, side, side, variant, crossover)
+        });
+    }
+    let report = bench.run();
+    let (crossover, median) = candidates
+        .iter()
+        .zip(&report.rows[0].stats)
+        .map(|(&crossover, stats)| (crossover, stats.median))
+        .min_by_key(|&(_, median)| median)
+        .unwrap();
+    CrossoverResult {
+        variant,
+        side,
+        crossover,
+        median,
+        report,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn quick() -> BenchConfig {
+        BenchConfig {
+            warmup_iterations: 0,
+            measured_iterations: 1,
+            ..BenchConfig::default()
+        }
+    }
+
+    #[test]
+    fn tunes_every_kernel_on_a_small_shape() {
+        for kernel in TiledKernel::ALL {
+            let result = tune_kernel(kernel, TuningShape::matrix_multiply(24, 16, 20), quick());
+            let candidates = &result.report.case_names;
+            assert!(!candidates.is_empty());
+            assert!(candidates.contains(&block_case_name(result.block_size)));
+            assert_eq!(
+                result
+                    .report
+                    .stats(&block_case_name(result.block_size), 24)
+                    .unwrap()
+                    .median,
+                result.median
+            );
+            assert!(result.block_size <= 32);
+        }
+    }
+
+    #[test]
+    fn shapes_are_normalised_per_kernel() {
+        let shape = TuningShape::matrix_multiply(16, 8, 12);
+        let transpose = tune_kernel(TiledKernel::Transpose2dVecLoopTiling, shape, quick());
+        assert_eq!(transpose.shape, TuningShape::matrix(16, 12));
+        let square = tune_kernel(TiledKernel::TransposeSquareTiled, shape, quick());
+        assert_eq!(square.shape, TuningShape::matrix(16, 16));
+        let multiply = tune_kernel(TiledKernel::MatrixMultiplyLoopTiling, shape, quick());
+        assert_eq!(multiply.shape, shape);
+        assert_eq!(multiply.entry().shape, shape);
+    }
+
+    #[test]
+    fn crossover_tuning_picks_a_candidate() {
+        let result = tune_strassen_crossover(StrassenVariant::Winograd, 40, &[8, 16, 64], quick());
+        assert!([8, 16, 64].contains(&result.crossover));
+        assert_eq!(result.report.case_names.len(), 3);
+        assert_eq!(
+            result
+                .report
+                .stats(&crossover_case_name(result.crossover), 40)
+                .unwrap()
+                .median,
+            result.median
+        );
+    }
+}
@@ -4,4 +4,3 @@ pub mod arrays_module3;
 pub mod arrays_module4;
 pub mod arrays_module5;
 pub mod arrays_module6;
-mod common_util;
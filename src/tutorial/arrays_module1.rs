@@ -245,5 +245,5 @@ fn ptr_index_diff<T>(ptr1: *const T, ptr2: *const T) -> usize {
     // }
 
     // Solution 2
-    unsafe { ptr2.offset_from(ptr1).abs() as usize }
+    unsafe { ptr2.offset_from(ptr1).unsigned_abs() }
 }
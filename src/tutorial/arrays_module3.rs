@@ -1,197 +1,34 @@
-use std::alloc::{Layout, alloc, dealloc, realloc};
-use std::{
-    ops::{Index, IndexMut},
-    time::{Duration, Instant},
+use dsa_image_processing::dynamic_array::{
+    growth::{Doubling, Fibonacci, FixedIncrement, Golden, GrowthPolicy, JemallocSizeClasses},
+    my_vector::MyVector,
 };
-struct MyVector<T> {
-    data: *mut T,    // Pointer to start of allocated capacity.
-    size: usize,     // Current number of slots filled.
-    capacity: usize, // Current number of slots available.
-}
-
-// Implement Drop trait so that memory is freed if the program crashes.
-impl<T> Drop for MyVector<T> {
-    fn drop(&mut self) {
-        if !self.data.is_null() {
-            let layout = self.make_memory_layout(self.capacity);
-            unsafe {
-                dealloc(self.data as *mut u8, layout); // Note: need to always case to u8 when deallocating.
-            }
-        }
-    }
-}
-
-// read‑only indexing: &mylist[i] -> &T
-impl<T> Index<usize> for MyVector<T> {
-    type Output = T;
-
-    fn index(&self, idx: usize) -> &T {
-        self.validate_index(idx);
-        unsafe { &*self.data.add(idx) }
-    }
-}
-
-impl<T> MyVector<T> {
-    fn validate_index(&self, idx: usize) {
-        if self.size <= idx {
-            panic!(
-                "index out of bounds: the len is {} but the index is {}",
-                self.size, idx
-            );
-        }
-    }
-
-    pub fn new() -> Self {
-        Self {
-            data: std::ptr::null_mut(),
-            size: 0,
-            capacity: 0,
-        }
-    }
-
-    fn make_memory_layout(&self, capacity: usize) -> Layout {
-        let element_size = size_of::<T>();
-        Layout::from_size_align(capacity * element_size, align_of::<T>()).unwrap()
-    }
-
-    pub fn with_capacity(capacity: usize) -> Self {
-        let mut new_vec = MyVector::new();
-        new_vec.resize_capacity(capacity);
-        new_vec
-    }
-
-    fn resize_capacity(&mut self, capacity: usize) {
-        if self.data.is_null() {
-            // first allocation
-            unsafe {
-                self.data = alloc(self.make_memory_layout(capacity)).cast::<T>();
-            }
-        } else {
-            // resize to bigger memory
-            let current_layout = self.make_memory_layout(self.capacity);
-            unsafe {
-                self.data = realloc(
-                    self.data.cast::<u8>(),
-                    current_layout,
-                    capacity * size_of::<T>(), // Is size in u8 so needs to be multiplied
-                )
-                .cast::<T>();
-            }
-        }
-        self.capacity = capacity;
-    }
-
-    pub fn len(&self) -> usize {
-        self.size
-    }
-
-    // Increases Capacity by 1000 when full
-    pub fn push_fixed(&mut self, element: T) {
-        if self.capacity == self.size {
-            // Add Capacity
-            self.resize_capacity(self.capacity + 1000);
-        }
-        self.push_raw(element);
-    }
-
-    fn push_raw(&mut self, element: T) {
-        unsafe {
-            *self.data.add(self.size) = element;
-        }
-        self.size += 1;
-    }
-
-    // Increases size by golden ratio, except its a aproximation of 1.5 since
-    // integer arithmatic is supose to be faster... meh.
-    pub fn push_golden(&mut self, element: T) {
-        if self.capacity == self.size {
-            // Add Capacity
-            let mut new_cap = self.capacity.max(1).saturating_mul(3).saturating_div(2);
-            if new_cap <= self.capacity {
-                new_cap = self.capacity + 1;
-            }
-            self.resize_capacity(new_cap);
-        }
-        self.push_raw(element);
-    }
-
-    // Doubling capacity with each grow needed.
-    pub fn push_doubleing(&mut self, element: T) {
-        if self.capacity == self.size {
-            // Add Capacity
-            self.resize_capacity(self.capacity.max(1).saturating_mul(2) as usize);
-        }
-        self.push_raw(element);
-    }
-}
-
-// mutable indexing: &mut mylist[i] -> &mut T
-impl<T> IndexMut<usize> for MyVector<T> {
-    fn index_mut(&mut self, idx: usize) -> &mut T {
-        self.validate_index(idx);
-        unsafe { &mut *self.data.add(idx) }
-    }
-}
+use std::time::{Duration, Instant};
 
 pub fn arrays_module3_dynamic_arrays_and_amortized_analysis() {
     println!("Image Processing DSA - Module 3: Dynamic Arrays & Amortized Analysis");
     let _start = Instant::now(); // warming up the timer.
-    println!("==>Testing push_fixed");
-    let mut my_vector = MyVector::new();
-    my_vector.push_fixed(8);
-    my_vector.push_fixed(9);
-    my_vector.push_fixed(10);
-
-    println!(
-        "The values in my_vector are {}, {}, {}",
-        my_vector[0], my_vector[1], my_vector[2]
-    );
-    println!("There are {} elements.", my_vector.len());
-    println!();
-    let mut my_vector = MyVector::with_capacity(200);
-    my_vector.push_fixed(12);
-    println!(
-        "New vector capacity 200 with 1 element size is {} elements.",
-        my_vector.len()
-    );
-
-    println!("==>Testing push_golden");
-    let mut my_vector = MyVector::new();
-    my_vector.push_golden(8);
-    my_vector.push_golden(9);
-    my_vector.push_golden(10);
-
-    println!(
-        "The values in my_vector are {}, {}, {}",
-        my_vector[0], my_vector[1], my_vector[2]
-    );
-    println!("There are {} elements.", my_vector.len());
-    println!();
-    let mut my_vector = MyVector::with_capacity(200);
-    my_vector.push_golden(12);
+    test_growth_policy::<FixedIncrement>();
+    test_growth_policy::<Golden>();
+    test_growth_policy::<Doubling>();
+
+    println!("==>Testing the rest of the Vec like API");
+    let mut my_vector: MyVector<String> = ["a", "b", "c"].iter().map(|s| s.to_string()).collect();
+    my_vector.insert(1, String::from("inserted"));
+    let removed = my_vector.remove(0);
+    let popped = my_vector.pop();
+    my_vector.extend([String::from("d"), String::from("e")]);
     println!(
-        "New vector capacity 200 with 1 element size is {} elements.",
-        my_vector.len()
-    );
-
-    println!("==>Testing push_doubling");
-    let mut my_vector = MyVector::new();
-    my_vector.push_doubleing(8);
-    my_vector.push_doubleing(9);
-    my_vector.push_doubleing(10);
-
-    println!(
-        "The values in my_vector are {}, {}, {}",
-        my_vector[0], my_vector[1], my_vector[2]
-    );
-    println!("There are {} elements.", my_vector.len());
-    println!();
-    let mut my_vector = MyVector::with_capacity(200);
-    my_vector.push_doubleing(12);
-    println!(
-        "New vector capacity 200 with 1 element size is {} elements.",
-        my_vector.len()
+        "{:?} after insert, remove ({}), pop ({:?}) and extend, {} of {} slots used.",
+        my_vector,
+        removed,
+        popped,
+        my_vector.len(),
+        my_vector.capacity()
     );
+    my_vector.truncate(2);
+    my_vector.shrink_to_fit();
+    let joined: Vec<String> = my_vector.into_iter().collect();
+    println!("Truncated to 2, shrunk to fit and moved out: {:?}", joined);
 
     print!("\n\n");
     println!("==>Begining capturing performance info...");
@@ -204,46 +41,110 @@ pub fn arrays_module3_dynamic_arrays_and_amortized_analysis() {
         50_000, 60_000, 70_000, 80_000, 90_000, 100_000, 200_000, 300_000, 400_000, 500_000,
         600_000, 700_000, 800_000, 900_000, 1_000_000,
     ] {
-        let start = Instant::now();
-        create_my_vec_push_fixed(size);
-        let fixed_time = start.elapsed();
-        let start = Instant::now();
-        create_my_vec_push_golden(size);
-        let golden_time = start.elapsed();
-        let start = Instant::now();
-        create_my_vec_push_doubleing(size);
-        let doubling_time = start.elapsed();
+        let (fixed_time, _) = time_create_my_vec::<FixedIncrement>(size);
+        let (golden_time, _) = time_create_my_vec::<Golden>(size);
+        let (doubling_time, _) = time_create_my_vec::<Doubling>(size);
         print_three_results(size, fixed_time, golden_time, doubling_time);
     }
+
+    print!("\n\n");
+    copy_costs_per_growth_policy();
 }
 
-fn create_my_vec_push_fixed(element_count: usize) -> MyVector<i32> {
-    let mut my_vec = MyVector::new(); // We not using with capacity so we can test the growth speed.
+fn test_growth_policy<P: GrowthPolicy>() {
+    println!("==>Testing {} growth", P::name());
+    let mut my_vector: MyVector<i32, P> = MyVector::new_with_policy();
+    my_vector.push(8);
+    my_vector.push(9);
+    my_vector.push(10);
 
-    for i in 1..=(element_count as i32) {
-        my_vec.push_fixed(i);
-    }
-    my_vec
+    println!(
+        "The values in my_vector are {}, {}, {}",
+        my_vector[0], my_vector[1], my_vector[2]
+    );
+    println!(
+        "There are {} elements, capacity {}.",
+        my_vector.len(),
+        my_vector.capacity()
+    );
+    println!();
+    let mut my_vector: MyVector<i32, P> = MyVector::with_capacity_and_policy(200);
+    my_vector.push(12);
+    println!(
+        "New vector capacity 200 with 1 element size is {} elements.",
+        my_vector.len()
+    );
 }
 
-fn create_my_vec_push_golden(element_count: usize) -> MyVector<i32> {
-    let mut my_vec = MyVector::new(); // We not using with capacity so we can test the growth speed.
+// Returns how long the pushes took and what the growth cost the allocator
+fn time_create_my_vec<P: GrowthPolicy>(element_count: usize) -> (Duration, MyVector<i32, P>) {
+    let start = Instant::now();
+    // We not using with capacity so we can test the growth speed.
+    let mut my_vec: MyVector<i32, P> = MyVector::new_with_policy();
 
     for i in 1..=(element_count as i32) {
-        my_vec.push_golden(i);
+        my_vec.push(i);
     }
-    my_vec
+    (start.elapsed(), my_vec)
 }
 
-fn create_my_vec_push_doubleing(element_count: usize) -> MyVector<i32> {
-    let mut my_vec = MyVector::new(); // We not using with capacity so we can test the growth speed.
-
+// Pushes into two vectors in turn, so each one's block keeps running into the other's and
+// realloc has to move it far more often than for a vector growing alone at the heap's top.
+fn create_two_my_vecs_side_by_side<P: GrowthPolicy>(element_count: usize) -> MyVector<i32, P> {
+    let mut my_vec: MyVector<i32, P> = MyVector::new_with_policy();
+    let mut neighbour: MyVector<i32, P> = MyVector::new_with_policy();
     for i in 1..=(element_count as i32) {
-        my_vec.push_doubleing(i);
+        my_vec.push(i);
+        neighbour.push(i);
     }
     my_vec
 }
 
+// The mini-challenge: total copies for n pushes per policy. "worst case" is the textbook count
+// where every grow copies every element, "copied" is what realloc really had to copy: it
+// grows a block in place when the memory after it is free, and big blocks are remapped by the
+// OS instead of copied, so the measured cost sits well under the worst case.
+fn copy_costs_per_growth_policy() {
+    println!("==>Copy costs of each growth policy (i32 elements)");
+    println!(
+        "Data Size | Policy    |  Time (µs) | Reallocs | Worst case copies | Copied alone | Copied side by side (moves) | Wasted KB"
+    );
+    println!(
+        "----------|-----------|------------|----------|-------------------|--------------|-----------------------------|----------"
+    );
+    for size in [1000, 10_000, 100_000, 1_000_000] {
+        print_copy_costs::<FixedIncrement>(size);
+        print_copy_costs::<Golden>(size);
+        print_copy_costs::<Fibonacci>(size);
+        print_copy_costs::<Doubling>(size);
+        print_copy_costs::<JemallocSizeClasses>(size);
+        println!();
+    }
+    println!("Worst case, +1000 copies n^2 / 2000 elements, the factors under n / (factor - 1).");
+    println!("A larger factor copies less but wastes more capacity right after a grow.");
+    println!("Measured, realloc hides most of it while the memory after the block is free,");
+    println!("but side by side +1000 goes back to copying many times n while the factors stay ~n.");
+}
+
+fn print_copy_costs<P: GrowthPolicy>(size: usize) {
+    let (time, my_vec) = time_create_my_vec::<P>(size);
+    let stats = my_vec.growth_stats();
+    let side_by_side = create_two_my_vecs_side_by_side::<P>(size).growth_stats();
+    let element_size = std::mem::size_of::<i32>();
+    println!(
+        "{:<9} | {:<9} | {:>10} | {:>8} | {:>17} | {:>12} | {:>19} ({:>5}) | {:>9.1}",
+        size,
+        P::name(),
+        time.as_micros(),
+        stats.reallocations,
+        stats.worst_case_bytes_copied / element_size,
+        stats.bytes_copied / element_size,
+        side_by_side.bytes_copied / element_size,
+        side_by_side.moves,
+        my_vec.wasted_capacity_bytes() as f64 / 1024.0
+    );
+}
+
 fn print_three_results(n: usize, f: Duration, g: Duration, d: Duration) {
     let f_uq = f.as_micros() as f64;
     let g_uq = g.as_micros() as f64;
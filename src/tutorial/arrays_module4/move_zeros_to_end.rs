@@ -1,5 +1,6 @@
-use crate::tutorial::common_util::{
-    print_header_one_metric_result, print_output_one_metric_result,
+use dsa_image_processing::{
+    array::partition::move_zeros_to_end,
+    bench::report::{print_header_one_metric_result, print_output_one_metric_result},
 };
 use std::time::Instant;
 
@@ -40,30 +41,3 @@ fn create_array_with_zeros(element_count: usize) -> Vec<i32> {
     }
     arr
 }
-
-fn move_zeros_to_end(arr: &mut [i32]) {
-    // Move non 0's forward
-    let mut write_pos = 0;
-    for read_pos in 0..arr.len() {
-        if arr[read_pos] != 0 {
-            // Move to write pos
-            arr[write_pos] = arr[read_pos];
-            // Move write pos forward.
-            write_pos += 1;
-        }
-    }
-
-    // Fill remainder from write pos to end with 0's
-    // Insight: no need to care about the 0's at all, rather we were making sure
-    // the non zero ordering is maintained.
-    for i in write_pos..arr.len() {
-        arr[i] = 0;
-    }
-}
-
-#[test]
-fn test_move_zeros_to_end_flag() {
-    let mut arr = vec![0, 1, 0, 3, 12];
-    move_zeros_to_end(&mut arr);
-    assert_eq!(arr, vec![1, 3, 12, 0, 0]);
-}
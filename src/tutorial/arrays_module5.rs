@@ -6,6 +6,7 @@ mod leetcode_76_min_sub_string_contains_t_chars_including_dupes;
 mod max_sum_for_k_slice;
 mod min_slice_len_which_sums_ge_target;
 mod prefix_sum_arrays;
+mod streaming_subarray_sums;
 mod suffix_sum_arrays;
 
 use challenge_count_sub_arrays_sum_k::challenge_count_sub_arrays_sum_k;
@@ -16,6 +17,7 @@ use leetcode_560_count_subarray_sum_equals_k::bonus_leetcode_560_subarray_sum_eq
 use max_sum_for_k_slice::max_sum_for_k_slice;
 use min_slice_len_which_sums_ge_target::min_slice_len_which_sums_ge_target;
 use prefix_sum_arrays::prefix_sum_arrays;
+use streaming_subarray_sums::bonus_streaming_subarray_sums;
 use suffix_sum_arrays::suffix_sum_arrays;
 
 // Technique's demonstrated:
@@ -76,4 +78,8 @@ pub fn arrays_module5_sliding_windows_and_prefix_sum_tricks() {
     // Bonus 3: Leetcode 76: Minimum Window Substring
     //https://leetcode.com/problems/minimum-window-substring/description/
     bonus_leetcode_76_min_sub_string_contains_t_chars_including_dupes();
+
+    // Bonus 4: The Leetcode 560 prefix sum + hashmap trick over a stream instead of a slice,
+    // plus the longest subarray summing to k and subarray sums divisible by k.
+    bonus_streaming_subarray_sums();
 }
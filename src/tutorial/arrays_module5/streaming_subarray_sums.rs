@@ -0,0 +1,87 @@
+use dsa_image_processing::{
+    array::{
+        streaming_sum::{
+            DivisibleSubarrayCounter, LongestSubarrayWithSum, SubarraySumCounter,
+            count_subarrays_sum_k_streaming, for_each_number, running_prefix_sums,
+        },
+        subarray_sum::count_sub_arrays_sum_k_using_prefix_and_hashmap,
+    },
+    bench::{data::XorShift64, harness::Bench, report::TimeUnit},
+};
+use std::io::Cursor;
+
+// Bonus 4: the prefix sum + hashmap trick from challenge_count_sub_arrays_sum_k and
+// Leetcode 560 never needs the array itself, only the prefix sums seen so far. So it can
+// consume a sensor log one number at a time instead of loading it all first.
+pub fn bonus_streaming_subarray_sums() {
+    println!(
+        "==> Bonus Streaming prefix sums - subarray sum = k counts, longest sum = k and sum divisible by k over a stream"
+    );
+    streaming_subarray_sums_example();
+    streaming_subarray_sums_performance_compare();
+}
+
+fn streaming_subarray_sums_example() {
+    // Temperature deltas from a sensor, the same pass feeds every counter
+    let log = "# sensor 7 deltas\n3, 4, -2\n1 -1 0\n5,-5\n2\n";
+    let mut sum_counter = SubarraySumCounter::new(5);
+    let mut longest = LongestSubarrayWithSum::new(5);
+    let mut divisible_counter = DivisibleSubarrayCounter::new(3);
+    let mut values = Vec::new();
+    let number_count = for_each_number(Cursor::new(log), |value| {
+        sum_counter.push(value);
+        longest.push(value);
+        divisible_counter.push(value);
+        values.push(value);
+    })
+    .unwrap();
+    println!("read {} numbers: {:?}", number_count, values);
+    println!(
+        "running prefix sums: {:?}",
+        running_prefix_sums(values.iter().copied()).collect::<Vec<i64>>()
+    );
+    println!("subarrays summing to 5: {}", sum_counter.count());
+    println!(
+        "longest subarray summing to 5 (l, r): {:?}",
+        longest.longest()
+    );
+    println!(
+        "subarrays with a sum divisible by 3: {}\n",
+        divisible_counter.count()
+    );
+}
+
+fn streaming_subarray_sums_performance_compare() {
+    let start_size = 1024;
+    let number_of_doubles = 8;
+    let arr_sizes: Vec<usize> = (0..number_of_doubles).map(|i| start_size << i).collect();
+    let k = 10;
+    Bench::new("Slice vs stream", arr_sizes, |size| {
+        let mut rng = XorShift64::new(560);
+        (0..size)
+            .map(|_| (rng.next_u64() % 21) as i32 - 10)
+            .collect::<Vec<i32>>()
+    })
+    .unit(TimeUnit::Micros)
+    .measured_iterations(100)
+    .case("slice + hashmap", |arr| {
+        count_sub_arrays_sum_k_using_prefix_and_hashmap(arr, k)
+    })
+    .case("stream + hashmap", |arr| {
+        count_subarrays_sum_k_streaming(arr.iter().copied(), k as i64)
+    })
+    // Generated on the fly, nothing is ever stored but the hashmap
+    .case("generated stream", |arr| {
+        let mut rng = XorShift64::new(560);
+        let stream = (0..arr.len()).map(|_| (rng.next_u64() % 21) as i32 - 10);
+        count_subarrays_sum_k_streaming(stream, k as i64)
+    })
+    .compare("slice + hashmap", "stream + hashmap")
+    .compare("slice + hashmap", "generated stream")
+    .run()
+    .print_ratio_table();
+    println!("Same work per value, the stream pays for i64 prefix sums as hashmap keys and in");
+    println!("exchange never keeps the values, only the hashmap.");
+
+    print!("\n\n");
+}
@@ -1,11 +1,41 @@
+mod access_order_heatmaps;
 mod basic_matrix_operations;
+mod block_size_auto_tuning;
+mod cache_simulation;
+mod curve_layouts;
+mod explicit_simd;
+mod linear_algebra;
 mod matrix_multiplication;
+mod max_submatrix_sum;
+mod packed_gemm;
+mod parallel_kernels;
 mod prefix_sums_2d;
+mod range_queries;
+mod sparse_matrices;
+mod tile_pool;
+mod tiled_loop_visualizer;
+mod transposed_operands;
+mod traversal_orders;
 mod warm_up_2d_arrays_and_flattening_them;
 
+use access_order_heatmaps::access_order_heatmaps;
 use basic_matrix_operations::basic_matrix_operations;
+use block_size_auto_tuning::block_size_auto_tuning;
+use cache_simulation::cache_simulation;
+use curve_layouts::curve_layouts;
+use explicit_simd::explicit_simd;
+use linear_algebra::linear_algebra;
 use matrix_multiplication::matrix_multiplication;
+use max_submatrix_sum::max_submatrix_sum;
+use packed_gemm::packed_gemm;
+use parallel_kernels::parallel_kernels;
 use prefix_sums_2d::prefix_sums_2d;
+use range_queries::range_queries;
+use sparse_matrices::sparse_matrices;
+use tile_pool::tile_pool;
+use tiled_loop_visualizer::tiled_loop_visualizer;
+use transposed_operands::transposed_operands;
+use traversal_orders::traversal_orders;
 use warm_up_2d_arrays_and_flattening_them::warm_up_2d_arrays_and_flattening_them;
 
 pub fn arrays_module6_multi_dimensional_arrays_and_cache() {
@@ -29,26 +59,59 @@ pub fn arrays_module6_multi_dimensional_arrays_and_cache() {
     // 3b) Optimize: precompute transpose(B) and use it to get cache-friendly row access.
     // 3c) Loop Tiling / Blocking in Matrix Multiplication
     // Note: Experement with blocksizes 32,64,128
+    // (the search is automated by `cargo run --release -- tune`, see 3e below)
+    // (step through the tiled loops with `cargo run --release -- visualize`)
     // 3d) Compare the performance of all 3 approaches using The performance comparison
     // suggestion of all three matrix multiplication approaches.
     matrix_multiplication();
+    // 3e) Block size auto-tuning: too slow to run with the rest of the module,
+    // run it on its own with arrays_module6_block_size_auto_tuning.
+    // 3f) Morton and Hilbert curve layouts: every quadrant is contiguous, so the recursive
+    // transpose and multiply are blocked at every size. Benchmarked against the tiled kernels.
+    curve_layouts();
+    // 3g) Packed GEMM: A and B copied into micro-panels for a 4x8 register blocked
+    // micro-kernel, f32 and f64, C = alpha*A*B + beta*C.
+    packed_gemm();
+    // 3h) Explicit SIMD: SSE2/AVX2 kernels picked at runtime with is_x86_feature_detected,
+    // used by the GEMM micro-kernel above, checked bit for bit against the scalar fallback.
+    explicit_simd();
+    // 3i) Multithreaded matmul, transpose, blur and two pass prefix sum on scoped threads,
+    // one band of output rows per thread, with a speedup table per thread count.
+    parallel_kernels();
+    // 3j) The same threads kept alive in a work-stealing pool and handed tiles, for the
+    // small images where spawning per call costs more than the work (transpose, rotate, blur).
+    tile_pool();
+    // 3k) op(A)·op(B) and op(A)·x with BLAS style transpose flags instead of transposing
+    // first, and a batched API for lots of small products of the same shape.
+    transposed_operands();
+    // 3l) Going the other way: LU and QR for determinant, inverse, solve and least squares.
+    linear_algebra();
+    // 3m) Mostly zero matrices: COO/CSR/CSC storage, sparse products and transpose.
+    sparse_matrices();
 
     // 4) 2D Prefix Sums
     // 4a) Implement the Prefix sum
     // 4b) Implement Submatrix sums using inclusion-exclusion.
+    // 4c) Point updates without rebuilding: 2D Fenwick tree vs rebuilding the prefix matrix.
     prefix_sums_2d();
+    // 4d) Range min/max/gcd: sparse tables and segment trees, 1D and over image regions.
+    range_queries();
 
-    // [NOT GONNA DO THIS: It is obvious that row-major out performs col-major.]
     // 5) Cache-Friendly vs. Cache-Unfriendly Traversals
     // 5a) Row-major sums
     // 5b) Col-major sums
     // 5c) Evaluate Claim: row-major is faster on large matrices.
+    // Timing it was obvious enough, so instead count the cache hits/misses with a simulator.
+    cache_simulation();
+    // ...and drawn as heatmaps with `cargo run --release -- heatmap`
 
-    // 6) Advanced Patterns [NOT GONNA DO THIS: spiral is obvious, and Kadane's
-    //      2D Extension is some complicated edge case stuff, I'll maybe come back
-    //      after I come back from my other projects.]
-    // 6a) Spiral Traversal
-    // 6b) Maximum submatrix sum (Kadane's 2D Extension)
+    // 6) Advanced Patterns
+    // 6a) Spiral Traversal, came back for it once the other orders (zig-zag, diagonal, snake,
+    //     border rings) were needed as reusable iterators too.
+    traversal_orders();
+    // 6b) Maximum submatrix sum (Kadane's 2D Extension), plus the largest all-ones
+    //     rectangle in a mask using the same row squashing.
+    max_submatrix_sum();
 
     // While interesting 7) really is duplication of the eventual module 7 and
     // the final project so we leave this off till then.
@@ -61,3 +124,18 @@ pub fn arrays_module6_multi_dimensional_arrays_and_cache() {
     // 7e) Blur (replace each pixel with average of it's neighbors)
     // 7f) Edge detection (difference with neighbors)
 }
+
+pub fn arrays_module6_tiled_loop_visualizer() {
+    println!("Image Processing DSA - Module 6: Loop Tiling Visualizer");
+    tiled_loop_visualizer();
+}
+
+pub fn arrays_module6_access_order_heatmaps() {
+    println!("Image Processing DSA - Module 6: Access Order Heatmaps");
+    access_order_heatmaps();
+}
+
+pub fn arrays_module6_block_size_auto_tuning() {
+    println!("Image Processing DSA - Module 6: Block Size Auto-Tuning");
+    block_size_auto_tuning();
+}
@@ -0,0 +1,70 @@
+use dsa_image_processing::{
+    matrix::traversal::Traversal,
+    visualize::{
+        access_order::{
+            AccessOrder, col_major_sum_access_order, hilbert_order_access_order,
+            row_major_sum_access_order, transpose_write_access_order, traversal_access_order,
+            z_order_access_order,
+        },
+        heatmap::{SvgOptions, print_access_order, write_heatmaps},
+    },
+};
+use std::{fs, path::Path};
+
+const OUTPUT_DIR: &str = "access-order";
+
+// 5) Cache-Friendly vs. Cache-Unfriendly Traversals, drawn: the order each traversal visits
+// the cells in, written as SVG/PGM heatmaps (dark = early, bright = late).
+pub fn access_order_heatmaps() {
+    println!("==> 5) Access order heatmaps\n");
+
+    // Small enough to read as a table
+    let size = 8;
+    for order in traversals(size) {
+        print_access_order(&order);
+    }
+
+    let size = 64;
+    if let Err(e) = fs::create_dir_all(OUTPUT_DIR) {
+        println!("Could not create {}: {}", OUTPUT_DIR, e);
+        return;
+    }
+    println!(
+        "Mean distance in row-major memory between consecutive visits ({0}x{0}):",
+        size
+    );
+    for order in traversals(size) {
+        let stem = Path::new(OUTPUT_DIR).join(file_stem(&order.name));
+        match write_heatmaps(&order, &stem, SvgOptions::default()) {
+            Ok((svg, pgm)) => println!(
+                " {:<32} {:>8.1}   {} {}",
+                order.name,
+                order.mean_row_major_stride(),
+                svg.display(),
+                pgm.display()
+            ),
+            Err(e) => println!("{}", e),
+        }
+    }
+}
+
+fn traversals(size: usize) -> Vec<AccessOrder> {
+    vec![
+        row_major_sum_access_order(size, size),
+        col_major_sum_access_order(size, size),
+        transpose_write_access_order(size, size, size),
+        transpose_write_access_order(size, size, 4),
+        z_order_access_order(size, size),
+        hilbert_order_access_order(size, size),
+        traversal_access_order(Traversal::SpiralClockwise, size, size),
+        traversal_access_order(Traversal::ZigZag, size, size),
+    ]
+}
+
+// "tiled transpose 4x4 (writes)" -> "tiled-transpose-4x4-writes"
+fn file_stem(name: &str) -> String {
+    name.split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|part| !part.is_empty())
+        .collect::<Vec<_>>()
+        .join("-")
+}
@@ -1,4 +1,6 @@
-use crate::tutorial::common_util::{make_matrix, print_matrix_2d_vec_matrix};
+use dsa_image_processing::matrix::{
+    display::print_matrix_2d_vec_matrix, make_matrix, rotate::rotate_2d_vec,
+};
 
 pub fn rotate_90_degrees_clockwise() {
     println!("---> Rotate 90 Degrees Clockwise (Rect)");
@@ -11,32 +13,3 @@ pub fn rotate_90_degrees_clockwise() {
     println!("After Rotate 2d Vec<Vec<T>>:");
     print_matrix_2d_vec_matrix(&rotated_matrix);
 }
-
-fn rotate_2d_vec<T: Copy>(matrix: Vec<Vec<T>>, block_size: usize) -> Vec<Vec<T>> {
-    let row_count = matrix.len();
-
-    if row_count < 1 || matrix[0].len() < 1 {
-        panic!("Matrix must be a 2d matrix with at least 1 element.");
-    }
-    let col_count = matrix[0].len();
-
-    // Make new rows vector based on amount of columns
-    let mut new_matrix = vec![vec![matrix[0][0]; row_count]; col_count];
-
-    for block_start_row_idx in (0..row_count).step_by(block_size) {
-        for block_start_col_idx in (0..col_count).step_by(block_size) {
-            // Determine the row/cell ends (some blocks get chopped off)
-            let row_end = (block_start_row_idx + block_size).min(row_count);
-            let col_end = (block_start_col_idx + block_size).min(col_count);
-
-            // Transpose the block
-            for cell_row_idx in block_start_row_idx..row_end {
-                for cell_col_idx in block_start_col_idx..col_end {
-                    new_matrix[cell_col_idx][row_count - cell_row_idx - 1] =
-                        matrix[cell_row_idx][cell_col_idx];
-                }
-            }
-        }
-    }
-    new_matrix
-}
@@ -1,7 +1,14 @@
-use crate::tutorial::common_util::{
-    make_matrix, print_header, print_output_row_ratio_compare_result_nanos,
+use dsa_image_processing::{
+    bench::harness::Bench,
+    matrix::{
+        make_matrix,
+        sum::{
+            col_sum_matrix, row_sum_matrix, row_sum_matrix_iterator, row_sum_matrix_simd,
+            row_sum_matrix_trying_to_coax_llvm_to_optimize_using_simd,
+        },
+    },
 };
-use std::time::{Duration, Instant};
+use std::time::Instant;
 
 pub fn row_sum_column_sum() {
     row_sum_column_sum_impl();
@@ -38,135 +45,35 @@ fn row_sum_column_sum_impl() {
     println!("Col Sum Result: {} in {:?}", sum, start.elapsed());
 }
 
-fn row_sum_matrix_iterator(matrix: &Vec<Vec<i32>>) -> i32 {
-    matrix.iter().map(|row| row.iter().sum::<i32>()).sum()
-}
-
-fn row_sum_matrix(matrix: &Vec<Vec<i32>>) -> i32 {
-    let row_count = matrix.len();
-    let col_count = matrix[0].len();
-    let mut sum = 0;
-    for row_idx in 0..row_count {
-        for col_idx in 0..col_count {
-            sum += matrix[row_idx][col_idx];
-        }
-    }
-    sum
-}
-
-fn row_sum_matrix_trying_to_coax_llvm_to_optimize_using_simd(matrix: &Vec<Vec<i32>>) -> i32 {
-    let mut sum = 0;
-    for row in matrix {
-        for value in row {
-            sum += value;
-        }
-    }
-    sum
-}
-
-fn col_sum_matrix(matrix: &Vec<Vec<i32>>) -> i32 {
-    let row_count = matrix.len();
-    let col_count = matrix[0].len();
-    let mut sum = 0;
-    for col_idx in 0..col_count {
-        for row_idx in 0..row_count {
-            sum += matrix[row_idx][col_idx];
-        }
-    }
-    sum
-}
-
 fn row_sum_column_sum_benchmarks() {
-    println!(
-        "---> Column Sum vs Row Sum-For vs Row Sum-Itterator vs Row Sum-Coax-LLVM-to-SIMD-Vector-Optimize"
-    );
-    let columns = [
-        "Data Size^2",
-        "Column-based (ns)",
-        "Row-based For (ns)",
-        "      Ratio", // Ratio values getting a bit big so hacking a bit of space
-        "Row-based For (ns)",
-        "Row-based Itterator (ns)",
-        "      Ratio", // Ratio values getting a bit big so hacking a bit of space
-        "Row-based Itterator (ns)",
-        "Row-based coax llvm to simd vector optimize (ns)",
-        "      Ratio",
-    ];
-    print_header(&columns);
     let start_size = 256;
     let number_of_doubles = 6;
     // starting at
     let arr_sizes: Vec<usize> = (0..number_of_doubles).map(|i| start_size << i).collect();
-
-    for size in &arr_sizes {
-        let mut arr_time_col: Vec<Duration> = Vec::with_capacity(10);
-        let mut arr_time_row_iter: Vec<Duration> = Vec::with_capacity(10);
-        let mut arr_time_row: Vec<Duration> = Vec::with_capacity(10);
-        let mut arr_time_row_llvm_coax: Vec<Duration> = Vec::with_capacity(10);
-
-        for _ in 0..10 {
-            let matrix = make_matrix(size.clone(), size.clone(), 1);
-            let start = Instant::now();
-            let _sum = col_sum_matrix(&matrix);
-            std::hint::black_box(_sum);
-            arr_time_col.push(start.elapsed());
-        }
-
-        for _ in 0..10 {
-            let matrix = make_matrix(size.clone(), size.clone(), 1);
-            let start = Instant::now();
-            let _sum = row_sum_matrix(&matrix);
-            std::hint::black_box(_sum);
-            arr_time_row.push(start.elapsed());
-        }
-
-        for _ in 0..10 {
-            let matrix = make_matrix(size.clone(), size.clone(), 1);
-            let start = Instant::now();
-            let _sum = row_sum_matrix_iterator(&matrix);
-            std::hint::black_box(_sum);
-            arr_time_row_iter.push(start.elapsed());
-        }
-
-        for _ in 0..10 {
-            let matrix = make_matrix(size.clone(), size.clone(), 1);
-            let start = Instant::now();
-            let _sum = row_sum_matrix_trying_to_coax_llvm_to_optimize_using_simd(&matrix);
-            std::hint::black_box(_sum);
-            arr_time_row_llvm_coax.push(start.elapsed());
-        }
-
-        let time_col = Duration::from_nanos(
-            (arr_time_col.iter().map(|d| d.as_nanos()).sum::<u128>() / arr_time_col.len() as u128)
-                as u64,
-        );
-
-        let time_row = Duration::from_nanos(
-            (arr_time_row.iter().map(|d| d.as_nanos()).sum::<u128>() / arr_time_row.len() as u128)
-                as u64,
-        );
-
-        let time_row_iter = Duration::from_nanos(
-            (arr_time_row_iter.iter().map(|d| d.as_nanos()).sum::<u128>()
-                / arr_time_row_iter.len() as u128) as u64,
-        );
-
-        let time_row_llvm_coax = Duration::from_nanos(
-            (arr_time_row_llvm_coax
-                .iter()
-                .map(|d| d.as_nanos())
-                .sum::<u128>()
-                / arr_time_row_llvm_coax.len() as u128) as u64,
-        );
-
-        print_output_row_ratio_compare_result_nanos(
-            &columns,
-            *size,
-            vec![
-                (time_col, time_row),
-                (time_row, time_row_iter),
-                (time_row_iter, time_row_llvm_coax),
-            ],
-        );
-    }
+    Bench::new(
+        "Column Sum vs Row Sum-For vs Row Sum-Itterator vs Row Sum-Coax-LLVM-to-SIMD-Vector-Optimize",
+        arr_sizes,
+        |size| make_matrix(size, size, 1),
+    )
+    .size_column("Data Size^2")
+    .case("Column-based", |matrix| col_sum_matrix(matrix))
+    .case("Row-based For", |matrix| row_sum_matrix(matrix))
+    .case("Row-based Itterator", |matrix| row_sum_matrix_iterator(matrix))
+    .case("Row-based coax llvm to simd vector optimize", |matrix| {
+        row_sum_matrix_trying_to_coax_llvm_to_optimize_using_simd(matrix)
+    })
+    // SSE2/AVX2 written out by hand, see 3h
+    .case("Row-based explicit SIMD", |matrix| row_sum_matrix_simd(matrix))
+    .compare("Column-based", "Row-based For")
+    .compare("Row-based For", "Row-based Itterator")
+    .compare(
+        "Row-based Itterator",
+        "Row-based coax llvm to simd vector optimize",
+    )
+    .compare(
+        "Row-based coax llvm to simd vector optimize",
+        "Row-based explicit SIMD",
+    )
+    .run()
+    .print_ratio_table();
 }
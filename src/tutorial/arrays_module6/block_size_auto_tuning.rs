@@ -0,0 +1,67 @@
+use dsa_image_processing::{
+    bench::harness::BenchConfig,
+    tuning::{
+        TiledKernel, TuningShape,
+        cache::read_cache_levels,
+        heuristic_block_size,
+        store::tuning_file_path,
+        tuner::{save_tuning_results, tune_kernel},
+    },
+};
+
+// 3e) Instead of guessing 32/64/128 search the block sizes per kernel and shape and
+// remember the winners, the *_tuned kernels pick them up from the tuning file.
+pub fn block_size_auto_tuning() {
+    println!("==> 3e) Block size auto-tuning\n");
+    let caches = read_cache_levels();
+    if caches.is_empty() {
+        println!("No cache information found, candidates fall back to powers of two up to 256");
+    }
+    for cache in &caches {
+        println!(
+            "L{} {:?}: {} KiB, {} byte lines, {}-way",
+            cache.level,
+            cache.kind,
+            cache.size_bytes / 1024,
+            cache.line_size,
+            cache.ways
+        );
+    }
+
+    let config = BenchConfig {
+        warmup_iterations: 1,
+        measured_iterations: 5,
+        ..BenchConfig::default()
+    };
+    let mut results = Vec::new();
+    for kernel in TiledKernel::ALL {
+        // matmul is O(n^3) so it gets smaller matrices than the O(n^2) transposes
+        let sizes = if kernel.is_matrix_multiply() {
+            [128, 256, 512]
+        } else {
+            [512, 1024, 2048]
+        };
+        for size in sizes {
+            let shape = if kernel.is_matrix_multiply() {
+                TuningShape::matrix_multiply(size, size, size)
+            } else {
+                TuningShape::matrix(size, size)
+            };
+            let result = tune_kernel(kernel, shape, config);
+            result.report.print_summary();
+            println!(
+                "best block for {} at {}: {} (L1 heuristic {})\n",
+                kernel.name(),
+                size,
+                result.block_size,
+                heuristic_block_size(kernel, &caches, size_of::<i32>())
+            );
+            results.push(result);
+        }
+    }
+
+    match save_tuning_results(&results) {
+        Ok(()) => println!("Saved tuning results to {}", tuning_file_path().display()),
+        Err(message) => println!("Could not save tuning results: {}", message),
+    }
+}
@@ -0,0 +1,176 @@
+use dsa_image_processing::{
+    bench::harness::Bench,
+    matrix::{
+        curve_layout::{
+            CurveLayout, CurveMatrix, matrix_multiply_curve_matrix, transpose_curve_matrix,
+        },
+        display::print_matrix_1d_vec_matrix,
+        flatten::flatten_row_major,
+        make_matrix,
+        multiply::matrix_multiply_loop_tiling_flat_row_major,
+        transpose::transpose_1d_row_major_matrix_loop_tiling_aka_block_tiling,
+    },
+};
+
+const LAYOUTS: [CurveLayout; 2] = [CurveLayout::Morton, CurveLayout::Hilbert];
+
+pub fn curve_layouts() {
+    println!("==> 3f) Morton and Hilbert curve layouts\n");
+    // 3f) Instead of picking a block size, store the matrix so that every quadrant is
+    // contiguous: a Z (Morton) or Hilbert curve through the cells.
+    curve_layout_indices();
+    curve_layout_transpose_and_multiply();
+    curve_layout_performance_compare();
+}
+
+fn curve_layout_indices() {
+    let side = 8;
+    for layout in LAYOUTS {
+        println!(
+            "--> Position of each cell along the {} curve",
+            layout.name()
+        );
+        let mut positions = Vec::with_capacity(side * side);
+        for row_idx in 0..side {
+            for col_idx in 0..side {
+                positions.push(layout.encode(side, row_idx, col_idx) as i32);
+            }
+        }
+        print_matrix_1d_vec_matrix(&positions, side);
+    }
+}
+
+fn curve_layout_transpose_and_multiply() {
+    let a_row_count = 5;
+    // must match
+    let a_col_count = 6;
+    let b_col_count = 3;
+
+    let matrix_a_flat = flatten_row_major(&make_matrix(a_row_count, a_col_count, 0));
+    let matrix_b_flat = flatten_row_major(&make_matrix(a_col_count, b_col_count, 0));
+    println!("[A] = ");
+    print_matrix_1d_vec_matrix(&matrix_a_flat, a_col_count);
+
+    for layout in LAYOUTS {
+        println!("--> {} layout", layout.name());
+        // A and B have to be padded to the same side to be multiplied
+        let side = 8;
+        let matrix_a = CurveMatrix::from_row_major_with_side(
+            &matrix_a_flat,
+            a_row_count,
+            a_col_count,
+            layout,
+            side,
+        );
+        let matrix_b = CurveMatrix::from_row_major_with_side(
+            &matrix_b_flat,
+            a_col_count,
+            b_col_count,
+            layout,
+            side,
+        );
+        println!("[A] in curve order (padded to {}x{}) = ", side, side);
+        print_matrix_1d_vec_matrix(&matrix_a.data, side);
+        println!("[A transposed] = ");
+        print_matrix_1d_vec_matrix(
+            &transpose_curve_matrix(&matrix_a).to_row_major(),
+            a_row_count,
+        );
+        println!("[A]*[B] = [C] = ");
+        print_matrix_1d_vec_matrix(
+            &matrix_multiply_curve_matrix(&matrix_a, &matrix_b).to_row_major(),
+            b_col_count,
+        );
+    }
+}
+
+fn curve_layout_performance_compare() {
+    // The curve kernels work on square powers of two, so the row-major side gets the same
+    // square matrices. The conversion to and from the curve layout isn't timed.
+    let arr_sizes: Vec<usize> = (0..5).map(|i| 128 << i).collect();
+    let bench = Bench::new(
+        "Transpose Benchmarks (tiled row-major vs curve layouts)",
+        arr_sizes,
+        |size| {
+            let matrix_flat = flatten_row_major(&make_matrix(size, size, 0));
+            let matrix_morton =
+                CurveMatrix::from_row_major(&matrix_flat, size, size, CurveLayout::Morton);
+            let matrix_hilbert =
+                CurveMatrix::from_row_major(&matrix_flat, size, size, CurveLayout::Hilbert);
+            (matrix_flat, matrix_morton, matrix_hilbert)
+        },
+    )
+    .data_size(|size| size * size)
+    .case_owned("Loop Tile (32^2)", |(matrix_flat, _, _)| {
+        let col_count = matrix_flat.len().isqrt();
+        transpose_1d_row_major_matrix_loop_tiling_aka_block_tiling(matrix_flat, col_count, 32)
+    })
+    .case("Morton", |(_, matrix_morton, _)| {
+        transpose_curve_matrix(matrix_morton)
+    })
+    .case("Hilbert", |(_, _, matrix_hilbert)| {
+        transpose_curve_matrix(matrix_hilbert)
+    })
+    .compare("Loop Tile (32^2)", "Morton")
+    .compare("Loop Tile (32^2)", "Hilbert")
+    .measured_iterations(10);
+    bench.run().print_ratio_table();
+
+    let arr_sizes: Vec<usize> = (0..5).map(|i| 32 << i).collect();
+    let bench = Bench::new(
+        "Matrix Multiplication Benchmarks (tiled row-major vs curve layouts)",
+        arr_sizes,
+        |size| {
+            let matrix_a_flat = flatten_row_major(&make_matrix(size, size, 0));
+            let matrix_b_flat = flatten_row_major(&make_matrix(size, size, 0));
+            let curve = |matrix_flat: &[i32], layout| {
+                CurveMatrix::from_row_major(matrix_flat, size, size, layout)
+            };
+            let matrix_morton = (
+                curve(&matrix_a_flat, CurveLayout::Morton),
+                curve(&matrix_b_flat, CurveLayout::Morton),
+            );
+            let matrix_hilbert = (
+                curve(&matrix_a_flat, CurveLayout::Hilbert),
+                curve(&matrix_b_flat, CurveLayout::Hilbert),
+            );
+            (
+                size,
+                (matrix_a_flat, matrix_b_flat),
+                matrix_morton,
+                matrix_hilbert,
+            )
+        },
+    )
+    .data_size(|size| size * size)
+    .case(
+        "Loop Tile (64^2)",
+        |(size, (matrix_a_flat, matrix_b_flat), _, _)| {
+            matrix_multiply_loop_tiling_flat_row_major(
+                matrix_a_flat,
+                matrix_b_flat,
+                *size,
+                *size,
+                *size,
+                64,
+            )
+        },
+    )
+    .case("Morton", |(_, _, (matrix_a, matrix_b), _)| {
+        matrix_multiply_curve_matrix(matrix_a, matrix_b)
+    })
+    .case("Hilbert", |(_, _, _, (matrix_a, matrix_b))| {
+        matrix_multiply_curve_matrix(matrix_a, matrix_b)
+    })
+    .compare("Loop Tile (64^2)", "Morton")
+    .compare("Loop Tile (64^2)", "Hilbert")
+    .measured_iterations(5);
+    bench.run().print_ratio_table();
+    println!(
+        "\nOn my machine the curve layouts lose on small matrices (building the leaf lookup
+tables costs more than the work) but the transposes win about 3x from 512x512 up, both
+reads and writes stay inside one contiguous quadrant. Matmul is a wash: the 64x64 tiles
+already keep A, B and C in cache and the table lookups in the curve leaves cost about what
+the better locality saves."
+    );
+}
@@ -0,0 +1,101 @@
+use dsa_image_processing::{
+    bench::{data::XorShift64, harness::Bench},
+    matrix::{
+        flatten::flatten_row_major,
+        make_matrix,
+        transpose::{transpose_1d_row_major_matrix, transpose_1d_row_major_matrix_simd},
+    },
+    simd::{self, SimdLevel},
+};
+
+pub fn explicit_simd() {
+    println!("==> 3h) Explicit SIMD with runtime feature detection\n");
+    // 3h) The "coax llvm" row sum only gets SSE2 since that is all x86_64 guarantees. Write
+    // the SSE2 and AVX2 versions by hand with std::arch and pick one when the program starts.
+    explicit_simd_levels();
+    explicit_simd_performance_compare();
+}
+
+fn explicit_simd_levels() {
+    let supported: Vec<&str> = SimdLevel::supported()
+        .iter()
+        .map(|level| level.name())
+        .collect();
+    println!(
+        "--> Supported on this machine: {:?}, dispatching to {}",
+        supported,
+        SimdLevel::detect().name()
+    );
+    let mut pixels: Vec<u8> = (0..40).map(|v| (v * 7) as u8).collect();
+    println!("Pixels before:  {:?}", pixels);
+    simd::adjust_brightness_u8(&mut pixels, 100);
+    println!("Brightness +100 (saturating at 255): {:?}", pixels);
+    simd::adjust_brightness_u8(&mut pixels, -150);
+    println!("Brightness -150 (saturating at 0):   {:?}\n", pixels);
+}
+
+fn explicit_simd_performance_compare() {
+    let levels = SimdLevel::supported();
+    let arr_sizes: Vec<usize> = (0..6).map(|i| 4096 << (i * 2)).collect();
+    let mut bench = Bench::new("Explicit SIMD: sum i32", arr_sizes.clone(), |size| {
+        let mut rng = XorShift64::new(size as u64);
+        (0..size)
+            .map(|_| rng.next_u64() as i32)
+            .collect::<Vec<i32>>()
+    });
+    for &level in &levels {
+        bench = bench.case(level.name(), move |values| {
+            simd::sum_i32_using(level, values)
+        });
+    }
+    compare_levels(bench, &levels).run().print_ratio_table();
+
+    let mut bench = Bench::new("Explicit SIMD: dot f32", arr_sizes.clone(), |size| {
+        let mut rng = XorShift64::new(size as u64);
+        let mut vector = || {
+            (0..size)
+                .map(|_| (rng.next_u64() % 1000) as f32 / 1000.0)
+                .collect::<Vec<f32>>()
+        };
+        (vector(), vector())
+    });
+    for &level in &levels {
+        bench = bench.case(level.name(), move |(a, b)| simd::dot_f32_using(level, a, b));
+    }
+    compare_levels(bench, &levels).run().print_ratio_table();
+
+    let mut bench = Bench::new("Explicit SIMD: brightness u8", arr_sizes, |size| {
+        let mut rng = XorShift64::new(size as u64);
+        (0..size).map(|_| rng.next_u64() as u8).collect::<Vec<u8>>()
+    });
+    for &level in &levels {
+        bench = bench.case_mut(level.name(), move |pixels| {
+            simd::adjust_brightness_u8_using(level, pixels, 40)
+        });
+    }
+    compare_levels(bench, &levels).run().print_ratio_table();
+
+    // The 8x8 blocks do all the shuffling in registers, the tiled kernels are in 2b
+    let arr_sizes: Vec<usize> = (0..6).map(|i| 64 << i).collect();
+    Bench::new("Explicit SIMD: transpose i32 (n x n)", arr_sizes, |size| {
+        (size, flatten_row_major(&make_matrix(size, size, 0)))
+    })
+    .data_size(|size| size * size)
+    .case_owned("1d RM", |(size, flat)| {
+        transpose_1d_row_major_matrix(flat, size)
+    })
+    .case_owned("1d RM 8x8 SIMD Blocks", |(size, flat)| {
+        transpose_1d_row_major_matrix_simd(flat, size)
+    })
+    .compare("1d RM", "1d RM 8x8 SIMD Blocks")
+    .run()
+    .print_ratio_table();
+}
+
+// Every level against the scalar fallback
+fn compare_levels<'a, I>(mut bench: Bench<'a, I>, levels: &[SimdLevel]) -> Bench<'a, I> {
+    for level in &levels[1..] {
+        bench = bench.compare(SimdLevel::Scalar.name(), level.name());
+    }
+    bench
+}
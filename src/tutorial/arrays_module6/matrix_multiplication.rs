@@ -1,14 +1,26 @@
-use std::time::{Duration, Instant};
-
-use crate::tutorial::{
-    arrays_module6::{
-        basic_matrix_operations::transpose::transpose_2d_vec_loop_tiling_aka_block_tiling,
-        warm_up_2d_arrays_and_flattening_them::{flatten_col_major, flatten_row_major},
+use dsa_image_processing::{
+    bench::{
+        data::XorShift64,
+        harness::{Bench, BenchConfig},
     },
-    common_util::{
-        make_matrix, print_header, print_matrix_1d_vec_matrix, print_matrix_2d_vec_matrix,
-        print_output_row_ratio_compare_result_nanos,
+    matrix::{
+        display::{print_matrix_1d_vec_matrix, print_matrix_2d_vec_matrix},
+        flatten::{flatten_col_major, flatten_row_major},
+        make_matrix,
+        multiply::{
+            matrix_multiply_cache_oblivious_flat_row_major, matrix_multiply_loop_tiling,
+            matrix_multiply_loop_tiling_flat_row_major,
+            matrix_multiply_loop_tiling_flat_row_major_a_col_major_b,
+            matrix_multiply_loop_tiling_flat_row_major_a_col_major_b_tuned,
+            matrix_multiply_triple_loop_vec_2d,
+            matrix_multiply_triple_loop_vec_2d_with_transposed_b,
+        },
+        strassen::{
+            STRASSEN_DEFAULT_CROSSOVER, StrassenVariant, matrix_multiply_strassen_flat_row_major,
+        },
+        transpose::transpose_2d_vec_loop_tiling_aka_block_tiling,
     },
+    tuning::tuner::tune_strassen_crossover,
 };
 
 pub fn matrix_multiplication() {
@@ -53,34 +65,6 @@ fn matrix_multiplication_triple_loop() {
     print_matrix_2d_vec_matrix(&matrix_c_2d_vec);
 }
 
-fn matrix_multiply_triple_loop_vec_2d(
-    matrix_a: &Vec<Vec<i32>>,
-    matrix_b: &Vec<Vec<i32>>,
-) -> Vec<Vec<i32>> {
-    let a_col_count = matrix_a[0].len();
-    let b_row_count = matrix_b.len();
-    if a_col_count != b_row_count {
-        panic!("Can not multiply matrices [A]'s columns do not match [B]'s rows.")
-    }
-    let a_row_count = matrix_a.len();
-    let b_col_count = matrix_b[0].len();
-
-    // a_col_count == b_row_count == k
-    let k = a_col_count;
-    let mut matrix_c = vec![vec![0; b_col_count]; a_row_count];
-
-    for a_row_idx in 0..a_row_count {
-        for b_col_idx in 0..b_col_count {
-            let mut total = 0;
-            for k_idx in 0..k {
-                total += matrix_a[a_row_idx][k_idx] * matrix_b[k_idx][b_col_idx];
-            }
-            matrix_c[a_row_idx][b_col_idx] = total;
-        }
-    }
-    matrix_c
-}
-
 fn matrix_multiplication_triple_loop_precompute_transpose_b() {
     println!("--> Vec_2d Tripple Loop Pre-compute transpose [B]");
     let a_row_count = 5;
@@ -111,35 +95,6 @@ fn matrix_multiplication_triple_loop_precompute_transpose_b() {
     print_matrix_2d_vec_matrix(&matrix_c_2d_vec);
 }
 
-fn matrix_multiply_triple_loop_vec_2d_with_transposed_b(
-    matrix_a: &Vec<Vec<i32>>,
-    matrix_b_transposed: &Vec<Vec<i32>>,
-) -> Vec<Vec<i32>> {
-    let a_col_count = matrix_a[0].len();
-    let b_row_count = matrix_b_transposed[0].len();
-    if a_col_count != b_row_count {
-        panic!("Can not multiply matrices [A]'s columns do not match [B]'s rows.")
-    }
-    let a_row_count = matrix_a.len();
-    let b_col_count = matrix_b_transposed.len();
-
-    // a_col_count == b_row_count == k
-    let k = a_col_count;
-    let mut matrix_c = vec![vec![0; b_col_count]; a_row_count];
-
-    for a_row_idx in 0..a_row_count {
-        let a_row = &matrix_a[a_row_idx];
-        for b_col_idx in 0..b_col_count {
-            // Since b is transposed, the row is a the column.
-            let b_column = &matrix_b_transposed[b_col_idx];
-            for k_idx in 0..k {
-                matrix_c[a_row_idx][b_col_idx] += a_row[k_idx] * b_column[k_idx];
-            }
-        }
-    }
-    matrix_c
-}
-
 fn matrix_multiplication_loop_tiling_blocking() {
     println!("--> Vec_2d Loop Tiling / Blocking");
     let a_row_count = 5;
@@ -162,52 +117,6 @@ fn matrix_multiplication_loop_tiling_blocking() {
     print_matrix_2d_vec_matrix(&matrix_c_2d_vec);
 }
 
-// Just got this straight out of a article, need to ponder this to understand it.
-// A good way would be to make a matrix A/B/C visuallizer highlighting the parts
-// being worked on to see it visually: I think the main thing tripping me up here
-// is understanding the for block_k and for k_in_block loops.
-fn matrix_multiply_loop_tiling(
-    matrix_a: &Vec<Vec<i32>>,
-    matrix_b: &Vec<Vec<i32>>,
-    block_size: usize,
-) -> Vec<Vec<i32>> {
-    let a_col_count = matrix_a[0].len();
-    let b_row_count = matrix_b.len();
-    if a_col_count != b_row_count {
-        panic!("Can not multiply matrices [A]'s columns do not match [B]'s rows.")
-    }
-    let a_row_count = matrix_a.len();
-    let b_col_count = matrix_b[0].len();
-
-    // a_col_count == b_row_count == k
-    let k = a_col_count;
-    let mut matrix_c = vec![vec![0; b_col_count]; a_row_count];
-
-    // Iterate over blocks of rows in C (and rows in A)
-    for block_row_c in (0..a_row_count).step_by(block_size) {
-        // Iterate over blocks of columns in C (and columns in B)
-        for block_col_c in (0..b_col_count).step_by(block_size) {
-            // Iterate over blocks along the inner dimension (columns of A / rows of B)
-            for block_k in (0..k).step_by(block_size) {
-                // Iterate over rows inside the current C block
-                for row_in_c_block in block_row_c..(block_row_c + block_size).min(a_row_count) {
-                    // Iterate over columns inside the current C block
-                    for col_in_c_block in block_col_c..(block_col_c + block_size).min(b_col_count) {
-                        // Iterate along the inner dimension inside the block
-                        for k_in_block in block_k..(block_k + block_size).min(a_col_count) {
-                            // Multiply and accumulate the corresponding elements
-                            matrix_c[row_in_c_block][col_in_c_block] += matrix_a[row_in_c_block]
-                                [k_in_block]
-                                * matrix_b[k_in_block][col_in_c_block];
-                        }
-                    }
-                }
-            }
-        }
-    }
-    matrix_c
-}
-
 fn matrix_multiplication_loop_tiling_blocking_flat_row_major() {
     println!("--> 1drow major Loop Tiling / Blocking");
     let a_row_count = 5;
@@ -239,52 +148,6 @@ fn matrix_multiplication_loop_tiling_blocking_flat_row_major() {
     print_matrix_1d_vec_matrix(&matrix_c_flat, b_col_count);
 }
 
-// Just got this straight out of a article, need to ponder this to understand it.
-/// Matrix multiplication with loop tiling (blocking),
-/// using flat row-major Vec<i32> for storage.
-fn matrix_multiply_loop_tiling_flat_row_major(
-    matrix_a: &Vec<i32>, // row-major [a_row_count × a_col_count]
-    matrix_b: &Vec<i32>, // row-major [b_row_count × b_col_count]
-    a_row_count: usize,
-    a_col_count: usize,
-    b_col_count: usize,
-    block_size: usize,
-) -> Vec<i32> {
-    let b_row_count = a_col_count;
-    if matrix_b.len() != b_row_count * b_col_count {
-        panic!("Matrix B dimensions do not match multiplication requirements.");
-    }
-
-    // Output matrix C [a_row_count × b_col_count], row-major
-    let mut matrix_c = vec![0; a_row_count * b_col_count];
-
-    // Iterate over blocks of rows in C (and rows in A)
-    for block_row_c in (0..a_row_count).step_by(block_size) {
-        // Iterate over blocks of columns in C (and columns in B)
-        for block_col_c in (0..b_col_count).step_by(block_size) {
-            // Iterate over blocks along the inner dimension (columns of A / rows of B)
-            for block_k in (0..a_col_count).step_by(block_size) {
-                // Rows inside the current C block
-                for row_in_c_block in block_row_c..(block_row_c + block_size).min(a_row_count) {
-                    // Cols inside the current C block
-                    for col_in_c_block in block_col_c..(block_col_c + block_size).min(b_col_count) {
-                        let mut sum = matrix_c[row_in_c_block * b_col_count + col_in_c_block];
-                        // Inner dimension inside the block
-                        for k_in_block in block_k..(block_k + block_size).min(a_col_count) {
-                            let a_val = matrix_a[row_in_c_block * a_col_count + k_in_block];
-                            let b_val = matrix_b[k_in_block * b_col_count + col_in_c_block];
-                            sum += a_val * b_val;
-                        }
-                        matrix_c[row_in_c_block * b_col_count + col_in_c_block] = sum;
-                    }
-                }
-            }
-        }
-    }
-
-    matrix_c
-}
-
 fn matrix_multiplication_loop_tiling_blocking_flat_row_major_a_with_col_major_b() {
     println!("--> 1drow major Loop Tiling / Blocking but with b being 1dcol major");
     let a_row_count = 5;
@@ -316,298 +179,96 @@ fn matrix_multiplication_loop_tiling_blocking_flat_row_major_a_with_col_major_b(
     print_matrix_1d_vec_matrix(&matrix_c_flat, b_col_count);
 }
 
-/// Matrix multiplication with loop tiling (blocking),
-/// using flat row-major Vec<i32> for A and C,
-/// and flat column-major Vec<i32> for B.
-fn matrix_multiply_loop_tiling_flat_row_major_a_col_major_b(
-    matrix_a: &Vec<i32>, // row-major [a_row_count × a_col_count]
-    matrix_b: &Vec<i32>, // column-major [b_row_count × b_col_count]
-    a_row_count: usize,
-    a_col_count: usize,
-    b_col_count: usize,
-    block_size: usize,
-) -> Vec<i32> {
-    let b_row_count = a_col_count;
-    if matrix_b.len() != b_row_count * b_col_count {
-        panic!("Matrix B dimensions do not match multiplication requirements.");
-    }
-
-    // Output matrix C [a_row_count × b_col_count], row-major
-    let mut matrix_c = vec![0; a_row_count * b_col_count];
-
-    // Iterate over blocks of rows in C (and rows in A)
-    for block_row_c in (0..a_row_count).step_by(block_size) {
-        // Iterate over blocks of columns in C (and columns in B)
-        for block_col_c in (0..b_col_count).step_by(block_size) {
-            // Iterate over blocks along the inner dimension (columns of A / rows of B)
-            for block_k in (0..a_col_count).step_by(block_size) {
-                // Rows inside the current C block
-                for row_in_c_block in block_row_c..(block_row_c + block_size).min(a_row_count) {
-                    // Cols inside the current C block
-                    for col_in_c_block in block_col_c..(block_col_c + block_size).min(b_col_count) {
-                        let mut sum = matrix_c[row_in_c_block * b_col_count + col_in_c_block];
-                        // Inner dimension inside the block
-                        for k_in_block in block_k..(block_k + block_size).min(a_col_count) {
-                            let a_val = matrix_a[row_in_c_block * a_col_count + k_in_block];
-                            // <-- difference: column-major indexing
-                            let b_val = matrix_b[col_in_c_block * b_row_count + k_in_block];
-                            sum += a_val * b_val;
-                        }
-                        matrix_c[row_in_c_block * b_col_count + col_in_c_block] = sum;
-                    }
-                }
-            }
-        }
-    }
-
-    matrix_c
-}
-
 fn matrix_multiplication_performance_compare() {
-    //
-    println!("---> Matrix Multiplication Benchmarks (Vec<Vec<i32>> Loop Tiles)");
-    let columns = [
-        "Data Size",
-        "Triple Loop (ns)",
-        "Transpose [B] (ns)",
-        "Ratio", // Ratio values getting a bit big so hacking a bit of space
-        "Triple Loop (ns)",
-        "Loop Tile (32^2) (ns)",
-        "Ratio", // Ratio values getting a bit big so hacking a bit of space
-        "Triple Loop (ns)",
-        "Loop Tile (64^2) (ns)",
-        "Ratio", // Ratio values getting a bit big so hacking a bit of space
-        "Triple Loop (ns)",
-        "Loop Tile (128^2) (ns)",
-        "Ratio", // Ratio values getting a bit big so hacking a bit of space
-    ];
-    print_header(&columns);
+    // A is (size/2 x size), B is (size x size/2) so C is (size/2 x size/2)
     let start_size = 8;
     let number_of_doubles = 7;
     // starting at
     let arr_sizes: Vec<usize> = (0..number_of_doubles).map(|i| start_size << i).collect();
-
-    for size in &arr_sizes {
-        let mut arr_time_tl: Vec<Duration> = Vec::with_capacity(10);
-        let mut arr_time_tl_tp_b: Vec<Duration> = Vec::with_capacity(10);
-        let mut arr_time_block_32_tile_loop: Vec<Duration> = Vec::with_capacity(10);
-        let mut arr_time_block_64_tile_loop: Vec<Duration> = Vec::with_capacity(10);
-        let mut arr_time_block_128_tile_loop: Vec<Duration> = Vec::with_capacity(10);
-        let matrix_a = make_matrix(*size / 2, *size, 0);
-        let matrix_b = make_matrix(*size, *size / 2, 0);
-        let matrix_b_transposed = transpose_2d_vec_loop_tiling_aka_block_tiling(matrix_b, 32);
-        let matrix_b = make_matrix(*size, *size / 2, 0);
-
-        for _ in 0..10 {
-            let start = Instant::now();
-            let _matrix_c = matrix_multiply_triple_loop_vec_2d(&matrix_a, &matrix_b);
-            std::hint::black_box(_matrix_c);
-            arr_time_tl.push(start.elapsed());
-        }
-        for _ in 0..10 {
-            let start = Instant::now();
-            let _matrix_c = matrix_multiply_triple_loop_vec_2d_with_transposed_b(
-                &matrix_a,
-                &matrix_b_transposed,
-            );
-            std::hint::black_box(_matrix_c);
-            arr_time_tl_tp_b.push(start.elapsed());
-        }
-
-        for _ in 0..10 {
-            let start = Instant::now();
-
-            let _matrix_c = matrix_multiply_loop_tiling(&matrix_a, &matrix_b, 32);
-            std::hint::black_box(_matrix_c);
-            arr_time_block_32_tile_loop.push(start.elapsed());
-        }
-
-        for _ in 0..10 {
-            let start = Instant::now();
-
-            let _matrix_c = matrix_multiply_loop_tiling(&matrix_a, &matrix_b, 64);
-            std::hint::black_box(_matrix_c);
-            arr_time_block_64_tile_loop.push(start.elapsed());
-        }
-
-        for _ in 0..10 {
-            let start = Instant::now();
-
-            let _matrix_c = matrix_multiply_loop_tiling(&matrix_a, &matrix_b, 128);
-            std::hint::black_box(_matrix_c);
-            arr_time_block_128_tile_loop.push(start.elapsed());
-        }
-
-        let time_tl = Duration::from_nanos(
-            (arr_time_tl.iter().map(|d| d.as_nanos()).sum::<u128>() / arr_time_tl.len() as u128)
-                as u64,
-        );
-
-        let time_tl_tp_b = Duration::from_nanos(
-            (arr_time_tl_tp_b.iter().map(|d| d.as_nanos()).sum::<u128>()
-                / arr_time_tl_tp_b.len() as u128) as u64,
-        );
-
-        let time_loop_tile_32 = Duration::from_nanos(
-            (arr_time_block_32_tile_loop
-                .iter()
-                .map(|d| d.as_nanos())
-                .sum::<u128>()
-                / arr_time_block_32_tile_loop.len() as u128) as u64,
-        );
-
-        let time_loop_tile_64 = Duration::from_nanos(
-            (arr_time_block_64_tile_loop
-                .iter()
-                .map(|d| d.as_nanos())
-                .sum::<u128>()
-                / arr_time_block_64_tile_loop.len() as u128) as u64,
-        );
-
-        let time_loop_tile_128 = Duration::from_nanos(
-            (arr_time_block_128_tile_loop
-                .iter()
-                .map(|d| d.as_nanos())
-                .sum::<u128>()
-                / arr_time_block_128_tile_loop.len() as u128) as u64,
-        );
-
-        print_output_row_ratio_compare_result_nanos(
-            &columns,
-            (*size / 2) * *size / 2,
-            vec![
-                (time_tl, time_tl_tp_b),
-                (time_tl, time_loop_tile_32),
-                (time_tl, time_loop_tile_64),
-                (time_tl, time_loop_tile_128),
-            ],
-        );
+    let mut bench = Bench::new(
+        "Matrix Multiplication Benchmarks (Vec<Vec<i32>> Loop Tiles)",
+        arr_sizes,
+        |size| {
+            let matrix_a = make_matrix(size / 2, size, 0);
+            let matrix_b = make_matrix(size, size / 2, 0);
+            let matrix_b_transposed =
+                transpose_2d_vec_loop_tiling_aka_block_tiling(matrix_b.clone(), 32);
+            (matrix_a, matrix_b, matrix_b_transposed)
+        },
+    )
+    .data_size(|size| (size / 2) * size / 2)
+    .case("Triple Loop", |(matrix_a, matrix_b, _)| {
+        matrix_multiply_triple_loop_vec_2d(matrix_a, matrix_b)
+    })
+    .case("Transpose [B]", |(matrix_a, _, matrix_b_transposed)| {
+        matrix_multiply_triple_loop_vec_2d_with_transposed_b(matrix_a, matrix_b_transposed)
+    })
+    .compare("Triple Loop", "Transpose [B]");
+    for block_size in [32, 64, 128] {
+        let name = format!("Loop Tile ({}^2)", block_size);
+        bench = bench
+            .case(&name, move |(matrix_a, matrix_b, _)| {
+                matrix_multiply_loop_tiling(matrix_a, matrix_b, block_size)
+            })
+            .compare("Triple Loop", &name);
     }
+    bench.run().print_ratio_table();
+
     println!("\nNow let's try with a flattened 1d Vec<i32> Row Major A and B into C...");
-    println!("---> Matrix Multiplication Benchmarks (Vec<i32> row major Loop Tiles)");
-    let columns = [
-        "Data Size",
-        "Triple Loop (ns)",
-        "Loop Tile (32^2) (ns)",
-        "Ratio", // Ratio values getting a bit big so hacking a bit of space
-        "Triple Loop (ns)",
-        "Loop Tile (64^2) (ns)",
-        "Ratio", // Ratio values getting a bit big so hacking a bit of space
-        "Triple Loop (ns)",
-        "Loop Tile (128^2) (ns)",
-        "Ratio", // Ratio values getting a bit big so hacking a bit of space
-    ];
-    print_header(&columns);
     let start_size = 8;
     let number_of_doubles = 8;
     // starting at
     let arr_sizes: Vec<usize> = (0..number_of_doubles).map(|i| start_size << i).collect();
-
-    for size in &arr_sizes {
-        let mut arr_time_tl: Vec<Duration> = Vec::with_capacity(10);
-        let mut arr_time_block_32_tile_loop: Vec<Duration> = Vec::with_capacity(10);
-        let mut arr_time_block_64_tile_loop: Vec<Duration> = Vec::with_capacity(10);
-        let mut arr_time_block_128_tile_loop: Vec<Duration> = Vec::with_capacity(10);
-        let a_row_count = *size / 2;
-        let b_col_count = a_row_count;
-        let a_col_count = *size;
-        let b_row_count = *size;
-        let matrix_a = make_matrix(a_row_count, a_col_count, 0);
-        let matrix_b = make_matrix(b_row_count, b_col_count, 0);
-        let matrix_a_flat = flatten_row_major(&matrix_a);
-        let matrix_b_flat = flatten_row_major(&matrix_b);
-
-        for _ in 0..10 {
-            let start = Instant::now();
-            let _matrix_c = matrix_multiply_triple_loop_vec_2d(&matrix_a, &matrix_b);
-            std::hint::black_box(_matrix_c);
-            arr_time_tl.push(start.elapsed());
-        }
-
-        for _ in 0..10 {
-            let start = Instant::now();
-
-            let _matrix_c = matrix_multiply_loop_tiling_flat_row_major(
-                &matrix_a_flat,
-                &matrix_b_flat,
-                a_row_count,
-                a_col_count,
-                b_col_count,
-                32,
-            );
-            std::hint::black_box(_matrix_c);
-            arr_time_block_32_tile_loop.push(start.elapsed());
-        }
-
-        for _ in 0..10 {
-            let start = Instant::now();
-
-            let _matrix_c = matrix_multiply_loop_tiling_flat_row_major(
-                &matrix_a_flat,
-                &matrix_b_flat,
-                a_row_count,
-                a_col_count,
-                b_col_count,
-                64,
-            );
-            std::hint::black_box(_matrix_c);
-            arr_time_block_64_tile_loop.push(start.elapsed());
-        }
-
-        for _ in 0..10 {
-            let start = Instant::now();
-
-            let _matrix_c = matrix_multiply_loop_tiling_flat_row_major(
-                &matrix_a_flat,
-                &matrix_b_flat,
-                a_row_count,
-                a_col_count,
-                b_col_count,
-                128,
-            );
-            std::hint::black_box(_matrix_c);
-            arr_time_block_128_tile_loop.push(start.elapsed());
-        }
-
-        let time_tl = Duration::from_nanos(
-            (arr_time_tl.iter().map(|d| d.as_nanos()).sum::<u128>() / arr_time_tl.len() as u128)
-                as u64,
-        );
-
-        let time_loop_tile_32 = Duration::from_nanos(
-            (arr_time_block_32_tile_loop
-                .iter()
-                .map(|d| d.as_nanos())
-                .sum::<u128>()
-                / arr_time_block_32_tile_loop.len() as u128) as u64,
-        );
-
-        let time_loop_tile_64 = Duration::from_nanos(
-            (arr_time_block_64_tile_loop
-                .iter()
-                .map(|d| d.as_nanos())
-                .sum::<u128>()
-                / arr_time_block_64_tile_loop.len() as u128) as u64,
-        );
-
-        let time_loop_tile_128 = Duration::from_nanos(
-            (arr_time_block_128_tile_loop
-                .iter()
-                .map(|d| d.as_nanos())
-                .sum::<u128>()
-                / arr_time_block_128_tile_loop.len() as u128) as u64,
-        );
-
-        print_output_row_ratio_compare_result_nanos(
-            &columns,
-            (*size / 2) * *size / 2,
-            vec![
-                (time_tl, time_loop_tile_32),
-                (time_tl, time_loop_tile_64),
-                (time_tl, time_loop_tile_128),
-            ],
-        );
+    let mut bench = Bench::new(
+        "Matrix Multiplication Benchmarks (Vec<i32> row major Loop Tiles)",
+        arr_sizes.clone(),
+        |size| {
+            let matrix_a = make_matrix(size / 2, size, 0);
+            let matrix_b = make_matrix(size, size / 2, 0);
+            let matrix_a_flat = flatten_row_major(&matrix_a);
+            let matrix_b_flat = flatten_row_major(&matrix_b);
+            (matrix_a, matrix_b, matrix_a_flat, matrix_b_flat)
+        },
+    )
+    .data_size(|size| (size / 2) * size / 2)
+    .case("Triple Loop", |(matrix_a, matrix_b, _, _)| {
+        matrix_multiply_triple_loop_vec_2d(matrix_a, matrix_b)
+    });
+    for block_size in [32, 64, 128] {
+        let name = format!("Loop Tile ({}^2)", block_size);
+        bench = bench
+            .case(&name, move |(matrix_a, _, matrix_a_flat, matrix_b_flat)| {
+                let a_row_count = matrix_a.len();
+                let a_col_count = matrix_a[0].len();
+                matrix_multiply_loop_tiling_flat_row_major(
+                    matrix_a_flat,
+                    matrix_b_flat,
+                    a_row_count,
+                    a_col_count,
+                    a_row_count,
+                    block_size,
+                )
+            })
+            .compare("Triple Loop", &name);
     }
+    // No block size at all: recursively halve the largest dimension down to ~32^3 multiply-adds
+    bench = bench
+        .case(
+            "Cache Oblivious",
+            |(matrix_a, _, matrix_a_flat, matrix_b_flat)| {
+                let a_row_count = matrix_a.len();
+                let a_col_count = matrix_a[0].len();
+                matrix_multiply_cache_oblivious_flat_row_major(
+                    matrix_a_flat,
+                    matrix_b_flat,
+                    a_row_count,
+                    a_col_count,
+                    a_row_count,
+                )
+            },
+        )
+        .compare("Triple Loop", "Cache Oblivious");
+    bench.run().print_ratio_table();
     println!(
         "\nThese benchmarks are madening, I don't get whats the point of 
 blocks with loop tiling if it only operates faster for matrixes of 64x64..
@@ -615,130 +276,64 @@ doesn't make sense there must be something wrong with these algo's I'll
 come back later..., WTF screw it let me try making B col major:"
     );
 
-    println!(
-        "---> Matrix Multiplication Benchmarks (Vec<i32> row major * Vec<i32> col major Loop Tiles)"
-    );
-    let columns = [
-        "Data Size",
-        "Triple Loop (ns)",
-        "Loop Tile (32^2) (ns)",
-        "Ratio", // Ratio values getting a bit big so hacking a bit of space
-        "Triple Loop (ns)",
-        "Loop Tile (64^2) (ns)",
-        "Ratio", // Ratio values getting a bit big so hacking a bit of space
-        "Triple Loop (ns)",
-        "Loop Tile (128^2) (ns)",
-        "Ratio", // Ratio values getting a bit big so hacking a bit of space
-    ];
-    print_header(&columns);
-    let start_size = 8;
-    let number_of_doubles = 8;
-    // starting at
-    let arr_sizes: Vec<usize> = (0..number_of_doubles).map(|i| start_size << i).collect();
-
-    for size in &arr_sizes {
-        let mut arr_time_tl: Vec<Duration> = Vec::with_capacity(10);
-        let mut arr_time_block_32_tile_loop: Vec<Duration> = Vec::with_capacity(10);
-        let mut arr_time_block_64_tile_loop: Vec<Duration> = Vec::with_capacity(10);
-        let mut arr_time_block_128_tile_loop: Vec<Duration> = Vec::with_capacity(10);
-        let a_row_count = *size / 2;
-        let b_col_count = a_row_count;
-        let a_col_count = *size;
-        let b_row_count = *size;
-        let matrix_a = make_matrix(a_row_count, a_col_count, 0);
-        let matrix_b = make_matrix(b_row_count, b_col_count, 0);
-        let matrix_a_flat_row_major = flatten_row_major(&matrix_a);
-        let matrix_b_flat_col_major = flatten_col_major(&matrix_b);
-
-        for _ in 0..10 {
-            let start = Instant::now();
-            let _matrix_c = matrix_multiply_triple_loop_vec_2d(&matrix_a, &matrix_b);
-            std::hint::black_box(_matrix_c);
-            arr_time_tl.push(start.elapsed());
-        }
-
-        for _ in 0..10 {
-            let start = Instant::now();
-            let _matrix_c = matrix_multiply_loop_tiling_flat_row_major_a_col_major_b(
-                &matrix_a_flat_row_major,
-                &matrix_b_flat_col_major,
-                a_row_count,
-                a_col_count,
-                b_col_count,
-                32,
-            );
-            std::hint::black_box(_matrix_c);
-            arr_time_block_32_tile_loop.push(start.elapsed());
-        }
-
-        for _ in 0..10 {
-            let start = Instant::now();
-            let _matrix_c = matrix_multiply_loop_tiling_flat_row_major_a_col_major_b(
-                &matrix_a_flat_row_major,
-                &matrix_b_flat_col_major,
-                a_row_count,
-                a_col_count,
-                b_col_count,
-                64,
-            );
-            std::hint::black_box(_matrix_c);
-            arr_time_block_64_tile_loop.push(start.elapsed());
-        }
-
-        for _ in 0..10 {
-            let start = Instant::now();
-
-            let _matrix_c = matrix_multiply_loop_tiling_flat_row_major_a_col_major_b(
-                &matrix_a_flat_row_major,
-                &matrix_b_flat_col_major,
-                a_row_count,
-                a_col_count,
-                b_col_count,
-                128,
-            );
-            std::hint::black_box(_matrix_c);
-            arr_time_block_128_tile_loop.push(start.elapsed());
-        }
-
-        let time_tl = Duration::from_nanos(
-            (arr_time_tl.iter().map(|d| d.as_nanos()).sum::<u128>() / arr_time_tl.len() as u128)
-                as u64,
-        );
-
-        let time_loop_tile_32 = Duration::from_nanos(
-            (arr_time_block_32_tile_loop
-                .iter()
-                .map(|d| d.as_nanos())
-                .sum::<u128>()
-                / arr_time_block_32_tile_loop.len() as u128) as u64,
-        );
-
-        let time_loop_tile_64 = Duration::from_nanos(
-            (arr_time_block_64_tile_loop
-                .iter()
-                .map(|d| d.as_nanos())
-                .sum::<u128>()
-                / arr_time_block_64_tile_loop.len() as u128) as u64,
-        );
-
-        let time_loop_tile_128 = Duration::from_nanos(
-            (arr_time_block_128_tile_loop
-                .iter()
-                .map(|d| d.as_nanos())
-                .sum::<u128>()
-                / arr_time_block_128_tile_loop.len() as u128) as u64,
-        );
-
-        print_output_row_ratio_compare_result_nanos(
-            &columns,
-            (*size / 2) * *size / 2,
-            vec![
-                (time_tl, time_loop_tile_32),
-                (time_tl, time_loop_tile_64),
-                (time_tl, time_loop_tile_128),
-            ],
-        );
+    let mut bench = Bench::new(
+        "Matrix Multiplication Benchmarks (Vec<i32> row major * Vec<i32> col major Loop Tiles)",
+        arr_sizes,
+        |size| {
+            let matrix_a = make_matrix(size / 2, size, 0);
+            let matrix_b = make_matrix(size, size / 2, 0);
+            let matrix_a_flat_row_major = flatten_row_major(&matrix_a);
+            let matrix_b_flat_col_major = flatten_col_major(&matrix_b);
+            (
+                matrix_a,
+                matrix_b,
+                matrix_a_flat_row_major,
+                matrix_b_flat_col_major,
+            )
+        },
+    )
+    .data_size(|size| (size / 2) * size / 2)
+    .case("Triple Loop", |(matrix_a, matrix_b, _, _)| {
+        matrix_multiply_triple_loop_vec_2d(matrix_a, matrix_b)
+    });
+    for block_size in [32, 64, 128] {
+        let name = format!("Loop Tile ({}^2)", block_size);
+        bench = bench
+            .case(
+                &name,
+                move |(matrix_a, _, matrix_a_flat_row_major, matrix_b_flat_col_major)| {
+                    let a_row_count = matrix_a.len();
+                    let a_col_count = matrix_a[0].len();
+                    matrix_multiply_loop_tiling_flat_row_major_a_col_major_b(
+                        matrix_a_flat_row_major,
+                        matrix_b_flat_col_major,
+                        a_row_count,
+                        a_col_count,
+                        a_row_count,
+                        block_size,
+                    )
+                },
+            )
+            .compare("Triple Loop", &name);
     }
+    // block size from the tuning file (cargo run --release -- tune), L1 sized otherwise
+    bench = bench
+        .case(
+            "Loop Tile (tuned)",
+            |(matrix_a, _, matrix_a_flat_row_major, matrix_b_flat_col_major)| {
+                let a_row_count = matrix_a.len();
+                let a_col_count = matrix_a[0].len();
+                matrix_multiply_loop_tiling_flat_row_major_a_col_major_b_tuned(
+                    matrix_a_flat_row_major,
+                    matrix_b_flat_col_major,
+                    a_row_count,
+                    a_col_count,
+                    a_row_count,
+                )
+            },
+        )
+        .compare("Triple Loop", "Loop Tile (tuned)");
+    bench.run().print_ratio_table();
 
     println!(
         "\nFinally results I can live with, here we see that 1d block tiling, combined
@@ -760,4 +355,150 @@ come back later..., WTF screw it let me try making B col major:"
  1048576   |       4076774170 |             723973150 |  5.6x |       4076774170 |             569111340 |  7.2x |       4076774170 |              583272550 |  7.0x
  4194304   |      78664407060 |            5731472960 | 13.7x |      78664407060 |            5978903970 | 13.2x |      78664407060 |             5549987740 | 14.2x"
     );
+
+    // Past the tiled kernels: fewer multiplications rather than better cache use
+    matrix_multiplication_strassen_crossover();
+    matrix_multiplication_strassen_error_analysis();
+}
+
+fn matrix_multiplication_strassen_crossover() {
+    println!("\nStrassen: 7 half size products instead of 8, recursing down to a crossover side");
+    println!("below which the 64^2 tiled kernel takes over. Square n x n, i32:");
+    let arr_sizes: Vec<usize> = (0..5).map(|i| 64 << i).collect();
+    let tiled = "Loop Tile (64^2)";
+    let mut bench = Bench::new(
+        "Matrix Multiplication Benchmarks (Strassen / Winograd crossover, Vec<i32> row major)",
+        arr_sizes,
+        |size| {
+            let matrix = flatten_row_major(&make_matrix(size, size, 0));
+            // keep the sums well inside i32
+            let matrix: Vec<i32> = matrix.into_iter().map(|v| v % 16).collect();
+            (size, matrix)
+        },
+    )
+    .measured_iterations(3)
+    .case(tiled, |(size, matrix)| {
+        matrix_multiply_loop_tiling_flat_row_major(matrix, matrix, *size, *size, *size, 64)
+    });
+    let variants = [
+        (StrassenVariant::Strassen, 32),
+        (StrassenVariant::Strassen, 64),
+        (StrassenVariant::Strassen, 128),
+        (StrassenVariant::Strassen, 256),
+        (StrassenVariant::Winograd, 128),
+    ];
+    for (variant, crossover) in variants {
+        let name = format!("{} (cross {})", variant.name(), crossover);
+        bench = bench
+            .case(&name, move |(size, matrix)| {
+                matrix_multiply_strassen_flat_row_major(
+                    matrix, matrix, *size, *size, *size, variant, crossover,
+                )
+            })
+            .compare(tiled, &name);
+    }
+    bench.run().print_ratio_table();
+
+    let config = BenchConfig {
+        warmup_iterations: 1,
+        measured_iterations: 3,
+        ..BenchConfig::default()
+    };
+    let result = tune_strassen_crossover(
+        StrassenVariant::Winograd,
+        512,
+        &[32, 64, 128, 256, 512],
+        config,
+    );
+    println!(
+        "Tuned Winograd crossover for 512x512 on this machine: {} (default {}), median {:?}\n",
+        result.crossover, STRASSEN_DEFAULT_CROSSOVER, result.median
+    );
+}
+
+// Strassen's sums and differences mix entries of very different sizes before multiplying, so
+// its rounding error is bounded by the norms of A and B rather than entry by entry. Measure
+// it against the classical product computed in f64, relative to |A|*|B|.
+fn matrix_multiplication_strassen_error_analysis() {
+    let side = 256;
+    let mut rng = XorShift64::new(42);
+    let mut random = || -> Vec<f64> {
+        (0..side * side)
+            .map(|_| (rng.next_u64() % 2_000_001) as f64 / 1_000_000.0 - 1.0)
+            .collect()
+    };
+    let (matrix_a, matrix_b) = (random(), random());
+    let reference =
+        matrix_multiply_loop_tiling_flat_row_major(&matrix_a, &matrix_b, side, side, side, 64);
+    // |A|*|B|: what each entry of C is made of in absolute terms
+    let abs = |matrix: &[f64]| matrix.iter().map(|v| v.abs()).collect::<Vec<f64>>();
+    let magnitude = matrix_multiply_loop_tiling_flat_row_major(
+        &abs(&matrix_a),
+        &abs(&matrix_b),
+        side,
+        side,
+        side,
+        64,
+    );
+    let matrix_a_f32: Vec<f32> = matrix_a.iter().map(|&v| v as f32).collect();
+    let matrix_b_f32: Vec<f32> = matrix_b.iter().map(|&v| v as f32).collect();
+    let max_relative_error = |matrix_c: &[f64]| {
+        matrix_c
+            .iter()
+            .zip(&reference)
+            .zip(&magnitude)
+            .map(|((c, r), m)| (c - r).abs() / m)
+            .fold(0.0, f64::max)
+    };
+
+    println!(
+        "--> Max |C - C_exact| / (|A|*|B|) for a {}x{} product of values in [-1, 1]",
+        side, side
+    );
+    println!(
+        " {:>20} | {:>6} | {:>12} | {:>12}",
+        "Kernel", "Levels", "f64", "f32"
+    );
+    let mut rows = vec![("Loop Tile (64^2)".to_string(), 0, side)];
+    for variant in [StrassenVariant::Strassen, StrassenVariant::Winograd] {
+        for levels in [1, 3, 5] {
+            rows.push((variant.name().to_string(), levels, side >> levels));
+        }
+    }
+    for (name, levels, crossover) in rows {
+        let variant = if name == "Winograd" {
+            StrassenVariant::Winograd
+        } else {
+            StrassenVariant::Strassen
+        };
+        let matrix_c_f64 = matrix_multiply_strassen_flat_row_major(
+            &matrix_a, &matrix_b, side, side, side, variant, crossover,
+        );
+        let matrix_c_f32: Vec<f64> = matrix_multiply_strassen_flat_row_major(
+            &matrix_a_f32,
+            &matrix_b_f32,
+            side,
+            side,
+            side,
+            variant,
+            crossover,
+        )
+        .into_iter()
+        .map(f64::from)
+        .collect();
+        println!(
+            " {:>20} | {:>6} | {:>12.3e} | {:>12.3e}",
+            name,
+            levels,
+            max_relative_error(&matrix_c_f64),
+            max_relative_error(&matrix_c_f32)
+        );
+    }
+    println!(
+        "The error grows with every level of recursion, faster for Winograd whose shared sums"
+    );
+    println!(
+        "are reused, but a few levels stay within an order of magnitude of the classical kernel"
+    );
+    println!("(the f64 loop tile row is the reference itself).\n");
 }
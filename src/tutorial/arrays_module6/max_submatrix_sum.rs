@@ -0,0 +1,90 @@
+use dsa_image_processing::{
+    array::max_subarray::{max_subarray_sum_kadane, max_subarray_sum_naive},
+    bench::{data::XorShift64, harness::Bench},
+    matrix::{
+        display::print_matrix_2d_vec_matrix,
+        max_submatrix::{
+            largest_all_ones_rectangle, max_submatrix_sum_kadane,
+            max_submatrix_sum_using_prefix_matrix,
+        },
+    },
+};
+
+pub fn max_submatrix_sum() {
+    // 6b) 1D Kadane, then the same thing over every pair of rows for the best rectangle, and
+    // the largest all-ones rectangle in a mask which squashes rows the same way.
+    println!("--> Maximum sum subarray and submatrix (Kadane)");
+    max_submatrix_sum_example();
+    largest_all_ones_rectangle_example();
+    max_submatrix_sum_performance_compare();
+}
+
+fn max_submatrix_sum_example() {
+    let arr = [-2, 1, -3, 4, -1, 2, 1, -5, 4];
+    println!(
+        "{:?}: naive {:?}, Kadane {:?}",
+        arr,
+        max_subarray_sum_naive(&arr),
+        max_subarray_sum_kadane(&arr)
+    );
+
+    let matrix = vec![
+        vec![1, 2, -1, -4, -20],
+        vec![-8, -3, 4, 2, 1],
+        vec![3, 8, 10, 1, 3],
+        vec![-4, -1, 1, 7, -6],
+    ];
+    print_matrix_2d_vec_matrix(&matrix);
+    println!("2D Kadane: {:?}", max_submatrix_sum_kadane(&matrix));
+    println!(
+        "Prefix matrix strips: {:?}\n",
+        max_submatrix_sum_using_prefix_matrix(&matrix)
+    );
+}
+
+fn largest_all_ones_rectangle_example() {
+    let rows = ["#..#####", "#.######", "########", "..####.#", "...##..."];
+    let mask: Vec<Vec<bool>> = rows
+        .iter()
+        .map(|row| row.chars().map(|c| c == '#').collect())
+        .collect();
+    let rect = largest_all_ones_rectangle(&mask).unwrap();
+    println!("--> Largest all-ones rectangle in a mask");
+    for (row_idx, row) in rows.iter().enumerate() {
+        let marked: String = row
+            .chars()
+            .enumerate()
+            .map(|(col_idx, c)| {
+                let inside = (rect.l_row..=rect.r_row).contains(&row_idx)
+                    && (rect.l_col..=rect.r_col).contains(&col_idx);
+                if inside { '@' } else { c }
+            })
+            .collect();
+        println!(" {}", marked);
+    }
+    println!("{:?}\n", rect);
+}
+
+fn max_submatrix_sum_performance_compare() {
+    let arr_sizes: Vec<usize> = (0..4).map(|i| 32 << i).collect();
+    Bench::new("Maximum sum submatrix (n x n)", arr_sizes, |size| {
+        let mut rng = XorShift64::new(6);
+        (0..size)
+            .map(|_| {
+                (0..size)
+                    .map(|_| (rng.next_u64() % 21) as i32 - 10)
+                    .collect()
+            })
+            .collect::<Vec<Vec<i32>>>()
+    })
+    .measured_iterations(5)
+    .case("2D Kadane", |matrix| max_submatrix_sum_kadane(matrix))
+    .case("Prefix matrix strips", |matrix| {
+        max_submatrix_sum_using_prefix_matrix(matrix)
+    })
+    .compare("2D Kadane", "Prefix matrix strips")
+    .run()
+    .print_ratio_table();
+    println!("Both are O(rows^2 * cols). The running column sums read one row per step, the");
+    println!("prefix matrix version reads two rows and branches on the edges for every cell.\n");
+}
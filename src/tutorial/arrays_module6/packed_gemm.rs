@@ -0,0 +1,144 @@
+use dsa_image_processing::{
+    bench::harness::{Bench, BenchReport},
+    matrix::{
+        flatten::{flatten_col_major, flatten_row_major},
+        gemm::{GemmBlocking, GemmShape, gemm, gemm_triple_loop},
+        make_matrix,
+        multiply::matrix_multiply_loop_tiling_flat_row_major_a_col_major_b,
+    },
+};
+
+pub fn packed_gemm() {
+    println!("==> 3g) Packed GEMM (C = alpha*A*B + beta*C)\n");
+    // 3g) The 1d row-major A * col-major B tiling was the best we had. Going further means
+    // packing A and B into the exact order a register blocked micro-kernel reads them.
+    packed_gemm_example();
+    packed_gemm_performance_compare();
+}
+
+fn packed_gemm_example() {
+    let shape = GemmShape::new(5, 7, 6);
+    let matrix_a: Vec<f64> = (0..35).map(|v| f64::from(v % 5)).collect();
+    let matrix_b: Vec<f64> = (0..42).map(|v| f64::from(v % 3)).collect();
+    let matrix_c: Vec<f64> = vec![1.0; 30];
+    let (alpha, beta) = (2.0, -1.0);
+
+    let mut expected = matrix_c.clone();
+    gemm_triple_loop(shape, alpha, &matrix_a, &matrix_b, beta, &mut expected);
+    let mut packed = matrix_c;
+    gemm(shape, alpha, &matrix_a, &matrix_b, beta, &mut packed);
+
+    println!("--> 2*[A]*[B] - [C] with [C] all ones (5x7 * 7x6)");
+    for row in packed.chunks(shape.b_col_count) {
+        println!(" {:?}", row);
+    }
+    println!(
+        "Matches the triple loop: {}, blocking on this machine: f64 {:?}, f32 {:?}\n",
+        packed == expected,
+        GemmBlocking::host::<f64>(),
+        GemmBlocking::host::<f32>()
+    );
+}
+
+fn packed_gemm_performance_compare() {
+    let arr_sizes: Vec<usize> = (0..5).map(|i| 64 << i).collect();
+    let report = Bench::new(
+        "Matrix Multiplication Benchmarks (packed GEMM, square n x n)",
+        arr_sizes,
+        |size| {
+            let matrix = make_matrix(size, size, 0)
+                .into_iter()
+                .map(|row| row.into_iter().map(|v| v % 16).collect())
+                .collect::<Vec<Vec<i32>>>();
+            let matrix_row_major = flatten_row_major(&matrix);
+            let matrix_col_major = flatten_col_major(&matrix);
+            let matrix_f64: Vec<f64> = matrix_row_major.iter().map(|&v| f64::from(v)).collect();
+            let matrix_f32: Vec<f32> = matrix_row_major.iter().map(|&v| v as f32).collect();
+            (
+                size,
+                (matrix_row_major, matrix_col_major),
+                matrix_f64,
+                matrix_f32,
+            )
+        },
+    )
+    .measured_iterations(5)
+    .case("Triple Loop f64", |(size, _, matrix_f64, _)| {
+        let mut matrix_c = vec![0.0; size * size];
+        gemm_triple_loop(
+            GemmShape::new(*size, *size, *size),
+            1.0,
+            matrix_f64,
+            matrix_f64,
+            0.0,
+            &mut matrix_c,
+        );
+        matrix_c
+    })
+    .case(
+        "Loop Tile i32 (64^2)",
+        |(size, (matrix_row_major, matrix_col_major), _, _)| {
+            matrix_multiply_loop_tiling_flat_row_major_a_col_major_b(
+                matrix_row_major,
+                matrix_col_major,
+                *size,
+                *size,
+                *size,
+                64,
+            )
+        },
+    )
+    .case("GEMM f64", |(size, _, matrix_f64, _)| {
+        let mut matrix_c = vec![0.0; size * size];
+        gemm(
+            GemmShape::new(*size, *size, *size),
+            1.0,
+            matrix_f64,
+            matrix_f64,
+            0.0,
+            &mut matrix_c,
+        );
+        matrix_c
+    })
+    .case("GEMM f32", |(size, _, _, matrix_f32)| {
+        let mut matrix_c = vec![0.0; size * size];
+        gemm(
+            GemmShape::new(*size, *size, *size),
+            1.0,
+            matrix_f32,
+            matrix_f32,
+            0.0,
+            &mut matrix_c,
+        );
+        matrix_c
+    })
+    .compare("Loop Tile i32 (64^2)", "GEMM f64")
+    .compare("Loop Tile i32 (64^2)", "GEMM f32")
+    .run();
+    report.print_ratio_table();
+    print_gflops(&report);
+}
+
+// Throughput is easier to compare with published numbers than nanoseconds: 2n^3 operations
+// (one multiply and one add per inner step) over the median time
+fn print_gflops(report: &BenchReport) {
+    let columns: Vec<String> = report
+        .case_names
+        .iter()
+        .map(|case_name| format!("{} GFLOP/s", case_name.split(" (").next().unwrap()))
+        .collect();
+    print!(" {:>6} |", "n");
+    for column in &columns {
+        print!(" {} |", column);
+    }
+    println!();
+    for row in &report.rows {
+        let flop_count = GemmShape::new(row.size, row.size, row.size).flop_count() as f64;
+        print!(" {:>6} |", row.size);
+        for (column, stats) in columns.iter().zip(&row.stats) {
+            let gflops = flop_count / stats.median.as_secs_f64() / 1e9;
+            print!(" {:>width$.2} |", gflops, width = column.len());
+        }
+        println!();
+    }
+}
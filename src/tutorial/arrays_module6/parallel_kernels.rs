@@ -0,0 +1,151 @@
+use dsa_image_processing::{
+    array::prefix_sum::{make_prefix_sum_array, make_prefix_sum_array_parallel},
+    bench::{
+        data::XorShift64,
+        harness::{Bench, BenchReport},
+    },
+    matrix::{
+        convolve::{box_blur_kernel, convolve_2d_clamped_parallel},
+        flatten::flatten_row_major,
+        make_matrix,
+        multiply::matrix_multiply_cache_oblivious_flat_row_major_parallel,
+        transpose::transpose_1d_row_major_matrix_parallel,
+    },
+    parallel::{self, row_bands, thread_counts_up_to},
+};
+
+pub fn parallel_kernels() {
+    println!("==> 3i) Multithreaded kernels (scoped threads, row bands)\n");
+    // 3i) Everything so far runs on one core. Split the output rows into one band per
+    // thread so every thread writes its own slice and nothing needs a lock.
+    parallel_kernels_bands();
+    parallel_kernels_performance_compare();
+}
+
+fn parallel_kernels_bands() {
+    println!(
+        "--> 10 output rows over 4 threads: {:?} (change the thread count with --threads N or DSA_THREADS, using {})",
+        row_bands(10, 4),
+        parallel::thread_count()
+    );
+    let arr: Vec<i32> = (1..=10).collect();
+    println!(
+        "Two pass prefix sum over 3 bands: {:?}, single threaded: {:?}\n",
+        make_prefix_sum_array_parallel(&arr, 3),
+        make_prefix_sum_array(&arr)
+    );
+}
+
+fn parallel_kernels_performance_compare() {
+    let thread_counts = thread_counts_up_to(parallel::thread_count());
+
+    let arr_sizes: Vec<usize> = (0..4).map(|i| 128 << i).collect();
+    let mut bench = Bench::new(
+        "Parallel matrix multiplication (cache oblivious, n x n)",
+        arr_sizes.clone(),
+        |size| (size, flatten_row_major(&make_matrix(size, size, 1))),
+    )
+    .measured_iterations(5);
+    for &thread_count in &thread_counts {
+        bench = bench.case(&threads_case_name(thread_count), move |(size, matrix)| {
+            matrix_multiply_cache_oblivious_flat_row_major_parallel(
+                matrix,
+                matrix,
+                *size,
+                *size,
+                *size,
+                thread_count,
+            )
+        });
+    }
+    print_speedups(&bench.run());
+
+    let arr_sizes: Vec<usize> = (0..5).map(|i| 256 << i).collect();
+    let mut bench = Bench::new(
+        "Parallel transpose (64^2 blocks, n x n)",
+        arr_sizes.clone(),
+        |size| (size, flatten_row_major(&make_matrix(size, size, 0))),
+    );
+    for &thread_count in &thread_counts {
+        bench = bench.case_owned(&threads_case_name(thread_count), move |(size, flat)| {
+            transpose_1d_row_major_matrix_parallel(flat, size, 64, thread_count)
+        });
+    }
+    print_speedups(&bench.run());
+
+    let kernel = box_blur_kernel(5);
+    let mut bench = Bench::new(
+        "Parallel 5x5 box blur (clamped edges, n x n f32)",
+        arr_sizes,
+        |size| {
+            let mut rng = XorShift64::new(size as u64);
+            let image: Vec<f32> = (0..size * size)
+                .map(|_| (rng.next_u64() % 256) as f32)
+                .collect();
+            (size, image)
+        },
+    );
+    for &thread_count in &thread_counts {
+        let kernel = &kernel;
+        bench = bench.case(&threads_case_name(thread_count), move |(size, image)| {
+            convolve_2d_clamped_parallel(image, *size, *size, kernel, 5, thread_count)
+        });
+    }
+    print_speedups(&bench.run());
+
+    let arr_sizes: Vec<usize> = (0..5).map(|i| 1 << (16 + i * 2)).collect();
+    let mut bench = Bench::new("Parallel two pass prefix sum", arr_sizes, |size| {
+        // small values so the sums stay well inside i32
+        let mut rng = XorShift64::new(size as u64);
+        (0..size)
+            .map(|_| (rng.next_u64() % 16) as i32)
+            .collect::<Vec<i32>>()
+    });
+    for &thread_count in &thread_counts {
+        bench = bench.case(&threads_case_name(thread_count), move |arr| {
+            make_prefix_sum_array_parallel(arr, thread_count)
+        });
+    }
+    print_speedups(&bench.run());
+    println!(
+        "The prefix sum reads and writes every element twice, so expect it to need a few threads just to catch up.\n"
+    );
+}
+
+fn threads_case_name(thread_count: usize) -> String {
+    if thread_count == 1 {
+        "1 thread".to_string()
+    } else {
+        format!("{} threads", thread_count)
+    }
+}
+
+// Median time on 1 thread over the median on N threads, per size
+fn print_speedups(report: &BenchReport) {
+    println!("---> {} (speedup over 1 thread)", report.title);
+    let columns: Vec<String> = report
+        .case_names
+        .iter()
+        .map(|case_name| format!("{} (ns)", case_name))
+        .collect();
+    print!(" {:>9} |", report.size_column);
+    for column in &columns {
+        print!(" {} | Speedup |", column);
+    }
+    println!();
+    for row in &report.rows {
+        let single_thread = row.stats[0].median.as_secs_f64();
+        print!(" {:>9} |", row.size);
+        for (column, stats) in columns.iter().zip(&row.stats) {
+            let median = stats.median.as_secs_f64();
+            print!(
+                " {:>width$} | {:>6.2}x |",
+                stats.median.as_nanos(),
+                single_thread / median,
+                width = column.len()
+            );
+        }
+        println!();
+    }
+    println!();
+}
@@ -1,8 +1,17 @@
-use crate::tutorial::common_util::{make_matrix, print_matrix_2d_vec_matrix};
+use dsa_image_processing::{
+    bench::{data::XorShift64, harness::Bench},
+    matrix::{
+        display::print_matrix_2d_vec_matrix,
+        make_matrix,
+        prefix_sum::{make_prefix_sum_matrix, sub_matrix_sum_using_prefix_matrix},
+    },
+    range_query::fenwick::{FenwickTree, FenwickTree2d},
+};
 
 pub fn prefix_sums_2d() {
     // 4a) Implement the Prefix sum
     // 4b) Implement Submatrix sums using inclusion-exclusion.
+    // 4c) Keep them correct while pixels change with a 2D Fenwick tree.
     println!("--> Vec_2d Prefix Sum Build");
     let row_count = 5;
     let col_count = 10;
@@ -18,83 +27,75 @@ pub fn prefix_sums_2d() {
     println!("--> Submatrix sums using inclusion-exclusion");
     let result = sub_matrix_sum_using_prefix_matrix(&prefix_sum_matrix, 2, 2, 4, 7);
     println!("The sub matrix sum of 2,2:4,7 = {}", result);
-}
-
-// O(n*m) build prefix sum matrix
-pub fn make_prefix_sum_matrix(matrix: &Vec<Vec<i32>>) -> Vec<Vec<i32>> {
-    let row_size = matrix.len();
-    if row_size == 0 {
-        // No rows = empty
-        return Vec::new();
-    }
-    let col_size = matrix[0].len();
-    if col_size == 0 {
-        // No cols = empty
-        return Vec::new();
-    }
-    // 1) Initialize destination matrix
-    let mut prefix_matrix = Vec::with_capacity(row_size);
-
-    // 2) Create the first row, and populate it's first element
-    let mut first_row = Vec::with_capacity(col_size);
-    first_row.push(matrix[0][0]); // Initializing corner.
-
-    // 3) Populate the rest of the first row
-    for col_idx in 1..col_size {
-        first_row.push(first_row[col_idx - 1] + matrix[0][col_idx]);
-    }
-    // 4) Add it to the matrix.
-    prefix_matrix.push(first_row);
-
-    // 4) Create the rest of the rows populating only the first column.
-    for row_idx in 1..row_size {
-        let mut new_row = Vec::with_capacity(col_size);
-        new_row.push(prefix_matrix[row_idx - 1][0] + matrix[row_idx][0]);
-        prefix_matrix.push(new_row);
-    }
 
-    // 5) Build out the rest of the prefix cells row by row from row 1 col 1 row by row.
-    for row_idx in 1..row_size {
-        for col_idx in 1..col_size {
-            // area above
-            let above = prefix_matrix[row_idx - 1][col_idx];
-            // area to the left
-            let left = prefix_matrix[row_idx][col_idx - 1];
-            let corner_that_gets_double_counted = prefix_matrix[row_idx - 1][col_idx - 1];
-            prefix_matrix[row_idx].push(
-                matrix[row_idx][col_idx] + above + left - corner_that_gets_double_counted, // fix double-count
-            );
-        }
-    }
+    prefix_sums_2d_with_updates();
+    prefix_sums_2d_with_updates_performance_compare();
+}
 
-    prefix_matrix
+fn prefix_sums_2d_with_updates() {
+    // The prefix matrix goes stale as soon as one pixel changes. A Fenwick tree answers the
+    // same query and takes the edit in O(log n * log m) instead of an O(n * m) rebuild.
+    println!("--> Submatrix sums while editing pixels (2D Fenwick tree)");
+    let mut matrix_2d_vec = make_matrix(5, 10, 0);
+    let mut tree = FenwickTree2d::from_matrix(&matrix_2d_vec);
+    println!(
+        "Sum of 2,2:4,7 before the edit = {}",
+        tree.sub_matrix_sum(2, 2, 4, 7)
+    );
+    tree.set(3, 5, 1000);
+    matrix_2d_vec[3][5] = 1000;
+    println!(
+        "After setting 3,5 to 1000 = {} (a rebuilt prefix matrix says {})",
+        tree.sub_matrix_sum(2, 2, 4, 7),
+        sub_matrix_sum_using_prefix_matrix(&make_prefix_sum_matrix(&matrix_2d_vec), 2, 2, 4, 7)
+    );
+    let mut row_tree = FenwickTree::from_slice(&matrix_2d_vec[3]);
+    row_tree.add(0, -30);
+    println!(
+        "1D version over row 3 with 30 taken off col 0: sum of cols 0..=5 = {}\n",
+        row_tree.range_sum(0, 5)
+    );
 }
 
-// O(1) get sub matrix sum of matrix using prefix matrix.
-// I left out the checking that r is to the right of l, this is just a tutorial
-// for now.
-pub fn sub_matrix_sum_using_prefix_matrix(
-    prefix_matrix: &Vec<Vec<i32>>,
-    l_row: usize,
-    l_col: usize,
-    r_row: usize,
-    r_col: usize,
-) -> i32 {
-    if l_row == 0 && l_col == 0 {
-        // there is no left, top, or top left
-        prefix_matrix[r_row][r_col]
-    } else if l_row == 0 {
-        // there is no top or top left
-        // remove left rectangle
-        prefix_matrix[r_row][r_col] - prefix_matrix[r_row][l_col - 1]
-    } else if l_col == 0 {
-        // there is no left and no top left
-        // remove top rectangle
-        prefix_matrix[r_row][r_col] - prefix_matrix[l_row - 1][r_col]
-    } else {
-        prefix_matrix[r_row][r_col]
-      - prefix_matrix[l_row-1][r_col]      // remove top rectangle
-      - prefix_matrix[r_row][l_col-1]      // remove left rectangle
-      + prefix_matrix[l_row-1][l_col-1] // add back top-left overlap (double removed)
-    }
+fn prefix_sums_2d_with_updates_performance_compare() {
+    // 100 rounds of edit one pixel, then ask for one submatrix sum
+    let edit_count = 100;
+    let arr_sizes: Vec<usize> = (0..5).map(|i| 64 << i).collect();
+    Bench::new(
+        "100 pixel edits, each followed by a submatrix sum (n x n)",
+        arr_sizes,
+        |size| {
+            let mut rng = XorShift64::new(3);
+            let edits: Vec<(usize, usize, i32)> = (0..edit_count)
+                .map(|_| {
+                    let row = rng.next_u64() as usize % size;
+                    let col = rng.next_u64() as usize % size;
+                    (row, col, (rng.next_u64() % 256) as i32)
+                })
+                .collect();
+            (make_matrix(size, size, 1), edits)
+        },
+    )
+    .measured_iterations(5)
+    .case_owned("Rebuild prefix matrix", |(mut matrix, edits)| {
+        let mut total = 0;
+        for (row, col, value) in edits {
+            matrix[row][col] = value;
+            let prefix_matrix = make_prefix_sum_matrix(&matrix);
+            total += sub_matrix_sum_using_prefix_matrix(&prefix_matrix, 0, 0, row, col);
+        }
+        total
+    })
+    .case_owned("Fenwick 2D", |(matrix, edits)| {
+        let mut tree = FenwickTree2d::from_matrix(&matrix);
+        let mut total = 0;
+        for (row, col, value) in edits {
+            tree.set(row, col, value);
+            total += tree.sub_matrix_sum(0, 0, row, col);
+        }
+        total
+    })
+    .compare("Rebuild prefix matrix", "Fenwick 2D")
+    .run()
+    .print_ratio_table();
 }
@@ -0,0 +1,110 @@
+use dsa_image_processing::{
+    bench::{data::XorShift64, harness::Bench},
+    range_query::{
+        segment_tree::{LazySegmentTree, SegmentTree, SegmentTree2d},
+        sparse_table::{SparseTable, SparseTable2d, gcd},
+    },
+};
+use std::cmp::{max, min};
+
+pub fn range_queries() {
+    // 4d) Prefix sums only work because subtraction undoes addition. Min and max have no
+    // inverse, so region min/max needs a sparse table (static) or a segment tree (updates).
+    println!("--> Range min/max/gcd queries");
+    range_queries_example();
+    region_min_max_performance_compare();
+}
+
+fn range_queries_example() {
+    let arr = [12, 18, 6, 30, 42, 24, 36, 9];
+    println!("Array {:?}", arr);
+    let min_table = SparseTable::new(&arr, min);
+    let gcd_table = SparseTable::new(&arr, gcd);
+    println!(
+        "Sparse table min 1..=5 = {}, gcd 0..=6 = {}, gcd 0..=7 = {}",
+        min_table.query(1, 5),
+        gcd_table.query(0, 6),
+        gcd_table.query(0, 7)
+    );
+    let mut sum_tree = SegmentTree::new(&arr, 0, |a, b| a + b);
+    sum_tree.set(3, 0);
+    println!(
+        "Segment tree sum 2..=4 after setting index 3 to 0 = {}",
+        sum_tree.query(2, 4)
+    );
+
+    let values: Vec<i64> = arr.iter().map(|&v| v as i64).collect();
+    let mut lazy_tree = LazySegmentTree::new(&values);
+    lazy_tree.range_add(0, 3, 100);
+    lazy_tree.range_assign(5, 7, -1);
+    println!(
+        "Lazy segment tree after +100 on 0..=3 and = -1 on 5..=7: {:?} over 2..=6\n",
+        lazy_tree.range_summary(2, 6)
+    );
+}
+
+fn region_min_max_performance_compare() {
+    // 1000 random region max queries over an n x n image. The sparse table pays for an
+    // O(n^2 log^2 n) build and memory, the 2D segment tree for O(log^2 n) queries.
+    let query_count = 1000;
+    let arr_sizes: Vec<usize> = (0..4).map(|i| 64 << i).collect();
+    Bench::new("1000 region max queries (n x n image)", arr_sizes, |size| {
+        let mut rng = XorShift64::new(21);
+        let image: Vec<Vec<i32>> = (0..size)
+            .map(|_| (0..size).map(|_| (rng.next_u64() % 256) as i32).collect())
+            .collect();
+        let regions: Vec<(usize, usize, usize, usize)> = (0..query_count)
+            .map(|_| {
+                let (a, b) = (
+                    rng.next_u64() as usize % size,
+                    rng.next_u64() as usize % size,
+                );
+                let (c, d) = (
+                    rng.next_u64() as usize % size,
+                    rng.next_u64() as usize % size,
+                );
+                (a.min(b), c.min(d), a.max(b), c.max(d))
+            })
+            .collect();
+        let sparse_table = SparseTable2d::new(&image, max);
+        let segment_tree = SegmentTree2d::new(&image, i32::MIN, max);
+        (image, regions, sparse_table, segment_tree)
+    })
+    .measured_iterations(5)
+    .case("Scan", |(image, regions, _, _)| {
+        regions
+            .iter()
+            .map(|&(l_row, l_col, r_row, r_col)| {
+                image[l_row..=r_row]
+                    .iter()
+                    .flat_map(|row| &row[l_col..=r_col])
+                    .copied()
+                    .max()
+                    .unwrap()
+            })
+            .sum::<i32>()
+    })
+    .case("Sparse table 2D", |(_, regions, sparse_table, _)| {
+        regions
+            .iter()
+            .map(|&(l_row, l_col, r_row, r_col)| sparse_table.query(l_row, l_col, r_row, r_col))
+            .sum::<i32>()
+    })
+    .case("Segment tree 2D", |(_, regions, _, segment_tree)| {
+        regions
+            .iter()
+            .map(|&(l_row, l_col, r_row, r_col)| segment_tree.query(l_row, l_col, r_row, r_col))
+            .sum::<i32>()
+    })
+    .case("Sparse table 2D build", |(image, _, _, _)| {
+        SparseTable2d::new(image, max).row_count()
+    })
+    .case("Segment tree 2D build", |(image, _, _, _)| {
+        SegmentTree2d::new(image, i32::MIN, max).row_count()
+    })
+    .compare("Scan", "Sparse table 2D")
+    .compare("Scan", "Segment tree 2D")
+    .compare("Sparse table 2D build", "Segment tree 2D build")
+    .run()
+    .print_ratio_table();
+}
@@ -0,0 +1,154 @@
+use dsa_image_processing::{
+    bench::{data::XorShift64, harness::Bench},
+    matrix::{
+        display::print_matrix_2d_vec_matrix,
+        make_matrix,
+        op_multiply::{MatrixOp, matrix_multiply_op_flat_row_major, matrix_vector_multiply},
+        sparse::{CooMatrix, CscMatrix, CsrMatrix, dense_memory_bytes},
+    },
+};
+
+// Nonzeros per 10000 cells
+const DENSITIES: [usize; 5] = [1, 10, 100, 1000, 5000];
+
+pub fn sparse_matrices() {
+    println!("==> 3m) Sparse matrices: COO, CSR and CSC\n");
+    // 3m) Pixel graph adjacency and convolution operators are mostly zeros. Storing only the
+    // nonzeros with their indices trades a little indirection for skipping all of that.
+    sparse_matrices_example();
+    sparse_matrices_memory();
+    sparse_matrices_performance_compare();
+}
+
+fn sparse_matrices_example() {
+    // make_matrix numbers the cells, keep every 5th so most of it is zero
+    let dense: Vec<Vec<i32>> = make_matrix(5, 6, 0)
+        .into_iter()
+        .map(|row| {
+            row.into_iter()
+                .map(|v| if v % 5 == 3 { v } else { 0 })
+                .collect()
+        })
+        .collect();
+    println!("--> Dense 5x6:");
+    print_matrix_2d_vec_matrix(&dense);
+    let coo = CooMatrix::from_dense(&dense);
+    println!(
+        "COO rows {:?}\n    cols {:?}\n    values {:?}",
+        coo.row_indices, coo.col_indices, coo.values
+    );
+    let csr = coo.to_csr();
+    println!(
+        "CSR row_offsets {:?}\n    col_indices {:?}\n    values {:?}",
+        csr.row_offsets, csr.col_indices, csr.values
+    );
+    let csc = csr.to_csc();
+    println!(
+        "CSC col_offsets {:?}\n    row_indices {:?}\n    values {:?}",
+        csc.col_offsets, csc.row_indices, csc.values
+    );
+    println!("Transposed (6x5):");
+    print_matrix_2d_vec_matrix(&csr.transpose().to_dense());
+    println!(
+        "[A]·[1, 1, 1, 1, 1, 1] = {:?}\n",
+        csr.multiply_vector(&[1; 6])
+    );
+}
+
+fn random_sparse(seed: u64, row_count: usize, col_count: usize, density: usize) -> Vec<Vec<f64>> {
+    let mut rng = XorShift64::new(seed);
+    (0..row_count)
+        .map(|_| {
+            (0..col_count)
+                .map(|_| {
+                    if (rng.next_u64() % 10000) < density as u64 {
+                        (rng.next_u64() % 100) as f64 / 10.0 + 0.1
+                    } else {
+                        0.0
+                    }
+                })
+                .collect()
+        })
+        .collect()
+}
+
+fn sparse_matrices_memory() {
+    let side = 2048;
+    println!("--> Memory of a {side}x{side} f64 matrix");
+    println!(
+        " {:>18} | {:>12} | {:>12} | {:>8}",
+        "Nonzeros per 10000", "Dense (KiB)", "CSR (KiB)", "CSR/Dense"
+    );
+    for density in DENSITIES {
+        let csr = CsrMatrix::from_dense(&random_sparse(1, side, side, density));
+        let dense_bytes = dense_memory_bytes::<f64>(side, side);
+        println!(
+            " {:>18} | {:>12} | {:>12} | {:>8.3}",
+            density,
+            dense_bytes / 1024,
+            csr.memory_bytes() / 1024,
+            csr.memory_bytes() as f64 / dense_bytes as f64
+        );
+    }
+    println!("Every nonzero costs a usize index on top of its value, so CSR needs more than");
+    println!("half of the cells to be zero (fewer than half nonzero) before it uses less memory");
+    println!("than dense.\n");
+}
+
+fn sparse_matrices_performance_compare() {
+    let side = 2048;
+    Bench::new(
+        &format!("y = A·x, dense vs sparse (f64, {side} x {side})"),
+        DENSITIES.to_vec(),
+        |density| {
+            let dense = random_sparse(2, side, side, density);
+            let flat: Vec<f64> = dense.iter().flatten().copied().collect();
+            let csr = CsrMatrix::from_dense(&dense);
+            let csc = CscMatrix::from_dense(&dense);
+            let vector_x: Vec<f64> = (0..side).map(|v| v as f64).collect();
+            (flat, csr, csc, vector_x)
+        },
+    )
+    .size_column("Nonzeros per 10000")
+    .case("Dense", |(flat, _, _, vector_x)| {
+        matrix_vector_multiply(MatrixOp::Normal, flat, side, side, vector_x)
+    })
+    .case("CSR", |(_, csr, _, vector_x)| csr.multiply_vector(vector_x))
+    .case("CSC", |(_, _, csc, vector_x)| csc.multiply_vector(vector_x))
+    .compare("Dense", "CSR")
+    .compare("Dense", "CSC")
+    .run()
+    .print_ratio_table();
+
+    let (side, b_col_count) = (512, 64);
+    Bench::new(
+        &format!("C = A·B, dense vs sparse A (f64, {side} x {side} times {side} x {b_col_count})"),
+        DENSITIES.to_vec(),
+        |density| {
+            let dense = random_sparse(3, side, side, density);
+            let flat: Vec<f64> = dense.iter().flatten().copied().collect();
+            let matrix_b: Vec<f64> = (0..side * b_col_count).map(|v| (v % 13) as f64).collect();
+            (flat, CsrMatrix::from_dense(&dense), matrix_b)
+        },
+    )
+    .size_column("Nonzeros per 10000")
+    .measured_iterations(5)
+    .case("Dense", |(flat, _, matrix_b)| {
+        matrix_multiply_op_flat_row_major(
+            MatrixOp::Normal,
+            MatrixOp::Normal,
+            flat,
+            matrix_b,
+            side,
+            side,
+            b_col_count,
+        )
+    })
+    .case("CSR", |(_, csr, matrix_b)| {
+        csr.multiply_dense(matrix_b, b_col_count)
+    })
+    .case("CSR transpose", |(_, csr, _)| csr.transpose())
+    .compare("Dense", "CSR")
+    .run()
+    .print_ratio_table();
+}
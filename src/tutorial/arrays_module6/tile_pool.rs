@@ -0,0 +1,137 @@
+use dsa_image_processing::{
+    bench::{data::XorShift64, harness::Bench},
+    matrix::{
+        convolve::{box_blur_kernel, convolve_2d_clamped_parallel, convolve_2d_clamped_pooled},
+        display::print_matrix_1d_vec_matrix,
+        flatten::flatten_row_major,
+        make_matrix,
+        rotate::{rotate_1d_row_major_matrix_pooled, rotate_2d_vec},
+        transpose::{transpose_1d_row_major_matrix_parallel, transpose_1d_row_major_matrix_pooled},
+    },
+    parallel::{
+        self,
+        pool::{ThreadPool, tile_grid},
+    },
+};
+use std::sync::Mutex;
+
+const TILE: usize = 64;
+
+pub fn tile_pool() {
+    println!("==> 3j) Work-stealing tile pool\n");
+    // 3j) Spawning scoped threads on every call (3i) costs more than a small image takes to
+    // process. Keep the threads around in a pool, hand them tiles, and let idle workers steal.
+    tile_pool_order();
+    tile_pool_performance_compare();
+}
+
+fn tile_pool_order() {
+    let (width, height, tile) = (10, 6, 4);
+    println!(
+        "--> {}x{} image in {}x{} tiles: {} tiles",
+        width,
+        height,
+        tile,
+        tile,
+        tile_grid(width, height, tile).len()
+    );
+    // Number each pixel by the step its tile ran at, deterministic mode first
+    for pool in [ThreadPool::deterministic(), ThreadPool::new(3)] {
+        let step = Mutex::new(0);
+        let mut visit_order = vec![0; width * height];
+        pool.par_for_each_tile_mut(&mut visit_order, width, height, tile, |mut view| {
+            let tile_step = {
+                let mut step = step.lock().unwrap();
+                *step += 1;
+                *step
+            };
+            for y in view.tile().rows() {
+                view.row_mut(y).fill(tile_step);
+            }
+        });
+        if pool.is_deterministic() {
+            println!("Deterministic mode, always row-major tile order:");
+        } else {
+            println!(
+                "{} workers, each takes a run of tiles, order varies from run to run:",
+                pool.worker_count()
+            );
+        }
+        print_matrix_1d_vec_matrix(&visit_order, width);
+    }
+
+    let flat = flatten_row_major(&make_matrix(3, 4, 0));
+    println!("Rotated 90 degrees on the global pool (3x4 -> 4x3):");
+    print_matrix_1d_vec_matrix(
+        &rotate_1d_row_major_matrix_pooled(flat, 4, 2, ThreadPool::global()),
+        3,
+    );
+    println!();
+}
+
+fn tile_pool_performance_compare() {
+    let thread_count = parallel::thread_count();
+    let pool = ThreadPool::global();
+    let scoped = format!("Scoped Threads ({})", thread_count);
+    let pooled = format!("Pool ({} workers, {}^2 tiles)", pool.worker_count(), TILE);
+    let arr_sizes: Vec<usize> = (0..5).map(|i| 64 << i).collect();
+
+    Bench::new(
+        "Transpose, scoped threads per call vs persistent pool (n x n)",
+        arr_sizes.clone(),
+        |size| (size, flatten_row_major(&make_matrix(size, size, 0))),
+    )
+    .data_size(|size| size * size)
+    .case_owned(&scoped, |(size, flat)| {
+        transpose_1d_row_major_matrix_parallel(flat, size, TILE, thread_count)
+    })
+    .case_owned(&pooled, |(size, flat)| {
+        transpose_1d_row_major_matrix_pooled(flat, size, TILE, pool)
+    })
+    .compare(&scoped, &pooled)
+    .run()
+    .print_ratio_table();
+
+    Bench::new(
+        "Rotate 90, single threaded blocks vs persistent pool (n x n)",
+        arr_sizes.clone(),
+        |size| {
+            let matrix = make_matrix(size, size, 0);
+            let flat = flatten_row_major(&matrix);
+            (size, matrix, flat)
+        },
+    )
+    .data_size(|size| size * size)
+    .case_owned("2d Blocks(64^2)", |(_, matrix, _)| {
+        rotate_2d_vec(matrix, TILE)
+    })
+    .case_owned(&pooled, |(size, _, flat)| {
+        rotate_1d_row_major_matrix_pooled(flat, size, TILE, pool)
+    })
+    .compare("2d Blocks(64^2)", &pooled)
+    .run()
+    .print_ratio_table();
+
+    let kernel = box_blur_kernel(3);
+    Bench::new(
+        "3x3 box blur, scoped threads per call vs persistent pool (n x n f32)",
+        arr_sizes,
+        |size| {
+            let mut rng = XorShift64::new(size as u64);
+            let image: Vec<f32> = (0..size * size)
+                .map(|_| (rng.next_u64() % 256) as f32)
+                .collect();
+            (size, image)
+        },
+    )
+    .data_size(|size| size * size)
+    .case(&scoped, |(size, image)| {
+        convolve_2d_clamped_parallel(image, *size, *size, &kernel, 3, thread_count)
+    })
+    .case(&pooled, |(size, image)| {
+        convolve_2d_clamped_pooled(image, *size, *size, &kernel, 3, TILE, pool)
+    })
+    .compare(&scoped, &pooled)
+    .run()
+    .print_ratio_table();
+}
@@ -0,0 +1,61 @@
+use dsa_image_processing::{
+    matrix::make_matrix,
+    visualize::{
+        Frame, StepGranularity,
+        frames::{
+            matrix_multiply_loop_tiling_frames, rotate_2d_vec_frames, transpose_loop_tiling_frames,
+        },
+        player::{export_text_animation, play_interactive},
+        render::RenderOptions,
+    },
+};
+use std::io::{self, IsTerminal};
+
+// 3c) The "matrix A/B/C visualizer highlighting the parts being worked on" from the note above
+// matrix_multiply_loop_tiling. Steps on Enter in a terminal, otherwise prints every frame so
+// the output can be saved as a text animation (e.g. `... -- visualize > tiling.txt`).
+pub fn tiled_loop_visualizer() {
+    println!("==> 3c) Loop tiling visualizer\n");
+    let interactive = io::stdin().is_terminal() && io::stdout().is_terminal();
+    let options = RenderOptions {
+        color: io::stdout().is_terminal(),
+    };
+
+    let matrix_a = make_matrix(4, 6, 0);
+    let matrix_b = make_matrix(6, 5, 0);
+    show(
+        "Loop tiling [A]*[B] = [C] (2x2 blocks)",
+        matrix_multiply_loop_tiling_frames(&matrix_a, &matrix_b, 2, StepGranularity::Element),
+        interactive,
+        options,
+    );
+    show(
+        "Block tiled in place transpose (2x2 blocks)",
+        transpose_loop_tiling_frames(&make_matrix(6, 6, 0), 2, StepGranularity::Element),
+        interactive,
+        options,
+    );
+    show(
+        "Block tiled transpose into a new matrix (3x3 blocks)",
+        transpose_loop_tiling_frames(&make_matrix(5, 7, 0), 3, StepGranularity::Element),
+        interactive,
+        options,
+    );
+    show(
+        "rotate_2d_vec 90 degrees clockwise (2x2 blocks)",
+        rotate_2d_vec_frames(&make_matrix(4, 6, 0), 2, StepGranularity::Element),
+        interactive,
+        options,
+    );
+}
+
+fn show(title: &str, frames: Vec<Frame>, interactive: bool, options: RenderOptions) {
+    println!("--> {} ({} steps)", title, frames.len());
+    if interactive {
+        if let Err(e) = play_interactive(&frames, options, io::stdin().lock(), io::stdout()) {
+            println!("visualizer stopped: {}", e);
+        }
+    } else {
+        print!("{}", export_text_animation(&frames, options));
+    }
+}
@@ -0,0 +1,202 @@
+use dsa_image_processing::{
+    bench::{data::i32s_from_seed, harness::Bench},
+    matrix::{
+        display::print_matrix_1d_vec_matrix,
+        op_multiply::{
+            MatrixOp, matrix_multiply_batched_flat_row_major,
+            matrix_multiply_batched_flat_row_major_parallel, matrix_multiply_op_flat_row_major,
+            matrix_vector_multiply,
+        },
+        transpose::transpose_1d_row_major_matrix_loop_tiling_aka_block_tiling,
+    },
+    parallel,
+};
+
+pub fn transposed_operands() {
+    println!("==> 3k) Transposed operands, matrix-vector and batched multiply\n");
+    // 3k) matrix_multiplication_performance_compare transposes B before multiplying. BLAS
+    // never does that: a transa/transb flag picks a loop order that reads the stored matrix
+    // with unit stride instead.
+    transposed_operands_example();
+    transposed_operands_performance_compare();
+}
+
+fn transposed_operands_example() {
+    // A is stored 2x3, so Aᵀ is 3x2
+    let matrix_a = vec![1, 2, 3, 4, 5, 6];
+    println!("--> [A] stored 2x3:");
+    print_matrix_1d_vec_matrix(&matrix_a, 3);
+    println!("[A]ᵀ·[A] (3x3) without building [A]ᵀ:");
+    let product = matrix_multiply_op_flat_row_major(
+        MatrixOp::Transposed,
+        MatrixOp::Normal,
+        &matrix_a,
+        &matrix_a,
+        3,
+        2,
+        3,
+    );
+    print_matrix_1d_vec_matrix(&product, 3);
+    println!("[A]·[A]ᵀ (2x2):");
+    let product = matrix_multiply_op_flat_row_major(
+        MatrixOp::Normal,
+        MatrixOp::Transposed,
+        &matrix_a,
+        &matrix_a,
+        2,
+        3,
+        2,
+    );
+    print_matrix_1d_vec_matrix(&product, 2);
+    println!(
+        "[A]·[1, 1, 1] = {:?}, [A]ᵀ·[1, 1] = {:?}\n",
+        matrix_vector_multiply(MatrixOp::Normal, &matrix_a, 2, 3, &[1, 1, 1]),
+        matrix_vector_multiply(MatrixOp::Transposed, &matrix_a, 3, 2, &[1, 1])
+    );
+}
+
+fn transposed_operands_performance_compare() {
+    let arr_sizes: Vec<usize> = (0..5).map(|i| 64 << i).collect();
+    let transpose_first = "Transpose then A·B";
+    let mut bench = Bench::new(
+        "C = op(A)·op(B), transposing first vs op flags (i32, n x n)",
+        arr_sizes.clone(),
+        |size| {
+            (
+                size,
+                i32s_from_seed(1, size * size),
+                i32s_from_seed(2, size * size),
+            )
+        },
+    )
+    .measured_iterations(5)
+    .case(transpose_first, |(size, matrix_a, matrix_b)| {
+        let matrix_b_transposed =
+            transpose_1d_row_major_matrix_loop_tiling_aka_block_tiling(matrix_b.clone(), *size, 64);
+        matrix_multiply_op_flat_row_major(
+            MatrixOp::Normal,
+            MatrixOp::Normal,
+            matrix_a,
+            &matrix_b_transposed,
+            *size,
+            *size,
+            *size,
+        )
+    });
+    for (op_a, op_b) in [
+        (MatrixOp::Normal, MatrixOp::Normal),
+        (MatrixOp::Transposed, MatrixOp::Normal),
+        (MatrixOp::Normal, MatrixOp::Transposed),
+        (MatrixOp::Transposed, MatrixOp::Transposed),
+    ] {
+        let name = format!("{}{} flags", op_a.name(), op_b.name());
+        bench = bench
+            .case(&name, move |(size, matrix_a, matrix_b)| {
+                matrix_multiply_op_flat_row_major(
+                    op_a, op_b, matrix_a, matrix_b, *size, *size, *size,
+                )
+            })
+            .compare(transpose_first, &name);
+    }
+    bench.run().print_ratio_table();
+
+    Bench::new(
+        "y = op(A)·x (i32, n x n)",
+        arr_sizes.iter().map(|size| size * 4).collect(),
+        |size| {
+            (
+                size,
+                i32s_from_seed(3, size * size),
+                i32s_from_seed(4, size),
+            )
+        },
+    )
+    .case("A·x", |(size, matrix_a, vector_x)| {
+        matrix_vector_multiply(MatrixOp::Normal, matrix_a, *size, *size, vector_x)
+    })
+    .case("Aᵀ·x", |(size, matrix_a, vector_x)| {
+        matrix_vector_multiply(MatrixOp::Transposed, matrix_a, *size, *size, vector_x)
+    })
+    .case("Transpose then A·x", |(size, matrix_a, vector_x)| {
+        let matrix_a_transposed =
+            transpose_1d_row_major_matrix_loop_tiling_aka_block_tiling(matrix_a.clone(), *size, 64);
+        matrix_vector_multiply(
+            MatrixOp::Normal,
+            &matrix_a_transposed,
+            *size,
+            *size,
+            vector_x,
+        )
+    })
+    .compare("A·x", "Aᵀ·x")
+    .compare("Transpose then A·x", "Aᵀ·x")
+    .run()
+    .print_ratio_table();
+
+    // Lots of 4x4 products, one call each vs one call for the whole batch
+    let thread_count = parallel::thread_count();
+    let batched_parallel = format!("Batched ({} threads)", thread_count);
+    let side = 4;
+    Bench::new(
+        "Batched 4x4 A·Bᵀ products (batch count)",
+        (0..4).map(|i| 1000 * 10usize.pow(i) / 10).collect(),
+        |batch_count| {
+            (
+                batch_count,
+                i32s_from_seed(5, batch_count * side * side),
+                i32s_from_seed(6, batch_count * side * side),
+            )
+        },
+    )
+    .case(
+        "One call per product",
+        |(batch_count, matrices_a, matrices_b)| {
+            (0..*batch_count)
+                .map(|batch_idx| {
+                    let range = batch_idx * side * side..(batch_idx + 1) * side * side;
+                    matrix_multiply_op_flat_row_major(
+                        MatrixOp::Normal,
+                        MatrixOp::Transposed,
+                        &matrices_a[range.clone()],
+                        &matrices_b[range],
+                        side,
+                        side,
+                        side,
+                    )
+                })
+                .collect::<Vec<Vec<i32>>>()
+        },
+    )
+    .case("Batched", |(batch_count, matrices_a, matrices_b)| {
+        matrix_multiply_batched_flat_row_major(
+            MatrixOp::Normal,
+            MatrixOp::Transposed,
+            matrices_a,
+            matrices_b,
+            *batch_count,
+            side,
+            side,
+            side,
+        )
+    })
+    .case(
+        &batched_parallel,
+        |(batch_count, matrices_a, matrices_b)| {
+            matrix_multiply_batched_flat_row_major_parallel(
+                MatrixOp::Normal,
+                MatrixOp::Transposed,
+                matrices_a,
+                matrices_b,
+                *batch_count,
+                side,
+                side,
+                side,
+                thread_count,
+            )
+        },
+    )
+    .compare("One call per product", "Batched")
+    .compare("Batched", &batched_parallel)
+    .run()
+    .print_ratio_table();
+}
@@ -0,0 +1,92 @@
+use dsa_image_processing::{
+    bench::harness::Bench,
+    matrix::{
+        flatten::flatten_row_major,
+        make_matrix,
+        traversal::{Traversal, border_rings, traverse_1d_row_major, traverse_2d_vec},
+    },
+    visualize::access_order::traversal_access_order,
+};
+
+pub fn traversal_orders() {
+    // 6a) Spiral traversal, and the other orders that are not just row or col major: JPEG
+    // zig-zag, diagonals, snake and border rings, as coordinate iterators over any layout.
+    println!("--> Traversal orders (spiral, zig-zag, diagonal, snake, border rings)");
+    traversal_orders_example();
+    border_rings_example();
+    traversal_orders_performance_compare();
+}
+
+fn print_visit_steps(traversal: Traversal, row_count: usize, col_count: usize) {
+    let mut steps = vec![vec![0; col_count]; row_count];
+    for (step, (row, col)) in traversal.coords(row_count, col_count).enumerate() {
+        steps[row][col] = step;
+    }
+    println!("{} (visit step per cell):", traversal.name());
+    for row in &steps {
+        let cells: Vec<String> = row.iter().map(|step| format!("{:>3}", step)).collect();
+        println!("{}", cells.join(""));
+    }
+}
+
+fn traversal_orders_example() {
+    let (row_count, col_count) = (4, 5);
+    for traversal in Traversal::ALL {
+        print_visit_steps(traversal, row_count, col_count);
+        let order = traversal_access_order(traversal, 64, 64);
+        println!(
+            "mean row-major stride on 64x64: {:.2}\n",
+            order.mean_row_major_stride()
+        );
+    }
+
+    // The same order over both layouts
+    let matrix = make_matrix(3, 4, 0);
+    let flat = flatten_row_major(&matrix);
+    let from_2d_vec: Vec<&i32> = traverse_2d_vec(&matrix, Traversal::ZigZag).collect();
+    let from_flat: Vec<&i32> = traverse_1d_row_major(&flat, 4, Traversal::ZigZag).collect();
+    println!("zig-zag over Vec<Vec<i32>>: {:?}", from_2d_vec);
+    println!("zig-zag over flat row-major: {:?}\n", from_flat);
+}
+
+fn border_rings_example() {
+    let (row_count, col_count) = (5, 6);
+    let mut ring_of_cell = vec![vec![0; col_count]; row_count];
+    for (ring, coords) in border_rings(row_count, col_count).enumerate() {
+        for (row, col) in coords {
+            ring_of_cell[row][col] = ring;
+        }
+    }
+    println!("border ring per cell:");
+    for row in &ring_of_cell {
+        let cells: Vec<String> = row.iter().map(|ring| ring.to_string()).collect();
+        println!(" {}", cells.join(" "));
+    }
+    println!();
+}
+
+fn traversal_orders_performance_compare() {
+    let arr_sizes: Vec<usize> = (0..4).map(|i| 256 << i).collect();
+    let mut bench = Bench::new(
+        "Summing a flat n x n matrix in each order",
+        arr_sizes,
+        |size| (flatten_row_major(&make_matrix(size, size, 0)), size),
+    )
+    .measured_iterations(5);
+    for traversal in Traversal::ALL {
+        bench = bench.case(traversal.name(), move |(flat, col_count)| {
+            traverse_1d_row_major(flat, *col_count, traversal)
+                .map(|&v| v as i64)
+                .sum::<i64>()
+        });
+    }
+    bench
+        .compare("snake", "spiral clockwise")
+        .compare("snake", "zig-zag")
+        .compare("snake", "diagonal")
+        .run()
+        .print_ratio_table();
+    println!("All of them pay for the coordinate iterator, snake is the baseline because it stays");
+    println!("on the row-major cache lines. The spiral only jumps rows on its column sides, every");
+    println!("zig-zag and diagonal step does, which is why JPEG only zig-zags 8x8 blocks.\n");
+}
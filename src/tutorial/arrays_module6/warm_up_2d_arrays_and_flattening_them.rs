@@ -1,4 +1,7 @@
-use crate::tutorial::common_util::make_matrix;
+use dsa_image_processing::matrix::{
+    flatten::{flatten_col_major, flatten_row_major, get_col_major, get_row_major},
+    make_matrix,
+};
 
 pub fn warm_up_2d_arrays_and_flattening_them() {
     // 1) Warm-Up - 2D Arrays
@@ -10,18 +13,18 @@ pub fn warm_up_2d_arrays_and_flattening_them() {
     for col_idx in 0..array[0].len() {
         print!(" {:>4} |", col_idx);
     }
-    print!("\n");
+    println!();
     for _ in 0..=array[0].len() {
         print!("------|");
     }
-    print!("\n");
+    println!();
     for row_idx in 0..array.len() {
         let row = &array[row_idx];
         print!(" {:>4} |", row_idx);
         row.iter().for_each(|column_value| {
             print!(" {:>4} |", column_value);
         });
-        print!("\n");
+        println!();
     }
     let row_idx = 1;
     let col_idx = 1;
@@ -35,18 +38,18 @@ pub fn warm_up_2d_arrays_and_flattening_them() {
     for col_idx in 0..matrix[0].len() {
         print!(" {:>4} |", col_idx);
     }
-    print!("\n");
+    println!();
     for _ in 0..=matrix[0].len() {
         print!("------|");
     }
-    print!("\n");
+    println!();
     for row_idx in 0..matrix.len() {
         let row = &matrix[row_idx];
         print!(" {:>4} |", row_idx);
         row.iter().for_each(|column_value| {
             print!(" {:>4} |", column_value);
         });
-        print!("\n");
+        println!();
     }
     let row_idx = 5;
     let col_idx = 5;
@@ -71,18 +74,18 @@ pub fn warm_up_2d_arrays_and_flattening_them() {
     for col_idx in 0..sub_matrix_slice[0].len() {
         print!(" {:>4} |", (col_range_start + col_idx));
     }
-    print!("\n");
+    println!();
     for _ in 0..=sub_matrix_slice[0].len() {
         print!("------|");
     }
-    print!("\n");
+    println!();
     for row_idx in 0..sub_matrix_slice.len() {
         let row = &sub_matrix_slice[row_idx];
         print!(" {:>4} |", row_range_start + row_idx);
         row.iter().for_each(|column_value| {
             print!(" {:>4} |", column_value);
         });
-        print!("\n");
+        println!();
     }
     println!("\n");
     // That took way to much work, would have hoped rust had some sugar syntax
@@ -101,11 +104,11 @@ pub fn warm_up_2d_arrays_and_flattening_them() {
     for col_idx in 0..column_count {
         print!(" {:>4} |", col_idx);
     }
-    print!("\n");
+    println!();
     for _ in 0..=column_count {
         print!("------|");
     }
-    print!("\n");
+    println!();
     // just print out the rows
     for row_idx in 0..row_count {
         print!(" {:>4} |", row_idx);
@@ -115,20 +118,20 @@ pub fn warm_up_2d_arrays_and_flattening_them() {
                 row_major_1d_flatten[row_idx * column_count + col_idx]
             );
         }
-        print!("\n");
+        println!();
     }
     println!("\n");
     print!("Let's traverse it with for each row, get a slice of columns, and ");
-    print!("print the columns as if we are back in a 2d array.\n");
+    println!("print the columns as if we are back in a 2d array.");
     print!(" {:>4} |", "idx");
     for col_idx in 0..column_count {
         print!(" {:>4} |", col_idx);
     }
-    print!("\n");
+    println!();
     for _ in 0..=column_count {
         print!("------|");
     }
-    print!("\n");
+    println!();
     // just print out the rows
     for row_idx in 0..row_count {
         print!(" {:>4} |", row_idx);
@@ -138,7 +141,7 @@ pub fn warm_up_2d_arrays_and_flattening_them() {
         row.iter().for_each(|column_value| {
             print!(" {:>4} |", column_value);
         });
-        print!("\n");
+        println!();
     }
     print!("\n\n");
     println!(
@@ -158,11 +161,11 @@ pub fn warm_up_2d_arrays_and_flattening_them() {
     for col_idx in 0..column_count {
         print!(" {:>4} |", col_idx);
     }
-    print!("\n");
+    println!();
     for _ in 0..=column_count {
         print!("------|");
     }
-    print!("\n");
+    println!();
     // just print out the rows
     for row_idx in 0..row_count {
         print!(" {:>4} |", row_idx);
@@ -172,7 +175,7 @@ pub fn warm_up_2d_arrays_and_flattening_them() {
                 col_major_1d_flatten[col_idx * row_count + row_idx]
             );
         }
-        print!("\n");
+        println!();
     }
     println!("\n");
     println!(
@@ -183,8 +186,8 @@ pub fn warm_up_2d_arrays_and_flattening_them() {
 }
 
 fn make_array() -> [[i32; 3]; 3] {
-    let array = [[1, 2, 3], [4, 5, 6], [7, 8, 9]];
-    array
+    
+    [[1, 2, 3], [4, 5, 6], [7, 8, 9]]
 }
 
 fn row_major_1d_flatten_i32(matrix: Vec<Vec<i32>>) -> Vec<i32> {
@@ -194,43 +197,3 @@ fn row_major_1d_flatten_i32(matrix: Vec<Vec<i32>>) -> Vec<i32> {
 fn col_major_1d_flatten_i32(matrix: Vec<Vec<i32>>) -> Vec<i32> {
     flatten_col_major(&matrix)
 }
-
-/// Flatten a 2D vector into 1D row-major order.
-pub fn flatten_row_major<T: Clone>(matrix: &Vec<Vec<T>>) -> Vec<T> {
-    let row_count = matrix.len();
-    let col_count = matrix[0].len();
-    let mut result = Vec::with_capacity(row_count * col_count);
-    for row_idx in 0..row_count {
-        for col_idx in 0..col_count {
-            let value = matrix[row_idx][col_idx].clone();
-            result.push(value);
-        }
-    }
-    result
-}
-
-/// Flatten a 2D vector into 1D column-major order.
-pub fn flatten_col_major<T: Clone>(matrix: &Vec<Vec<T>>) -> Vec<T> {
-    let row_count = matrix.len();
-    let col_count = matrix[0].len();
-    let mut result = Vec::with_capacity(row_count * col_count);
-    for col_idx in 0..col_count {
-        for row_idx in 0..row_count {
-            let value = matrix[row_idx][col_idx].clone();
-            result.push(value);
-        }
-    }
-    result
-}
-
-/// Access element in row-major flattened array.
-/// rows = number of rows in the original matrix
-/// cols = number of columns in the original matrix
-pub fn get_row_major<T>(flat: &Vec<T>, row: usize, col: usize, cols: usize) -> &T {
-    &flat[row * cols + col]
-}
-
-/// Access element in column-major flattened array.
-pub fn get_col_major<T>(flat: &Vec<T>, row: usize, col: usize, rows: usize) -> &T {
-    &flat[col * rows + row]
-}
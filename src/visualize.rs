@@ -0,0 +1,146 @@
+// Step-through visualizer for the tiled loops in module 6. The frame generators replay a
+// kernel's loops and record, for every step, which block / row / column / cell of each matrix
+// is being worked on; the renderer draws that with ANSI colours and the player steps through
+// the frames on Enter or writes them all out as a text animation.
+//
+// access_order/heatmap cover the whole traversal at once instead: the visit time of every cell
+// written out as an SVG heatmap or PGM image.
+pub mod access_order;
+pub mod frames;
+pub mod heatmap;
+pub mod player;
+pub mod render;
+
+// Half open rectangle of cells, rows row_start..row_end and cols col_start..col_end
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Region {
+    pub row_start: usize,
+    pub row_end: usize,
+    pub col_start: usize,
+    pub col_end: usize,
+}
+
+impl Region {
+    pub fn new(rows: std::ops::Range<usize>, cols: std::ops::Range<usize>) -> Region {
+        Region {
+            row_start: rows.start,
+            row_end: rows.end,
+            col_start: cols.start,
+            col_end: cols.end,
+        }
+    }
+
+    pub fn cell(row: usize, col: usize) -> Region {
+        Region::new(row..row + 1, col..col + 1)
+    }
+
+    pub fn contains(&self, row: usize, col: usize) -> bool {
+        (self.row_start..self.row_end).contains(&row)
+            && (self.col_start..self.col_end).contains(&col)
+    }
+}
+
+// Ordered weakest to strongest, a cell covered by several highlights shows the strongest
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum HighlightKind {
+    Block,
+    Row,
+    Column,
+    Cell,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Highlight {
+    pub kind: HighlightKind,
+    pub region: Region,
+}
+
+// One matrix as it looks at a given step. Cells that haven't been written yet are None.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MatrixView {
+    pub name: String,
+    pub row_count: usize,
+    pub col_count: usize,
+    // row-major
+    pub values: Vec<Option<i32>>,
+    pub highlights: Vec<Highlight>,
+}
+
+impl MatrixView {
+    pub fn from_2d_vec(name: &str, matrix: &[Vec<i32>]) -> MatrixView {
+        MatrixView {
+            name: name.to_string(),
+            row_count: matrix.len(),
+            col_count: matrix.first().map_or(0, |row| row.len()),
+            values: matrix.iter().flatten().map(|&value| Some(value)).collect(),
+            highlights: Vec::new(),
+        }
+    }
+
+    pub fn empty(name: &str, row_count: usize, col_count: usize) -> MatrixView {
+        MatrixView {
+            name: name.to_string(),
+            row_count,
+            col_count,
+            values: vec![None; row_count * col_count],
+            highlights: Vec::new(),
+        }
+    }
+
+    pub fn value(&self, row: usize, col: usize) -> Option<i32> {
+        self.values[row * self.col_count + col]
+    }
+
+    pub fn set(&mut self, row: usize, col: usize, value: i32) {
+        self.values[row * self.col_count + col] = Some(value);
+    }
+
+    pub fn highlight(&mut self, kind: HighlightKind, region: Region) {
+        self.highlights.push(Highlight { kind, region });
+    }
+
+    // Strongest highlight covering the cell, if any
+    pub fn highlight_at(&self, row: usize, col: usize) -> Option<HighlightKind> {
+        self.highlights
+            .iter()
+            .filter(|highlight| highlight.region.contains(row, col))
+            .map(|highlight| highlight.kind)
+            .max()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Frame {
+    pub caption: String,
+    pub matrices: Vec<MatrixView>,
+}
+
+// How often the generators emit a frame
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StepGranularity {
+    // one frame per block (per block triple for matmul)
+    Block,
+    // one frame per innermost loop iteration
+    Element,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn strongest_highlight_wins() {
+        let mut view = MatrixView::from_2d_vec("A", &[vec![1, 2, 3], vec![4, 5, 6]]);
+        view.highlight(HighlightKind::Block, Region::new(0..2, 0..2));
+        view.highlight(HighlightKind::Row, Region::new(1..2, 0..2));
+        view.highlight(HighlightKind::Cell, Region::cell(1, 1));
+        assert_eq!(view.highlight_at(0, 0), Some(HighlightKind::Block));
+        assert_eq!(view.highlight_at(1, 0), Some(HighlightKind::Row));
+        assert_eq!(view.highlight_at(1, 1), Some(HighlightKind::Cell));
+        assert_eq!(view.highlight_at(0, 2), None);
+        assert_eq!(view.value(1, 2), Some(6));
+        view.set(0, 2, 9);
+        assert_eq!(view.value(0, 2), Some(9));
+        assert_eq!(MatrixView::empty("C", 2, 2).value(1, 1), None);
+    }
+}
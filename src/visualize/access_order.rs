@@ -0,0 +1,220 @@
+use crate::matrix::curve_layout::{enclosing_side, hilbert_decode_2d, morton_decode_2d};
+use crate::matrix::traversal::Traversal;
+
+// Records the order in which a traversal visits the cells of a matrix, the input for the
+// heatmaps. The traversals replay the loop nests of the module 6 kernels on a row_count x
+// col_count grid without touching any data.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AccessOrder {
+    pub name: String,
+    pub row_count: usize,
+    pub col_count: usize,
+    // (row, col) in visit order
+    pub sequence: Vec<(usize, usize)>,
+}
+
+impl AccessOrder {
+    pub fn new(name: &str, row_count: usize, col_count: usize) -> AccessOrder {
+        AccessOrder {
+            name: name.to_string(),
+            row_count,
+            col_count,
+            sequence: Vec::with_capacity(row_count * col_count),
+        }
+    }
+
+    pub fn visit(&mut self, row: usize, col: usize) {
+        debug_assert!(row < self.row_count && col < self.col_count);
+        self.sequence.push((row, col));
+    }
+
+    // Row-major grid of the time step each cell was first visited, None if never visited
+    pub fn first_visit_times(&self) -> Vec<Option<usize>> {
+        let mut times = vec![None; self.row_count * self.col_count];
+        for (time, &(row, col)) in self.sequence.iter().enumerate() {
+            let cell = &mut times[row * self.col_count + col];
+            if cell.is_none() {
+                *cell = Some(time);
+            }
+        }
+        times
+    }
+
+    // Average distance in row-major memory between consecutive visits, a rough locality score
+    pub fn mean_row_major_stride(&self) -> f64 {
+        if self.sequence.len() < 2 {
+            return 0.0;
+        }
+        let total: usize = self
+            .sequence
+            .windows(2)
+            .map(|pair| {
+                let from = pair[0].0 * self.col_count + pair[0].1;
+                let to = pair[1].0 * self.col_count + pair[1].1;
+                from.abs_diff(to)
+            })
+            .sum();
+        total as f64 / (self.sequence.len() - 1) as f64
+    }
+}
+
+// row_sum_matrix
+pub fn row_major_sum_access_order(row_count: usize, col_count: usize) -> AccessOrder {
+    let mut order = AccessOrder::new("row-major sum", row_count, col_count);
+    for row_idx in 0..row_count {
+        for col_idx in 0..col_count {
+            order.visit(row_idx, col_idx);
+        }
+    }
+    order
+}
+
+// col_sum_matrix
+pub fn col_major_sum_access_order(row_count: usize, col_count: usize) -> AccessOrder {
+    let mut order = AccessOrder::new("col-major sum", row_count, col_count);
+    for col_idx in 0..col_count {
+        for row_idx in 0..row_count {
+            order.visit(row_idx, col_idx);
+        }
+    }
+    order
+}
+
+// Writes into the col_count x row_count destination of an out of place transpose, one block at
+// a time (a block covering the whole matrix is the naive transpose). The source reads are
+// simply row-major inside each block, the destination writes are the strided side.
+pub fn transpose_write_access_order(
+    row_count: usize,
+    col_count: usize,
+    block_size: usize,
+) -> AccessOrder {
+    let name = if block_size >= row_count.max(col_count) {
+        "naive transpose (writes)".to_string()
+    } else {
+        format!("tiled transpose {}x{} (writes)", block_size, block_size)
+    };
+    let mut order = AccessOrder::new(&name, col_count, row_count);
+    for block_start_row_idx in (0..row_count).step_by(block_size) {
+        for block_start_col_idx in (0..col_count).step_by(block_size) {
+            let row_end = (block_start_row_idx + block_size).min(row_count);
+            let col_end = (block_start_col_idx + block_size).min(col_count);
+            for cell_row_idx in block_start_row_idx..row_end {
+                for cell_col_idx in block_start_col_idx..col_end {
+                    order.visit(cell_col_idx, cell_row_idx);
+                }
+            }
+        }
+    }
+    order
+}
+
+// Z-order (Morton) curve over the enclosing power of two square, cells outside the matrix skipped
+pub fn z_order_access_order(row_count: usize, col_count: usize) -> AccessOrder {
+    let mut order = AccessOrder::new("z-order", row_count, col_count);
+    let side = enclosing_side(row_count, col_count);
+    for code in 0..side * side {
+        let (row, col) = morton_decode_2d(code);
+        if row < row_count && col < col_count {
+            order.visit(row, col);
+        }
+    }
+    order
+}
+
+// Hilbert curve over the enclosing power of two square, cells outside the matrix skipped
+pub fn hilbert_order_access_order(row_count: usize, col_count: usize) -> AccessOrder {
+    let mut order = AccessOrder::new("hilbert order", row_count, col_count);
+    let side = enclosing_side(row_count, col_count);
+    for code in 0..side * side {
+        let (row, col) = hilbert_decode_2d(side, code);
+        if row < row_count && col < col_count {
+            order.visit(row, col);
+        }
+    }
+    order
+}
+
+// Any of the matrix::traversal orders (spiral, zig-zag, diagonal, snake)
+pub fn traversal_access_order(
+    traversal: Traversal,
+    row_count: usize,
+    col_count: usize,
+) -> AccessOrder {
+    let mut order = AccessOrder::new(traversal.name(), row_count, col_count);
+    for (row, col) in traversal.coords(row_count, col_count) {
+        order.visit(row, col);
+    }
+    order
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sums_visit_every_cell_once() {
+        let rows = row_major_sum_access_order(2, 3);
+        assert_eq!(
+            rows.sequence,
+            vec![(0, 0), (0, 1), (0, 2), (1, 0), (1, 1), (1, 2)]
+        );
+        let cols = col_major_sum_access_order(2, 3);
+        assert_eq!(
+            cols.sequence,
+            vec![(0, 0), (1, 0), (0, 1), (1, 1), (0, 2), (1, 2)]
+        );
+        assert_eq!(
+            cols.first_visit_times(),
+            vec![Some(0), Some(2), Some(4), Some(1), Some(3), Some(5)]
+        );
+        assert_eq!(rows.mean_row_major_stride(), 1.0);
+        assert!(cols.mean_row_major_stride() > 1.0);
+    }
+
+    #[test]
+    fn tiled_transpose_writes_stay_in_blocks() {
+        let naive = transpose_write_access_order(4, 4, 4);
+        assert_eq!(naive.name, "naive transpose (writes)");
+        assert_eq!(&naive.sequence[..3], &[(0, 0), (1, 0), (2, 0)]);
+        let tiled = transpose_write_access_order(4, 4, 2);
+        assert_eq!(&tiled.sequence[..4], &[(0, 0), (1, 0), (0, 1), (1, 1)]);
+        assert!(tiled.mean_row_major_stride() < naive.mean_row_major_stride());
+        assert!(tiled.first_visit_times().iter().all(|time| time.is_some()));
+    }
+
+    #[test]
+    fn z_order_walks_quadrants() {
+        let order = z_order_access_order(4, 4);
+        assert_eq!(
+            &order.sequence[..8],
+            &[
+                (0, 0),
+                (0, 1),
+                (1, 0),
+                (1, 1),
+                (0, 2),
+                (0, 3),
+                (1, 2),
+                (1, 3)
+            ]
+        );
+        assert_eq!(order.sequence[15], (3, 3));
+        // non square matrices skip the padding cells
+        let order = z_order_access_order(3, 5);
+        assert_eq!(order.sequence.len(), 15);
+        assert!(order.first_visit_times().iter().all(|time| time.is_some()));
+    }
+
+    #[test]
+    fn hilbert_order_only_takes_unit_steps() {
+        let order = hilbert_order_access_order(8, 8);
+        assert_eq!(&order.sequence[..4], &[(0, 0), (1, 0), (1, 1), (0, 1)]);
+        assert!(
+            order
+                .sequence
+                .windows(2)
+                .all(|pair| pair[0].0.abs_diff(pair[1].0) + pair[0].1.abs_diff(pair[1].1) == 1)
+        );
+        assert_eq!(hilbert_order_access_order(5, 3).sequence.len(), 15);
+    }
+}
@@ -0,0 +1,524 @@
+// Frame generators. Each one runs the same loops as the kernel in crate::matrix (so the
+// visited order is the real one) and snapshots the matrices after every step.
+use super::{Frame, HighlightKind, MatrixView, Region, StepGranularity};
+
+fn frame(
+    caption: String,
+    views: &[&MatrixView],
+    highlights: Vec<Vec<(HighlightKind, Region)>>,
+) -> Frame {
+    let matrices = views
+        .iter()
+        .zip(highlights)
+        .map(|(view, highlights)| {
+            let mut view = (*view).clone();
+            for (kind, region) in highlights {
+                view.highlight(kind, region);
+            }
+            view
+        })
+        .collect();
+    Frame { caption, matrices }
+}
+
+// Replays matrix_multiply_loop_tiling
+pub fn matrix_multiply_loop_tiling_frames(
+    matrix_a: &[Vec<i32>],
+    matrix_b: &[Vec<i32>],
+    block_size: usize,
+    granularity: StepGranularity,
+) -> Vec<Frame> {
+    let a_row_count = matrix_a.len();
+    let k = matrix_a[0].len();
+    let b_col_count = matrix_b[0].len();
+    if k != matrix_b.len() {
+        panic!("Can not multiply matrices [A]'s columns do not match [B]'s rows.")
+    }
+    let view_a = MatrixView::from_2d_vec("A", matrix_a);
+    let view_b = MatrixView::from_2d_vec("B", matrix_b);
+    let mut view_c = MatrixView::empty("C", a_row_count, b_col_count);
+    let mut frames = Vec::new();
+
+    for block_row_c in (0..a_row_count).step_by(block_size) {
+        let row_end = (block_row_c + block_size).min(a_row_count);
+        for block_col_c in (0..b_col_count).step_by(block_size) {
+            let col_end = (block_col_c + block_size).min(b_col_count);
+            for block_k in (0..k).step_by(block_size) {
+                let k_end = (block_k + block_size).min(k);
+                let block_a = Region::new(block_row_c..row_end, block_k..k_end);
+                let block_b = Region::new(block_k..k_end, block_col_c..col_end);
+                let block_c = Region::new(block_row_c..row_end, block_col_c..col_end);
+
+                for row_in_c_block in block_row_c..row_end {
+                    for col_in_c_block in block_col_c..col_end {
+                        for k_in_block in block_k..k_end {
+                            let a = matrix_a[row_in_c_block][k_in_block];
+                            let b = matrix_b[k_in_block][col_in_c_block];
+                            let c =
+                                view_c.value(row_in_c_block, col_in_c_block).unwrap_or(0) + a * b;
+                            view_c.set(row_in_c_block, col_in_c_block, c);
+                            if granularity == StepGranularity::Element {
+                                frames.push(frame(
+                                    format!(
+                                        "C[{row}][{col}] += A[{row}][{k}] * B[{k}][{col}]  ({a} * {b}, C[{row}][{col}] = {c})",
+                                        row = row_in_c_block,
+                                        col = col_in_c_block,
+                                        k = k_in_block,
+                                    ),
+                                    &[&view_a, &view_b, &view_c],
+                                    vec![
+                                        vec![
+                                            (HighlightKind::Block, block_a),
+                                            (
+                                                HighlightKind::Row,
+                                                Region::new(row_in_c_block..row_in_c_block + 1, block_k..k_end),
+                                            ),
+                                            (HighlightKind::Cell, Region::cell(row_in_c_block, k_in_block)),
+                                        ],
+                                        vec![
+                                            (HighlightKind::Block, block_b),
+                                            (
+                                                HighlightKind::Column,
+                                                Region::new(block_k..k_end, col_in_c_block..col_in_c_block + 1),
+                                            ),
+                                            (HighlightKind::Cell, Region::cell(k_in_block, col_in_c_block)),
+                                        ],
+                                        vec![
+                                            (HighlightKind::Block, block_c),
+                                            (HighlightKind::Cell, Region::cell(row_in_c_block, col_in_c_block)),
+                                        ],
+                                    ],
+                                ));
+                            }
+                        }
+                    }
+                }
+
+                if granularity == StepGranularity::Block {
+                    frames.push(frame(
+                        format!(
+                            "C[{}..{}][{}..{}] += A[{}..{}][{}..{}] * B[{}..{}][{}..{}]",
+                            block_row_c,
+                            row_end,
+                            block_col_c,
+                            col_end,
+                            block_row_c,
+                            row_end,
+                            block_k,
+                            k_end,
+                            block_k,
+                            k_end,
+                            block_col_c,
+                            col_end
+                        ),
+                        &[&view_a, &view_b, &view_c],
+                        vec![
+                            vec![(HighlightKind::Block, block_a)],
+                            vec![(HighlightKind::Block, block_b)],
+                            vec![(HighlightKind::Block, block_c)],
+                        ],
+                    ));
+                }
+            }
+        }
+    }
+    frames
+}
+
+// Replays transpose_2d_vec_loop_tiling_aka_block_tiling: square matrices swap in place across
+// the diagonal, anything else is copied block by block into a new matrix.
+pub fn transpose_loop_tiling_frames(
+    matrix: &[Vec<i32>],
+    block_size: usize,
+    granularity: StepGranularity,
+) -> Vec<Frame> {
+    let row_count = matrix.len();
+    let col_count = matrix[0].len();
+    if row_count == col_count {
+        transpose_square_in_place_frames(matrix, block_size, granularity)
+    } else {
+        transpose_copy_frames(matrix, block_size, granularity)
+    }
+}
+
+fn transpose_square_in_place_frames(
+    matrix: &[Vec<i32>],
+    block_size: usize,
+    granularity: StepGranularity,
+) -> Vec<Frame> {
+    let n = matrix.len();
+    let mut view = MatrixView::from_2d_vec("M", matrix);
+    let mut frames = Vec::new();
+
+    for block_start_row_idx in (0..n).step_by(block_size) {
+        for block_start_col_idx in (block_start_row_idx..n).step_by(block_size) {
+            let row_end = (block_start_row_idx + block_size).min(n);
+            let col_end = (block_start_col_idx + block_size).min(n);
+            let block = Region::new(block_start_row_idx..row_end, block_start_col_idx..col_end);
+            let mirrored = Region::new(block_start_col_idx..col_end, block_start_row_idx..row_end);
+            let on_diagonal = block_start_row_idx == block_start_col_idx;
+
+            for cell_row_idx in block_start_row_idx..row_end {
+                // diagonal blocks only swap their upper half
+                let first_col = if on_diagonal {
+                    cell_row_idx + 1
+                } else {
+                    block_start_col_idx
+                };
+                for cell_col_idx in first_col..col_end {
+                    let upper = view.value(cell_row_idx, cell_col_idx).unwrap();
+                    let lower = view.value(cell_col_idx, cell_row_idx).unwrap();
+                    view.set(cell_row_idx, cell_col_idx, lower);
+                    view.set(cell_col_idx, cell_row_idx, upper);
+                    if granularity == StepGranularity::Element {
+                        frames.push(frame(
+                            format!(
+                                "swap M[{r}][{c}] <-> M[{c}][{r}]",
+                                r = cell_row_idx,
+                                c = cell_col_idx
+                            ),
+                            &[&view],
+                            vec![vec![
+                                (HighlightKind::Block, block),
+                                (HighlightKind::Block, mirrored),
+                                (
+                                    HighlightKind::Row,
+                                    Region::new(
+                                        cell_row_idx..cell_row_idx + 1,
+                                        block_start_col_idx..col_end,
+                                    ),
+                                ),
+                                (
+                                    HighlightKind::Column,
+                                    Region::new(
+                                        block_start_col_idx..col_end,
+                                        cell_row_idx..cell_row_idx + 1,
+                                    ),
+                                ),
+                                (
+                                    HighlightKind::Cell,
+                                    Region::cell(cell_row_idx, cell_col_idx),
+                                ),
+                                (
+                                    HighlightKind::Cell,
+                                    Region::cell(cell_col_idx, cell_row_idx),
+                                ),
+                            ]],
+                        ));
+                    }
+                }
+            }
+
+            if granularity == StepGranularity::Block {
+                frames.push(frame(
+                    format!(
+                        "swap block M[{}..{}][{}..{}] with its mirror{}",
+                        block_start_row_idx,
+                        row_end,
+                        block_start_col_idx,
+                        col_end,
+                        if on_diagonal {
+                            " (diagonal, upper half only)"
+                        } else {
+                            ""
+                        }
+                    ),
+                    &[&view],
+                    vec![vec![
+                        (HighlightKind::Block, block),
+                        (HighlightKind::Block, mirrored),
+                    ]],
+                ));
+            }
+        }
+    }
+    frames
+}
+
+fn transpose_copy_frames(
+    matrix: &[Vec<i32>],
+    block_size: usize,
+    granularity: StepGranularity,
+) -> Vec<Frame> {
+    let row_count = matrix.len();
+    let col_count = matrix[0].len();
+    let source = MatrixView::from_2d_vec("M", matrix);
+    let mut destination = MatrixView::empty("M^T", col_count, row_count);
+    let mut frames = Vec::new();
+
+    for block_start_row_idx in (0..row_count).step_by(block_size) {
+        for block_start_col_idx in (0..col_count).step_by(block_size) {
+            let row_end = (block_start_row_idx + block_size).min(row_count);
+            let col_end = (block_start_col_idx + block_size).min(col_count);
+            let block = Region::new(block_start_row_idx..row_end, block_start_col_idx..col_end);
+            let transposed_block =
+                Region::new(block_start_col_idx..col_end, block_start_row_idx..row_end);
+
+            for cell_row_idx in block_start_row_idx..row_end {
+                for cell_col_idx in block_start_col_idx..col_end {
+                    destination.set(
+                        cell_col_idx,
+                        cell_row_idx,
+                        matrix[cell_row_idx][cell_col_idx],
+                    );
+                    if granularity == StepGranularity::Element {
+                        frames.push(frame(
+                            format!(
+                                "M^T[{c}][{r}] = M[{r}][{c}]",
+                                r = cell_row_idx,
+                                c = cell_col_idx
+                            ),
+                            &[&source, &destination],
+                            vec![
+                                vec![
+                                    (HighlightKind::Block, block),
+                                    (
+                                        HighlightKind::Row,
+                                        Region::new(
+                                            cell_row_idx..cell_row_idx + 1,
+                                            block_start_col_idx..col_end,
+                                        ),
+                                    ),
+                                    (
+                                        HighlightKind::Cell,
+                                        Region::cell(cell_row_idx, cell_col_idx),
+                                    ),
+                                ],
+                                vec![
+                                    (HighlightKind::Block, transposed_block),
+                                    (
+                                        HighlightKind::Column,
+                                        Region::new(
+                                            block_start_col_idx..col_end,
+                                            cell_row_idx..cell_row_idx + 1,
+                                        ),
+                                    ),
+                                    (
+                                        HighlightKind::Cell,
+                                        Region::cell(cell_col_idx, cell_row_idx),
+                                    ),
+                                ],
+                            ],
+                        ));
+                    }
+                }
+            }
+
+            if granularity == StepGranularity::Block {
+                frames.push(frame(
+                    format!(
+                        "M^T[{}..{}][{}..{}] = M[{}..{}][{}..{}]^T",
+                        block_start_col_idx,
+                        col_end,
+                        block_start_row_idx,
+                        row_end,
+                        block_start_row_idx,
+                        row_end,
+                        block_start_col_idx,
+                        col_end
+                    ),
+                    &[&source, &destination],
+                    vec![
+                        vec![(HighlightKind::Block, block)],
+                        vec![(HighlightKind::Block, transposed_block)],
+                    ],
+                ));
+            }
+        }
+    }
+    frames
+}
+
+// Replays rotate_2d_vec: source cell (r, c) lands on (c, row_count - r - 1)
+pub fn rotate_2d_vec_frames(
+    matrix: &[Vec<i32>],
+    block_size: usize,
+    granularity: StepGranularity,
+) -> Vec<Frame> {
+    let row_count = matrix.len();
+    let col_count = matrix[0].len();
+    let source = MatrixView::from_2d_vec("M", matrix);
+    let mut destination = MatrixView::empty("rotated", col_count, row_count);
+    let mut frames = Vec::new();
+
+    for block_start_row_idx in (0..row_count).step_by(block_size) {
+        for block_start_col_idx in (0..col_count).step_by(block_size) {
+            let row_end = (block_start_row_idx + block_size).min(row_count);
+            let col_end = (block_start_col_idx + block_size).min(col_count);
+            let block = Region::new(block_start_row_idx..row_end, block_start_col_idx..col_end);
+            let rotated_block = Region::new(
+                block_start_col_idx..col_end,
+                (row_count - row_end)..(row_count - block_start_row_idx),
+            );
+
+            for cell_row_idx in block_start_row_idx..row_end {
+                let new_col_idx = row_count - cell_row_idx - 1;
+                for cell_col_idx in block_start_col_idx..col_end {
+                    destination.set(
+                        cell_col_idx,
+                        new_col_idx,
+                        matrix[cell_row_idx][cell_col_idx],
+                    );
+                    if granularity == StepGranularity::Element {
+                        frames.push(frame(
+                            format!(
+                                "rotated[{}][{}] = M[{}][{}]",
+                                cell_col_idx, new_col_idx, cell_row_idx, cell_col_idx
+                            ),
+                            &[&source, &destination],
+                            vec![
+                                vec![
+                                    (HighlightKind::Block, block),
+                                    (
+                                        HighlightKind::Row,
+                                        Region::new(
+                                            cell_row_idx..cell_row_idx + 1,
+                                            block_start_col_idx..col_end,
+                                        ),
+                                    ),
+                                    (
+                                        HighlightKind::Cell,
+                                        Region::cell(cell_row_idx, cell_col_idx),
+                                    ),
+                                ],
+                                vec![
+                                    (HighlightKind::Block, rotated_block),
+                                    (
+                                        HighlightKind::Column,
+                                        Region::new(
+                                            block_start_col_idx..col_end,
+                                            new_col_idx..new_col_idx + 1,
+                                        ),
+                                    ),
+                                    (HighlightKind::Cell, Region::cell(cell_col_idx, new_col_idx)),
+                                ],
+                            ],
+                        ));
+                    }
+                }
+            }
+
+            if granularity == StepGranularity::Block {
+                frames.push(frame(
+                    format!(
+                        "rotate block M[{}..{}][{}..{}]",
+                        block_start_row_idx, row_end, block_start_col_idx, col_end
+                    ),
+                    &[&source, &destination],
+                    vec![
+                        vec![(HighlightKind::Block, block)],
+                        vec![(HighlightKind::Block, rotated_block)],
+                    ],
+                ));
+            }
+        }
+    }
+    frames
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::matrix::make_matrix;
+    use crate::matrix::multiply::matrix_multiply_triple_loop_vec_2d;
+    use crate::matrix::rotate::rotate_2d_vec;
+    use crate::matrix::transpose::transpose_2d_vec;
+
+    fn values(view: &MatrixView) -> Vec<Vec<i32>> {
+        (0..view.row_count)
+            .map(|row| {
+                (0..view.col_count)
+                    .map(|col| view.value(row, col).unwrap())
+                    .collect()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn matmul_frames_end_with_the_product() {
+        let matrix_a = make_matrix(3, 5, 0);
+        let matrix_b = make_matrix(5, 4, 0);
+        let expected = matrix_multiply_triple_loop_vec_2d(&matrix_a, &matrix_b);
+
+        let frames =
+            matrix_multiply_loop_tiling_frames(&matrix_a, &matrix_b, 2, StepGranularity::Element);
+        assert_eq!(frames.len(), 3 * 5 * 4);
+        assert_eq!(values(&frames.last().unwrap().matrices[2]), expected);
+        // first step: C[0][0] += A[0][0] * B[0][0] inside the first 2x2 blocks
+        let first = &frames[0];
+        assert_eq!(
+            first.caption,
+            "C[0][0] += A[0][0] * B[0][0]  (0 * 0, C[0][0] = 0)"
+        );
+        assert_eq!(
+            first.matrices[0].highlight_at(0, 0),
+            Some(HighlightKind::Cell)
+        );
+        assert_eq!(
+            first.matrices[0].highlight_at(0, 1),
+            Some(HighlightKind::Row)
+        );
+        assert_eq!(
+            first.matrices[0].highlight_at(1, 1),
+            Some(HighlightKind::Block)
+        );
+        assert_eq!(first.matrices[0].highlight_at(0, 2), None);
+        assert_eq!(
+            first.matrices[1].highlight_at(1, 0),
+            Some(HighlightKind::Column)
+        );
+        assert_eq!(first.matrices[2].value(0, 1), None);
+
+        let blocks =
+            matrix_multiply_loop_tiling_frames(&matrix_a, &matrix_b, 2, StepGranularity::Block);
+        // 2 row blocks * 2 col blocks * 3 k blocks
+        assert_eq!(blocks.len(), 12);
+        assert_eq!(values(&blocks.last().unwrap().matrices[2]), expected);
+    }
+
+    #[test]
+    fn transpose_frames_match_the_kernel() {
+        for (row_count, col_count) in [(4, 4), (5, 3)] {
+            let matrix = make_matrix(row_count, col_count, 0);
+            let expected = transpose_2d_vec(matrix.clone());
+            for granularity in [StepGranularity::Element, StepGranularity::Block] {
+                let frames = transpose_loop_tiling_frames(&matrix, 2, granularity);
+                let last = frames.last().unwrap().matrices.last().unwrap();
+                assert_eq!(values(last), expected);
+            }
+        }
+        // 4x4 in place: 6 swaps above the diagonal, 3 blocks on or above it
+        let square = make_matrix(4, 4, 0);
+        assert_eq!(
+            transpose_loop_tiling_frames(&square, 2, StepGranularity::Element).len(),
+            6
+        );
+        assert_eq!(
+            transpose_loop_tiling_frames(&square, 2, StepGranularity::Block).len(),
+            3
+        );
+    }
+
+    #[test]
+    fn rotate_frames_match_the_kernel() {
+        let matrix = make_matrix(3, 5, 0);
+        let expected = rotate_2d_vec(matrix.clone(), 2);
+        let frames = rotate_2d_vec_frames(&matrix, 2, StepGranularity::Element);
+        assert_eq!(frames.len(), 15);
+        assert_eq!(values(&frames.last().unwrap().matrices[1]), expected);
+        let first = &frames[0];
+        assert_eq!(first.caption, "rotated[0][2] = M[0][0]");
+        assert_eq!(
+            first.matrices[1].highlight_at(0, 2),
+            Some(HighlightKind::Cell)
+        );
+        assert_eq!(
+            first.matrices[1].highlight_at(1, 1),
+            Some(HighlightKind::Block)
+        );
+        assert_eq!(
+            rotate_2d_vec_frames(&matrix, 2, StepGranularity::Block).len(),
+            6
+        );
+    }
+}
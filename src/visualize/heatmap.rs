@@ -0,0 +1,206 @@
+// Heatmaps of an AccessOrder: a plain PGM where pixel intensity is the visit time, and an SVG
+// with one coloured square per cell (dark = early, bright = late) plus the visit path.
+use super::access_order::AccessOrder;
+use crate::matrix::display::print_matrix_1d_vec_matrix;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+const PGM_MAX_VALUE: usize = 65535;
+
+// Viridis, sampled at 5 points
+const COLOR_STOPS: [(u8, u8, u8); 5] = [
+    (68, 1, 84),
+    (59, 82, 139),
+    (33, 145, 140),
+    (94, 201, 98),
+    (253, 231, 37),
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SvgOptions {
+    pub cell_size: usize,
+    // draw a line through the cell centres in visit order
+    pub show_path: bool,
+}
+
+impl Default for SvgOptions {
+    fn default() -> Self {
+        SvgOptions {
+            cell_size: 16,
+            show_path: true,
+        }
+    }
+}
+
+// Plain (P2) greyscale, one pixel per cell. Never visited cells are 0 and the n-th visit is n,
+// scaled down only when there are more than 65535 visits.
+pub fn to_pgm(order: &AccessOrder) -> String {
+    let times = order.first_visit_times();
+    let visits = order.sequence.len().max(1);
+    let max_value = visits.min(PGM_MAX_VALUE);
+    let mut out = format!(
+        "P2\n# access order: {}\n{} {}\n{}\n",
+        order.name, order.col_count, order.row_count, max_value
+    );
+    for row in times.chunks(order.col_count.max(1)) {
+        let pixels: Vec<String> = row
+            .iter()
+            .map(|time| match time {
+                None => "0".to_string(),
+                Some(time) => ((time + 1) * max_value / visits).to_string(),
+            })
+            .collect();
+        out.push_str(&pixels.join(" "));
+        out.push('\n');
+    }
+    out
+}
+
+fn color_for(fraction: f64) -> String {
+    let scaled = fraction.clamp(0.0, 1.0) * (COLOR_STOPS.len() - 1) as f64;
+    let idx = (scaled.floor() as usize).min(COLOR_STOPS.len() - 2);
+    let t = scaled - idx as f64;
+    let lerp = |a: u8, b: u8| (a as f64 + (b as f64 - a as f64) * t).round() as u8;
+    let (r0, g0, b0) = COLOR_STOPS[idx];
+    let (r1, g1, b1) = COLOR_STOPS[idx + 1];
+    format!(
+        "#{:02x}{:02x}{:02x}",
+        lerp(r0, r1),
+        lerp(g0, g1),
+        lerp(b0, b1)
+    )
+}
+
+pub fn to_svg(order: &AccessOrder, options: SvgOptions) -> String {
+    let cell = options.cell_size;
+    let width = order.col_count * cell;
+    let height = order.row_count * cell;
+    let times = order.first_visit_times();
+    let last = order.sequence.len().saturating_sub(1).max(1) as f64;
+
+    let mut out = format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{w}\" height=\"{h}\" viewBox=\"0 0 {w} {h}\">\n<title>{}</title>\n<rect width=\"{w}\" height=\"{h}\" fill=\"#000000\"/>\n",
+        order.name,
+        w = width,
+        h = height
+    );
+    for row in 0..order.row_count {
+        for col in 0..order.col_count {
+            if let Some(time) = times[row * order.col_count + col] {
+                out.push_str(&format!(
+                    "<rect x=\"{}\" y=\"{}\" width=\"{cell}\" height=\"{cell}\" fill=\"{}\"><title>({}, {}) t={}</title></rect>\n",
+                    col * cell,
+                    row * cell,
+                    color_for(time as f64 / last),
+                    row,
+                    col,
+                    time
+                ));
+            }
+        }
+    }
+    if options.show_path && order.sequence.len() > 1 {
+        let points: Vec<String> = order
+            .sequence
+            .iter()
+            .map(|&(row, col)| format!("{},{}", col * cell + cell / 2, row * cell + cell / 2))
+            .collect();
+        out.push_str(&format!(
+            "<polyline points=\"{}\" fill=\"none\" stroke=\"#ffffff\" stroke-opacity=\"0.6\" stroke-width=\"1\"/>\n",
+            points.join(" ")
+        ));
+    }
+    out.push_str("</svg>\n");
+    out
+}
+
+// Writes <stem>.svg and <stem>.pgm, returning both paths
+pub fn write_heatmaps(
+    order: &AccessOrder,
+    stem: &Path,
+    options: SvgOptions,
+) -> Result<(PathBuf, PathBuf), String> {
+    let svg_path = stem.with_extension("svg");
+    let pgm_path = stem.with_extension("pgm");
+    fs::write(&svg_path, to_svg(order, options))
+        .map_err(|e| format!("could not write {}: {}", svg_path.display(), e))?;
+    fs::write(&pgm_path, to_pgm(order))
+        .map_err(|e| format!("could not write {}: {}", pgm_path.display(), e))?;
+    Ok((svg_path, pgm_path))
+}
+
+// The visit time of every cell in the same table as print_matrix_1d_vec_matrix, -1 = never
+pub fn print_access_order(order: &AccessOrder) {
+    println!("--> access order: {}", order.name);
+    let times: Vec<i32> = order
+        .first_visit_times()
+        .iter()
+        .map(|time| time.map_or(-1, |time| time as i32))
+        .collect();
+    print_matrix_1d_vec_matrix(&times, order.col_count);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::visualize::access_order::{col_major_sum_access_order, row_major_sum_access_order};
+
+    #[test]
+    fn pgm_intensity_is_visit_time() {
+        let pgm = to_pgm(&col_major_sum_access_order(2, 3));
+        assert_eq!(
+            pgm,
+            "P2\n# access order: col-major sum\n3 2\n6\n1 3 5\n2 4 6\n"
+        );
+
+        let mut partial = AccessOrder::new("partial", 1, 3);
+        partial.visit(0, 2);
+        partial.visit(0, 2);
+        assert_eq!(to_pgm(&partial).lines().last(), Some("0 0 1"));
+    }
+
+    #[test]
+    fn pgm_scales_past_sixteen_bits() {
+        let pgm = to_pgm(&row_major_sum_access_order(300, 300));
+        let mut lines = pgm.lines().skip(3);
+        assert_eq!(lines.next(), Some("65535"));
+        let last_row = pgm.lines().last().unwrap();
+        assert!(last_row.ends_with(" 65535"));
+    }
+
+    #[test]
+    fn svg_has_a_cell_per_visit_and_the_path() {
+        let order = row_major_sum_access_order(2, 2);
+        let svg = to_svg(&order, SvgOptions::default());
+        assert!(svg.starts_with("<svg"));
+        assert_eq!(svg.matches("<rect x=").count(), 4);
+        assert!(svg.contains("(1, 1) t=3"));
+        assert!(svg.contains(&format!("fill=\"{}\"", color_for(0.0))));
+        assert!(svg.contains("#fde725"));
+        assert!(svg.contains("<polyline points=\"8,8 24,8 8,24 24,24\""));
+        let no_path = to_svg(
+            &order,
+            SvgOptions {
+                cell_size: 4,
+                show_path: false,
+            },
+        );
+        assert!(!no_path.contains("polyline"));
+        assert!(no_path.contains("width=\"8\""));
+    }
+
+    #[test]
+    fn writes_both_files() {
+        let stem = std::env::temp_dir().join(format!("dsa-heatmap-{}", std::process::id()));
+        let (svg, pgm) = write_heatmaps(
+            &row_major_sum_access_order(3, 3),
+            &stem,
+            SvgOptions::default(),
+        )
+        .unwrap();
+        assert!(fs::read_to_string(&svg).unwrap().contains("</svg>"));
+        assert!(fs::read_to_string(&pgm).unwrap().starts_with("P2"));
+        fs::remove_file(svg).unwrap();
+        fs::remove_file(pgm).unwrap();
+    }
+}
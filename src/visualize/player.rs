@@ -0,0 +1,144 @@
+// Plays frames back: interactively one step per keypress (Enter, since std has no raw terminal
+// mode), or all at once as a text animation that can be saved and replayed with `cat`/`less -R`.
+use super::Frame;
+use super::render::{RenderOptions, legend, render_frame};
+use std::io::{BufRead, Write};
+
+// Clear screen + cursor home, each frame of a coloured animation redraws in place
+const CLEAR_SCREEN: &str = "\x1b[2J\x1b[H";
+
+fn frame_header(idx: usize, frame_count: usize) -> String {
+    format!("frame {}/{}", idx + 1, frame_count)
+}
+
+// Every frame in order. With colour each frame starts by clearing the screen, without colour
+// frames are separated by a header line so the file reads top to bottom.
+pub fn export_text_animation(frames: &[Frame], options: RenderOptions) -> String {
+    let mut out = String::new();
+    for (idx, frame) in frames.iter().enumerate() {
+        if options.color {
+            out.push_str(CLEAR_SCREEN);
+        }
+        out.push_str(&format!("--- {} ---\n", frame_header(idx, frames.len())));
+        out.push_str(&render_frame(frame, options));
+        out.push('\n');
+    }
+    out
+}
+
+// Enter/n: next, p: previous, <number>: jump to frame, q: quit.
+// Returns the number of frames shown.
+pub fn play_interactive(
+    frames: &[Frame],
+    options: RenderOptions,
+    mut input: impl BufRead,
+    mut output: impl Write,
+) -> std::io::Result<usize> {
+    let mut idx = 0;
+    let mut shown = 0;
+    while idx < frames.len() {
+        if options.color {
+            write!(output, "{}", CLEAR_SCREEN)?;
+        }
+        writeln!(
+            output,
+            "{}  {}",
+            frame_header(idx, frames.len()),
+            legend(options)
+        )?;
+        write!(output, "{}", render_frame(&frames[idx], options))?;
+        write!(
+            output,
+            "[Enter/n] next  [p] previous  [number] jump  [q] quit > "
+        )?;
+        output.flush()?;
+        shown += 1;
+
+        let mut line = String::new();
+        if input.read_line(&mut line)? == 0 {
+            break;
+        }
+        match line.trim() {
+            "" | "n" => idx += 1,
+            "p" => idx = idx.saturating_sub(1),
+            "q" => break,
+            number => match number.parse::<usize>() {
+                Ok(frame_number) if (1..=frames.len()).contains(&frame_number) => {
+                    idx = frame_number - 1
+                }
+                _ => {}
+            },
+        }
+    }
+    writeln!(output)?;
+    Ok(shown)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::visualize::MatrixView;
+
+    fn frames(count: usize) -> Vec<Frame> {
+        (0..count)
+            .map(|idx| Frame {
+                caption: format!("step {}", idx),
+                matrices: vec![MatrixView::from_2d_vec("A", &[vec![idx as i32]])],
+            })
+            .collect()
+    }
+
+    #[test]
+    fn animation_has_every_frame_in_order() {
+        let plain = export_text_animation(&frames(3), RenderOptions { color: false });
+        assert!(!plain.contains(CLEAR_SCREEN));
+        let first = plain.find("--- frame 1/3 ---\nstep 0").unwrap();
+        let last = plain.find("--- frame 3/3 ---\nstep 2").unwrap();
+        assert!(first < last);
+
+        let color = export_text_animation(&frames(3), RenderOptions { color: true });
+        assert_eq!(color.matches(CLEAR_SCREEN).count(), 3);
+    }
+
+    #[test]
+    fn interactive_keys_step_jump_and_quit() {
+        let mut output = Vec::new();
+        // next, next, previous, jump to 4, next past the end
+        let shown = play_interactive(
+            &frames(4),
+            RenderOptions { color: false },
+            "\nn\np\n4\n\n".as_bytes(),
+            &mut output,
+        )
+        .unwrap();
+        assert_eq!(shown, 5);
+        let text = String::from_utf8(output).unwrap();
+        let captions: Vec<&str> = text
+            .lines()
+            .filter(|line| line.starts_with("step"))
+            .collect();
+        assert_eq!(
+            captions,
+            vec!["step 0", "step 1", "step 2", "step 1", "step 3"]
+        );
+
+        let mut output = Vec::new();
+        let shown = play_interactive(
+            &frames(4),
+            RenderOptions { color: false },
+            "q\n".as_bytes(),
+            &mut output,
+        )
+        .unwrap();
+        assert_eq!(shown, 1);
+        // end of input stops too
+        let shown = play_interactive(
+            &frames(4),
+            RenderOptions { color: false },
+            "".as_bytes(),
+            &mut output,
+        )
+        .unwrap();
+        assert_eq!(shown, 1);
+    }
+}
@@ -0,0 +1,154 @@
+// Draws frames as text. With colour on, highlights are ANSI background colours; with colour
+// off (files, pipes, tests) each cell gets a marker character in front of it instead.
+use super::{Frame, HighlightKind, MatrixView};
+
+const RESET: &str = "\x1b[0m";
+const GAP: &str = "    ";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RenderOptions {
+    pub color: bool,
+}
+
+fn ansi_style(kind: HighlightKind) -> &'static str {
+    match kind {
+        HighlightKind::Block => "\x1b[44;97m",
+        HighlightKind::Row | HighlightKind::Column => "\x1b[43;30m",
+        HighlightKind::Cell => "\x1b[41;1;97m",
+    }
+}
+
+fn marker(kind: Option<HighlightKind>) -> char {
+    match kind {
+        None => ' ',
+        Some(HighlightKind::Block) => ':',
+        Some(HighlightKind::Row) => '-',
+        Some(HighlightKind::Column) => '|',
+        Some(HighlightKind::Cell) => '*',
+    }
+}
+
+pub fn legend(options: RenderOptions) -> String {
+    if options.color {
+        format!(
+            "{} block {} {} row/col {} {} cell {}",
+            ansi_style(HighlightKind::Block),
+            RESET,
+            ansi_style(HighlightKind::Row),
+            RESET,
+            ansi_style(HighlightKind::Cell),
+            RESET
+        )
+    } else {
+        ": block  - row  | column  * cell  . not written yet".to_string()
+    }
+}
+
+// Lines of one matrix plus their visible width (escape codes don't take up columns)
+fn render_matrix(view: &MatrixView, options: RenderOptions) -> (Vec<String>, usize) {
+    let value_width = view
+        .values
+        .iter()
+        .flatten()
+        .map(|value| value.to_string().len())
+        .max()
+        .unwrap_or(1)
+        .max(2);
+    // marker (or space) + value
+    let cell_width = value_width + 1;
+    let title = format!("{} ({}x{})", view.name, view.row_count, view.col_count);
+    let width = (cell_width * view.col_count).max(title.chars().count());
+
+    let mut lines = vec![format!("{:<width$}", title)];
+    for row in 0..view.row_count {
+        let mut line = String::new();
+        for col in 0..view.col_count {
+            let text = view
+                .value(row, col)
+                .map_or(".".to_string(), |value| value.to_string());
+            let highlight = view.highlight_at(row, col);
+            match (options.color, highlight) {
+                (true, Some(kind)) => line.push_str(&format!(
+                    "{}{:>cell_width$}{}",
+                    ansi_style(kind),
+                    text,
+                    RESET
+                )),
+                (true, None) => line.push_str(&format!("{:>cell_width$}", text)),
+                (false, _) => {
+                    line.push_str(&format!("{}{:>value_width$}", marker(highlight), text))
+                }
+            }
+        }
+        line.push_str(&" ".repeat(width - cell_width * view.col_count));
+        lines.push(line);
+    }
+    (lines, width)
+}
+
+// Caption, then the matrices side by side
+pub fn render_frame(frame: &Frame, options: RenderOptions) -> String {
+    let rendered: Vec<(Vec<String>, usize)> = frame
+        .matrices
+        .iter()
+        .map(|view| render_matrix(view, options))
+        .collect();
+    let height = rendered
+        .iter()
+        .map(|(lines, _)| lines.len())
+        .max()
+        .unwrap_or(0);
+
+    let mut out = format!("{}\n\n", frame.caption);
+    for line_idx in 0..height {
+        let parts: Vec<String> = rendered
+            .iter()
+            .map(|(lines, width)| {
+                lines
+                    .get(line_idx)
+                    .cloned()
+                    .unwrap_or_else(|| " ".repeat(*width))
+            })
+            .collect();
+        out.push_str(parts.join(GAP).trim_end());
+        out.push('\n');
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::visualize::{MatrixView, Region};
+
+    fn frame() -> Frame {
+        let mut a = MatrixView::from_2d_vec("A", &[vec![1, 2], vec![3, 40]]);
+        a.highlight(HighlightKind::Block, Region::new(0..2, 0..2));
+        a.highlight(HighlightKind::Row, Region::new(1..2, 0..2));
+        a.highlight(HighlightKind::Cell, Region::cell(1, 1));
+        let mut c = MatrixView::empty("C", 3, 1);
+        c.set(0, 0, 7);
+        Frame {
+            caption: "step".to_string(),
+            matrices: vec![a, c],
+        }
+    }
+
+    #[test]
+    fn plain_rendering_uses_markers_side_by_side() {
+        let text = render_frame(&frame(), RenderOptions { color: false });
+        assert_eq!(
+            text,
+            "step\n\nA (2x2)    C (3x1)\n: 1: 2       7\n- 3*40       .\n             .\n"
+        );
+    }
+
+    #[test]
+    fn colour_rendering_wraps_highlighted_cells() {
+        let text = render_frame(&frame(), RenderOptions { color: true });
+        assert!(text.contains(&format!("{}  1{}", ansi_style(HighlightKind::Block), RESET)));
+        assert!(text.contains(&format!("{} 40{}", ansi_style(HighlightKind::Cell), RESET)));
+        assert!(!text.contains('*'));
+        assert!(legend(RenderOptions { color: true }).contains(RESET));
+    }
+}
@@ -0,0 +1,7 @@
+// Module 5 sliding window kernels.
+pub mod distinct;
+pub mod fixed;
+pub mod min_len;
+pub mod min_sub_array_len;
+pub mod min_window;
+pub mod sliding;
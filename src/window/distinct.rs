@@ -0,0 +1,446 @@
+use crate::window::sliding::{DistinctCount, longest_window_where};
+use std::collections::HashMap;
+use std::hash::Hash;
+
+pub fn max_sub_element_slice_len_with_at_most_k_distinct_elements_naive_very<T>(
+    arr: &[T],
+    k: usize,
+) -> Option<usize>
+where
+    T: PartialEq,
+{
+    if k == 0 || arr.is_empty() {
+        return None;
+    }
+
+    let mut max_len = 0;
+
+    for l in 0..arr.len() {
+        'right: for r in l..arr.len() {
+            // Count distinct elements in arr[i..=j]
+            let mut distinct = Vec::new();
+            for i in l..=r {
+                let element_value = &arr[i];
+                if !distinct.contains(&element_value) {
+                    distinct.push(element_value);
+                }
+                if distinct.len() > k {
+                    // no need to count further and technically we are exceeding k
+                    // so break up to moving right onward.
+                    break 'right;
+                }
+            }
+
+            if distinct.len() <= k {
+                let curr_len = r - l + 1;
+                if curr_len > max_len {
+                    max_len = curr_len;
+                }
+            } else {
+                // exceeded k distinct, no need to check longer subarrays starting at i
+                break;
+            }
+        }
+    }
+
+    if max_len == 0 { None } else { Some(max_len) }
+}
+
+pub fn max_sub_element_slice_len_with_at_most_k_distinct_elements_naive_with_hashmap<T>(
+    arr: &[T],
+    k: usize,
+) -> Option<usize>
+where
+    T: Eq + Hash,
+{
+    if k == 0 || arr.is_empty() {
+        return None;
+    }
+
+    let mut max_len = 0;
+    let mut map_distinct_entries_and_counts = HashMap::<&T, usize>::new();
+    // Moving left
+    for l in 0..arr.len() {
+        map_distinct_entries_and_counts.clear();
+        // Check right
+        for r in l..arr.len() {
+            *map_distinct_entries_and_counts.entry(&arr[r]).or_insert(0) += 1;
+
+            if map_distinct_entries_and_counts.len() <= k {
+                // We have a potential winner
+                max_len = max_len.max(r - l + 1)
+            } else {
+                // We no longer have a winner no need to to check further right
+                // without moving the left.
+                break;
+            }
+        }
+    }
+
+    if max_len == 0 { None } else { Some(max_len) }
+}
+
+// The distinct count map is a DistinctCount over references into arr, and longest_window_where
+// drops values on the left whenever the window goes over k distinct.
+pub fn max_sub_element_slice_len_with_at_most_k_distinct_elements_using_sliding_window_and_hashmap<
+    T,
+>(
+    arr: &[T],
+    k: usize,
+) -> Option<usize>
+where
+    T: Eq + Hash,
+{
+    longest_window_where(arr.iter(), DistinctCount::new(), |counts| {
+        counts.distinct() <= k
+    })
+    .map(|span| span.len())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_empty_array_sliding_window_and_hashmap() {
+        let arr: Vec<char> = vec![];
+        assert_eq!(
+            max_sub_element_slice_len_with_at_most_k_distinct_elements_using_sliding_window_and_hashmap(
+                &arr, 2
+            ),
+            None
+        );
+    }
+
+    #[test]
+    fn test_k_zero_sliding_window_and_hashmap() {
+        let arr = vec![1, 2, 3];
+        assert_eq!(
+            max_sub_element_slice_len_with_at_most_k_distinct_elements_using_sliding_window_and_hashmap(
+                &arr, 0
+            ),
+            None
+        );
+    }
+
+    #[test]
+    fn test_single_element_array_sliding_window_and_hashmap() {
+        let arr = vec!['a'];
+        assert_eq!(
+            max_sub_element_slice_len_with_at_most_k_distinct_elements_using_sliding_window_and_hashmap(
+                &arr, 1
+            ),
+            Some(1)
+        );
+        assert_eq!(
+            max_sub_element_slice_len_with_at_most_k_distinct_elements_using_sliding_window_and_hashmap(
+                &arr, 2
+            ),
+            Some(1)
+        );
+    }
+
+    #[test]
+    fn test_all_same_elements_sliding_window_and_hashmap() {
+        let arr = vec![5, 5, 5, 5];
+        assert_eq!(
+            max_sub_element_slice_len_with_at_most_k_distinct_elements_using_sliding_window_and_hashmap(
+                &arr, 1
+            ),
+            Some(4)
+        );
+        assert_eq!(
+            max_sub_element_slice_len_with_at_most_k_distinct_elements_using_sliding_window_and_hashmap(
+                &arr, 2
+            ),
+            Some(4)
+        );
+    }
+
+    #[test]
+    fn test_simple_case_sliding_window_and_hashmap() {
+        let arr = vec!['a', 'b', 'c', 'a', 'b', 'c'];
+        assert_eq!(
+            max_sub_element_slice_len_with_at_most_k_distinct_elements_using_sliding_window_and_hashmap(
+                &arr, 2
+            ),
+            Some(2)
+        );
+
+        assert_eq!(
+            max_sub_element_slice_len_with_at_most_k_distinct_elements_using_sliding_window_and_hashmap(
+                &arr, 3
+            ),
+            Some(6)
+        );
+    }
+
+    #[test]
+    fn test_mixed_types_sliding_window_and_hashmap() {
+        let arr = vec![1, 2, 1, 2, 3];
+        assert_eq!(
+            max_sub_element_slice_len_with_at_most_k_distinct_elements_using_sliding_window_and_hashmap(
+                &arr, 2
+            ),
+            Some(4)
+        );
+        assert_eq!(
+            max_sub_element_slice_len_with_at_most_k_distinct_elements_using_sliding_window_and_hashmap(
+                &arr, 1
+            ),
+            Some(1)
+        );
+        assert_eq!(
+            max_sub_element_slice_len_with_at_most_k_distinct_elements_using_sliding_window_and_hashmap(
+                &['a', 'b', 'a', 'c', 'b'],
+                2
+            ),
+            Some(3) // 'a', 'b', 'a'
+        );
+    }
+
+    #[test]
+    fn test_large_k_sliding_window_and_hashmap() {
+        let arr = vec![1, 2, 3, 4, 5];
+        assert_eq!(
+            max_sub_element_slice_len_with_at_most_k_distinct_elements_using_sliding_window_and_hashmap(
+                &arr, 10
+            ),
+            Some(5)
+        );
+    }
+
+    #[test]
+    fn test_k_equals_array_length_sliding_window_and_hashmap() {
+        let arr = vec!['x', 'y', 'z'];
+        assert_eq!(
+            max_sub_element_slice_len_with_at_most_k_distinct_elements_using_sliding_window_and_hashmap(
+                &arr, 3
+            ),
+            Some(3)
+        );
+    }
+
+    #[test]
+    fn test_no_valid_subarray_sliding_window_and_hashmap() {
+        let arr = vec![1, 2, 3];
+        assert_eq!(
+            max_sub_element_slice_len_with_at_most_k_distinct_elements_using_sliding_window_and_hashmap(
+                &arr, 0
+            ),
+            None
+        );
+    }
+
+    //////////
+    #[test]
+    fn test_empty_array_naive_with_hashmap() {
+        let arr: Vec<char> = vec![];
+        assert_eq!(
+            max_sub_element_slice_len_with_at_most_k_distinct_elements_naive_with_hashmap(&arr, 2),
+            None
+        );
+    }
+
+    #[test]
+    fn test_k_zero_naive_with_hashmap() {
+        let arr = vec![1, 2, 3];
+        assert_eq!(
+            max_sub_element_slice_len_with_at_most_k_distinct_elements_naive_with_hashmap(&arr, 0),
+            None
+        );
+    }
+
+    #[test]
+    fn test_single_element_array_naive_with_hashmap() {
+        let arr = vec!['a'];
+        assert_eq!(
+            max_sub_element_slice_len_with_at_most_k_distinct_elements_naive_with_hashmap(&arr, 1),
+            Some(1)
+        );
+        assert_eq!(
+            max_sub_element_slice_len_with_at_most_k_distinct_elements_naive_with_hashmap(&arr, 2),
+            Some(1)
+        );
+    }
+
+    #[test]
+    fn test_all_same_elements_naive_with_hashmap() {
+        let arr = vec![5, 5, 5, 5];
+        assert_eq!(
+            max_sub_element_slice_len_with_at_most_k_distinct_elements_naive_with_hashmap(&arr, 1),
+            Some(4)
+        );
+        assert_eq!(
+            max_sub_element_slice_len_with_at_most_k_distinct_elements_naive_with_hashmap(&arr, 2),
+            Some(4)
+        );
+    }
+
+    #[test]
+    fn test_simple_case_naive_with_hashmap() {
+        let arr = vec!['a', 'b', 'c', 'a', 'b', 'c'];
+        assert_eq!(
+            max_sub_element_slice_len_with_at_most_k_distinct_elements_naive_with_hashmap(&arr, 2),
+            Some(2)
+        );
+
+        assert_eq!(
+            max_sub_element_slice_len_with_at_most_k_distinct_elements_naive_with_hashmap(&arr, 3),
+            Some(6)
+        );
+    }
+
+    #[test]
+    fn test_mixed_types_naive_with_hashmap() {
+        let arr = vec![1, 2, 1, 2, 3];
+        assert_eq!(
+            max_sub_element_slice_len_with_at_most_k_distinct_elements_naive_with_hashmap(&arr, 2),
+            Some(4)
+        );
+        assert_eq!(
+            max_sub_element_slice_len_with_at_most_k_distinct_elements_naive_with_hashmap(&arr, 1),
+            Some(1)
+        );
+        assert_eq!(
+            max_sub_element_slice_len_with_at_most_k_distinct_elements_naive_with_hashmap(
+                &['a', 'b', 'a', 'c', 'b'],
+                2
+            ),
+            Some(3) // 'a', 'b', 'a'
+        );
+    }
+
+    #[test]
+    fn test_large_k_naive_with_hashmap() {
+        let arr = vec![1, 2, 3, 4, 5];
+        assert_eq!(
+            max_sub_element_slice_len_with_at_most_k_distinct_elements_naive_with_hashmap(&arr, 10),
+            Some(5)
+        );
+    }
+
+    #[test]
+    fn test_k_equals_array_length_naive_with_hashmap() {
+        let arr = vec!['x', 'y', 'z'];
+        assert_eq!(
+            max_sub_element_slice_len_with_at_most_k_distinct_elements_naive_with_hashmap(&arr, 3),
+            Some(3)
+        );
+    }
+
+    #[test]
+    fn test_no_valid_subarray_naive_with_hashmap() {
+        let arr = vec![1, 2, 3];
+        assert_eq!(
+            max_sub_element_slice_len_with_at_most_k_distinct_elements_naive_with_hashmap(&arr, 0),
+            None
+        );
+    }
+
+    //////////
+    #[test]
+    fn test_empty_array_naive_very() {
+        let arr: Vec<char> = vec![];
+        assert_eq!(
+            max_sub_element_slice_len_with_at_most_k_distinct_elements_naive_very(&arr, 2),
+            None
+        );
+    }
+
+    #[test]
+    fn test_k_zero_naive_very() {
+        let arr = vec![1, 2, 3];
+        assert_eq!(
+            max_sub_element_slice_len_with_at_most_k_distinct_elements_naive_very(&arr, 0),
+            None
+        );
+    }
+
+    #[test]
+    fn test_single_element_array_naive_very() {
+        let arr = vec!['a'];
+        assert_eq!(
+            max_sub_element_slice_len_with_at_most_k_distinct_elements_naive_very(&arr, 1),
+            Some(1)
+        );
+        assert_eq!(
+            max_sub_element_slice_len_with_at_most_k_distinct_elements_naive_very(&arr, 2),
+            Some(1)
+        );
+    }
+
+    #[test]
+    fn test_all_same_elements_naive_very() {
+        let arr = vec![5, 5, 5, 5];
+        assert_eq!(
+            max_sub_element_slice_len_with_at_most_k_distinct_elements_naive_very(&arr, 1),
+            Some(4)
+        );
+        assert_eq!(
+            max_sub_element_slice_len_with_at_most_k_distinct_elements_naive_very(&arr, 2),
+            Some(4)
+        );
+    }
+
+    #[test]
+    fn test_simple_case_naive_very() {
+        let arr = vec!['a', 'b', 'c', 'a', 'b', 'c'];
+        assert_eq!(
+            max_sub_element_slice_len_with_at_most_k_distinct_elements_naive_very(&arr, 2),
+            Some(2)
+        );
+
+        assert_eq!(
+            max_sub_element_slice_len_with_at_most_k_distinct_elements_naive_very(&arr, 3),
+            Some(6)
+        );
+    }
+
+    #[test]
+    fn test_mixed_types_naive_very() {
+        let arr = vec![1, 2, 1, 2, 3];
+        assert_eq!(
+            max_sub_element_slice_len_with_at_most_k_distinct_elements_naive_very(&arr, 2),
+            Some(4)
+        );
+        assert_eq!(
+            max_sub_element_slice_len_with_at_most_k_distinct_elements_naive_very(&arr, 1),
+            Some(1)
+        );
+        assert_eq!(
+            max_sub_element_slice_len_with_at_most_k_distinct_elements_naive_very(
+                &['a', 'b', 'a', 'c', 'b'],
+                2
+            ),
+            Some(3) // 'a', 'b', 'a'
+        );
+    }
+
+    #[test]
+    fn test_large_k_naive_very() {
+        let arr = vec![1, 2, 3, 4, 5];
+        assert_eq!(
+            max_sub_element_slice_len_with_at_most_k_distinct_elements_naive_very(&arr, 10),
+            Some(5)
+        );
+    }
+
+    #[test]
+    fn test_k_equals_array_length_naive_very() {
+        let arr = vec!['x', 'y', 'z'];
+        assert_eq!(
+            max_sub_element_slice_len_with_at_most_k_distinct_elements_naive_very(&arr, 3),
+            Some(3)
+        );
+    }
+
+    #[test]
+    fn test_no_valid_subarray_naive_very() {
+        let arr = vec![1, 2, 3];
+        assert_eq!(
+            max_sub_element_slice_len_with_at_most_k_distinct_elements_naive_very(&arr, 0),
+            None
+        );
+    }
+}
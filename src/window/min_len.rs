@@ -0,0 +1,336 @@
+use crate::array::prefix_sum::range_sum_prefix_sum_arr;
+
+// O(n^3)
+pub fn min_slice_len_which_sums_ge_target_naive(arr: &[i32], target: i32) -> Option<usize> {
+    if arr.is_empty() {
+        return None;
+    }
+    // For each length to be tested starting at the smallest length
+    for slice_len in 1..=arr.len() {
+        let slice_len_right_offset = slice_len - 1;
+        // For each starting position less than the slice length
+        for left in 0..arr.len() - slice_len_right_offset {
+            let right = left + slice_len_right_offset;
+            // Sum left through right
+            let mut sum = 0;
+            for i in left..=right {
+                sum += arr[i];
+            }
+            // Test
+            if sum >= target {
+                return Some(slice_len);
+            }
+        }
+    }
+    None
+}
+
+// Prefix Array version
+pub fn min_slice_len_which_sums_ge_target_using_prefix_array_for_sum(
+    arr_prefix_array: &[i32],
+    target: i32,
+) -> Option<usize> {
+    if arr_prefix_array.is_empty() {
+        return None;
+    }
+    // For each length to be tested starting at the smallest length
+    for slice_len in 1..=arr_prefix_array.len() {
+        let slice_len_right_offset = slice_len - 1;
+        // For each starting position less than the slice length
+        for left in 0..arr_prefix_array.len() - slice_len_right_offset {
+            let right = left + slice_len_right_offset;
+            // Sum left through right
+            let sum = range_sum_prefix_sum_arr(arr_prefix_array, left, right);
+            // Test
+            if sum >= target {
+                return Some(slice_len);
+            }
+        }
+    }
+    None
+}
+
+///--- O(n) time complexity implementation.
+// The key insight here is to realize you are looking for a qualifying window,
+// while at the same time eliminating as many elements i.e. previously checked
+// window space as posible.
+//
+// 1) find the furthest right that makes arr[left..=right] qualify.
+// 2) Then contract left until arr[left..=right] no longer qualifies
+//      and if smaller record right - length +1 as your current min_window_size
+//      and subtract 1 from left.
+//
+// 3) repeat: add 1 to right and left until arr[left..=right] qualifies
+//    again or r would go out of bounds.
+// 3.1) If not going out of bounds repeat from 2)
+// 3.2) If r was going to go out of bounds return min_window_size.
+//
+// Aparently you can exceed this implementation's performance more by
+// not taking a prefix array itself as the argument but instead implementing a
+// prefix sum inside it while sliding, aparently it's more cache friendly to the
+// processor, however in this case we just wanted to go from O(n^3) to O(n)
+// which we did achieve.
+//
+// Kept hand-written because the module 5 tutorial walks through these three steps on a
+// prefix array, everything else uses window::sliding::shortest_window_where which does the
+// same steps for any window aggregate (see min_sub_array_len).
+pub fn min_slice_len_which_sums_ge_target_using_variable_sliding_window_and_prefix_array_for_sum(
+    arr_prefix_array: &[i32],
+    target: i32,
+) -> Option<usize> {
+    if arr_prefix_array.is_empty() {
+        return None;
+    }
+    let mut l = 0;
+    let mut r = 0;
+
+    // Step 1)
+    // Find the qualifying right
+    while r < arr_prefix_array.len() && range_sum_prefix_sum_arr(arr_prefix_array, l, r) < target {
+        r += 1; // Expand right side
+    }
+
+    let mut result = None;
+    // r reaching the end means no (more) qualifying slices, and result holds the answer.
+    while r < arr_prefix_array.len() {
+        // Step 2)
+        // Find the contracted left.
+        while l <= r && range_sum_prefix_sum_arr(arr_prefix_array, l, r) >= target {
+            l += 1; // Contract left side
+        }
+
+        // Record our minimum window size that qualified.
+        // extra -1 on l is because the while moved it beyond where it qualfied.
+        result = Some(1 + r - (l - 1));
+        // if r was at the end of the array we are effectively done
+        // and can return the result.
+        if r == (arr_prefix_array.len() - 1) {
+            break;
+        }
+        // l moves past r when the sliding window lenght is 1, to counter
+        // act this in this scenario when l moves r moves as well.
+        if l > r {
+            r = l;
+        }
+
+        // Step 3) find a window to the right that can potentially match a
+        //  smaller result
+        while r < arr_prefix_array.len()
+            && range_sum_prefix_sum_arr(arr_prefix_array, l, r) < target
+        {
+            // Slide the whole fixed window now.
+            r += 1;
+            l += 1;
+        }
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::array::prefix_sum::make_prefix_sum_array;
+
+    #[test]
+    fn empty_array_using_prefix_array_for_sum_with_variable_sliding_window() {
+        assert_eq!(
+            min_slice_len_which_sums_ge_target_using_variable_sliding_window_and_prefix_array_for_sum(
+                &make_prefix_sum_array(&[]),
+                5
+            ),
+            None
+        );
+    }
+
+    #[test]
+    fn single_element_less_than_target_using_prefix_array_for_sum_with_variable_sliding_window() {
+        assert_eq!(
+            min_slice_len_which_sums_ge_target_using_variable_sliding_window_and_prefix_array_for_sum(
+                &make_prefix_sum_array(&[3]),
+                5
+            ),
+            None
+        );
+    }
+
+    #[test]
+    fn single_element_equal_to_target_using_prefix_array_for_sum_with_variable_sliding_window() {
+        assert_eq!(
+            min_slice_len_which_sums_ge_target_using_variable_sliding_window_and_prefix_array_for_sum(
+                &make_prefix_sum_array(&[5]),
+                5
+            ),
+            Some(1)
+        );
+    }
+
+    #[test]
+    fn single_element_greater_than_target_using_prefix_array_for_sum_with_variable_sliding_window()
+    {
+        assert_eq!(
+            min_slice_len_which_sums_ge_target_using_variable_sliding_window_and_prefix_array_for_sum(
+                &make_prefix_sum_array(&[10]),
+                5
+            ),
+            Some(1)
+        );
+    }
+
+    #[test]
+    fn multiple_elements_no_subarray_meets_target_using_prefix_array_for_sum_with_variable_sliding_window()
+     {
+        assert_eq!(
+            min_slice_len_which_sums_ge_target_using_variable_sliding_window_and_prefix_array_for_sum(
+                &make_prefix_sum_array(&[1, 1, 1, 1]),
+                10
+            ),
+            None
+        );
+    }
+
+    #[test]
+    fn multiple_elements_with_valid_subarray_using_prefix_array_for_sum_with_variable_sliding_window()
+     {
+        assert_eq!(
+            min_slice_len_which_sums_ge_target_using_variable_sliding_window_and_prefix_array_for_sum(
+                &make_prefix_sum_array(&[2, 3, 1, 2, 4, 3]),
+                7
+            ),
+            Some(2)
+        );
+    }
+
+    #[test]
+    fn target_zero_using_prefix_array_for_sum_with_variable_sliding_window() {
+        assert_eq!(
+            min_slice_len_which_sums_ge_target_using_variable_sliding_window_and_prefix_array_for_sum(
+                &make_prefix_sum_array(&[1, 2, 3]),
+                0
+            ),
+            Some(1)
+        );
+    }
+
+    //
+    #[test]
+    fn empty_array_using_prefix_array_for_sum() {
+        assert_eq!(
+            min_slice_len_which_sums_ge_target_using_prefix_array_for_sum(
+                &make_prefix_sum_array(&[]),
+                5
+            ),
+            None
+        );
+    }
+
+    #[test]
+    fn single_element_less_than_target_using_prefix_array_for_sum() {
+        assert_eq!(
+            min_slice_len_which_sums_ge_target_using_prefix_array_for_sum(
+                &make_prefix_sum_array(&[3]),
+                5
+            ),
+            None
+        );
+    }
+
+    #[test]
+    fn single_element_equal_to_target_using_prefix_array_for_sum() {
+        assert_eq!(
+            min_slice_len_which_sums_ge_target_using_prefix_array_for_sum(
+                &make_prefix_sum_array(&[5]),
+                5
+            ),
+            Some(1)
+        );
+    }
+
+    #[test]
+    fn single_element_greater_than_target_using_prefix_array_for_sum() {
+        assert_eq!(
+            min_slice_len_which_sums_ge_target_using_prefix_array_for_sum(
+                &make_prefix_sum_array(&[10]),
+                5
+            ),
+            Some(1)
+        );
+    }
+
+    #[test]
+    fn multiple_elements_no_subarray_meets_target_using_prefix_array_for_sum() {
+        assert_eq!(
+            min_slice_len_which_sums_ge_target_using_prefix_array_for_sum(
+                &make_prefix_sum_array(&[1, 1, 1, 1]),
+                10
+            ),
+            None
+        );
+    }
+
+    #[test]
+    fn multiple_elements_with_valid_subarray_using_prefix_array_for_sum() {
+        assert_eq!(
+            min_slice_len_which_sums_ge_target_using_prefix_array_for_sum(
+                &make_prefix_sum_array(&[2, 3, 1, 2, 4, 3]),
+                7
+            ),
+            Some(2)
+        );
+    }
+
+    #[test]
+    fn target_zero_using_prefix_array_for_sum() {
+        assert_eq!(
+            min_slice_len_which_sums_ge_target_using_prefix_array_for_sum(
+                &make_prefix_sum_array(&make_prefix_sum_array(&[1, 2, 3])),
+                0
+            ),
+            Some(1)
+        );
+    }
+
+    //
+    #[test]
+    fn empty_array_naive() {
+        assert_eq!(min_slice_len_which_sums_ge_target_naive(&[], 5), None);
+    }
+
+    #[test]
+    fn single_element_less_than_target_naive() {
+        assert_eq!(min_slice_len_which_sums_ge_target_naive(&[3], 5), None);
+    }
+
+    #[test]
+    fn single_element_equal_to_target_naive() {
+        assert_eq!(min_slice_len_which_sums_ge_target_naive(&[5], 5), Some(1));
+    }
+
+    #[test]
+    fn single_element_greater_than_target_naive() {
+        assert_eq!(min_slice_len_which_sums_ge_target_naive(&[10], 5), Some(1));
+    }
+
+    #[test]
+    fn multiple_elements_no_subarray_meets_target_naive() {
+        assert_eq!(
+            min_slice_len_which_sums_ge_target_naive(&[1, 1, 1, 1], 10),
+            None
+        );
+    }
+
+    #[test]
+    fn multiple_elements_with_valid_subarray_naive() {
+        assert_eq!(
+            min_slice_len_which_sums_ge_target_naive(&[2, 3, 1, 2, 4, 3], 7),
+            Some(2)
+        );
+    }
+
+    #[test]
+    fn target_zero_naive() {
+        assert_eq!(
+            min_slice_len_which_sums_ge_target_naive(&[1, 2, 3], 0),
+            Some(1)
+        );
+    }
+}
@@ -0,0 +1,18 @@
+// Leetcode 209: Minimum Size Subarray Sum
+// https://leetcode.com/problems/minimum-size-subarray-sum/description/
+// Functionally the same as min_len.rs, but keeps a running sum of the window
+// while processing the array instead of having a prefix array passed in.
+use crate::window::sliding::{WindowSum, shortest_window_where};
+
+// The running sum is a WindowSum, shortest_window_where does the expand right, contract left
+// and slide right steps. 0 when no window qualifies.
+pub fn min_sub_array_len(target: i32, nums: &[i32]) -> i32 {
+    // nums is positive integers
+    // target is posive integer
+    // find length of shortest slice, that's sum is >= target
+    // if it doesnt exist return 0
+    shortest_window_where(nums.iter().copied(), WindowSum::new(), |sum| {
+        sum.sum() >= target
+    })
+    .map_or(0, |span| span.len() as i32)
+}
@@ -0,0 +1,68 @@
+use crate::window::sliding::{RequiredCounts, shortest_window_where};
+
+// Given two strings s and t of lengths m and n respectively, return the minimum
+// window substring of s such that every character in t (including duplicates)
+// is included in the window. If there is no such substring, return the empty
+// string "".
+//
+// I have no idea why they always have to have questions that sound like it comes
+// out of a set theory math book, they basically asking find us the smallest,
+// substring of s that include at least 1 of each of the characters of t.
+//
+// Well its going to be a sliding window problem for sure... since we wouldn't
+// want to revisit stuff in s already processed.
+//
+// like wise we would want to reduce t to some sort of easily verifiable value on
+// the slice of s under examination.
+// -- algo idea: I think this will work--
+// So I guess we need a hashmap of all characters in t first called thash and record
+// the length of thash as thlen. Then we process s with a left/right boundry variable
+// sliding window starting at l=0 r=0 with a hashmap called shash and building the
+// state machine which works in steps of find qualifying window, contract left,
+// slide smallest window right till finding the next qualifying window, and then
+// looping back into the contract left step repeating and so on.
+//
+// Every time r+=1 occurs we increment a count of the occurances of the string's [r-1]
+// position in the shash, every time we l+=1 we decrement the count of the occurances
+// of the l-1 character from shash, if it falls to 0 we remove it from shash.
+//
+// In the 3 steps mentioned, when a comparison is needed if the window is a qualifying
+// window or not check every element in thash if it has an element in shash. This part
+// I don't really know how to make faster..., but I'm thinking on it.. faster aproaches
+// must exist... Hmm maybe I can think of something faster, what if:
+//      I keep a extra variable match_count whenever I add a new element in shash which
+//      already exists in t I increment the match count.
+//      Whenever the shash falls to a count of 0 and the element is removed we decrement
+//      match_count, therefore if match_count==thlen then the current window qualifies.
+// Yes this will work.
+// The final result should be O(n+t)
+//
+//
+// OK so I originally made a mistake: duplicate characters in t means that
+// the result from s should have the same number of duplicates,
+// this means that when thashmap is constructed the value portion of each
+// required character key should be a count of how many is required.
+//
+// Then instead of comparing match_count = thash.len() to determine validity
+// we compare match_count to thash.sum()
+// this necesitates one additional change: instead of increasing match_count
+// on the first itteration a new char gets added into shash we need to keep
+// incrementing match_count while the count of the character in shash is <
+// the count of the character in thash.
+// Likewise instead of decrementing when shash's character value reaches 0
+// we will now decrement while shash's value is moved < thash for said character
+//
+// NOTE THE ABOVE VARIABLE NAMES ETC IS NOT WHATS USED IN THE CODE.
+// The frequency maps and match_count are RequiredCounts, and the expand right, contract left
+// and slide right steps are what shortest_window_where does for any window aggregate.
+pub fn min_window(s: &str, t: &str) -> String {
+    if s.is_empty() || t.is_empty() {
+        return String::from("");
+    }
+    match shortest_window_where(s.chars(), RequiredCounts::new(t.chars()), |counts| {
+        counts.is_satisfied()
+    }) {
+        Some(span) => s.chars().skip(span.l).take(span.len()).collect(),
+        None => String::from(""),
+    }
+}
@@ -0,0 +1,464 @@
+// Generic sliding windows, the parts every module 5 solution wrote by hand.
+//
+// A window aggregate is whatever state the solution kept about the current window (a running
+// sum, a map of counts, required vs found character counts): add is called for each value
+// entering on the right, remove for each value leaving on the left, always oldest first.
+//
+// - fixed_windows: one aggregate value per window of k consecutive values.
+// - shortest_window_where: the shortest window a predicate accepts, for predicates that keep
+//   accepting a window when it grows (sum >= target, contains every char of t).
+// - longest_window_where: the longest window a predicate accepts, for predicates that keep
+//   accepting a window when it shrinks (at most k distinct values).
+//
+// All three take any IntoIterator and only keep the values of the current window, so they
+// also run over streams that are never collected into a slice. Slices can be passed as
+// arr.iter() so the window only holds references.
+use std::collections::{HashMap, VecDeque};
+use std::hash::Hash;
+use std::ops::{AddAssign, SubAssign};
+
+pub trait WindowAggregate<T> {
+    type Output;
+
+    fn add(&mut self, value: &T);
+    fn remove(&mut self, value: &T);
+    fn value(&self) -> Self::Output;
+}
+
+// inclusive bounds, like SubarraySum
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WindowSpan {
+    pub l: usize,
+    pub r: usize,
+}
+
+impl WindowSpan {
+    pub fn len(&self) -> usize {
+        self.r + 1 - self.l
+    }
+
+    // A span always covers at least one value
+    pub fn is_empty(&self) -> bool {
+        false
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct WindowSum<T> {
+    sum: T,
+}
+
+impl<T: Copy + Default> WindowSum<T> {
+    pub fn new() -> Self {
+        WindowSum { sum: T::default() }
+    }
+
+    pub fn sum(&self) -> T {
+        self.sum
+    }
+}
+
+impl<T: Copy + AddAssign + SubAssign> WindowAggregate<T> for WindowSum<T> {
+    type Output = T;
+
+    fn add(&mut self, value: &T) {
+        self.sum += *value;
+    }
+
+    fn remove(&mut self, value: &T) {
+        self.sum -= *value;
+    }
+
+    fn value(&self) -> T {
+        self.sum
+    }
+}
+
+// Window max or min with a monotonic deque: a value can never be the extreme again once a
+// better one entered after it, so it is dropped on the spot. The front is the extreme of the
+// window and leaves as soon as the window moves past it, amortised O(1) per value.
+#[derive(Debug, Clone)]
+pub struct WindowExtreme<T> {
+    keep_max: bool,
+    candidates: VecDeque<T>,
+}
+
+impl<T: PartialOrd + Clone> WindowExtreme<T> {
+    pub fn max() -> Self {
+        WindowExtreme {
+            keep_max: true,
+            candidates: VecDeque::new(),
+        }
+    }
+
+    pub fn min() -> Self {
+        WindowExtreme {
+            keep_max: false,
+            candidates: VecDeque::new(),
+        }
+    }
+
+    fn beats(&self, a: &T, b: &T) -> bool {
+        if self.keep_max { a > b } else { a < b }
+    }
+}
+
+impl<T: PartialOrd + Clone> WindowAggregate<T> for WindowExtreme<T> {
+    type Output = Option<T>;
+
+    fn add(&mut self, value: &T) {
+        while let Some(back) = self.candidates.back()
+            && self.beats(value, back)
+        {
+            self.candidates.pop_back();
+        }
+        self.candidates.push_back(value.clone());
+    }
+
+    // Only the oldest value ever leaves, so if it is still a candidate it is the front one
+    fn remove(&mut self, value: &T) {
+        if self.candidates.front() == Some(value) {
+            self.candidates.pop_front();
+        }
+    }
+
+    fn value(&self) -> Option<T> {
+        self.candidates.front().cloned()
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct DistinctCount<T> {
+    counts: HashMap<T, usize>,
+}
+
+impl<T: Eq + Hash> DistinctCount<T> {
+    pub fn new() -> Self {
+        DistinctCount {
+            counts: HashMap::new(),
+        }
+    }
+
+    pub fn distinct(&self) -> usize {
+        self.counts.len()
+    }
+}
+
+impl<T: Eq + Hash> Default for DistinctCount<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Eq + Hash + Clone> WindowAggregate<T> for DistinctCount<T> {
+    type Output = usize;
+
+    fn add(&mut self, value: &T) {
+        *self.counts.entry(value.clone()).or_insert(0) += 1;
+    }
+
+    fn remove(&mut self, value: &T) {
+        if let Some(count) = self.counts.get_mut(value) {
+            *count -= 1;
+            if *count == 0 {
+                self.counts.remove(value);
+            }
+        }
+    }
+
+    fn value(&self) -> usize {
+        self.counts.len()
+    }
+}
+
+// Counts how many of the required values (duplicates included) the window covers, the
+// match count trick from min_window: a value only counts while its found frequency is at or
+// below its required frequency, so the window qualifies once found == required in total.
+#[derive(Debug, Clone)]
+pub struct RequiredCounts<T> {
+    required: HashMap<T, usize>,
+    found: HashMap<T, usize>,
+    required_total: usize,
+    found_total: usize,
+}
+
+impl<T: Eq + Hash> RequiredCounts<T> {
+    pub fn new(required_values: impl IntoIterator<Item = T>) -> Self {
+        let mut required = HashMap::new();
+        let mut required_total = 0;
+        for value in required_values {
+            *required.entry(value).or_insert(0) += 1;
+            required_total += 1;
+        }
+        RequiredCounts {
+            required,
+            found: HashMap::new(),
+            required_total,
+            found_total: 0,
+        }
+    }
+
+    pub fn is_satisfied(&self) -> bool {
+        self.found_total == self.required_total
+    }
+}
+
+impl<T: Eq + Hash + Clone> WindowAggregate<T> for RequiredCounts<T> {
+    type Output = bool;
+
+    fn add(&mut self, value: &T) {
+        if let Some(&required_count) = self.required.get(value) {
+            let found_count = self.found.entry(value.clone()).or_insert(0);
+            *found_count += 1;
+            if *found_count <= required_count {
+                self.found_total += 1;
+            }
+        }
+    }
+
+    fn remove(&mut self, value: &T) {
+        if let Some(&required_count) = self.required.get(value)
+            && let Some(found_count) = self.found.get_mut(value)
+        {
+            if *found_count <= required_count {
+                self.found_total -= 1;
+            }
+            *found_count -= 1;
+        }
+    }
+
+    fn value(&self) -> bool {
+        self.is_satisfied()
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct FixedWindows<I: Iterator, A> {
+    iter: std::iter::Fuse<I>,
+    k: usize,
+    window: VecDeque<I::Item>,
+    aggregate: A,
+}
+
+impl<I: Iterator, A: WindowAggregate<I::Item>> Iterator for FixedWindows<I, A> {
+    type Item = A::Output;
+
+    fn next(&mut self) -> Option<A::Output> {
+        if self.window.len() == self.k {
+            let dropped = self.window.pop_front().unwrap();
+            self.aggregate.remove(&dropped);
+        }
+        while self.window.len() < self.k {
+            let value = self.iter.next()?;
+            self.aggregate.add(&value);
+            self.window.push_back(value);
+        }
+        Some(self.aggregate.value())
+    }
+}
+
+// aggregate.value() for every k consecutive values, n - k + 1 of them (none if n < k)
+pub fn fixed_windows<I, A>(values: I, k: usize, aggregate: A) -> FixedWindows<I::IntoIter, A>
+where
+    I: IntoIterator,
+    A: WindowAggregate<I::Item>,
+{
+    if k == 0 {
+        panic!("window size must be > 0")
+    }
+    FixedWindows {
+        iter: values.into_iter().fuse(),
+        k,
+        window: VecDeque::with_capacity(k),
+        aggregate,
+    }
+}
+
+// Grows the window on the right, and while it qualifies records it and drops values on the
+// left to look for a shorter one. The first shortest window wins ties. O(n) add/remove calls.
+pub fn shortest_window_where<I, A>(
+    values: I,
+    mut aggregate: A,
+    qualifies: impl Fn(&A) -> bool,
+) -> Option<WindowSpan>
+where
+    I: IntoIterator,
+    A: WindowAggregate<I::Item>,
+{
+    let mut window = VecDeque::new();
+    let mut l = 0;
+    let mut best: Option<WindowSpan> = None;
+    for (r, value) in values.into_iter().enumerate() {
+        aggregate.add(&value);
+        window.push_back(value);
+        while !window.is_empty() && qualifies(&aggregate) {
+            if best.is_none_or(|best| r + 1 - l < best.len()) {
+                best = Some(WindowSpan { l, r });
+            }
+            let dropped = window.pop_front().unwrap();
+            aggregate.remove(&dropped);
+            l += 1;
+        }
+    }
+    best
+}
+
+// Grows the window on the right, drops values on the left until it fits again and records
+// it if it is the longest so far. The first longest window wins ties. O(n) add/remove calls.
+pub fn longest_window_where<I, A>(
+    values: I,
+    mut aggregate: A,
+    fits: impl Fn(&A) -> bool,
+) -> Option<WindowSpan>
+where
+    I: IntoIterator,
+    A: WindowAggregate<I::Item>,
+{
+    let mut window = VecDeque::new();
+    let mut l = 0;
+    let mut best: Option<WindowSpan> = None;
+    for (r, value) in values.into_iter().enumerate() {
+        aggregate.add(&value);
+        window.push_back(value);
+        while !window.is_empty() && !fits(&aggregate) {
+            let dropped = window.pop_front().unwrap();
+            aggregate.remove(&dropped);
+            l += 1;
+        }
+        if !window.is_empty() && best.is_none_or(|best| r + 1 - l > best.len()) {
+            best = Some(WindowSpan { l, r });
+        }
+    }
+    best
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::array::prefix_sum::make_prefix_sum_array;
+    use crate::bench::data::{XorShift64, alpha_string_from_seed};
+    use crate::window::distinct::{
+        max_sub_element_slice_len_with_at_most_k_distinct_elements_naive_with_hashmap,
+        max_sub_element_slice_len_with_at_most_k_distinct_elements_using_sliding_window_and_hashmap,
+    };
+    use crate::window::fixed::{
+        max_sum_fixed_window_naive, max_sum_fixed_window_using_sliding_window,
+    };
+    use crate::window::min_len::{
+        min_slice_len_which_sums_ge_target_naive,
+        min_slice_len_which_sums_ge_target_using_variable_sliding_window_and_prefix_array_for_sum,
+    };
+    use crate::window::min_sub_array_len::min_sub_array_len;
+    use crate::window::min_window::min_window;
+
+    fn random_arr(rng: &mut XorShift64, len: usize, modulo: u64) -> Vec<i32> {
+        (0..len).map(|_| (rng.next_u64() % modulo) as i32).collect()
+    }
+
+    #[test]
+    fn fixed_windows_over_a_stream() {
+        let mut rng = XorShift64::new(47);
+        let arr = random_arr(&mut rng, 200, 1000);
+        for k in [1, 2, 7, 200] {
+            let sums: Vec<i32> = fixed_windows(arr.iter().copied(), k, WindowSum::new()).collect();
+            let maxes: Vec<Option<i32>> =
+                fixed_windows(arr.iter().copied(), k, WindowExtreme::max()).collect();
+            let mins: Vec<Option<&i32>> = fixed_windows(&arr, k, WindowExtreme::min()).collect();
+            assert_eq!(sums.len(), arr.len() - k + 1);
+            for (l, window) in arr.windows(k).enumerate() {
+                assert_eq!(sums[l], window.iter().sum::<i32>());
+                assert_eq!(maxes[l], window.iter().max().copied());
+                assert_eq!(mins[l], window.iter().min());
+            }
+        }
+        // a stream that is never a slice, and one shorter than the window
+        let stream = (0..10).map(|v| v * v);
+        assert_eq!(
+            fixed_windows(stream, 3, WindowSum::new()).collect::<Vec<i32>>(),
+            [5, 14, 29, 50, 77, 110, 149, 194]
+        );
+        assert_eq!(fixed_windows(0..2, 3, WindowSum::new()).count(), 0);
+    }
+
+    #[test]
+    fn variable_windows() {
+        let arr = [2, 3, 1, 2, 4, 3];
+        let shortest = shortest_window_where(arr, WindowSum::new(), |sum| sum.sum() >= 7);
+        assert_eq!(shortest, Some(WindowSpan { l: 4, r: 5 }));
+        assert_eq!(
+            shortest_window_where(arr, WindowSum::new(), |sum| sum.sum() >= 100),
+            None
+        );
+
+        let longest =
+            longest_window_where("eceba".chars(), DistinctCount::new(), |d| d.distinct() <= 2);
+        assert_eq!(longest, Some(WindowSpan { l: 0, r: 2 }));
+        assert_eq!(
+            longest_window_where([1, 2], DistinctCount::new(), |d| d.distinct() == 0),
+            None
+        );
+    }
+
+    #[test]
+    fn module_5_ports_match_the_naive_versions() {
+        let mut rng = XorShift64::new(5);
+        for len in 1..60 {
+            let arr = random_arr(&mut rng, len, 20);
+            let k = 1 + rng.next_u64() as usize % len;
+            assert_eq!(
+                max_sum_fixed_window_using_sliding_window(&arr, k),
+                max_sum_fixed_window_naive(&arr, k)
+            );
+
+            let target = (rng.next_u64() % 200) as i32;
+            let expected = min_slice_len_which_sums_ge_target_naive(&arr, target);
+            assert_eq!(
+                min_slice_len_which_sums_ge_target_using_variable_sliding_window_and_prefix_array_for_sum(
+                    &make_prefix_sum_array(&arr),
+                    target
+                ),
+                expected
+            );
+            assert_eq!(
+                min_sub_array_len(target, &arr),
+                expected.map_or(0, |len| len as i32)
+            );
+
+            let distinct_arr = random_arr(&mut rng, len, 6);
+            assert_eq!(
+                max_sub_element_slice_len_with_at_most_k_distinct_elements_using_sliding_window_and_hashmap(
+                    &distinct_arr,
+                    k % 5
+                ),
+                max_sub_element_slice_len_with_at_most_k_distinct_elements_naive_with_hashmap(
+                    &distinct_arr,
+                    k % 5
+                )
+            );
+        }
+
+        // min_window against trying every substring, shortest first
+        let covers = |window: &[char], t: &str| {
+            t.chars().all(|c| {
+                window.iter().filter(|&&w| w == c).count() >= t.chars().filter(|&p| p == c).count()
+            })
+        };
+        for seed in 1..40 {
+            let s = alpha_string_from_seed(seed, 40);
+            let t: String = s
+                .chars()
+                .skip(seed as usize % 10)
+                .step_by(7)
+                .take(4)
+                .collect();
+            let s_chars: Vec<char> = s.chars().collect();
+            let expected: String = (1..=s_chars.len())
+                .flat_map(|len| s_chars.windows(len))
+                .find(|window| covers(window, &t))
+                .map_or(String::new(), |window| window.iter().collect());
+            assert_eq!(min_window(&s, &t), expected, "s = {}, t = {}", s, t);
+        }
+        assert_eq!(min_window("ADOBECODEBANC", "ABC"), "BANC");
+        assert_eq!(min_window("a", "aa"), "");
+    }
+}